@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, DataKey, Error};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env,
+};
+
+struct AmendmentTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+}
+
+fn setup_test() -> AmendmentTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &1_000);
+    client.create_escrow(&buyer, &seller, &token, &10_000, &1, &Some(604800));
+
+    AmendmentTest {
+        env,
+        client,
+        buyer,
+        seller,
+        token,
+    }
+}
+
+fn total_locked(t: &AmendmentTest) -> i128 {
+    t.env.as_contract(&t.client.address, || {
+        t.env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalLocked(t.token.clone()))
+            .unwrap_or(0)
+    })
+}
+
+#[test]
+fn test_buyer_top_up_increases_escrow() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.amend_escrow_amount(&1, &15_000);
+
+    assert_eq!(t.client.get_escrow(&1).amount, 15_000);
+    assert_eq!(t.client.get_escrow_amendment_version(&1), 1);
+    assert_eq!(total_locked(&t), 15_000);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000 - 15_000);
+    assert_eq!(token_client.balance(&t.client.address), 15_000);
+}
+
+#[test]
+fn test_price_reduction_refunds_difference_with_seller_consent() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.amend_escrow_amount(&1, &6_000);
+    let auths = t.env.auths();
+    assert!(auths.iter().any(|(signer, _)| *signer == t.buyer));
+    assert!(auths.iter().any(|(signer, _)| *signer == t.seller));
+
+    assert_eq!(t.client.get_escrow(&1).amount, 6_000);
+    assert_eq!(total_locked(&t), 6_000);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000 - 6_000);
+
+    // The amended amount is what settles.
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 5_700);
+    assert_eq!(total_locked(&t), 0);
+}
+
+#[test]
+fn test_amendment_validation() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client.try_amend_escrow_amount(&1, &999),
+        Err(Ok(Error::AmountBelowMinimum.into()))
+    );
+    assert_eq!(
+        t.client.try_amend_escrow_amount(&1, &10_000),
+        Err(Ok(Error::InvalidEscrowState.into()))
+    );
+
+    t.client.amend_escrow_amount(&1, &12_000);
+    t.client.amend_escrow_amount(&1, &11_000);
+    assert_eq!(t.client.get_escrow_amendment_version(&1), 2);
+
+    t.client.release_funds(&1);
+    assert_eq!(
+        t.client.try_amend_escrow_amount(&1, &20_000),
+        Err(Ok(Error::InvalidEscrowState.into()))
+    );
+}
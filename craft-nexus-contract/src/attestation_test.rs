@@ -0,0 +1,255 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, DeliveryAttestation, DeliveryStatus, Error,
+    EscrowStatus,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token,
+    xdr::ToXdr,
+    Address, BytesN, Env,
+};
+
+struct AttestationTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+    attester: SigningKey,
+}
+
+fn setup_test() -> AttestationTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+
+    let attester = SigningKey::from_bytes(&[7u8; 32]);
+    client.set_delivery_attester(&admin, &public_key(&env, &attester), &true);
+
+    AttestationTest {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        token,
+        attester,
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn sign(t: &AttestationTest, key: &SigningKey, attestation: &DeliveryAttestation) -> BytesN<64> {
+    let message = (t.client.address.clone(), attestation.clone()).to_xdr(&t.env);
+    let mut bytes = [0u8; 1024];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut bytes[..len]);
+    BytesN::from_array(&t.env, &key.sign(&bytes[..len]).to_bytes())
+}
+
+fn delivered(t: &AttestationTest, order_id: u32) -> DeliveryAttestation {
+    DeliveryAttestation {
+        order_id,
+        status: DeliveryStatus::Delivered,
+        timestamp: t.env.ledger().timestamp(),
+    }
+}
+
+#[test]
+fn test_attestation_shortens_release_window() {
+    let t = setup_test();
+    let seven_days = 7 * 24 * 60 * 60;
+
+    t.client.create_escrow(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &Some(seven_days),
+    );
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+
+    let attestation = delivered(&t, 1);
+    let signature = sign(&t, &t.attester, &attestation);
+    t.client.submit_delivery_attestation(
+        &attestation,
+        &public_key(&t.env, &t.attester),
+        &signature,
+    );
+
+    // Releasable 24h after the attested delivery instead of after 7 days.
+    let escrow = t.client.get_escrow(&1);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.release_window, 3600 + 24 * 60 * 60);
+    assert_eq!(t.client.get_delivery_attestation(&1), Some(attestation));
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 24 * 60 * 60;
+    });
+    assert!(t.client.can_auto_release(&1));
+}
+
+#[test]
+fn test_attestation_releases_immediately_with_zero_window() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_attested_release_window(&t.admin, &0);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(604800));
+    // Past the one-second minimum release window.
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 1;
+    });
+
+    let attestation = delivered(&t, 1);
+    let signature = sign(&t, &t.attester, &attestation);
+    t.client.submit_delivery_attestation(
+        &attestation,
+        &public_key(&t.env, &t.attester),
+        &signature,
+    );
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+}
+
+#[test]
+fn test_attestation_window_is_clamped_to_min_release_window() {
+    let t = setup_test();
+
+    t.client.set_attested_release_window(&t.admin, &0);
+    t.client.set_min_release_window(&3600);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(604800));
+
+    let attestation = delivered(&t, 1);
+    let signature = sign(&t, &t.attester, &attestation);
+    t.client.submit_delivery_attestation(
+        &attestation,
+        &public_key(&t.env, &t.attester),
+        &signature,
+    );
+
+    let escrow = t.client.get_escrow(&1);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.release_window, 3600);
+    assert!(!t.client.can_auto_release(&1));
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+    assert!(t.client.can_auto_release(&1));
+}
+
+#[test]
+fn test_attestation_rejects_unregistered_attester_and_replay() {
+    let t = setup_test();
+    let outsider = SigningKey::from_bytes(&[9u8; 32]);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(604800));
+    let attestation = delivered(&t, 1);
+
+    let outsider_signature = sign(&t, &outsider, &attestation);
+    assert_eq!(
+        t.client.try_submit_delivery_attestation(
+            &attestation,
+            &public_key(&t.env, &outsider),
+            &outsider_signature
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let signature = sign(&t, &t.attester, &attestation);
+    t.client.submit_delivery_attestation(
+        &attestation,
+        &public_key(&t.env, &t.attester),
+        &signature,
+    );
+    assert_eq!(
+        t.client.try_submit_delivery_attestation(
+            &attestation,
+            &public_key(&t.env, &t.attester),
+            &signature
+        ),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+
+    // Removing the key revokes it for future orders.
+    t.client
+        .set_delivery_attester(&t.admin, &public_key(&t.env, &t.attester), &false);
+    assert!(!t
+        .client
+        .is_delivery_attester(&public_key(&t.env, &t.attester)));
+}
+
+#[test]
+fn test_attestation_rejects_future_timestamp() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(604800));
+
+    let mut future = delivered(&t, 1);
+    future.timestamp += 60;
+    let signature = sign(&t, &t.attester, &future);
+    assert_eq!(
+        t.client.try_submit_delivery_attestation(
+            &future,
+            &public_key(&t.env, &t.attester),
+            &signature
+        ),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+    assert_eq!(t.client.get_delivery_attestation(&1), None);
+}
+
+#[test]
+#[should_panic]
+fn test_attestation_with_tampered_payload_panics() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(604800));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &Some(604800));
+
+    let signature = sign(&t, &t.attester, &delivered(&t, 1));
+    t.client.submit_delivery_attestation(
+        &delivered(&t, 2),
+        &public_key(&t.env, &t.attester),
+        &signature,
+    );
+}
@@ -0,0 +1,148 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, EscrowStatus, Resolution};
+use soroban_sdk::{
+    testutils::{Address as _, IssuerFlags, Ledger as _},
+    token, Address, Env, Symbol,
+};
+
+struct ClaimTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+    token_admin: token::StellarAssetClient<'static>,
+}
+
+fn setup_test() -> ClaimTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    // Revocable so tests can deauthorize a recipient's balance.
+    sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = sac.address();
+    let token_admin = token::StellarAssetClient::new(&env, &token);
+    token_admin.mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+
+    ClaimTest {
+        env,
+        client,
+        buyer,
+        seller,
+        token,
+        token_admin,
+    }
+}
+
+#[test]
+fn test_failed_push_falls_back_to_claim_ledger() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.token_admin.set_authorized(&t.seller, &false);
+
+    t.client.release_funds(&1);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.seller), 0);
+    assert_eq!(t.client.get_claimable_balance(&t.seller, &t.token), 9_500);
+    assert_eq!(t.client.get_total_claimable(&t.token), 9_500);
+
+    let destination = Address::generate(&t.env);
+    assert_eq!(
+        t.client
+            .claim(&t.seller, &t.token, &Some(destination.clone())),
+        9_500
+    );
+    assert_eq!(token_client.balance(&destination), 9_500);
+    assert_eq!(t.client.get_claimable_balance(&t.seller, &t.token), 0);
+    assert_eq!(t.client.get_total_claimable(&t.token), 0);
+
+    // Nothing left to claim.
+    assert_eq!(t.client.claim(&t.seller, &t.token, &None), 0);
+}
+
+#[test]
+fn test_blocked_buyer_does_not_stall_dispute_refund() {
+    let t = setup_test();
+    let arbitrator = t.client.get_platform_config().arbitrator;
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client
+        .dispute_escrow(&1, &Symbol::new(&t.env, "not_delivered"), &t.buyer);
+    t.token_admin.set_authorized(&t.buyer, &false);
+
+    t.client
+        .resolve_dispute(&1, &Resolution::RefundToBuyer, &arbitrator);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Resolved);
+    assert_eq!(t.client.get_claimable_balance(&t.buyer, &t.token), 10_000);
+}
+
+#[test]
+fn test_pull_payouts_opt_in() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    assert!(!t.client.is_pull_payouts_enabled(&t.seller));
+    t.client.set_pull_payouts(&t.seller, &true);
+    assert!(t.client.is_pull_payouts_enabled(&t.seller));
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client.release_funds(&1);
+
+    assert_eq!(token_client.balance(&t.seller), 0);
+    assert_eq!(t.client.claim(&t.seller, &t.token, &None), 9_500);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+
+    t.client.set_pull_payouts(&t.seller, &false);
+    assert!(!t.client.is_pull_payouts_enabled(&t.seller));
+}
+
+#[test]
+fn test_sweep_excludes_claimable_balances() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_pull_payouts(&t.buyer, &true);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client.refund(&1);
+    assert_eq!(t.client.get_total_claimable(&t.token), 10_000);
+
+    // Stray tokens sent directly to the contract are the only sweepable funds.
+    t.token_admin.mint(&t.client.address, &250);
+    let destination = Address::generate(&t.env);
+    assert_eq!(
+        t.client.sweep_unallocated_funds(&t.token, &destination),
+        250
+    );
+    assert_eq!(token_client.balance(&destination), 250);
+    assert_eq!(token_client.balance(&t.client.address), 10_000);
+
+    assert_eq!(t.client.claim(&t.buyer, &t.token, &None), 10_000);
+}
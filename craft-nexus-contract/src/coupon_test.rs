@@ -0,0 +1,360 @@
+#![cfg(test)]
+
+use crate::{
+    CouponDataKey, CouponError, CraftNexusContract, CraftNexusContractClient, DataKey, Error,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Bytes, BytesN, Env,
+};
+
+const DAY: u64 = 24 * 60 * 60;
+
+struct CouponTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    admin: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> CouponTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let asset = token::StellarAssetClient::new(&env, &token);
+    asset.mint(&buyer, &1_000_000);
+    asset.mint(&admin, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    CouponTest {
+        env,
+        client,
+        buyer,
+        seller,
+        admin,
+        platform_wallet,
+        token,
+    }
+}
+
+fn code(env: &Env) -> Bytes {
+    Bytes::from_slice(env, b"SPRING-CRAFT")
+}
+
+fn issue_coupon(t: &CouponTest, discount: i128, budget: i128, per_user_limit: u32) {
+    let code_hash: BytesN<32> = t.env.crypto().sha256(&code(&t.env)).into();
+    let expires_at = t.env.ledger().timestamp() + DAY;
+    t.client.create_coupon(
+        &t.admin,
+        &code_hash,
+        &t.token,
+        &discount,
+        &budget,
+        &per_user_limit,
+        &expires_at,
+    );
+}
+
+#[test]
+fn test_coupon_subsidizes_deposit_and_seller_gets_full_price() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.fund_promo_balance(&t.admin, &t.token, &5_000);
+    issue_coupon(&t, 2_000, 5_000, 1);
+
+    let escrow = t.client.create_escrow_with_coupon(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &None,
+        &code(&t.env),
+    );
+    assert_eq!(escrow.amount, 10_000);
+    assert_eq!(token_client.balance(&t.buyer), 992_000);
+    assert_eq!(t.client.get_promo_balance(&t.token), 3_000);
+
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    // Only the undrawn promo balance is left in the contract.
+    assert_eq!(token_client.balance(&t.client.address), 3_000);
+}
+
+#[test]
+fn test_refund_returns_subsidy_to_promo_balance() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.fund_promo_balance(&t.admin, &t.token, &5_000);
+    issue_coupon(&t, 2_000, 5_000, 1);
+    t.client.create_escrow_with_coupon(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &None,
+        &code(&t.env),
+    );
+
+    t.client.refund(&1);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000);
+    assert_eq!(t.client.get_promo_balance(&t.token), 5_000);
+
+    t.client
+        .withdraw_promo_balance(&t.admin, &t.token, &5_000, &t.admin);
+    assert_eq!(token_client.balance(&t.admin), 1_000_000);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+}
+
+#[test]
+fn test_partial_release_then_refund_keeps_promo_balance_solvent() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.fund_promo_balance(&t.admin, &t.token, &5_000);
+    issue_coupon(&t, 5_000, 5_000, 1);
+    t.client.create_escrow_with_coupon(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &None,
+        &code(&t.env),
+    );
+
+    // The released 8_000 spends 4_000 of the subsidy; the refunded 2_000
+    // returns the other 1_000 to the promo balance.
+    t.client.release_partial_funds(&1, &8_000);
+    t.client.refund(&1);
+    assert_eq!(token_client.balance(&t.seller), 7_600);
+    assert_eq!(token_client.balance(&t.buyer), 996_000);
+    assert_eq!(t.client.get_promo_balance(&t.token), 1_000);
+    assert_eq!(token_client.balance(&t.client.address), 1_000);
+
+    t.env.as_contract(&t.client.address, || {
+        let storage = t.env.storage().persistent();
+        assert_eq!(
+            storage
+                .get::<_, i128>(&DataKey::TotalLocked(t.token.clone()))
+                .unwrap_or(0),
+            0
+        );
+        assert!(!storage.has(&CouponDataKey::Subsidy(1)));
+    });
+}
+
+#[test]
+fn test_release_clears_spent_subsidy() {
+    let t = setup_test();
+
+    t.client.fund_promo_balance(&t.admin, &t.token, &5_000);
+    issue_coupon(&t, 2_000, 5_000, 1);
+    t.client.create_escrow_with_coupon(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &None,
+        &code(&t.env),
+    );
+
+    t.client.release_funds(&1);
+    t.env.as_contract(&t.client.address, || {
+        assert!(!t.env.storage().persistent().has(&CouponDataKey::Subsidy(1)));
+    });
+}
+
+#[test]
+fn test_coupon_limits_and_expiry() {
+    let t = setup_test();
+
+    t.client.fund_promo_balance(&t.admin, &t.token, &5_000);
+    issue_coupon(&t, 2_000, 3_000, 1);
+
+    assert_eq!(
+        t.client.try_create_escrow_with_coupon(
+            &t.buyer,
+            &t.seller,
+            &t.token,
+            &10_000,
+            &1,
+            &None,
+            &Bytes::from_slice(&t.env, b"WRONG"),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            CouponError::CouponNotFound as u32
+        )))
+    );
+
+    t.client.create_escrow_with_coupon(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &None,
+        &code(&t.env),
+    );
+    // Per-user limit reached.
+    assert_eq!(
+        t.client.try_create_escrow_with_coupon(
+            &t.buyer,
+            &t.seller,
+            &t.token,
+            &10_000,
+            &2,
+            &None,
+            &code(&t.env),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            CouponError::RedemptionLimitReached as u32
+        )))
+    );
+
+    // Budget of 3_000 cannot cover a second 2_000 discount.
+    let other_buyer = Address::generate(&t.env);
+    token::StellarAssetClient::new(&t.env, &t.token).mint(&other_buyer, &100_000);
+    assert_eq!(
+        t.client.try_create_escrow_with_coupon(
+            &other_buyer,
+            &t.seller,
+            &t.token,
+            &10_000,
+            &2,
+            &None,
+            &code(&t.env),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            CouponError::CouponBudgetExhausted as u32
+        )))
+    );
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += DAY;
+    });
+    assert_eq!(
+        t.client.try_create_escrow_with_coupon(
+            &other_buyer,
+            &t.seller,
+            &t.token,
+            &10_000,
+            &2,
+            &None,
+            &code(&t.env),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            CouponError::CouponExpired as u32
+        )))
+    );
+}
+
+#[test]
+fn test_coupon_requires_promo_balance() {
+    let t = setup_test();
+
+    issue_coupon(&t, 2_000, 5_000, 1);
+    assert_eq!(
+        t.client.try_create_escrow_with_coupon(
+            &t.buyer,
+            &t.seller,
+            &t.token,
+            &10_000,
+            &1,
+            &None,
+            &code(&t.env),
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            CouponError::PromoBalanceInsufficient as u32
+        )))
+    );
+    assert_eq!(
+        t.client
+            .try_withdraw_promo_balance(&t.admin, &t.token, &1, &t.admin),
+        Err(Ok(Error::InvalidRefundAmount))
+    );
+}
+
+#[test]
+fn test_create_coupon_validation() {
+    let t = setup_test();
+    let code_hash: BytesN<32> = t.env.crypto().sha256(&code(&t.env)).into();
+    let now = t.env.ledger().timestamp();
+
+    assert_eq!(
+        t.client.try_create_coupon(
+            &t.admin,
+            &code_hash,
+            &t.token,
+            &2_000,
+            &1_000,
+            &1,
+            &(now + DAY)
+        ),
+        Err(Ok(CouponError::InvalidCouponTerms))
+    );
+    assert_eq!(
+        t.client
+            .try_create_coupon(&t.admin, &code_hash, &t.token, &2_000, &5_000, &1, &now),
+        Err(Ok(CouponError::InvalidCouponExpiry))
+    );
+    issue_coupon(&t, 2_000, 5_000, 1);
+    assert_eq!(
+        t.client.try_create_coupon(
+            &t.admin,
+            &code_hash,
+            &t.token,
+            &2_000,
+            &5_000,
+            &1,
+            &(now + DAY)
+        ),
+        Err(Ok(CouponError::CouponAlreadyExists))
+    );
+}
+
+#[test]
+fn test_sweep_leaves_promo_balance_in_place() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let destination = Address::generate(&t.env);
+
+    t.client.fund_promo_balance(&t.admin, &t.token, &5_000);
+    // Stray tokens sent directly to the contract are the only sweepable funds.
+    token::StellarAssetClient::new(&t.env, &t.token).mint(&t.client.address, &700);
+
+    assert_eq!(
+        t.client.sweep_unallocated_funds(&t.token, &destination),
+        700
+    );
+    assert_eq!(token_client.balance(&destination), 700);
+    assert_eq!(t.client.get_promo_balance(&t.token), 5_000);
+    assert_eq!(token_client.balance(&t.client.address), 5_000);
+}
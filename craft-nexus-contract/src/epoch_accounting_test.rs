@@ -0,0 +1,133 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, Error, Resolution};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, Symbol,
+};
+
+const DAY: u64 = 86_400;
+
+struct EpochAccountingTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    admin: Address,
+    token: Address,
+}
+
+fn setup_test() -> EpochAccountingTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    EpochAccountingTest {
+        env,
+        client,
+        buyer,
+        seller,
+        admin,
+        token,
+    }
+}
+
+#[test]
+fn test_settlements_are_bucketed_in_current_epoch() {
+    let t = setup_test();
+    let epoch = t.client.get_current_epoch();
+    assert_eq!(t.client.get_epoch_length(), 30 * DAY);
+    assert_eq!(epoch, 1711368000 / (30 * DAY));
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &4_000, &2, &None);
+    t.client.refund(&2);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &2_000, &3, &None);
+    t.client
+        .dispute_escrow(&3, &Symbol::new(&t.env, "not_delivered"), &t.buyer);
+    t.client
+        .resolve_dispute(&3, &Resolution::RefundToBuyer, &t.admin);
+
+    let stats = t.client.get_epoch_stats(&t.token, &epoch, &epoch, &10);
+    assert_eq!(stats.len(), 1);
+    let stats = stats.get(0).unwrap();
+    assert_eq!(stats.epoch, epoch);
+    assert_eq!(stats.start, epoch * 30 * DAY);
+    assert_eq!(stats.fees, 500);
+    assert_eq!(stats.released_volume, 9_500);
+    assert_eq!(stats.refunded_volume, 6_000);
+    assert_eq!(stats.disputes, 1);
+    assert_eq!(t.client.get_total_fees_for_token(&t.token), stats.fees);
+}
+
+#[test]
+fn test_epoch_rollover_and_pagination() {
+    let t = setup_test();
+    t.client.set_epoch_length(&t.admin, &DAY);
+    let first = t.client.get_current_epoch();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 2 * DAY;
+    });
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &20_000, &2, &None);
+    t.client.release_funds(&2);
+
+    let stats = t
+        .client
+        .get_epoch_stats(&t.token, &first, &(first + 2), &10);
+    assert_eq!(stats.len(), 3);
+    assert_eq!(stats.get(0).unwrap().fees, 500);
+    assert_eq!(stats.get(1).unwrap().fees, 0);
+    assert_eq!(stats.get(1).unwrap().epoch, first + 1);
+    assert_eq!(stats.get(2).unwrap().fees, 1_000);
+    assert_eq!(stats.get(2).unwrap().released_volume, 19_000);
+
+    let page = t.client.get_epoch_stats(&t.token, &first, &(first + 2), &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().epoch, first + 1);
+    assert_eq!(
+        t.client
+            .get_epoch_stats(&t.token, &(first + 2), &first, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_set_epoch_length_rejects_zero() {
+    let t = setup_test();
+    assert_eq!(
+        t.client.try_set_epoch_length(&t.admin, &0),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+}
@@ -0,0 +1,303 @@
+#![cfg(test)]
+
+use crate::{AdminActionKind, CraftNexusContract, CraftNexusContractClient, Error};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct FeeChangeScheduleTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> FeeChangeScheduleTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    FeeChangeScheduleTest {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        platform_wallet,
+        token,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += seconds;
+    });
+}
+
+#[test]
+fn test_open_escrow_keeps_fee_from_creation() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.update_platform_fee(&1000);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &None);
+
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    t.client.release_funds(&2);
+    assert_eq!(token_client.balance(&t.platform_wallet), 1_500);
+}
+
+#[test]
+fn test_recurring_escrow_keeps_fee_from_creation() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    let escrow = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.seller, &t.token, &3_000, &DAY, &3);
+    t.client.update_platform_fee(&1000);
+
+    advance(&t.env, DAY);
+    t.client.release_next_cycle(&escrow.id);
+    assert_eq!(token_client.balance(&t.platform_wallet), 50);
+    assert_eq!(token_client.balance(&t.seller), 950);
+}
+
+#[test]
+fn test_scheduled_platform_fee_change_takes_effect() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let effective_at = t.env.ledger().timestamp() + DAY;
+
+    let id = t
+        .client
+        .schedule_platform_fee_change(&t.admin, &200, &effective_at);
+    let pending = t.client.get_pending_fee_changes();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().id, id);
+    assert_eq!(pending.get(0).unwrap().artisan, None);
+    assert_eq!(t.client.get_platform_fee(), 500);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+
+    advance(&t.env, DAY);
+    assert_eq!(t.client.get_pending_fee_changes().len(), 0);
+    // Due changes apply to new escrows before they are materialized.
+    assert_eq!(t.client.get_platform_fee(), 200);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &None);
+
+    assert_eq!(t.client.apply_fee_changes(), 1);
+    assert_eq!(t.client.get_platform_config().platform_fee_bps, 200);
+    assert_eq!(t.client.apply_fee_changes(), 0);
+
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    t.client.release_funds(&2);
+    assert_eq!(token_client.balance(&t.platform_wallet), 700);
+}
+
+#[test]
+fn test_notice_period_is_enforced() {
+    let t = setup_test();
+    let now = t.env.ledger().timestamp();
+
+    t.client
+        .set_fee_change_notice_period(&t.admin, &(7 * DAY as u32));
+    assert_eq!(t.client.get_fee_change_notice_period(), 7 * DAY as u32);
+
+    assert_eq!(
+        t.client
+            .try_schedule_platform_fee_change(&t.admin, &200, &(now + 6 * DAY)),
+        Err(Ok(Error::AdminActionTimelockActive))
+    );
+    assert_eq!(
+        t.client
+            .try_schedule_platform_fee_change(&t.admin, &5_000, &(now + 7 * DAY)),
+        Err(Ok(Error::InvalidFee))
+    );
+    assert!(t.client.try_update_platform_fee(&200).is_err());
+    assert!(t.client.try_set_artisan_fee_tier(&t.seller, &200).is_err());
+
+    t.client
+        .schedule_platform_fee_change(&t.admin, &200, &(now + 7 * DAY));
+    assert_eq!(t.client.get_pending_fee_changes().len(), 1);
+}
+
+#[test]
+fn test_notice_period_blocks_fee_admin_actions() {
+    let t = setup_test();
+    t.client.set_admin_action_timelock_delay(&0);
+    t.client
+        .set_fee_change_notice_period(&t.admin, &(7 * DAY as u32));
+
+    for action in [
+        AdminActionKind::SetPlatformFee(200),
+        AdminActionKind::SetArtisanFeeTier(t.seller.clone(), 200),
+    ] {
+        let proposal = t.client.propose_admin_action(&t.admin, &action);
+        assert_eq!(
+            t.client.try_execute_admin_action(&proposal.id),
+            Err(Ok(Error::AdminActionTimelockActive))
+        );
+    }
+    assert_eq!(t.client.get_platform_fee(), 500);
+}
+
+#[test]
+fn test_notice_period_blocks_unscheduled_fee_setters() {
+    let t = setup_test();
+    t.client.set_fee_token_config(&t.token, &true, &None);
+    t.client
+        .set_fee_change_notice_period(&t.admin, &(7 * DAY as u32));
+
+    assert_eq!(
+        t.client
+            .try_set_fee_schedule(&t.admin, &t.token, &100, &0, &0),
+        Err(Ok(Error::AdminActionTimelockActive))
+    );
+    assert_eq!(
+        t.client.try_clear_fee_schedule(&t.admin, &t.token),
+        Err(Ok(Error::AdminActionTimelockActive))
+    );
+    assert_eq!(
+        t.client
+            .try_set_volume_fee_tiers(&t.admin, &Vec::new(&t.env)),
+        Err(Ok(Error::AdminActionTimelockActive))
+    );
+    assert_eq!(
+        t.client
+            .try_set_fee_token_config(&t.token, &true, &Some(200)),
+        Err(Ok(Error::AdminActionTimelockActive))
+    );
+
+    // Toggling the token without touching its fee is not a fee change.
+    t.client.set_fee_token_config(&t.token, &false, &None);
+    assert_eq!(t.client.get_fee_schedule(&t.token), None);
+}
+
+#[test]
+fn test_lower_notice_period_waits_for_current_notice() {
+    let t = setup_test();
+    let now = t.env.ledger().timestamp();
+    t.client
+        .set_fee_change_notice_period(&t.admin, &(7 * DAY as u32));
+
+    // Dropping the notice does not allow an immediate fee change.
+    t.client.set_fee_change_notice_period(&t.admin, &0);
+    assert_eq!(t.client.get_fee_change_notice_period(), 7 * DAY as u32);
+    assert_eq!(
+        t.client.get_pending_notice_period(),
+        Some((0, now + 7 * DAY))
+    );
+    assert!(t.client.try_update_platform_fee(&200).is_err());
+
+    advance(&t.env, 7 * DAY);
+    assert_eq!(t.client.get_fee_change_notice_period(), 0);
+    assert_eq!(t.client.get_pending_notice_period(), None);
+    t.client.update_platform_fee(&200);
+
+    // Raising the notice applies at once and a later cut waits again.
+    t.client
+        .set_fee_change_notice_period(&t.admin, &(DAY as u32));
+    assert_eq!(t.client.get_fee_change_notice_period(), DAY as u32);
+    t.client.set_fee_change_notice_period(&t.admin, &3_600);
+    assert_eq!(t.client.get_fee_change_notice_period(), DAY as u32);
+    advance(&t.env, DAY);
+    assert_eq!(t.client.get_fee_change_notice_period(), 3_600);
+}
+
+#[test]
+fn test_immediate_update_supersedes_due_scheduled_change() {
+    let t = setup_test();
+    let now = t.env.ledger().timestamp();
+
+    t.client
+        .schedule_platform_fee_change(&t.admin, &900, &(now + DAY));
+    t.client
+        .schedule_artisan_fee_tier_change(&t.admin, &t.seller, &800, &(now + DAY));
+    t.client
+        .schedule_platform_fee_change(&t.admin, &700, &(now + 3 * DAY));
+    advance(&t.env, 2 * DAY);
+
+    t.client.update_platform_fee(&300);
+    assert_eq!(t.client.get_platform_fee(), 300);
+    t.client.set_artisan_fee_tier(&t.seller, &100);
+    assert_eq!(t.client.get_effective_fee_bps(&t.seller), 100);
+    assert_eq!(t.client.apply_fee_changes(), 0);
+
+    // Changes that are not due yet still apply later.
+    advance(&t.env, DAY);
+    assert_eq!(t.client.get_platform_fee(), 700);
+}
+
+#[test]
+fn test_cancel_and_artisan_tier_schedule() {
+    let t = setup_test();
+    let now = t.env.ledger().timestamp();
+
+    let platform_id = t
+        .client
+        .schedule_platform_fee_change(&t.admin, &900, &(now + 2 * DAY));
+    let tier_id =
+        t.client
+            .schedule_artisan_fee_tier_change(&t.admin, &t.seller, &100, &(now + DAY));
+    let pending = t.client.get_pending_fee_changes();
+    assert_eq!(pending.get(0).unwrap().id, tier_id);
+    assert_eq!(pending.get(1).unwrap().id, platform_id);
+
+    t.client.cancel_fee_change(&t.admin, &platform_id);
+    assert_eq!(
+        t.client.try_cancel_fee_change(&t.admin, &platform_id),
+        Err(Ok(Error::ProposalNotFound))
+    );
+
+    advance(&t.env, DAY);
+    assert_eq!(t.client.get_effective_fee_bps(&t.seller), 100);
+    assert_eq!(
+        t.client
+            .get_effective_fee_bps_for_token(&t.seller, &t.token),
+        100
+    );
+    assert_eq!(
+        t.client.try_cancel_fee_change(&t.admin, &tier_id),
+        Err(Ok(Error::ProposalNotFound))
+    );
+
+    advance(&t.env, DAY);
+    assert_eq!(t.client.apply_fee_changes(), 1);
+    assert_eq!(t.client.get_platform_fee(), 500);
+    assert_eq!(t.client.get_effective_fee_bps(&t.seller), 100);
+}
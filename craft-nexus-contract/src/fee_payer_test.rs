@@ -0,0 +1,175 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, DataKey, EscrowFeeTerms, ExpiredDisputeFeePolicy,
+    FeePayer, FeePayerError, FeeTokenInfoV1,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, Symbol,
+};
+
+struct FeePayerTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> FeePayerTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    FeePayerTest {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        platform_wallet,
+        token,
+    }
+}
+
+#[test]
+fn test_buyer_pays_fee_on_top_of_price() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_fee_payer(&t.admin, &FeePayer::Buyer);
+    let escrow = t
+        .client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    assert_eq!(escrow.amount, 10_500);
+    assert_eq!(token_client.balance(&t.buyer), 989_500);
+    assert_eq!(
+        t.client.get_escrow_fee_terms(&1),
+        Some(EscrowFeeTerms {
+            payer: FeePayer::Buyer,
+            buyer_fee: 500,
+        })
+    );
+
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 10_000);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+}
+
+#[test]
+fn test_split_fee_per_token_overrides_global_model() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_fee_payer(&t.admin, &FeePayer::Buyer);
+    t.client
+        .set_fee_token_payer(&t.admin, &t.token, &Some(FeePayer::Split));
+    assert_eq!(t.client.get_token_fee_payer(&t.token), FeePayer::Split);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    assert_eq!(token_client.balance(&t.buyer), 989_750);
+
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 9_750);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+
+    // Clearing the override falls back to the global model.
+    t.client.set_fee_token_payer(&t.admin, &t.token, &None);
+    assert_eq!(t.client.get_token_fee_payer(&t.token), FeePayer::Buyer);
+}
+
+#[test]
+fn test_refunds_return_buyer_fee_portion() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_fee_payer(&t.admin, &FeePayer::Buyer);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.refund(&1);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000);
+
+    // An expired dispute charging the buyer takes the fee on the price and
+    // hands back the deposited fee.
+    t.client
+        .update_expired_dispute_policy(&ExpiredDisputeFeePolicy::RefundMinusPlatformFee);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &None);
+    t.client
+        .dispute_escrow(&2, &Symbol::new(&t.env, "not_delivered"), &t.buyer);
+    t.client.set_max_dispute_duration(&86_400);
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 86_400;
+    });
+    t.client.resolve_expired_dispute(&2);
+    assert_eq!(token_client.balance(&t.buyer), 999_500);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+}
+
+#[test]
+fn test_partial_release_and_amendment_rejected_under_buyer_fee() {
+    let t = setup_test();
+
+    t.client.set_fee_payer(&t.admin, &FeePayer::Buyer);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    let unsupported = Err(Ok(FeePayerError::BuyerFeeEscrowUnsupported.into()));
+    assert_eq!(t.client.try_release_partial_funds(&1, &1_000), unsupported);
+    assert_eq!(t.client.try_amend_escrow_amount(&1, &12_000), unsupported);
+}
+
+#[test]
+fn test_legacy_fee_token_config_reads_without_payer() {
+    let t = setup_test();
+
+    t.env.as_contract(&t.client.address, || {
+        t.env.storage().persistent().set(
+            &DataKey::FeeTokenConfig(t.token.clone()),
+            &FeeTokenInfoV1 {
+                active: true,
+                custom_fee_bps: Some(250),
+                accumulated: 42,
+            },
+        );
+    });
+
+    let info = t.client.get_fee_token_config(&t.token).unwrap();
+    assert_eq!(info.custom_fee_bps, Some(250));
+    assert_eq!(info.accumulated, 42);
+    assert!(!info.custom_fee_payer);
+    assert_eq!(t.client.get_token_fee_payer(&t.token), FeePayer::Seller);
+
+    t.client
+        .set_fee_token_payer(&t.admin, &t.token, &Some(FeePayer::Buyer));
+    let info = t.client.get_fee_token_config(&t.token).unwrap();
+    assert_eq!(info.accumulated, 42);
+    assert!(info.custom_fee_payer);
+    assert_eq!(info.fee_payer, FeePayer::Buyer);
+}
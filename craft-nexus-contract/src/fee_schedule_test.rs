@@ -0,0 +1,202 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, Error, FeeAllocation, FeePayer};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env,
+};
+
+struct FeeScheduleTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> FeeScheduleTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    FeeScheduleTest {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        platform_wallet,
+        token,
+    }
+}
+
+fn release_quote(platform_fee: i128, seller_amount: i128) -> FeeAllocation {
+    FeeAllocation {
+        platform_fee,
+        seller_amount,
+        buyer_amount: 0,
+        referral_fee: 0,
+        keeper_fee: 0,
+    }
+}
+
+#[test]
+fn test_schedule_applies_flat_fee_with_minimum_and_maximum() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .set_fee_schedule(&t.admin, &t.token, &100, &300, &1_000);
+
+    // 5% of 1_000 plus 100 is raised to the 300 minimum.
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &1_000),
+        release_quote(300, 700)
+    );
+    // 5% of 100_000 plus 100 is capped at 1_000.
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &100_000),
+        release_quote(1_000, 99_000)
+    );
+    // 5% of 10_000 plus 100 sits between the bounds.
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &10_000),
+        release_quote(600, 9_400)
+    );
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &1_000, &1, &None);
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 700);
+    assert_eq!(token_client.balance(&t.platform_wallet), 300);
+
+    // The minimum never takes more than the amount itself.
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &50),
+        release_quote(50, 0)
+    );
+}
+
+#[test]
+fn test_open_escrow_keeps_fee_schedule_from_creation() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_fee_schedule(&t.admin, &t.token, &100, &0, &0);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.clear_fee_schedule(&t.admin, &t.token);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &None);
+    t.client
+        .set_fee_schedule(&t.admin, &t.token, &0, &2_000, &0);
+
+    // 5% plus the 100 flat fee in force at creation.
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.platform_wallet), 600);
+    // Created without a schedule, so the later 2_000 minimum does not apply.
+    t.client.release_funds(&2);
+    assert_eq!(token_client.balance(&t.platform_wallet), 1_100);
+}
+
+#[test]
+fn test_bps_component_follows_tier_then_token_then_platform() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client
+            .get_effective_fee_bps_for_token(&t.seller, &t.token),
+        500
+    );
+    t.client.set_fee_token_config(&t.token, &true, &Some(200));
+    assert_eq!(
+        t.client
+            .get_effective_fee_bps_for_token(&t.seller, &t.token),
+        200
+    );
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &10_000),
+        release_quote(200, 9_800)
+    );
+
+    t.client.set_artisan_fee_tier(&t.seller, &100);
+    assert_eq!(
+        t.client
+            .get_effective_fee_bps_for_token(&t.seller, &t.token),
+        100
+    );
+
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 9_900);
+}
+
+#[test]
+fn test_quote_includes_buyer_fee_share() {
+    let t = setup_test();
+
+    t.client.set_fee_schedule(&t.admin, &t.token, &100, &0, &0);
+    t.client.set_fee_payer(&t.admin, &FeePayer::Buyer);
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &10_000),
+        release_quote(600, 10_000)
+    );
+
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    assert_eq!(token_client.balance(&t.buyer), 989_400);
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 10_000);
+    assert_eq!(token_client.balance(&t.platform_wallet), 600);
+}
+
+#[test]
+fn test_fee_schedule_validation_and_clear() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client
+            .try_set_fee_schedule(&t.admin, &t.token, &0, &500, &100),
+        Err(Ok(Error::InvalidFee))
+    );
+    assert_eq!(
+        t.client
+            .try_set_fee_schedule(&t.admin, &t.token, &-1, &0, &0),
+        Err(Ok(Error::InvalidFee))
+    );
+
+    t.client.set_fee_schedule(&t.admin, &t.token, &100, &0, &0);
+    assert!(t.client.get_fee_schedule(&t.token).is_some());
+    t.client.clear_fee_schedule(&t.admin, &t.token);
+    assert_eq!(t.client.get_fee_schedule(&t.token), None);
+    assert_eq!(
+        t.client.quote_fee_allocation(&t.seller, &t.token, &10_000),
+        release_quote(500, 9_500)
+    );
+}
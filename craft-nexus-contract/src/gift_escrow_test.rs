@@ -0,0 +1,169 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, EscrowStatus, EscrowV4, Resolution, ESCROW,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, Symbol,
+};
+
+struct GiftEscrowTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    payer: Address,
+    buyer: Address,
+    seller: Address,
+    admin: Address,
+    token: Address,
+}
+
+fn setup_test() -> GiftEscrowTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let payer = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&payer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    GiftEscrowTest {
+        env,
+        client,
+        payer,
+        buyer,
+        seller,
+        admin,
+        token,
+    }
+}
+
+#[test]
+fn test_gift_escrow_is_funded_by_payer_and_released_by_buyer() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    let escrow = t.client.create_gift_escrow(
+        &t.payer, &t.buyer, &t.seller, &t.token, &10_000, &1, &None, &None,
+    );
+    assert_eq!(escrow.payer, Some(t.payer.clone()));
+    assert_eq!(escrow.buyer, t.buyer);
+    assert_eq!(token_client.balance(&t.payer), 990_000);
+    assert_eq!(token_client.balance(&t.buyer), 0);
+
+    t.client.release_funds(&1);
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+}
+
+#[test]
+fn test_gift_escrow_refunds_go_to_payer_or_refund_address() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let refund_address = Address::generate(&t.env);
+
+    t.client.create_gift_escrow(
+        &t.payer, &t.buyer, &t.seller, &t.token, &10_000, &1, &None, &None,
+    );
+    t.client.create_gift_escrow(
+        &t.payer,
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &2,
+        &None,
+        &Some(refund_address.clone()),
+    );
+
+    t.client.refund(&1);
+    assert_eq!(token_client.balance(&t.payer), 990_000);
+    assert_eq!(token_client.balance(&t.buyer), 0);
+
+    t.client.refund(&2);
+    assert_eq!(token_client.balance(&refund_address), 10_000);
+    assert_eq!(token_client.balance(&t.payer), 990_000);
+}
+
+#[test]
+fn test_gift_escrow_dispute_refund_goes_to_payer() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.create_gift_escrow(
+        &t.payer, &t.buyer, &t.seller, &t.token, &10_000, &1, &None, &None,
+    );
+    t.client
+        .dispute_escrow(&1, &Symbol::new(&t.env, "not_delivered"), &t.buyer);
+    t.client
+        .resolve_dispute(&1, &Resolution::RefundToBuyer, &t.admin);
+
+    assert_eq!(token_client.balance(&t.payer), 1_000_000);
+    assert_eq!(token_client.balance(&t.buyer), 0);
+}
+
+#[test]
+fn test_gift_escrow_rejects_seller_as_payer() {
+    let t = setup_test();
+    token::StellarAssetClient::new(&t.env, &t.token).mint(&t.seller, &10_000);
+
+    assert_eq!(
+        t.client.try_create_gift_escrow(
+            &t.seller, &t.buyer, &t.seller, &t.token, &10_000, &1, &None, &None,
+        ),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::SameBuyerSeller as u32
+        )))
+    );
+}
+
+#[test]
+fn test_v4_escrow_is_migrated_without_payer() {
+    let t = setup_test();
+
+    let stored = EscrowV4 {
+        version: 4,
+        id: 7,
+        batch_id: None,
+        buyer: t.buyer.clone(),
+        seller: t.seller.clone(),
+        token: t.token.clone(),
+        amount: 500,
+        status: EscrowStatus::Active,
+        release_window: 3600,
+        created_at: 10,
+        ipfs_hash: None,
+        metadata_hash: None,
+        dispute_reason: None,
+        dispute_initiated_at: None,
+        funded: true,
+        funding_deadline: None,
+    };
+    t.env.as_contract(&t.client.address, || {
+        t.env.storage().persistent().set(&(ESCROW, 7u32), &stored);
+    });
+
+    let escrow = t.client.get_escrow(&7);
+    assert_eq!(escrow.version, 5);
+    assert_eq!(escrow.amount, 500);
+    assert_eq!(escrow.payer, None);
+    assert_eq!(escrow.refund_address, None);
+}
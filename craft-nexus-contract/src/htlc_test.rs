@@ -0,0 +1,210 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, EscrowStatus, HtlcError,
+    MetadataRevealProof, Resolution,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Bytes, Env, Symbol,
+};
+
+const TIMEOUT: u64 = 3 * 24 * 60 * 60;
+
+struct HtlcTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    arbitrator: Address,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+}
+
+fn setup_test() -> HtlcTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    HtlcTest {
+        env,
+        client,
+        arbitrator,
+        buyer,
+        seller,
+        token,
+    }
+}
+
+fn secret(env: &Env) -> Bytes {
+    Bytes::from_slice(env, b"pickup-code-4821")
+}
+
+fn create_locked(t: &HtlcTest, order_id: u32) {
+    let secret_hash: Bytes = t.env.crypto().sha256(&secret(&t.env)).into();
+    let timeout = t.env.ledger().timestamp() + TIMEOUT;
+    t.client.create_htlc_escrow(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &order_id,
+        &secret_hash,
+        &timeout,
+    );
+}
+
+fn proof(content: Bytes) -> MetadataRevealProof {
+    MetadataRevealProof {
+        content,
+        secret: None,
+    }
+}
+
+#[test]
+fn test_seller_claims_htlc_escrow_with_secret() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    create_locked(&t, 1);
+    t.client.claim_htlc_escrow(&1, &proof(secret(&t.env)));
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+    assert_eq!(t.client.get_htlc_lock(&1), None);
+}
+
+#[test]
+fn test_htlc_escrow_rejects_wrong_secret_and_late_claim() {
+    let t = setup_test();
+
+    create_locked(&t, 1);
+    assert_eq!(
+        t.client
+            .try_claim_htlc_escrow(&1, &proof(Bytes::from_slice(&t.env, b"guess"))),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += TIMEOUT;
+    });
+    assert_eq!(
+        t.client.try_claim_htlc_escrow(&1, &proof(secret(&t.env))),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+}
+
+#[test]
+fn test_buyer_reclaims_htlc_escrow_after_timeout() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    create_locked(&t, 1);
+    assert_eq!(
+        t.client.try_reclaim_htlc_escrow(&1),
+        Err(Ok(Error::ReleaseWindowNotElapsed))
+    );
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += TIMEOUT;
+    });
+    t.client.reclaim_htlc_escrow(&1);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Refunded);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000);
+}
+
+#[test]
+fn test_htlc_escrow_is_not_auto_released() {
+    let t = setup_test();
+
+    create_locked(&t, 1);
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += TIMEOUT;
+    });
+
+    assert!(!t.client.can_auto_release(&1));
+    assert!(t.client.try_auto_release(&1).is_err());
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Active);
+}
+
+#[test]
+fn test_create_htlc_escrow_rejects_malformed_lock() {
+    let t = setup_test();
+    let secret_hash: Bytes = t.env.crypto().sha256(&secret(&t.env)).into();
+    let timeout = t.env.ledger().timestamp() + TIMEOUT;
+
+    assert_eq!(
+        t.client
+            .try_create_htlc_escrow(
+                &t.buyer,
+                &t.seller,
+                &t.token,
+                &10_000,
+                &1,
+                &secret(&t.env),
+                &timeout,
+            )
+            .unwrap_err(),
+        Ok(HtlcError::InvalidSecretHash.into())
+    );
+    assert_eq!(
+        t.client
+            .try_create_htlc_escrow(
+                &t.buyer,
+                &t.seller,
+                &t.token,
+                &10_000,
+                &1,
+                &secret_hash,
+                &t.env.ledger().timestamp(),
+            )
+            .unwrap_err(),
+        Ok(HtlcError::InvalidHtlcTimeout.into())
+    );
+}
+
+#[test]
+fn test_htlc_lock_cleared_on_buyer_release() {
+    let t = setup_test();
+
+    create_locked(&t, 1);
+    t.client.release_funds(&1);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(t.client.get_htlc_lock(&1), None);
+}
+
+#[test]
+fn test_htlc_lock_cleared_on_dispute_resolution() {
+    let t = setup_test();
+
+    create_locked(&t, 1);
+    t.client
+        .dispute_escrow(&1, &Symbol::new(&t.env, "no_show"), &t.buyer);
+    t.client
+        .resolve_dispute(&1, &Resolution::RefundToBuyer, &t.arbitrator);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Resolved);
+    assert_eq!(t.client.get_htlc_lock(&1), None);
+}
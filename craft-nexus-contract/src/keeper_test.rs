@@ -0,0 +1,230 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, EscrowStatus, ExpiredDisputeFeePolicy,
+    KeeperAction,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env, Symbol,
+};
+
+struct KeeperTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    keeper: Address,
+    token: Address,
+    platform_wallet: Address,
+}
+
+fn setup_test() -> KeeperTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+    client.set_keeper_bounty_bps(&admin, &1_000);
+
+    KeeperTest {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        keeper,
+        token,
+        platform_wallet,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp += seconds;
+    });
+}
+
+#[test]
+fn test_keeper_release_pays_bounty_from_platform_fee() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    advance(&t.env, 3601);
+
+    let processed = t
+        .client
+        .keeper_process(&t.keeper, &KeeperAction::Release, &vec![&t.env, 1]);
+    assert_eq!(processed, vec![&t.env, 1]);
+
+    // 5% fee = 500; 10% of the fee goes to the keeper.
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.keeper), 50);
+    assert_eq!(token_client.balance(&t.platform_wallet), 450);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+    assert_eq!(t.client.get_total_fees_for_token(&t.token), 450);
+}
+
+#[test]
+fn test_keeper_skips_ineligible_and_duplicate_orders() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &Some(7200));
+    advance(&t.env, 3601);
+
+    // Order 2 is still inside its window, order 1 is listed twice and
+    // order 99 does not exist.
+    let processed = t.client.keeper_process(
+        &t.keeper,
+        &KeeperAction::Release,
+        &vec![&t.env, 1, 2, 1, 99],
+    );
+    assert_eq!(processed, vec![&t.env, 1]);
+    assert_eq!(token_client.balance(&t.keeper), 50);
+    assert_eq!(t.client.get_escrow(&2).status, EscrowStatus::Active);
+
+    // A second keeper run cannot claim the bounty again.
+    let processed = t
+        .client
+        .keeper_process(&t.keeper, &KeeperAction::Release, &vec![&t.env, 1]);
+    assert_eq!(processed.len(), 0);
+    assert_eq!(token_client.balance(&t.keeper), 50);
+}
+
+#[test]
+fn test_keeper_cancels_expired_unfunded_escrow_without_bounty() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.create_unfunded_escrow(
+        &1, &t.buyer, &t.seller, &t.token, &10_000, &3600, &None, &None,
+    );
+
+    let processed =
+        t.client
+            .keeper_process(&t.keeper, &KeeperAction::CancelUnfunded, &vec![&t.env, 1]);
+    assert_eq!(processed.len(), 0);
+
+    advance(&t.env, 24 * 60 * 60);
+    let processed =
+        t.client
+            .keeper_process(&t.keeper, &KeeperAction::CancelUnfunded, &vec![&t.env, 1]);
+    assert_eq!(processed, vec![&t.env, 1]);
+    assert_eq!(
+        t.client.try_get_escrow(&1),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::EscrowNotFound as u32
+        )))
+    );
+    assert_eq!(token_client.balance(&t.keeper), 0);
+}
+
+#[test]
+fn test_keeper_resolves_expired_dispute() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .update_expired_dispute_policy(&ExpiredDisputeFeePolicy::RefundMinusPlatformFee);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client
+        .dispute_escrow(&1, &Symbol::new(&t.env, "not_delivered"), &t.buyer);
+    t.client.set_max_dispute_duration(&86_400);
+    advance(&t.env, 86_400);
+
+    let processed = t.client.keeper_process(
+        &t.keeper,
+        &KeeperAction::ResolveExpiredDispute,
+        &vec![&t.env, 1],
+    );
+    assert_eq!(processed, vec![&t.env, 1]);
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Resolved);
+    assert_eq!(token_client.balance(&t.keeper), 50);
+    assert_eq!(token_client.balance(&t.platform_wallet), 450);
+}
+
+#[test]
+fn test_get_keeper_eligible_escrows() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &Some(7200));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &3, &Some(3600));
+
+    assert_eq!(
+        t.client
+            .get_keeper_eligible_escrows(&KeeperAction::Release, &0, &10)
+            .len(),
+        0
+    );
+
+    advance(&t.env, 3601);
+    assert_eq!(
+        t.client
+            .get_keeper_eligible_escrows(&KeeperAction::Release, &0, &10),
+        vec![&t.env, 1, 3]
+    );
+    assert_eq!(
+        t.client
+            .get_keeper_eligible_escrows(&KeeperAction::Release, &1, &2),
+        vec![&t.env, 3]
+    );
+    assert_eq!(
+        t.client
+            .get_keeper_eligible_escrows(&KeeperAction::ResolveExpiredDispute, &0, &10)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_keeper_bounty_config_validation() {
+    let t = setup_test();
+
+    assert_eq!(t.client.get_keeper_bounty_bps(), 1_000);
+    assert_eq!(
+        t.client.try_set_keeper_bounty_bps(&t.admin, &2_001),
+        Err(Ok(Error::InvalidFee))
+    );
+    t.client.set_keeper_bounty_bps(&t.admin, &0);
+    assert_eq!(t.client.get_keeper_bounty_bps(), 0);
+
+    let too_many = soroban_sdk::Vec::from_array(&t.env, [1u32; 21]);
+    assert_eq!(
+        t.client
+            .try_keeper_process(&t.keeper, &KeeperAction::Release, &too_many),
+        Err(Ok(Error::BatchLimitExceeded))
+    );
+}
@@ -8,22 +8,73 @@ use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes,
     BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
+use soroban_sdk::xdr::ToXdr;
 extern crate alloc;
 
+#[cfg(test)]
+mod amendment_test;
+#[cfg(test)]
+mod attestation_test;
+#[cfg(test)]
+mod claim_ledger_test;
+#[cfg(test)]
+mod coupon_test;
 #[cfg(test)]
 mod enhanced_features_test;
 #[cfg(test)]
+mod epoch_accounting_test;
+#[cfg(test)]
 mod event_snapshot_test;
 #[cfg(test)]
 mod expired_dispute_fee_test;
 #[cfg(test)]
+mod fee_change_schedule_test;
+#[cfg(test)]
+mod fee_payer_test;
+#[cfg(test)]
+mod fee_schedule_test;
+#[cfg(test)]
+mod gift_escrow_test;
+#[cfg(test)]
+mod htlc_test;
+#[cfg(test)]
+mod keeper_test;
+#[cfg(test)]
 mod min_release_window_test;
 #[cfg(test)]
+mod partial_release_test;
+#[cfg(test)]
+mod receivable_test;
+#[cfg(test)]
+mod recurring_dispute_test;
+#[cfg(test)]
+mod recurring_index_test;
+#[cfg(test)]
+mod recurring_pause_test;
+#[cfg(test)]
+mod recurring_schedule_test;
+#[cfg(test)]
 mod reentrancy_test;
 #[cfg(test)]
+mod referral_test;
+#[cfg(test)]
+mod release_extension_test;
+#[cfg(test)]
+mod role_test;
+#[cfg(test)]
 mod scalability_test;
 #[cfg(test)]
+mod seller_auto_release_test;
+#[cfg(test)]
+mod stream_test;
+#[cfg(test)]
 mod test;
+#[cfg(test)]
+mod tip_test;
+#[cfg(test)]
+mod treasury_test;
+#[cfg(test)]
+mod volume_fee_tier_test;
 // Onboarding is a separate logical contract; only one `#[contract]` may be linked per WASM
 // artifact. Keep it in this crate for host tests (`cargo test`) but omit from guest builds.
 #[cfg(not(target_family = "wasm"))]
@@ -177,6 +228,57 @@ pub fn is_retryable(error: Error) -> bool {
     )
 }
 
+/// Coupon errors. [`Error`] has no free codes left, so these live in their
+/// own enum starting at 100. None of them are retryable.
+#[contracterror]
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+#[repr(u32)]
+pub enum CouponError {
+    /// No coupon exists for the code
+    CouponNotFound = 100,
+    /// The coupon has expired
+    CouponExpired = 101,
+    /// The coupon was issued for a different token
+    CouponTokenMismatch = 102,
+    /// The buyer has used the coupon as often as it allows
+    RedemptionLimitReached = 103,
+    /// The coupon's remaining budget cannot cover the discount
+    CouponBudgetExhausted = 104,
+    /// The token's promo balance cannot cover the discount
+    PromoBalanceInsufficient = 105,
+    /// A coupon already exists for the code
+    CouponAlreadyExists = 106,
+    /// Discount, budget or per-user limit is invalid
+    InvalidCouponTerms = 107,
+    /// Coupon expiry is not in the future
+    InvalidCouponExpiry = 108,
+}
+
+/// Fee payer errors. [`Error`] has no free codes left, so these live in
+/// their own enum starting at 110.
+#[contracterror]
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+#[repr(u32)]
+pub enum FeePayerError {
+    /// The buyer deposited part of the escrow's fee, which only a full
+    /// release or refund can settle
+    BuyerFeeEscrowUnsupported = 110,
+}
+
+/// Hash-time-lock errors, numbered from 120 for the same reason.
+#[contracterror]
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+#[repr(u32)]
+pub enum HtlcError {
+    /// The secret hash is not a 32-byte SHA-256 digest
+    InvalidSecretHash = 120,
+    /// The timeout is not in the future
+    InvalidHtlcTimeout = 121,
+}
+
 const ESCROW: Symbol = symbol_short!("ESCROW");
 const PLATFORM_FEE: Symbol = symbol_short!("PLAT_FEE");
 const PLATFORM_WALLET: Symbol = symbol_short!("PLAT_WAL");
@@ -256,7 +358,7 @@ const ABSOLUTE_MAX_RELEASE_WINDOW: u32 = 365 * 24 * 60 * 60;
 /// Maximum platform fee in basis points (10000 = 100%)
 const MAX_PLATFORM_FEE_BPS: u32 = 1000; // 10% max
 const MAX_TOTAL_RELEASE_WINDOW: u32 = 2592000; // 30 days
-const CURRENT_ESCROW_VERSION: u32 = 4;
+const CURRENT_ESCROW_VERSION: u32 = 5;
 /// Explicit storage layout version for persisted contract state.
 ///
 /// New deployments initialize this to `CURRENT_STORAGE_LAYOUT_VERSION`; legacy
@@ -279,6 +381,25 @@ const UNFUNDED_CANCEL_TIMEOUT: u64 = 24 * 60 * 60;
 /// fast with `Error::RecurringEscrowIdExhausted` instead of silently
 /// colliding with an existing entry.
 const MAX_RECURRING_ESCROW_ID: u64 = u64::MAX - 1;
+/// Ceiling for the keeper bounty, as a share of the platform fee (20%).
+const MAX_KEEPER_BOUNTY_BPS: u32 = 2_000;
+/// Ceiling for the referral share, as a share of the platform fee (20%).
+const MAX_REFERRAL_SHARE_BPS: u32 = 2_000;
+/// Maximum number of assignments retained per escrow in the receivable
+/// history. Older records are dropped FIFO.
+const MAX_RECEIVABLE_ASSIGNMENTS: u32 = 10;
+/// Maximum number of fee changes that may be scheduled at once.
+const MAX_PENDING_FEE_CHANGES: u32 = 20;
+/// Maximum number of rows in the volume-based fee tier table.
+pub const MAX_VOLUME_FEE_TIERS: u32 = 10;
+/// Maximum number of treasury recipients.
+pub const MAX_TREASURY_RECIPIENTS: u32 = 10;
+/// Maximum number of cycles in a variable-amount recurring schedule.
+pub const MAX_RECURRING_SCHEDULE_LEN: u32 = 52;
+/// Default reporting epoch length (30 days).
+const DEFAULT_EPOCH_LENGTH: u64 = 30 * 24 * 60 * 60;
+/// Release window applied once a registered attester confirms delivery (24 hours).
+const DEFAULT_ATTESTED_RELEASE_WINDOW: u32 = 24 * 60 * 60;
 /// Deterministic fee policy version. Bump when fee allocation formulas change.
 const FEE_POLICY_VERSION: u32 = 1;
 /// Maximum number of upgrade records retained in `UpgradeHistory`. Older
@@ -305,6 +426,56 @@ const ADMIN_RECOVERY_DELAY: u64 = 7 * 24 * 60 * 60;
 const MIN_ADMIN_RECOVERY_COOLDOWN: u64 = 7 * 24 * 60 * 60;
 /// Default timelock delay for pending critical admin actions (24 hours).
 const DEFAULT_ADMIN_ACTION_TIMELOCK_DELAY: u64 = 24 * 60 * 60;
+/// Maximum number of addresses holding one role.
+pub const MAX_ROLE_MEMBERS: u32 = 20;
+
+/// Named permissions that the admin can grant to other addresses through
+/// `AdminActionKind::GrantRole` proposals. The admin holds every role.
+///
+/// Admin key and multisig management, upgrade signers and execution, storage
+/// migrations, fund-moving calls (`refund`, `auto_cancel_unfunded`,
+/// `sweep_unallocated_funds`, `update_platform_wallet`) and the addresses and
+/// windows that bind escrow counterparties stay admin-only; the README lists
+/// each of them.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+#[repr(u32)]
+pub enum Role {
+    /// Platform, artisan, volume and token fee settings, the fee change
+    /// notice period, the treasury split, coupons and the promo balance, and
+    /// the expired dispute fee policy.
+    FeeManager = 0,
+    /// Token whitelist, per-token minimums and fee token configs.
+    TokenManager = 1,
+    /// Pausing and unpausing the platform.
+    Pauser = 2,
+    /// Resolving escrow and recurring cycle disputes, alongside the
+    /// arbitrator and moderator, and refunding an active escrow.
+    DisputeResolver = 3,
+    /// Opening a WASM upgrade round; approvals still come from the upgrade
+    /// signers.
+    UpgradeProposer = 4,
+    /// Release, dispute and stake windows, delivery attesters, the
+    /// onboarding contract and moderator, the reporting epoch length, the
+    /// recurring escrow pause limit and dispute policy, and cancelling
+    /// unfunded escrows past their deadline.
+    Operator = 5,
+    /// One-off storage migrations run after an upgrade.
+    Migrator = 6,
+    /// Redirecting fee income to a new platform wallet and sweeping
+    /// unallocated funds.
+    Treasurer = 7,
+}
+
+/// Storage keys for the role registry.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum RoleDataKey {
+    /// Addresses granted a role.
+    Members(Role),
+}
 
 /// The kind of critical admin action that requires multi-sig approval
 /// and timelock enforcement.
@@ -328,6 +499,8 @@ pub enum AdminActionKind {
     SetMinReleaseWindow(u32),
     SetOnboardingContract(Address),
     SetExpiredDisputePolicy(ExpiredDisputeFeePolicy),
+    GrantRole(Role, Address),
+    RevokeRole(Role, Address),
 }
 
 /// A pending critical admin action proposal that requires multi-sig
@@ -360,6 +533,213 @@ pub enum AdminActionDataKey {
     AdminActionTimelockDelay,
 }
 
+/// Storage keys for referral payouts carved out of the platform fee.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ReferralDataKey {
+    /// Share of the platform fee (in bps of the fee) paid to referrers.
+    ShareBps,
+    /// Referrer recorded for an escrow order; overrides the seller's profile referrer.
+    EscrowReferrer(u32),
+    /// Cumulative referral payouts per token. Kept apart from `DataKey::TotalFees`.
+    TotalReferralFees(Address),
+    /// Cumulative referral payouts per (referrer, token).
+    ReferrerEarnings(Address, Address),
+}
+
+/// Storage keys for the pull-based claim ledger.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ClaimDataKey {
+    /// Claimable balance per (recipient, token).
+    Claimable(Address, Address),
+    /// Sum of all claimable balances for a token. Excluded from sweeps.
+    TotalClaimable(Address),
+    /// Recipient opted into pull payouts for every settlement.
+    PullPayouts(Address),
+}
+
+/// Storage keys for permissionless keeper processing.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum KeeperDataKey {
+    /// Share of the platform fee (in bps of the fee) paid to the keeper.
+    BountyBps,
+}
+
+/// Storage keys for signed delivery attestations.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum AttestationDataKey {
+    /// Registered attester ed25519 public key.
+    Attester(BytesN<32>),
+    /// Seconds from the attested delivery time until release (instance).
+    ReleaseWindow,
+    /// Accepted attestation for an order; at most one per escrow.
+    Attestation(u32),
+}
+
+/// Storage keys for hash-time-locked escrows.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum HtlcDataKey {
+    /// Hash lock of an escrow created with `create_htlc_escrow`.
+    Lock(u32),
+}
+
+/// Storage keys for seller-requested release window extensions.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ExtensionDataKey {
+    /// Pending extension request for an order; at most one at a time.
+    Request(u32),
+}
+
+/// Storage keys for escrow amount amendments.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum AmendmentDataKey {
+    /// Number of amendments applied to an escrow. Separate from
+    /// `Escrow::version`, which tracks the storage schema.
+    Version(u32),
+}
+
+/// Storage keys for recurring escrow extensions.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum RecurringDataKey {
+    /// Timestamp a recurring escrow was paused at; absent while running.
+    PausedAt(u64),
+    /// Maximum pause duration in seconds; 0 means unlimited.
+    MaxPauseDuration,
+    /// Open `RecurringCycleDispute` of a recurring escrow.
+    CycleDispute(u64),
+    /// `RecurringDisputePolicy` applied when a cycle is disputed.
+    DisputePolicy,
+    /// Indexed storage: recurring escrow id at a position in a buyer's list.
+    BuyerIndexed(Address, u32),
+    /// Number of recurring escrows in a buyer's list.
+    BuyerCount(Address),
+    /// Indexed storage: recurring escrow id at a position in an artisan's list.
+    ArtisanIndexed(Address, u32),
+    /// Number of recurring escrows in an artisan's list.
+    ArtisanCount(Address),
+    /// Backfilled recurring escrow id at a position in a buyer's legacy list,
+    /// which is read before `BuyerIndexed`.
+    BuyerLegacyIndexed(Address, u32),
+    /// Number of recurring escrows in a buyer's legacy list.
+    BuyerLegacyCount(Address),
+    /// Backfilled recurring escrow id at a position in an artisan's legacy
+    /// list, which is read before `ArtisanIndexed`.
+    ArtisanLegacyIndexed(Address, u32),
+    /// Number of recurring escrows in an artisan's legacy list.
+    ArtisanLegacyCount(Address),
+    /// First recurring escrow id indexed on creation; earlier ids need the
+    /// backfill.
+    IndexStart,
+    /// Next recurring escrow id the backfill will index.
+    BackfillCursor,
+    /// `RecurringCycle` list of a variable-amount recurring escrow.
+    Schedule(u64),
+    /// Total paused time in seconds, added to the due times of a schedule.
+    ScheduleShift(u64),
+    /// `FeeRateSnapshot` taken when a recurring escrow was created.
+    FeeRate(u64),
+}
+
+/// Storage keys for linear payment streams.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum StreamDataKey {
+    /// `PaymentStream` by id.
+    Stream(u64),
+    /// Next stream id.
+    NextId,
+}
+
+/// Storage keys for epoch-bucketed reporting accumulators.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum AccountingDataKey {
+    /// Epoch length in seconds.
+    EpochLength,
+    /// `EpochStats` per (token, epoch index).
+    Bucket(Address, u64),
+}
+
+/// Storage keys for the fee treasury.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum TreasuryDataKey {
+    /// Configured `TreasuryRecipient` split.
+    Recipients,
+    /// Platform fees accrued in the contract and not yet distributed, per token.
+    Accrued(Address),
+}
+
+/// Storage keys for fee payer models.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum FeeDataKey {
+    /// Global fee payer model; per-token overrides live in `FeeTokenInfo`.
+    Payer,
+    /// Fee terms of an escrow created under a buyer-side model.
+    EscrowTerms(u32),
+    /// Flat/min/max fee schedule of a token.
+    Schedule(Address),
+    /// `FeeRateSnapshot` taken when an escrow was created.
+    EscrowFeeRate(u32),
+    /// Scheduled fee changes, ordered by `effective_at`.
+    PendingChanges,
+    /// Minimum notice, in seconds, required for fee changes.
+    NoticePeriod,
+    /// Lower notice period and the time it takes effect, as `(u32, u64)`.
+    NoticePeriodChange,
+    /// Next `ScheduledFeeChange::id`.
+    NextChangeId,
+    /// Volume-based artisan fee tier table.
+    VolumeTiers,
+}
+
+/// Storage keys for platform-funded coupons.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum CouponDataKey {
+    /// Coupon keyed by the SHA-256 hash of its code.
+    Coupon(BytesN<32>),
+    /// Number of times a user redeemed a coupon.
+    Redemptions(BytesN<32>, Address),
+    /// Promo balance held by the contract for a token, outside `TotalLocked`.
+    PromoBalance(Address),
+    /// Unreclaimed platform subsidy of an escrow created with a coupon.
+    Subsidy(u32),
+}
+
+/// Storage keys for assigned seller receivables.
+#[contracttype(export = false)]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ReceivableDataKey {
+    /// Current holder of an escrow's payout right, when assigned away from
+    /// the seller.
+    Assignee(u32),
+    /// Bounded log of assignments, capped at `MAX_RECEIVABLE_ASSIGNMENTS`.
+    History(u32),
+}
+
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
@@ -508,6 +888,99 @@ pub enum RecurringEscrowAction {
     Created = 0,
     CycleReleased = 1,
     Cancelled = 2,
+    Paused = 3,
+    Resumed = 4,
+    CycleDisputed = 5,
+    CycleResolved = 6,
+}
+
+/// A payment that vests linearly from `start_time` to `end_time`.
+///
+/// The artisan withdraws the vested part at any time; the buyer can cancel
+/// to reclaim the unvested rest. `withdrawn_amount` is gross, before the fee
+/// charged on each withdrawal at `fee_rate`, fixed when the stream is created.
+/// Under a fee schedule the fee is computed once on `total_amount` and each
+/// withdrawal pays its pro-rata share.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct PaymentStream {
+    pub id: u64,
+    pub buyer: Address,
+    pub artisan: Address,
+    pub token: Address,
+    pub total_amount: i128,
+    pub withdrawn_amount: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub is_active: bool,
+    pub fee_rate: FeeRateSnapshot,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+#[repr(u32)]
+pub enum StreamAction {
+    Created = 0,
+    Withdrawn = 1,
+    Cancelled = 2,
+    Completed = 3,
+}
+
+/// Emitted under `(symbol "payment_stream", u64 id)` for every stream state
+/// change. `amount` is the locked total on creation, the gross withdrawal
+/// on `Withdrawn`/`Completed`, and the buyer refund on `Cancelled`.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct StreamEvent {
+    pub id: u64,
+    pub action: StreamAction,
+    pub buyer: Address,
+    pub artisan: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// What happens to later cycles of a recurring escrow while one cycle is
+/// disputed.
+#[contracttype]
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum RecurringDisputePolicy {
+    /// Later cycles keep releasing on schedule.
+    Continue,
+    /// The escrow is paused until the dispute is resolved.
+    Pause,
+}
+
+/// One cycle of a variable-amount recurring escrow: `amount` becomes
+/// releasable at `due_at`.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct RecurringCycle {
+    pub amount: i128,
+    pub due_at: u64,
+}
+
+/// A dispute over one cycle of a recurring escrow.
+///
+/// * `cycle`           — zero-based index of the disputed cycle.
+/// * `amount`          — cycle amount frozen until resolution.
+/// * `paused_schedule` — the dispute paused the escrow under
+///   `RecurringDisputePolicy::Pause`; resolving it resumes the schedule.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct RecurringCycleDispute {
+    pub cycle: u64,
+    pub amount: i128,
+    pub reason: Symbol,
+    pub opened_by: Address,
+    pub opened_at: u64,
+    pub paused_schedule: bool,
 }
 
 #[contracttype]
@@ -593,6 +1066,128 @@ pub enum SettlementKind {
     /// Partial-refund settlement. `refund_gross` and `seller_gross` are the
     /// gross portions *before* fees, supplied as context fields.
     PartialRefund(i128, i128),
+    /// Release of an escrow whose buyer deposited `buyer_fee` on top of the
+    /// price. When the flag is set the seller bears the other half of the
+    /// fee on the price (`FeePayer::Split`); otherwise the buyer's deposit is
+    /// the whole fee (`FeePayer::Buyer`).
+    ReleaseWithBuyerFee(i128, bool),
+}
+
+/// Who bears the platform fee of an escrow.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum FeePayer {
+    /// Fee deducted from the seller at release (default).
+    Seller,
+    /// Buyer deposits the fee on top of the price.
+    Buyer,
+    /// Buyer deposits half the fee on top of the price; the seller bears
+    /// the rest at release.
+    Split,
+}
+
+/// A platform fee or artisan fee tier change announced in advance.
+///
+/// * `artisan`      — artisan whose fee tier changes; `None` for the
+///   platform fee.
+/// * `effective_at` — ledger timestamp from which the new bps applies to
+///   newly created escrows.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct ScheduledFeeChange {
+    pub id: u32,
+    pub artisan: Option<Address>,
+    pub fee_bps: u32,
+    pub effective_at: u64,
+}
+
+/// One row of the volume-based artisan fee tier table.
+///
+/// An artisan qualifies when their onboarding `UserMetrics.total_volume`
+/// (7-decimal normalized) is at least `min_volume` and their trust score is
+/// at least `min_trust_score`. The lowest `fee_bps` among qualifying rows
+/// applies.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct VolumeFeeTier {
+    pub min_volume: i128,
+    pub min_trust_score: u32,
+    pub fee_bps: u32,
+}
+
+/// Per-token fee schedule layered on the effective bps fee.
+///
+/// * `flat_fee` — fixed fee added to every settlement.
+/// * `min_fee`  — lower bound on the fee.
+/// * `max_fee`  — upper bound on the fee; `0` means uncapped.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct FeeSchedule {
+    pub flat_fee: i128,
+    pub min_fee: i128,
+    pub max_fee: i128,
+}
+
+/// Fee rate fixed when an escrow is created, so later fee changes do not
+/// reprice it. `schedule` only applies when `has_schedule` is set.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct FeeRateSnapshot {
+    pub bps: u32,
+    pub schedule: FeeSchedule,
+    pub has_schedule: bool,
+}
+
+/// Fee rate resolved for one settlement: the effective bps plus the
+/// token's fee schedule.
+#[derive(Clone, Copy)]
+struct FeeRate {
+    bps: u32,
+    schedule: Option<FeeSchedule>,
+}
+
+impl FeeRate {
+    fn snapshot(&self) -> FeeRateSnapshot {
+        FeeRateSnapshot {
+            bps: self.bps,
+            schedule: self.schedule.unwrap_or(FeeSchedule {
+                flat_fee: 0,
+                min_fee: 0,
+                max_fee: 0,
+            }),
+            has_schedule: self.schedule.is_some(),
+        }
+    }
+
+    fn from_snapshot(snapshot: &FeeRateSnapshot) -> Self {
+        FeeRate {
+            bps: snapshot.bps,
+            schedule: snapshot.has_schedule.then_some(snapshot.schedule),
+        }
+    }
+}
+
+/// Fee rate of settlements that never charge a fee.
+const NO_FEE_RATE: FeeRate = FeeRate {
+    bps: 0,
+    schedule: None,
+};
+
+/// Fee terms fixed when an escrow is created under a buyer-side fee model.
+///
+/// * `buyer_fee` — fee deposited by the buyer on top of the price; it is
+///   included in `Escrow::amount` and returned in full on refund.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct EscrowFeeTerms {
+    pub payer: FeePayer,
+    pub buyer_fee: i128,
 }
 
 /// Output of `compute_fee_allocation`.
@@ -607,3001 +1202,6668 @@ pub enum SettlementKind {
 ///
 /// Callers **must** use these three values — and only these three values —
 /// when performing token transfers in any settlement path.
+///
+/// `referral_fee` and `keeper_fee` are not part of the balance invariant:
+/// they are slices of `platform_fee` that `transfer_platform_fee` routes to
+/// the escrow's referrer and to the keeper instead of the platform wallet,
+/// so `referral_fee + keeper_fee <= platform_fee` always holds.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
 pub struct FeeAllocation {
-    /// Amount transferred to the platform wallet.
+    /// Total platform fee, including any referral share.
     pub platform_fee: i128,
     /// Net amount transferred to the seller (artisan).
     pub seller_amount: i128,
     /// Net amount transferred back to the buyer.
     pub buyer_amount: i128,
+    /// Portion of `platform_fee` paid to the referrer rather than the
+    /// platform wallet.
+    pub referral_fee: i128,
+    /// Portion of `platform_fee` paid as a bounty to the keeper that
+    /// processed the settlement.
+    pub keeper_fee: i128,
 }
 
+/// Emitted when a referral share of the platform fee is paid out.
+///
+/// # Topics
+///
+/// Published under `(symbol "referral_paid", address referrer)` so a
+/// referrer can stream their own payout history.
+///
+/// # Preconditions
+///
+/// * A non-zero referral share is configured via `set_referral_share_bps`.
+/// * The settled escrow has a referrer, either recorded on the escrow or on
+///   the seller's onboarding profile.
+///
+/// # Storage side-effects
+///
+/// * `ReferralDataKey::ReferrerEarnings(referrer, token)` and
+///   `ReferralDataKey::TotalReferralFees(token)` are incremented before the
+///   transfer. `DataKey::TotalFees(token)` only counts the platform's share.
+///
+/// # Payload
+///
+/// * `order_id` — escrow order that produced the fee.
+/// * `referrer` — address that received the payout.
+/// * `token`    — token the payout was made in.
+/// * `amount`   — referral payout in raw token units.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct Escrow {
-    pub version: u32,
-    pub id: u64,
-    pub batch_id: Option<u64>,
-    pub buyer: Address,
-    pub seller: Address,
+pub struct ReferralPaidEvent {
+    pub order_id: u32,
+    pub referrer: Address,
     pub token: Address,
     pub amount: i128,
-    pub status: EscrowStatus,
-    pub release_window: u32, // Time in seconds before auto-release
-    pub created_at: u32,
-    pub ipfs_hash: Option<String>,
-    pub metadata_hash: Option<Bytes>,
-    pub dispute_reason: Option<Symbol>,
-    pub dispute_initiated_at: Option<u64>,
-    pub funded: bool,
-    /// Ledger timestamp after which any party (or admin) may cancel this escrow
-    /// if it has not yet been funded. Set to created_at + UNFUNDED_CANCEL_TIMEOUT
-    /// for unfunded escrows; None for escrows that were funded at creation (#656).
-    pub funding_deadline: Option<u64>,
 }
 
+/// Reporting accumulators for one token over one epoch.
+///
+/// * `epoch`           — epoch index, `timestamp / epoch_length`.
+/// * `start`           — first timestamp of the epoch.
+/// * `fees`            — platform fees counted in `TotalFees`.
+/// * `released_volume` — proceeds paid out to sellers (or their assignees).
+/// * `refunded_volume` — amounts returned to buyers or gift payers.
+/// * `disputes`        — disputes opened.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-struct LegacyEscrow {
-    pub id: u64,
-    pub buyer: Address,
-    pub seller: Address,
-    pub token: Address,
-    pub amount: i128,
-    pub status: EscrowStatus,
-    pub release_window: u32,
-    pub created_at: u32,
-    pub ipfs_hash: Option<String>,
-    pub metadata_hash: Option<Bytes>,
-    pub dispute_reason: Option<String>,
-    pub dispute_initiated_at: Option<u64>,
+pub struct EpochStats {
+    pub epoch: u64,
+    pub start: u64,
+    pub fees: i128,
+    pub released_volume: i128,
+    pub refunded_volume: i128,
+    pub disputes: u32,
 }
 
+/// A treasury recipient and its share of distributed platform fees.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-struct EscrowWithoutBatch {
-    pub version: u32,
-    pub id: u64,
-    pub buyer: Address,
-    pub seller: Address,
-    pub token: Address,
-    pub amount: i128,
-    pub status: EscrowStatus,
-    pub release_window: u32,
-    pub created_at: u32,
-    pub ipfs_hash: Option<String>,
-    pub metadata_hash: Option<Bytes>,
-    pub dispute_reason: Option<String>,
-    pub dispute_initiated_at: Option<u64>,
+pub struct TreasuryRecipient {
+    pub recipient: Address,
+    pub share_bps: u32,
 }
 
+/// Emitted when accrued platform fees are paid out to treasury recipients.
+///
+/// Published under `(symbol "treasury_distributed", address token)`. Each
+/// recipient's payout is also recorded in its fund audit history with
+/// reason `treasury_distribution`.
+///
+/// # Payload
+///
+/// * `token`      — token that was distributed.
+/// * `amount`     — total amount paid out.
+/// * `recipients` — number of recipients paid.
 #[contracttype]
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-#[repr(u32)]
-pub enum EscrowAction {
-    Created = 0,
-    Released = 1,
-    Refunded = 2,
-    Disputed = 3,
-    Resolved = 4,
-    Extended = 5,
-    BatchCreated = 6,
-    BatchReleased = 7,
+pub struct TreasuryDistributedEvent {
+    pub token: Address,
+    pub amount: i128,
+    pub recipients: u32,
 }
 
+/// Emitted when a settlement payout is credited to the claim ledger instead
+/// of being pushed to the recipient.
+///
+/// # Topics
+///
+/// Published under `(symbol "claim_credited", address recipient)`.
+///
+/// # Preconditions
+///
+/// * The recipient opted into pull payouts via `set_pull_payouts`, or the
+///   direct transfer to the recipient failed.
+///
+/// # Storage side-effects
+///
+/// * `ClaimDataKey::Claimable(recipient, token)` and
+///   `ClaimDataKey::TotalClaimable(token)` are incremented.
+///
+/// # Payload
+///
+/// * `recipient` — address that can claim the balance.
+/// * `token`     — token the balance is held in.
+/// * `amount`    — amount credited in raw token units.
+/// * `reason`    — audit reason of the settlement that produced the payout.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct FundMovementAuditEntry {
-    pub actor: Address,
+pub struct ClaimCreditedEvent {
+    pub recipient: Address,
+    pub token: Address,
     pub amount: i128,
     pub reason: Symbol,
-    pub timestamp: u64,
-    pub balance_impact: i128,
 }
 
+/// Emitted when a recipient withdraws their claimable balance.
+///
+/// # Topics
+///
+/// Published under `(symbol "claimed", address recipient)`.
+///
+/// # Payload
+///
+/// * `recipient`   — address whose claimable balance was withdrawn.
+/// * `destination` — address that received the tokens.
+/// * `token`       — token withdrawn.
+/// * `amount`      — amount withdrawn in raw token units.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct EscrowEvent {
-    pub escrow_id: u64,
-    pub action: EscrowAction,
-    pub buyer: Address,
-    pub seller: Address,
-    /// Monetary fields are emitted as raw integer types (i128/u64). Avoid
-    /// converting integers to strings inside the contract â€” emit numeric
-    /// values and perform human-friendly formatting off-chain (UI/indexer).
-    pub amount: i128,
+pub struct ClaimedEvent {
+    pub recipient: Address,
+    pub destination: Address,
     pub token: Address,
-    pub timestamp: u64,
+    pub amount: i128,
+}
+
+/// Permissionless maintenance actions a keeper can perform via `keeper_process`.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum KeeperAction {
+    /// Auto-release a funded escrow whose release window has elapsed.
+    Release,
+    /// Cancel an unfunded escrow whose funding deadline has passed.
+    CancelUnfunded,
+    /// Resolve a dispute that has outlived `max_dispute_duration`.
+    ResolveExpiredDispute,
 }
 
+/// Emitted for every escrow processed by a keeper.
+///
+/// # Topics
+///
+/// Published under `(symbol "keeper_processed", address keeper)`.
+///
+/// # Preconditions
+///
+/// * The escrow was eligible for `action` when `keeper_process` ran.
+///
+/// # Payload
+///
+/// * `keeper`   — address that processed the escrow.
+/// * `order_id` — processed escrow.
+/// * `action`   — action that was applied.
+/// * `bounty`   — bounty paid out of the platform fee (`0` for unfunded
+///   cancellations, which carry no fee).
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct EscrowResolvedEvent {
-    pub escrow_id: u64,
-    pub buyer: Address,
-    pub seller: Address,
-    pub arbitrator: Address,
-    pub amount: i128,
-    pub token: Address,
-    pub timestamp: u64,
+pub struct KeeperProcessedEvent {
+    pub keeper: Address,
+    pub order_id: u32,
+    pub action: KeeperAction,
+    pub bounty: i128,
+}
+
+/// Delivery state reported by an attester. Only confirmed deliveries are
+/// attested; a failed delivery is raised by the buyer through a dispute.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum DeliveryStatus {
+    Delivered,
 }
 
+/// Delivery confirmation signed off-chain by a registered attester.
+///
+/// The signed message is the XDR encoding of
+/// `(contract_address, DeliveryAttestation)`, which binds the signature to
+/// this contract instance.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct ReputationUpdateEvent {
-    pub address: Address,
-    pub successful_delta: u32,
-    pub disputed_delta: u32,
-    pub metrics_sales_delta: u32,
-    pub metrics_amount: i128,
-    pub token: Address,
+pub struct DeliveryAttestation {
+    pub order_id: u32,
+    pub status: DeliveryStatus,
+    /// Ledger-compatible unix timestamp of the delivery.
     pub timestamp: u64,
 }
 
-/// Tagged union used to carry a single configuration value inside
-/// [`ConfigUpdatedEvent`].
+/// Emitted when a delivery attestation is accepted.
 ///
-/// Soroban events must be self-describing for off-chain indexers, but
-/// `PlatformConfig` fields are heterogeneous (counts, monetary amounts,
-/// addresses, and free-form strings). Rather than emit a separate event type
-/// per field â€” which would bloat the contract's event ABI â€” every admin
-/// configuration change is normalized into one of these four variants. Indexers
-/// match on the variant tag to recover the underlying Rust type without any
-/// loss of precision (in particular, `I128` monetary values are never
-/// stringified on-chain; see the note on [`EscrowEvent::amount`]).
+/// # Topics
 ///
-/// # Variant mapping
+/// Published under `(symbol "delivery_attested", u32 order_id)`.
 ///
-/// * `U32`     â€” bounded counters and basis-point fees (e.g. `platform_fee_bps`).
-/// * `I128`    â€” monetary thresholds such as `min_escrow_amount`.
-/// * `Address` â€” role and token addresses (e.g. `fee_collector`).
-/// * `String`  â€” human-readable identifiers that have no compact encoding.
+/// * `attester` — public key that signed the attestation.
+/// * `delivered_at` — attested delivery timestamp.
+/// * `released` — `true` when the escrow was released immediately.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub enum ConfigValue {
-    U32(u32),
-    I128(i128),
-    Address(Address),
-    String(String),
+pub struct DeliveryAttestedEvent {
+    pub order_id: u32,
+    pub attester: BytesN<32>,
+    pub delivered_at: u64,
+    pub released: bool,
 }
 
-/// Emitted whenever an admin mutates a single field of the on-chain
-/// `PlatformConfig`.
+/// Emitted when an attester key is registered or removed.
 ///
 /// # Topics
 ///
-/// Published under `(symbol "config_updated", symbol field_name)` so indexers
-/// can subscribe to changes of a specific field cheaply. The `field_name` topic
-/// mirrors the `field_name` payload member.
-///
-/// # Preconditions
-///
-/// * The caller must be the current platform admin; the emitting function
-///   asserts `admin.require_auth()` before the storage write, so this event is
-///   only ever observed for an authorized change.
-///
-/// # Storage side-effects
-///
-/// * The corresponding `PlatformConfig` field has already been persisted by the
-///   time this event fires. The event is emitted *after* the storage write,
-///   in keeping with the check-effects-interactions ordering used throughout
-///   the contract.
-///
-/// # Payload
-///
-/// * `field_name` â€” symbolic name of the mutated field.
-/// * `old_value`  â€” value held immediately before the write.
-/// * `new_value`  â€” value persisted by this update.
+/// Published under `(symbol "attester_updated", BytesN<32> public_key)`.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct ConfigUpdatedEvent {
-    pub field_name: Symbol,
-    pub old_value: ConfigValue,
-    pub new_value: ConfigValue,
+pub struct AttesterUpdatedEvent {
+    pub public_key: BytesN<32>,
+    pub active: bool,
 }
 
-/// Emitted when an artisan's negotiated platform-fee tier is set or changed.
+/// Hash lock guarding an HTLC escrow.
 ///
-/// Per-artisan fee tiers let the platform reward high-reputation sellers with a
-/// reduced `fee_bps` (basis points, where `10_000` == 100%). The persisted tier
-/// overrides the global `platform_fee_bps` for that artisan's future escrows.
+/// * `secret_hash` — SHA-256 of the secret the buyer hands over at pickup.
+/// * `timeout`     — after this timestamp the seller can no longer claim and
+///   the buyer may reclaim the funds.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct HtlcLock {
+    pub secret_hash: Bytes,
+    pub timeout: u64,
+}
+
+/// Emitted when an HTLC escrow is created.
 ///
 /// # Topics
 ///
-/// Published under `(symbol "artisan_fee_tier_updated", address artisan)` so a
-/// client can stream the fee history of a single artisan.
-///
-/// # Preconditions
-///
-/// * The caller must be the platform admin (`require_auth`).
-/// * `fee_bps` is validated against `MAX_PLATFORM_FEE_BPS`; an out-of-range
-///   value aborts with [`Error::InvalidFee`] and no event is emitted.
-///
-/// # Storage side-effects
-///
-/// * The artisan's fee-tier ledger entry is written (and its TTL extended)
-///   before this event fires.
-///
-/// # Payload
-///
-/// * `artisan` â€” address whose fee tier was updated.
-/// * `fee_bps` â€” the new fee in basis points applied to future escrows.
+/// Published under `(symbol "htlc_locked", u32 order_id)`.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct ArtisanFeeTierUpdatedEvent {
-    pub artisan: Address,
-    pub fee_bps: u32,
+pub struct HtlcLockedEvent {
+    pub order_id: u32,
+    pub secret_hash: Bytes,
+    pub timeout: u64,
 }
 
-/// Emitted when an artisan stakes collateral tokens into the platform.
-///
-/// Staking is a precondition for accepting high-value escrows; the staked
-/// balance backs the artisan's dispute exposure. Token movement obeys the
-/// check-effects-interactions pattern: the artisan's persistent stake balance
-/// is increased and committed *before* the external `token.transfer` callback,
-/// so a malicious token contract cannot re-enter and observe a stale balance.
+/// Emitted when the seller claims an HTLC escrow. The revealed `secret`
+/// is published so off-chain systems can confirm the pickup.
 ///
 /// # Topics
 ///
-/// Published under `(symbol "tokens_staked", address artisan)`.
+/// Published under `(symbol "htlc_claimed", u32 order_id)`.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct HtlcClaimedEvent {
+    pub order_id: u32,
+    pub secret: Bytes,
+}
+
+/// Why a seller asks for more time before the release window closes.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ExtensionReason {
+    EquipmentFailure = 0,
+    SupplyShortage = 1,
+    ProductionDelay = 2,
+    ShippingDelay = 3,
+    Other = 4,
+}
+
+/// Seller request to extend an escrow's release window, awaiting the buyer.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct ExtensionRequest {
+    pub order_id: u32,
+    pub additional_seconds: u32,
+    pub reason: ExtensionReason,
+    pub requested_at: u64,
+}
+
+/// Emitted when an extension request is created, accepted, rejected or
+/// cancelled.
 ///
-/// # Preconditions
+/// # Topics
 ///
-/// * `artisan.require_auth()` â€” only the staker may stake on their own behalf.
-/// * `amount` must be positive and `token` whitelisted.
+/// Published under `(symbol "release_extension", u32 order_id)`, next to the
+/// escrow's regular `("escrow", order_id)` event for the same action.
 ///
-/// # Storage side-effects
+/// * `action` — `ExtensionRequested`, `Extended` (accepted),
+///   `ExtensionRejected` or `ExtensionCancelled`.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct ReleaseExtensionEvent {
+    pub order_id: u32,
+    pub action: EscrowAction,
+    pub additional_seconds: u32,
+    pub reason: ExtensionReason,
+    pub release_window: u32,
+}
+
+/// Emitted when part of an escrow is released early.
 ///
-/// * The artisan's staked-balance entry is incremented and its TTL extended.
+/// # Topics
 ///
-/// # Payload
+/// Published under `(symbol "partial_release", u32 order_id)`.
 ///
-/// * `artisan` â€” the staking address.
-/// * `token`   â€” the staked token's contract address.
-/// * `amount`  â€” raw token amount staked (never stringified on-chain).
+/// * `amount`    — gross amount taken out of the escrow.
+/// * `remaining` — escrow amount still locked afterwards.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct TokensStakedEvent {
-    pub artisan: Address,
-    pub token: Address,
+pub struct PartialReleaseEvent {
+    pub order_id: u32,
     pub amount: i128,
+    pub platform_fee: i128,
+    pub seller_amount: i128,
+    pub remaining: i128,
 }
 
-/// Emitted when an artisan withdraws previously staked collateral.
+/// Emitted when an escrow's amount is amended.
 ///
 /// # Topics
 ///
-/// Published under `(symbol "tokens_unstaked", address artisan)`.
+/// Published under `(symbol "escrow_amended", u32 order_id)`.
 ///
-/// # Preconditions
+/// * `version` — amendment version after this change (first amendment is 1).
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct EscrowAmendedEvent {
+    pub order_id: u32,
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub version: u32,
+}
+
+/// Published under `(symbol "release_tip", u32 order_id)` when a buyer tips
+/// the seller in `release_funds_with_tip`.
 ///
-/// * `artisan.require_auth()`.
-/// * The stake cooldown must have elapsed, otherwise the call aborts with
-///   [`Error::StakeCooldownActive`] and no event is emitted.
-/// * The withdrawal token must match the original staking token
-///   ([`Error::StakeTokenMismatch`]).
-///
-/// # Storage side-effects
-///
-/// * The artisan's staked-balance entry is decremented *before* the outbound
-///   `token.transfer`, preserving reentrancy safety (the transfer is the final
-///   interaction in the call path).
-///
-/// # Payload
-///
-/// * `artisan` â€” the withdrawing address.
-/// * `token`   â€” the unstaked token's contract address.
-/// * `amount`  â€” raw token amount returned to the artisan.
+/// * `amount` — tip in raw token units; no platform fee is taken from it.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct TokensUnstakedEvent {
-    pub artisan: Address,
+pub struct TipPaidEvent {
+    pub order_id: u32,
+    pub buyer: Address,
+    pub seller: Address,
     pub token: Address,
     pub amount: i128,
 }
 
-/// Emitted when an order's off-chain metadata is verified against its on-chain
-/// commitment.
-///
-/// The contract stores only a compact hash of an order's metadata (see
-/// [`EscrowMetadata`]); the full document lives off-chain (e.g. IPFS). When a
-/// verifier reveals the document and the contract confirms its hash matches the
-/// stored commitment, this event records the successful verification so
-/// indexers can mark the order's metadata as trusted.
-///
-/// # Topics
+/// Admin-issued discount, stored under the hash of its code.
 ///
-/// Published under `(symbol "metadata_verified", u64 order_id)`.
-///
-/// # Preconditions
-///
-/// * The stored commitment must exist and the revealed content must hash to it,
-///   otherwise the call aborts with [`Error::InvalidMetadataHash`].
-///
-/// # Storage side-effects
-///
-/// * None beyond TTL refresh of the order entry; verification is a read-and-
-///   compare operation that emits this audit event.
-///
-/// # Payload
-///
-/// * `order_id`  â€” the escrow/order whose metadata was verified.
-/// * `verifier`  â€” address that submitted the reveal proof.
-/// * `timestamp` â€” ledger timestamp at verification time.
+/// * `discount`       — subsidy per redemption in raw token units.
+/// * `budget`         — total subsidy the coupon may draw; `spent` tracks use.
+/// * `per_user_limit` — redemptions allowed per buyer.
+/// * `expires_at`     — ledger timestamp after which the code is rejected.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct MetadataVerifiedEvent {
-    pub order_id: u64,
-    pub verifier: Address,
-    pub timestamp: u64,
+pub struct Coupon {
+    pub token: Address,
+    pub discount: i128,
+    pub budget: i128,
+    pub spent: i128,
+    pub per_user_limit: u32,
+    pub expires_at: u64,
 }
 
+/// Published under `(symbol "coupon_redeemed", u32 order_id)`.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct PlatformPausedEvent {
-    pub initiator: Address,
-    pub timestamp: u64,
+pub struct CouponRedeemedEvent {
+    pub order_id: u32,
+    pub buyer: Address,
+    pub code_hash: BytesN<32>,
+    pub subsidy: i128,
 }
 
+/// One transfer of an escrow's payout right.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct PlatformUnpausedEvent {
-    pub initiator: Address,
-    pub timestamp: u64,
+pub struct ReceivableAssignment {
+    pub from: Address,
+    pub to: Address,
+    pub assigned_at: u64,
 }
 
+/// Published under `(symbol "receivable_assigned", u32 order_id)`.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct EscrowMetadata {
+pub struct ReceivableAssignedEvent {
+    pub order_id: u32,
+    pub from: Address,
+    pub to: Address,
+}
+
+/// One page of `auto_release_seller_escrows`.
+///
+/// * `released`    — order IDs released in this call, in index order.
+/// * `next_cursor` — `SellerEscrowIndexed` position to resume from.
+/// * `complete`    — `true` once the seller's whole index has been walked.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct SellerAutoReleaseBatch {
+    pub released: Vec<u64>,
+    pub next_cursor: u32,
+    pub complete: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct Escrow {
+    pub version: u32,
+    pub id: u64,
+    pub batch_id: Option<u64>,
+    pub buyer: Address,
+    pub seller: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub release_window: u32, // Time in seconds before auto-release
+    pub created_at: u32,
     pub ipfs_hash: Option<String>,
     pub metadata_hash: Option<Bytes>,
+    pub dispute_reason: Option<Symbol>,
+    pub dispute_initiated_at: Option<u64>,
+    pub funded: bool,
+    /// Ledger timestamp after which any party (or admin) may cancel this escrow
+    /// if it has not yet been funded. Set to created_at + UNFUNDED_CANCEL_TIMEOUT
+    /// for unfunded escrows; None for escrows that were funded at creation (#656).
+    pub funding_deadline: Option<u64>,
+    /// Third party that funded the escrow (gift or procurement purchases).
+    /// `buyer` stays the recipient who confirms delivery and releases funds.
+    pub payer: Option<Address>,
+    /// Destination for refunds; defaults to `payer`, then `buyer`.
+    pub refund_address: Option<Address>,
 }
 
-/// Metadata reveal proof for privacy verification (Issue #122)
+/// Escrow layout up to version 4, before payer and refund address.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct MetadataRevealProof {
-    /// The full metadata content (off-chain document)
-    pub content: Bytes,
-    /// Optional secret key for additional verification
-    pub secret: Option<Bytes>,
+struct EscrowV4 {
+    pub version: u32,
+    pub id: u64,
+    pub batch_id: Option<u64>,
+    pub buyer: Address,
+    pub seller: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub release_window: u32,
+    pub created_at: u32,
+    pub ipfs_hash: Option<String>,
+    pub metadata_hash: Option<Bytes>,
+    pub dispute_reason: Option<Symbol>,
+    pub dispute_initiated_at: Option<u64>,
+    pub funded: bool,
+    pub funding_deadline: Option<u64>,
 }
 
-/// Test-only metadata structure for simplified testing
-#[cfg(test)]
+#[contracttype]
 #[derive(Clone, Eq, PartialEq)]
-pub struct Metadata {
-    pub title: String,
-    pub description: String,
-    pub category: String,
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+struct LegacyEscrow {
+    pub id: u64,
+    pub buyer: Address,
+    pub seller: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub release_window: u32,
+    pub created_at: u32,
+    pub ipfs_hash: Option<String>,
+    pub metadata_hash: Option<Bytes>,
+    pub dispute_reason: Option<String>,
+    pub dispute_initiated_at: Option<u64>,
 }
 
-/// Proposal record for a pending WASM upgrade.
-///
-/// `upgrade_at` is the earliest ledger timestamp at which `execute_upgrade` may
-/// run; it equals `proposed_at + wasm_upgrade_cooldown` from `PlatformConfig`.
-/// `proposed_by` records the admin that submitted the proposal â€” note that the
-/// admin role can rotate via the two-step transfer (`update_admin` /
-/// `claim_admin`), so the value reflects the admin at proposal time, not at
-/// execution time. `execute_upgrade` re-checks the *current* admin's auth, so
-/// rotating admins cannot bypass authorization.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct WasmUpgradeProposal {
-    pub wasm_hash: BytesN<32>,
-    pub upgrade_at: u64,
-    pub proposed_by: Address,
-    pub proposed_at: u64,
+struct EscrowWithoutBatch {
+    pub version: u32,
+    pub id: u64,
+    pub buyer: Address,
+    pub seller: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub status: EscrowStatus,
+    pub release_window: u32,
+    pub created_at: u32,
+    pub ipfs_hash: Option<String>,
+    pub metadata_hash: Option<Bytes>,
+    pub dispute_reason: Option<String>,
+    pub dispute_initiated_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+#[repr(u32)]
+pub enum EscrowAction {
+    Created = 0,
+    Released = 1,
+    Refunded = 2,
+    Disputed = 3,
+    Resolved = 4,
+    Extended = 5,
+    BatchCreated = 6,
+    BatchReleased = 7,
+    ExtensionRequested = 8,
+    ExtensionRejected = 9,
+    PartiallyReleased = 10,
+    ExtensionCancelled = 11,
 }
 
-/// Lifecycle event emitted whenever a WASM upgrade proposal is created,
-/// replaced, cancelled, or executed. Indexers can use the `action` symbol to
-/// reconstruct the upgrade audit trail without scanning storage.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct UpgradeProposalEvent {
-    pub action: Symbol,
-    pub wasm_hash: BytesN<32>,
-    pub admin: Address,
+pub struct FundMovementAuditEntry {
+    pub actor: Address,
+    pub amount: i128,
+    pub reason: Symbol,
     pub timestamp: u64,
-    pub upgrade_at: u64,
+    pub balance_impact: i128,
 }
 
-/// On-chain record of a completed WASM upgrade.
-///
-/// One entry is appended to `UpgradeHistory` per successful `execute_upgrade`
-/// call, providing operators and auditors visibility into how the contract
-/// reached its current `ContractVersion`.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct UpgradeRecord {
-    pub from_version: u32,
-    pub to_version: u32,
-    pub wasm_hash: BytesN<32>,
-    pub admin: Address,
+pub struct EscrowEvent {
+    pub escrow_id: u64,
+    pub action: EscrowAction,
+    pub buyer: Address,
+    pub seller: Address,
+    /// Monetary fields are emitted as raw integer types (i128/u64). Avoid
+    /// converting integers to strings inside the contract â€” emit numeric
+    /// values and perform human-friendly formatting off-chain (UI/indexer).
+    pub amount: i128,
+    pub token: Address,
     pub timestamp: u64,
 }
 
-/// Immutable per-round state for the multi-sig upgrade approval flow.
-///
-/// Written once on the **first** approval call for a given proposal nonce and
-/// never mutated except to append new approvals.  Keyed by
-/// `DataKey::UpgradeApprovalState(nonce)`.
-///
-/// # Security properties
-///
-/// * `signers`   — snapshotted from `UpgradeSigners` (or admin fallback) at
-///   round open.  Subsequent `set_upgrade_signers` calls cannot alter which
-///   addresses are eligible for this round, closing the signer-rotation race.
-///
-/// * `threshold` — snapshotted from `UpgradeThreshold` at round open.
-///   Mid-round `set_upgrade_threshold` calls therefore cannot lower the bar
-///   for the current round.
-///
-/// * `approvals` — grows monotonically as valid signers call
-///   `propose_upgrade_wasm`.  Only addresses present in `signers` may appear
-///   here; duplicates are rejected with `AlreadyApproved`.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct UpgradeApprovalState {
-    /// Monotonically increasing round counter.  Incremented on every
-    /// `cancel_upgrade_wasm` call so that residual state from a prior
-    /// round cannot be replayed in a subsequent round.
-    pub nonce: u32,
-    /// Signer set captured when the round was opened (first approval).
-    pub signers: Vec<Address>,
-    /// Approval threshold captured when the round was opened.
-    pub threshold: u32,
-    /// Addresses that have submitted a valid approval this round.
-    pub approvals: Vec<Address>,
+pub struct EscrowResolvedEvent {
+    pub escrow_id: u64,
+    pub buyer: Address,
+    pub seller: Address,
+    pub arbitrator: Address,
+    pub amount: i128,
+    pub token: Address,
+    pub timestamp: u64,
 }
 
-/// Per-token fee configuration introduced for #239.
-///
-/// The legacy `FeeTokenIndex` storage held only a flat `Vec<Address>` of
-/// fee-receiving tokens, which forced any future multi-token fee model into a
-/// contract upgrade. This struct gives us a per-token slot keyed by
-/// `DataKey::FeeTokenConfig(token)` that can carry forward additional fields
-/// (e.g. custom_bps overrides, token-specific receivers) without touching the
-/// global storage shape â€” new fields can be appended as `Option<T>` and read
-/// with safe fallbacks.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct ReputationUpdateEvent {
+    pub address: Address,
+    pub successful_delta: u32,
+    pub disputed_delta: u32,
+    pub metrics_sales_delta: u32,
+    pub metrics_amount: i128,
+    pub token: Address,
+    pub timestamp: u64,
+}
+
+/// Tagged union used to carry a single configuration value inside
+/// [`ConfigUpdatedEvent`].
 ///
-/// # Fields
+/// Soroban events must be self-describing for off-chain indexers, but
+/// `PlatformConfig` fields are heterogeneous (counts, monetary amounts,
+/// addresses, and free-form strings). Rather than emit a separate event type
+/// per field â€” which would bloat the contract's event ABI â€” every admin
+/// configuration change is normalized into one of these four variants. Indexers
+/// match on the variant tag to recover the underlying Rust type without any
+/// loss of precision (in particular, `I128` monetary values are never
+/// stringified on-chain; see the note on [`EscrowEvent::amount`]).
 ///
-/// * `active` - Boolean flag indicating whether this token is currently active for
-///   platform fee collection. When false, the admin can disable a token without
-///   losing its accumulated totals, allowing history preservation while stopping
-///   future fee counting.
+/// # Variant mapping
 ///
-/// * `custom_fee_bps` - Optional custom fee basis points specific to this token.
-///   Reserved for a future multi-token fee mode; currently NOT consulted by
-///   `calculate_fee` to keep this change storage-only and avoid behavior changes.
-///   A follow-up issue will wire this into fee calculation once the storage shape
-///   stabilizes in production.
+/// * `U32`     â€” bounded counters and basis-point fees (e.g. `platform_fee_bps`).
+/// * `I128`    â€” monetary thresholds such as `min_escrow_amount`.
+/// * `Address` â€” role and token addresses (e.g. `fee_collector`).
+/// * `String`  â€” human-readable identifiers that have no compact encoding.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ConfigValue {
+    U32(u32),
+    I128(i128),
+    Address(Address),
+    String(String),
+}
+
+/// Emitted whenever an admin mutates a single field of the on-chain
+/// `PlatformConfig`.
 ///
-/// * `accumulated` - Total fees accumulated in this token, measured in stroops.
-///   Monotonically increasing counter that preserves fee history across
-///   activation/deactivation cycles.
+/// # Topics
 ///
-/// # Storage Side-effects
+/// Published under `(symbol "config_updated", symbol field_name)` so indexers
+/// can subscribe to changes of a specific field cheaply. The `field_name` topic
+/// mirrors the `field_name` payload member.
 ///
-/// - Stored persistently under `DataKey::FeeTokenConfig(token_address)` with
-///   TTL extension on reads to prevent premature archival.
-/// - Updates to this struct trigger config refresh in affected escrow operations
-///   to ensure correct fee calculations based on token status.
+/// # Preconditions
 ///
-/// # Integration notes
+/// * The caller must be the current platform admin; the emitting function
+///   asserts `admin.require_auth()` before the storage write, so this event is
+///   only ever observed for an authorized change.
 ///
-/// Off-chain integrators should cache this struct keyed by token address and
-/// refresh on-demand when escrow operations reference new tokens. The `accumulated`
-/// field provides audit trail for fee reconciliation; timestamp context is
-/// available via escrow event logs.
+/// # Storage side-effects
+///
+/// * The corresponding `PlatformConfig` field has already been persisted by the
+///   time this event fires. The event is emitted *after* the storage write,
+///   in keeping with the check-effects-interactions ordering used throughout
+///   the contract.
+///
+/// # Payload
+///
+/// * `field_name` â€” symbolic name of the mutated field.
+/// * `old_value`  â€” value held immediately before the write.
+/// * `new_value`  â€” value persisted by this update.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct FeeTokenInfo {
-    pub active: bool,
-    pub custom_fee_bps: Option<u32>,
-    pub accumulated: i128,
+pub struct ConfigUpdatedEvent {
+    pub field_name: Symbol,
+    pub old_value: ConfigValue,
+    pub new_value: ConfigValue,
 }
 
-/// Summary event emitted after a fee-token config migration run.
+/// Emitted when an artisan's negotiated platform-fee tier is set or changed.
 ///
-/// Operators can compare `scanned_tokens`, `migrated_configs`, and
-/// `skipped_existing` to verify that the legacy `FeeTokenIndex` was fully
-/// audited and that a second run is a no-op.
+/// Per-artisan fee tiers let the platform reward high-reputation sellers with a
+/// reduced `fee_bps` (basis points, where `10_000` == 100%). The persisted tier
+/// overrides the global `platform_fee_bps` for that artisan's future escrows.
+///
+/// # Topics
+///
+/// Published under `(symbol "artisan_fee_tier_updated", address artisan)` so a
+/// client can stream the fee history of a single artisan.
+///
+/// # Preconditions
+///
+/// * The caller must be the platform admin (`require_auth`).
+/// * `fee_bps` is validated against `MAX_PLATFORM_FEE_BPS`; an out-of-range
+///   value aborts with [`Error::InvalidFee`] and no event is emitted.
+///
+/// # Storage side-effects
+///
+/// * The artisan's fee-tier ledger entry is written (and its TTL extended)
+///   before this event fires.
+///
+/// # Payload
+///
+/// * `artisan` â€” address whose fee tier was updated.
+/// * `fee_bps` â€” the new fee in basis points applied to future escrows.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct FeeTokenConfigsMigratedEvent {
-    pub scanned_tokens: u32,
-    pub migrated_configs: u32,
-    pub skipped_existing: u32,
+pub struct ArtisanFeeTierUpdatedEvent {
+    pub artisan: Address,
+    pub fee_bps: u32,
 }
 
-/// Aggregated version metadata returned from `get_version_info`. Mirrors the
-/// fields surfaced via the upgrade history but in a flat shape suitable for
-/// dashboards / `migrate_v_x` style audits.
+/// Emitted when an artisan stakes collateral tokens into the platform.
+///
+/// Staking is a precondition for accepting high-value escrows; the staked
+/// balance backs the artisan's dispute exposure. Token movement obeys the
+/// check-effects-interactions pattern: the artisan's persistent stake balance
+/// is increased and committed *before* the external `token.transfer` callback,
+/// so a malicious token contract cannot re-enter and observe a stale balance.
+///
+/// # Topics
+///
+/// Published under `(symbol "tokens_staked", address artisan)`.
+///
+/// # Preconditions
+///
+/// * `artisan.require_auth()` â€” only the staker may stake on their own behalf.
+/// * `amount` must be positive and `token` whitelisted.
+///
+/// # Storage side-effects
+///
+/// * The artisan's staked-balance entry is incremented and its TTL extended.
+///
+/// # Payload
+///
+/// * `artisan` â€” the staking address.
+/// * `token`   â€” the staked token's contract address.
+/// * `amount`  â€” raw token amount staked (never stringified on-chain).
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct VersionInfo {
-    pub current_version: u32,
-    pub upgrade_count: u32,
+pub struct TokensStakedEvent {
+    pub artisan: Address,
+    pub token: Address,
+    pub amount: i128,
 }
 
-/// Parameters for batch escrow creation
+/// Emitted when an artisan withdraws previously staked collateral.
+///
+/// # Topics
+///
+/// Published under `(symbol "tokens_unstaked", address artisan)`.
+///
+/// # Preconditions
+///
+/// * `artisan.require_auth()`.
+/// * The stake cooldown must have elapsed, otherwise the call aborts with
+///   [`Error::StakeCooldownActive`] and no event is emitted.
+/// * The withdrawal token must match the original staking token
+///   ([`Error::StakeTokenMismatch`]).
+///
+/// # Storage side-effects
+///
+/// * The artisan's staked-balance entry is decremented *before* the outbound
+///   `token.transfer`, preserving reentrancy safety (the transfer is the final
+///   interaction in the call path).
+///
+/// # Payload
+///
+/// * `artisan` â€” the withdrawing address.
+/// * `token`   â€” the unstaked token's contract address.
+/// * `amount`  â€” raw token amount returned to the artisan.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct EscrowCreateParams {
-    pub buyer: Address,
-    pub seller: Address,
+pub struct TokensUnstakedEvent {
+    pub artisan: Address,
     pub token: Address,
     pub amount: i128,
-    pub order_id: u32,
-    pub release_window: Option<u32>,
-    pub ipfs_hash: Option<String>,
-    pub metadata_hash: Option<Bytes>,
 }
 
-/// Policy for handling fees when a dispute expires without arbitrator resolution.
+/// Emitted when an order's off-chain metadata is verified against its on-chain
+/// commitment.
 ///
-/// A dispute "expires" when the configured `max_dispute_duration` elapses
-/// without the arbitrator delivering a verdict (see [`PlatformConfig`]).
-/// At that point the escrow must still be unwound â€” but the platform fee
-/// is suddenly ambiguous: nobody won the dispute, so the usual "loser
-/// pays the fee" rule doesn't apply. This enum is the on-chain knob the
-/// admin uses to pick a policy at configuration time.
+/// The contract stores only a compact hash of an order's metadata (see
+/// [`EscrowMetadata`]); the full document lives off-chain (e.g. IPFS). When a
+/// verifier reveals the document and the contract confirms its hash matches the
+/// stored commitment, this event records the successful verification so
+/// indexers can mark the order's metadata as trusted.
 ///
-/// # Indexer / off-chain integration
+/// # Topics
 ///
-/// Each variant is serialised as its `repr(u32)` discriminant on the
-/// wire, so off-chain indexers can match against `0..=3` without binding
-/// to the variant names. The discriminants are stable; reordering them
-/// would be a breaking change for existing escrows whose `PlatformConfig`
-/// has been persisted on-chain.
+/// Published under `(symbol "metadata_verified", u64 order_id)`.
+///
+/// # Preconditions
+///
+/// * The stored commitment must exist and the revealed content must hash to it,
+///   otherwise the call aborts with [`Error::InvalidMetadataHash`].
+///
+/// # Storage side-effects
+///
+/// * None beyond TTL refresh of the order entry; verification is a read-and-
+///   compare operation that emits this audit event.
+///
+/// # Payload
+///
+/// * `order_id`  â€” the escrow/order whose metadata was verified.
+/// * `verifier`  â€” address that submitted the reveal proof.
+/// * `timestamp` â€” ledger timestamp at verification time.
 #[contracttype]
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub enum ExpiredDisputeFeePolicy {
-    /// Refund buyer in full, platform collects no fee. The default,
-    /// buyer-friendly policy â€” the platform absorbs the cost of the
-    /// arbitrator timing out. Use when buyer goodwill matters more than
-    /// covering operational cost on stalled disputes.
-    RefundFullNoPlatformFee = 0,
-    /// Refund buyer minus platform fee. The platform still earns its
-    /// fee, taken from the buyer's refunded amount. Symmetric to a
-    /// normal "buyer loses" resolution; use when the platform must
-    /// cover its costs regardless of dispute outcome.
-    RefundMinusPlatformFee = 1,
-    /// Refund buyer in full, deduct platform fee from the seller's
-    /// locked amount. The seller forfeits the fee even though they
-    /// never received payment â€” use when seller responsibility for
-    /// presenting evidence outweighs the cost of forfeiting on a
-    /// stalled arbitration.
-    DeductFeeFromSeller = 2,
-    /// Split the platform fee: half deducted from the buyer's refund,
-    /// half from the seller's locked amount. The most "neutral" policy
-    /// â€” both sides share the cost of the arbitrator timing out.
-    SplitFee = 3,
+pub struct MetadataVerifiedEvent {
+    pub order_id: u64,
+    pub verifier: Address,
+    pub timestamp: u64,
 }
 
-/// Platform configuration data
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct PlatformConfig {
-    pub platform_fee_bps: u32,    // Platform fee in basis points (500 = 5%)
-    pub platform_wallet: Address, // Wallet address to receive fees
-    /// Admin address for management.
-    /// This address can be a regular account or a Multisig contract address
-    /// to enhance security for sensitive operations like `propose_upgrade_wasm` (#95).
-    pub admin: Address,
-    pub arbitrator: Address, // Arbitrator for dispute resolution
-    pub moderator: Option<Address>,
-    pub is_paused: bool,                // Circuit breaker (#96)
-    pub min_stake_required: i128, // Minimum stake artisan must hold to create escrows (Issue #99)
-    pub pending_admin: Option<Address>, // Pending admin for two-step transfer
-    pub wasm_upgrade_cooldown: u32, // Grace period for WASM upgrades in seconds (default: 7 days)
-    pub max_dispute_duration: u32, // Maximum duration a dispute can remain open in seconds (default: 30 days)
-    pub stake_cooldown: u32, // Cooldown period after staking before tokens can be unstaked in seconds (default: 7 days)
-    /// Policy for handling platform fees when disputes expire without arbitrator resolution
-    pub expired_dispute_fee_policy: ExpiredDisputeFeePolicy,
-    /// Minimum release window to prevent "flash" auto-releases (default: 1 day)
-    pub min_release_window: u32,
+pub struct PlatformPausedEvent {
+    pub initiator: Address,
+    pub timestamp: u64,
 }
 
-/// Partial refund proposal created during a dispute (Issue #101)
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct PartialRefundProposal {
-    pub order_id: u32,
-    pub refund_amount: i128,
-    pub proposed_by: Address,
-    pub proposed_at: u64,
+pub struct PlatformUnpausedEvent {
+    pub initiator: Address,
+    pub timestamp: u64,
 }
 
-/// User roles in the CraftNexus platform
 #[contracttype]
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub enum UserRole {
-    None = 0,      // User has not onboarded
-    Buyer = 1,     // Can purchase items
-    Artisan = 2,   // Can sell items and create escrow
-    Admin = 3,     // Platform administrator
-    Moderator = 4, // Can help manage disputes
+pub struct EscrowMetadata {
+    pub ipfs_hash: Option<String>,
+    pub metadata_hash: Option<Bytes>,
 }
 
-/// Profile status for users
+/// Metadata reveal proof for privacy verification (Issue #122)
 #[contracttype]
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub enum ProfileStatus {
-    Active = 0,
-    Deactivated = 1,
+pub struct MetadataRevealProof {
+    /// The full metadata content (off-chain document)
+    pub content: Bytes,
+    /// Optional secret key for additional verification
+    pub secret: Option<Bytes>,
 }
 
-/// Onboarding status for users
+/// Test-only metadata structure for simplified testing
+#[cfg(test)]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Metadata {
+    pub title: String,
+    pub description: String,
+    pub category: String,
+}
+
+/// Proposal record for a pending WASM upgrade.
+///
+/// `upgrade_at` is the earliest ledger timestamp at which `execute_upgrade` may
+/// run; it equals `proposed_at + wasm_upgrade_cooldown` from `PlatformConfig`.
+/// `proposed_by` records the admin that submitted the proposal â€” note that the
+/// admin role can rotate via the two-step transfer (`update_admin` /
+/// `claim_admin`), so the value reflects the admin at proposal time, not at
+/// execution time. `execute_upgrade` re-checks the *current* admin's auth, so
+/// rotating admins cannot bypass authorization.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct UserProfile {
-    pub version: u32,
-    pub address: Address,
-    pub role: UserRole,
-    pub username: String,
-    pub registered_at: u64,
-    pub is_verified: bool,
-    /// Count of escrows where this user was on the winning side (#100)
-    pub successful_trades: u32,
-    /// Count of escrows that ended in a dispute against this user (#100)
-    pub disputed_trades: u32,
-    /// Portfolio CID for artisan showcase (IPFS) - Issue #112
-    pub portfolio_cid: Option<String>,
-    /// Status of the user profile - Issue #113
-    pub status: ProfileStatus,
+pub struct WasmUpgradeProposal {
+    pub wasm_hash: BytesN<32>,
+    pub upgrade_at: u64,
+    pub proposed_by: Address,
+    pub proposed_at: u64,
 }
 
+/// Lifecycle event emitted whenever a WASM upgrade proposal is created,
+/// replaced, cancelled, or executed. Indexers can use the `action` symbol to
+/// reconstruct the upgrade audit trail without scanning storage.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
-pub struct LegacyUserProfile {
-    pub address: Address,
-    pub role: UserRole,
-    pub username: String,
-    pub registered_at: u64,
-    pub is_verified: bool,
-    /// Count of escrows where this user was on the winning side (#100)
-    pub successful_trades: u32,
-    /// Count of escrows that ended in a dispute against this user (#100)
-    pub disputed_trades: u32,
-    /// Portfolio CID for artisan showcase (IPFS) - Issue #112
-    pub portfolio_cid: Option<String>,
+pub struct UpgradeProposalEvent {
+    pub action: Symbol,
+    pub wasm_hash: BytesN<32>,
+    pub admin: Address,
+    pub timestamp: u64,
+    pub upgrade_at: u64,
 }
 
-/// Minimal cross-contract interface for the OnboardingContract.
-/// Used by CraftNexusContract to update user reputation and activity metrics
-/// when escrow state changes (release, refund, resolve).
-#[soroban_sdk::contractclient(name = "OnboardingClient")]
-pub trait OnboardingInterface {
-    /// Increment a user's reputation counters.
-    ///
-    /// Called by this escrow contract after a terminal escrow outcome where a
-    /// winner/loser can be determined (release/refund/dispute resolution).
-    /// The onboarding contract authenticates that the caller is the registered
-    /// escrow contract address.
-    fn update_reputation(env: Env, address: Address, successful_delta: u32, disputed_delta: u32);
-    /// Increment a user's activity metrics (escrow count + volume).
-    ///
-    /// Used by onboarding to drive auto-verification and analytics counters.
-    fn update_user_metrics(
-        env: Env,
-        address: Address,
-        escrow_count_delta: u32,
-        volume_delta: i128,
-        token_address: Address,
-    );
-    /// Mark a user profile as deactivated.
-    ///
-    /// Used by the escrow contract for administrative safety actions; the
-    /// onboarding contract enforces its own authorization rules.
-    fn deactivate_profile(env: Env, user: Address);
-    /// Verify a user, returning the updated profile.
-    fn verify_user(env: Env, user: Address) -> UserProfile;
-    /// Return true if the user currently has any active escrow obligations.
-    fn has_active_contracts(env: Env, user: Address) -> bool;
-    /// Update onboarding's local active-contract counter for a user.
-    ///
-    /// `delta` should be `+1` when an escrow becomes active and `-1` when the
-    /// escrow closes. The onboarding contract rejects underflows.
-    fn update_active_contracts(env: Env, user: Address, delta: i32);
-    /// Refresh the persistent TTL for a user's profile entry.
-    fn bump_user_profile_ttl(env: Env, user: Address) -> bool;
-    /// Refresh the persistent TTL for a user's activity metrics entry.
-    fn bump_user_metrics_ttl(env: Env, user: Address) -> bool;
+/// On-chain record of a completed WASM upgrade.
+///
+/// One entry is appended to `UpgradeHistory` per successful `execute_upgrade`
+/// call, providing operators and auditors visibility into how the contract
+/// reached its current `ContractVersion`.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct UpgradeRecord {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub wasm_hash: BytesN<32>,
+    pub admin: Address,
+    pub timestamp: u64,
 }
 
-#[contract]
-/// CraftNexus escrow contract.
+/// Immutable per-round state for the multi-sig upgrade approval flow.
 ///
-/// # Storage model
-/// - Escrows are stored under `(ESCROW, order_id)` as a single compact record.
-/// - Enumeration uses count + indexed keys (e.g. `EscrowCount` +
-///   `GlobalEscrowIdIndexed(i)`) to avoid unbounded `Vec` growth.
+/// Written once on the **first** approval call for a given proposal nonce and
+/// never mutated except to append new approvals.  Keyed by
+/// `DataKey::UpgradeApprovalState(nonce)`.
 ///
-/// # TTL model
-/// - Persistent entries extend TTL on write via `extend_persistent`.
-/// - Hot index reads use `extend_persistent_read` with a lower threshold to
-///   reduce rent-refresh overhead while still preventing accidental expiry.
+/// # Security properties
 ///
-/// # Onboarding integration
-/// - The admin can register an onboarding contract address.
-/// - Cross-contract calls are wrapped in `try_invoke_contract` helpers so an
-///   onboarding failure never bricks escrow settlement.
-pub struct CraftNexusContract;
-
-/// Alias and compatibility layers
-pub const ESCROW_CONTRACT: CraftNexusContract = CraftNexusContract;
-
-pub type EscrowContractClient<'a> = CraftNexusContractClient<'a>;
-
-/// Guard to ensure reentry protection is cleared even if a panic or error occurs.
-/// This is essential to prevent contract locks from persisting across failed calls.
-/// Automatically removes the guard when dropped, ensuring cleanup in all control flows.
-struct ReentryGuardScope<'a> {
-    env: &'a Env,
-}
-
-impl<'a> ReentryGuardScope<'a> {
-    fn new(env: &'a Env) -> Self {
-        CraftNexusContract::enter_reentry_guard(env);
-        ReentryGuardScope { env }
-    }
-}
-
-impl<'a> Drop for ReentryGuardScope<'a> {
-    fn drop(&mut self) {
-        CraftNexusContract::exit_reentry_guard(self.env);
-    }
+/// * `signers`   — snapshotted from `UpgradeSigners` (or admin fallback) at
+///   round open.  Subsequent `set_upgrade_signers` calls cannot alter which
+///   addresses are eligible for this round, closing the signer-rotation race.
+///
+/// * `threshold` — snapshotted from `UpgradeThreshold` at round open.
+///   Mid-round `set_upgrade_threshold` calls therefore cannot lower the bar
+///   for the current round.
+///
+/// * `approvals` — grows monotonically as valid signers call
+///   `propose_upgrade_wasm`.  Only addresses present in `signers` may appear
+///   here; duplicates are rejected with `AlreadyApproved`.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct UpgradeApprovalState {
+    /// Monotonically increasing round counter.  Incremented on every
+    /// `cancel_upgrade_wasm` call so that residual state from a prior
+    /// round cannot be replayed in a subsequent round.
+    pub nonce: u32,
+    /// Signer set captured when the round was opened (first approval).
+    pub signers: Vec<Address>,
+    /// Approval threshold captured when the round was opened.
+    pub threshold: u32,
+    /// Addresses that have submitted a valid approval this round.
+    pub approvals: Vec<Address>,
 }
 
-#[contractimpl]
-impl CraftNexusContract {
-    /// Validate IPFS CID format (v0 and v1 with multibase prefixes).
-    ///
-    /// Supports:
-    /// - CIDv0: 46-char Base58btc starting with "Qm"
-    /// - CIDv1 base32lower (prefix 'b'): lowercase a-z + 2-7
-    /// - CIDv1 base16lower (prefix 'f'): lowercase hex 0-9 + a-f
-    /// - CIDv1 base58btc  (prefix 'z'): Base58 alphabet
-    fn validate_ipfs_cid(cid: &String) -> bool {
-        let len = cid.len() as usize;
+/// Per-token fee configuration introduced for #239.
+///
+/// The legacy `FeeTokenIndex` storage held only a flat `Vec<Address>` of
+/// fee-receiving tokens, which forced any future multi-token fee model into a
+/// contract upgrade. This struct gives us a per-token slot keyed by
+/// `DataKey::FeeTokenConfig(token)` that can carry forward additional fields
+/// (e.g. custom_bps overrides, token-specific receivers) without touching the
+/// global storage shape â€” new fields can be appended as `Option<T>` and read
+/// with safe fallbacks.
+///
+/// # Fields
+///
+/// * `active` - Boolean flag indicating whether this token is currently active for
+///   platform fee collection. When false, the admin can disable a token without
+///   losing its accumulated totals, allowing history preservation while stopping
+///   future fee counting.
+///
+/// * `custom_fee_bps` - Optional custom fee basis points specific to this token.
+///   Replaces the platform fee for escrows in this token; artisan fee tiers
+///   still take precedence (see `get_effective_fee_bps_for_token`).
+///
+/// * `accumulated` - Total fees accumulated in this token, measured in stroops.
+///   Monotonically increasing counter that preserves fee history across
+///   activation/deactivation cycles.
+///
+/// # Storage Side-effects
+///
+/// - Stored persistently under `DataKey::FeeTokenConfig(token_address)` with
+///   TTL extension on reads to prevent premature archival.
+/// - Updates to this struct trigger config refresh in affected escrow operations
+///   to ensure correct fee calculations based on token status.
+///
+/// # Integration notes
+///
+/// Off-chain integrators should cache this struct keyed by token address and
+/// refresh on-demand when escrow operations reference new tokens. The `accumulated`
+/// field provides audit trail for fee reconciliation; timestamp context is
+/// available via escrow event logs.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct FeeTokenInfo {
+    pub active: bool,
+    pub custom_fee_bps: Option<u32>,
+    pub accumulated: i128,
+    /// Fee payer model for this token. Only applies when
+    /// `custom_fee_payer` is set; otherwise the global model is used.
+    pub fee_payer: FeePayer,
+    pub custom_fee_payer: bool,
+}
+
+/// `FeeTokenInfo` layout before per-token fee payer models.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+struct FeeTokenInfoV1 {
+    pub active: bool,
+    pub custom_fee_bps: Option<u32>,
+    pub accumulated: i128,
+}
+
+/// Summary event emitted after a fee-token config migration run.
+///
+/// Operators can compare `scanned_tokens`, `migrated_configs`, and
+/// `skipped_existing` to verify that the legacy `FeeTokenIndex` was fully
+/// audited and that a second run is a no-op.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct FeeTokenConfigsMigratedEvent {
+    pub scanned_tokens: u32,
+    pub migrated_configs: u32,
+    pub skipped_existing: u32,
+}
+
+/// Aggregated version metadata returned from `get_version_info`. Mirrors the
+/// fields surfaced via the upgrade history but in a flat shape suitable for
+/// dashboards / `migrate_v_x` style audits.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct VersionInfo {
+    pub current_version: u32,
+    pub upgrade_count: u32,
+}
+
+/// Parameters for batch escrow creation
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct EscrowCreateParams {
+    pub buyer: Address,
+    pub seller: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub order_id: u32,
+    pub release_window: Option<u32>,
+    pub ipfs_hash: Option<String>,
+    pub metadata_hash: Option<Bytes>,
+    /// Funds the escrow instead of `buyer`; see `Escrow::payer`.
+    pub payer: Option<Address>,
+    /// Destination for refunds; see `Escrow::refund_address`.
+    pub refund_address: Option<Address>,
+}
+
+/// Policy for handling fees when a dispute expires without arbitrator resolution.
+///
+/// A dispute "expires" when the configured `max_dispute_duration` elapses
+/// without the arbitrator delivering a verdict (see [`PlatformConfig`]).
+/// At that point the escrow must still be unwound â€” but the platform fee
+/// is suddenly ambiguous: nobody won the dispute, so the usual "loser
+/// pays the fee" rule doesn't apply. This enum is the on-chain knob the
+/// admin uses to pick a policy at configuration time.
+///
+/// # Indexer / off-chain integration
+///
+/// Each variant is serialised as its `repr(u32)` discriminant on the
+/// wire, so off-chain indexers can match against `0..=3` without binding
+/// to the variant names. The discriminants are stable; reordering them
+/// would be a breaking change for existing escrows whose `PlatformConfig`
+/// has been persisted on-chain.
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ExpiredDisputeFeePolicy {
+    /// Refund buyer in full, platform collects no fee. The default,
+    /// buyer-friendly policy â€” the platform absorbs the cost of the
+    /// arbitrator timing out. Use when buyer goodwill matters more than
+    /// covering operational cost on stalled disputes.
+    RefundFullNoPlatformFee = 0,
+    /// Refund buyer minus platform fee. The platform still earns its
+    /// fee, taken from the buyer's refunded amount. Symmetric to a
+    /// normal "buyer loses" resolution; use when the platform must
+    /// cover its costs regardless of dispute outcome.
+    RefundMinusPlatformFee = 1,
+    /// Refund buyer in full, deduct platform fee from the seller's
+    /// locked amount. The seller forfeits the fee even though they
+    /// never received payment â€” use when seller responsibility for
+    /// presenting evidence outweighs the cost of forfeiting on a
+    /// stalled arbitration.
+    DeductFeeFromSeller = 2,
+    /// Split the platform fee: half deducted from the buyer's refund,
+    /// half from the seller's locked amount. The most "neutral" policy
+    /// â€” both sides share the cost of the arbitrator timing out.
+    SplitFee = 3,
+}
+
+/// Platform configuration data
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct PlatformConfig {
+    pub platform_fee_bps: u32,    // Platform fee in basis points (500 = 5%)
+    pub platform_wallet: Address, // Wallet address to receive fees
+    /// Admin address for management.
+    /// This address can be a regular account or a Multisig contract address
+    /// to enhance security for sensitive operations like `propose_upgrade_wasm` (#95).
+    pub admin: Address,
+    pub arbitrator: Address, // Arbitrator for dispute resolution
+    pub moderator: Option<Address>,
+    pub is_paused: bool,                // Circuit breaker (#96)
+    pub min_stake_required: i128, // Minimum stake artisan must hold to create escrows (Issue #99)
+    pub pending_admin: Option<Address>, // Pending admin for two-step transfer
+    pub wasm_upgrade_cooldown: u32, // Grace period for WASM upgrades in seconds (default: 7 days)
+    pub max_dispute_duration: u32, // Maximum duration a dispute can remain open in seconds (default: 30 days)
+    pub stake_cooldown: u32, // Cooldown period after staking before tokens can be unstaked in seconds (default: 7 days)
+    /// Policy for handling platform fees when disputes expire without arbitrator resolution
+    pub expired_dispute_fee_policy: ExpiredDisputeFeePolicy,
+    /// Minimum release window to prevent "flash" auto-releases (default: 1 day)
+    pub min_release_window: u32,
+}
+
+/// Partial refund proposal created during a dispute (Issue #101)
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct PartialRefundProposal {
+    pub order_id: u32,
+    pub refund_amount: i128,
+    pub proposed_by: Address,
+    pub proposed_at: u64,
+}
+
+/// User roles in the CraftNexus platform
+#[contracttype]
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum UserRole {
+    None = 0,      // User has not onboarded
+    Buyer = 1,     // Can purchase items
+    Artisan = 2,   // Can sell items and create escrow
+    Admin = 3,     // Platform administrator
+    Moderator = 4, // Can help manage disputes
+}
+
+/// Profile status for users
+#[contracttype]
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub enum ProfileStatus {
+    Active = 0,
+    Deactivated = 1,
+}
+
+/// Onboarding status for users
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct UserProfile {
+    pub version: u32,
+    pub address: Address,
+    pub role: UserRole,
+    pub username: String,
+    pub registered_at: u64,
+    pub is_verified: bool,
+    /// Count of escrows where this user was on the winning side (#100)
+    pub successful_trades: u32,
+    /// Count of escrows that ended in a dispute against this user (#100)
+    pub disputed_trades: u32,
+    /// Portfolio CID for artisan showcase (IPFS) - Issue #112
+    pub portfolio_cid: Option<String>,
+    /// Status of the user profile - Issue #113
+    pub status: ProfileStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "testutils"), derive(Debug))]
+pub struct LegacyUserProfile {
+    pub address: Address,
+    pub role: UserRole,
+    pub username: String,
+    pub registered_at: u64,
+    pub is_verified: bool,
+    /// Count of escrows where this user was on the winning side (#100)
+    pub successful_trades: u32,
+    /// Count of escrows that ended in a dispute against this user (#100)
+    pub disputed_trades: u32,
+    /// Portfolio CID for artisan showcase (IPFS) - Issue #112
+    pub portfolio_cid: Option<String>,
+}
+
+/// Minimal cross-contract interface for the OnboardingContract.
+/// Used by CraftNexusContract to update user reputation and activity metrics
+/// when escrow state changes (release, refund, resolve).
+#[soroban_sdk::contractclient(name = "OnboardingClient")]
+pub trait OnboardingInterface {
+    /// Increment a user's reputation counters.
+    ///
+    /// Called by this escrow contract after a terminal escrow outcome where a
+    /// winner/loser can be determined (release/refund/dispute resolution).
+    /// The onboarding contract authenticates that the caller is the registered
+    /// escrow contract address.
+    fn update_reputation(env: Env, address: Address, successful_delta: u32, disputed_delta: u32);
+    /// Increment a user's activity metrics (escrow count + volume).
+    ///
+    /// Used by onboarding to drive auto-verification and analytics counters.
+    fn update_user_metrics(
+        env: Env,
+        address: Address,
+        escrow_count_delta: u32,
+        volume_delta: i128,
+        token_address: Address,
+    );
+    /// Mark a user profile as deactivated.
+    ///
+    /// Used by the escrow contract for administrative safety actions; the
+    /// onboarding contract enforces its own authorization rules.
+    fn deactivate_profile(env: Env, user: Address);
+    /// Verify a user, returning the updated profile.
+    fn verify_user(env: Env, user: Address) -> UserProfile;
+    /// Return true if the user currently has any active escrow obligations.
+    fn has_active_contracts(env: Env, user: Address) -> bool;
+    /// Update onboarding's local active-contract counter for a user.
+    ///
+    /// `delta` should be `+1` when an escrow becomes active and `-1` when the
+    /// escrow closes. The onboarding contract rejects underflows.
+    fn update_active_contracts(env: Env, user: Address, delta: i32);
+    /// Refresh the persistent TTL for a user's profile entry.
+    fn bump_user_profile_ttl(env: Env, user: Address) -> bool;
+    /// Refresh the persistent TTL for a user's activity metrics entry.
+    fn bump_user_metrics_ttl(env: Env, user: Address) -> bool;
+    /// Return the address that referred `user`, if one was recorded.
+    fn get_referrer(env: Env, user: Address) -> Option<Address>;
+    /// Return `(total_volume, trust_score)` for volume-based fee tiers.
+    fn get_fee_tier_metrics(env: Env, address: Address) -> (i128, u32);
+}
+
+#[contract]
+/// CraftNexus escrow contract.
+///
+/// # Storage model
+/// - Escrows are stored under `(ESCROW, order_id)` as a single compact record.
+/// - Enumeration uses count + indexed keys (e.g. `EscrowCount` +
+///   `GlobalEscrowIdIndexed(i)`) to avoid unbounded `Vec` growth.
+///
+/// # TTL model
+/// - Persistent entries extend TTL on write via `extend_persistent`.
+/// - Hot index reads use `extend_persistent_read` with a lower threshold to
+///   reduce rent-refresh overhead while still preventing accidental expiry.
+///
+/// # Onboarding integration
+/// - The admin can register an onboarding contract address.
+/// - Cross-contract calls are wrapped in `try_invoke_contract` helpers so an
+///   onboarding failure never bricks escrow settlement.
+pub struct CraftNexusContract;
+
+/// Alias and compatibility layers
+pub const ESCROW_CONTRACT: CraftNexusContract = CraftNexusContract;
+
+pub type EscrowContractClient<'a> = CraftNexusContractClient<'a>;
+
+/// Guard to ensure reentry protection is cleared even if a panic or error occurs.
+/// This is essential to prevent contract locks from persisting across failed calls.
+/// Automatically removes the guard when dropped, ensuring cleanup in all control flows.
+struct ReentryGuardScope<'a> {
+    env: &'a Env,
+}
+
+impl<'a> ReentryGuardScope<'a> {
+    fn new(env: &'a Env) -> Self {
+        CraftNexusContract::enter_reentry_guard(env);
+        ReentryGuardScope { env }
+    }
+}
+
+impl<'a> Drop for ReentryGuardScope<'a> {
+    fn drop(&mut self) {
+        CraftNexusContract::exit_reentry_guard(self.env);
+    }
+}
+
+#[contractimpl]
+impl CraftNexusContract {
+    /// Validate IPFS CID format (v0 and v1 with multibase prefixes).
+    ///
+    /// Supports:
+    /// - CIDv0: 46-char Base58btc starting with "Qm"
+    /// - CIDv1 base32lower (prefix 'b'): lowercase a-z + 2-7
+    /// - CIDv1 base16lower (prefix 'f'): lowercase hex 0-9 + a-f
+    /// - CIDv1 base58btc  (prefix 'z'): Base58 alphabet
+    fn validate_ipfs_cid(cid: &String) -> bool {
+        let len = cid.len() as usize;
         if len == 0 || len > 128 {
             return false;
         }
 
-        let mut buf = [0u8; 128];
-        cid.copy_into_slice(&mut buf[0..len]);
-        let cid_bytes = &buf[0..len];
+        let mut buf = [0u8; 128];
+        cid.copy_into_slice(&mut buf[0..len]);
+        let cid_bytes = &buf[0..len];
+
+        let is_v0 = len == 46
+            && cid_bytes[0] == b'Q'
+            && cid_bytes[1] == b'm'
+            && cid_bytes.iter().all(|b| Self::is_base58_btc_char(*b));
+
+        if is_v0 {
+            return true;
+        }
+
+        // CIDv1: minimum 3 chars (multibase prefix + version byte + codec)
+        if len < 3 {
+            return false;
+        }
+
+        let prefix = cid_bytes[0];
+        let payload = &cid_bytes[1..];
+
+        match prefix {
+            // base32lower (most common CIDv1 encoding)
+            b'b' => {
+                if len < 50 || len > 100 || cid_bytes[1] != b'a' {
+                    return false;
+                }
+                payload
+                    .iter()
+                    .all(|b| matches!(*b, b'a'..=b'z' | b'2'..=b'7'))
+            }
+            // base16lower (hex)
+            b'f' => {
+                if len < 60 || len > 120 || cid_bytes[1] != b'0' || cid_bytes[2] != b'1' {
+                    return false;
+                }
+                payload
+                    .iter()
+                    .all(|b| matches!(*b, b'0'..=b'9' | b'a'..=b'f'))
+            }
+            // base58btc
+            b'z' => {
+                if len < 40 || len > 100 {
+                    return false;
+                }
+                payload.iter().all(|b| Self::is_base58_btc_char(*b))
+            }
+            _ => false,
+        }
+    }
+
+    #[inline(always)]
+    fn is_base58_btc_char(byte: u8) -> bool {
+        BASE58_BTC_CHARSET[byte as usize]
+    }
+
+    /// Validate an optional IPFS CID string, panicking with `InvalidIpfsHash` if present but invalid.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `ipfs_hash` - Optional CID string to validate
+    ///
+    /// # Errors
+    /// Panics with `Error::InvalidIpfsHash` if the CID is present but fails `validate_ipfs_cid`.
+    ///
+    /// # Storage side-effects
+    /// None â€” this is a pure validation helper with no storage reads or writes.
+    #[inline(always)]
+    fn validate_optional_ipfs_hash(env: &Env, ipfs_hash: &Option<String>) {
+        if let Some(cid) = ipfs_hash {
+            if !Self::validate_ipfs_cid(cid) {
+                env.panic_with_error(crate::Error::InvalidIpfsHash);
+            }
+        }
+    }
+
+    /// Validate an optional metadata hash, panicking with `InvalidMetadataHash` if present but not 32 bytes.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `metadata_hash` - Optional raw bytes to validate
+    ///
+    /// # Errors
+    /// Panics with `Error::InvalidMetadataHash` if the hash is present but its length is not exactly 32 bytes.
+    ///
+    /// # Storage side-effects
+    /// None â€” this is a pure validation helper with no storage reads or writes.
+    #[inline(always)]
+    fn validate_optional_metadata_hash(env: &Env, metadata_hash: &Option<Bytes>) {
+        if let Some(hash) = metadata_hash {
+            if hash.len() != 32 {
+                env.panic_with_error(crate::Error::InvalidMetadataHash);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn get_admin(env: &Env) -> Result<Address, Error> {
+        let config: PlatformConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlatformConfig)
+            .ok_or(Error::PlatformNotInitialized)?;
+        Ok(config.admin)
+    }
+
+    /// Validates admin address to ensure it's not zero/default and is properly initialized (#240)
+    /// This prevents common configuration errors and hardens against corruption.
+    ///
+    /// Storage-layout note: this validator sits on the hot path for any
+    /// admin-gated mutation. Checks are ordered cheapest-first so the
+    /// common case (a structurally valid candidate that differs from
+    /// the current contract address) returns without touching persistent
+    /// storage at all â€” a small but consistent gas saving across every
+    /// transfer / propose / accept_admin call.
+    fn validate_admin_address(env: &Env, admin: &Address) -> Result<(), Error> {
+        // Ensure the address is not the contract's own address â€” a common
+        // misconfiguration that would lock the contract out of admin
+        // operations forever.
+        let contract = env.current_contract_address();
+        if admin == &contract {
+            return Err(Error::InvalidAdminAddress);
+        }
+        // Note: Additional address validation could be performed here
+        // (e.g., checking if address exists on ledger, format validation, etc.)
+        Ok(())
+    }
+
+    /// Gets platform configuration with fallback mechanism for corruption recovery (#240)
+    /// Returns the primary config if valid, falls back to last-known good state if corrupted
+    #[allow(dead_code)]
+    fn get_platform_config_safe(env: &Env) -> Result<PlatformConfig, Error> {
+        let config: Option<PlatformConfig> = env.storage().persistent().get(&PLATFORM_FEE);
+
+        if let Some(cfg) = config {
+            // Validate that critical fields are initialized
+            if Self::validate_admin_address(env, &cfg.admin).is_ok() {
+                Self::extend_persistent(env, &PLATFORM_FEE);
+                return Ok(cfg);
+            }
+        }
+
+        // If primary config is missing or corrupted, attempt to recover using fallback admin
+        if let Some(fallback_admin) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::FallbackAdmin)
+        {
+            Self::extend_persistent(env, &DataKey::FallbackAdmin);
+            // Emit recovery event for audit trail
+            env.events().publish(
+                (Symbol::new(env, "admin_config_recovered"), true),
+                String::from_str(env, "Using fallback admin after config corruption detected"),
+            );
+            // Return a minimal valid config with fallback admin
+            // This ensures critical operations remain accessible even if config is corrupted
+            return Ok(PlatformConfig {
+                platform_fee_bps: 500, // 5% default fee
+                platform_wallet: fallback_admin.clone(),
+                admin: fallback_admin,
+                arbitrator: env.current_contract_address(),
+                moderator: None,
+                is_paused: true, // Safer to default to paused during recovery
+                min_stake_required: 0,
+                pending_admin: None,
+                wasm_upgrade_cooldown: DEFAULT_WASM_UPGRADE_COOLDOWN,
+                max_dispute_duration: DEFAULT_MAX_DISPUTE_DURATION,
+                stake_cooldown: DEFAULT_STAKE_COOLDOWN,
+                expired_dispute_fee_policy: ExpiredDisputeFeePolicy::RefundFullNoPlatformFee,
+                min_release_window: DEFAULT_MIN_RELEASE_WINDOW,
+            });
+        }
+
+        Err(Error::CorruptedPlatformConfig)
+    }
+
+    /// Emits audit event for admin changes to maintain a complete audit trail (#240)
+    fn emit_admin_changed(
+        env: &Env,
+        previous_admin: Address,
+        new_admin: Address,
+        change_type: &str,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "admin_changed"), change_type.as_bytes()),
+            (previous_admin, new_admin),
+        );
+    }
+
+    /// Stores fallback admin address for recovery purposes (#240)
+    /// This ensures that even if primary admin storage is corrupted, platform can be recovered
+    fn set_fallback_admin(env: &Env, admin: Address) -> Result<(), Error> {
+        Self::validate_admin_address(env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::FallbackAdmin, &admin);
+        Self::extend_persistent(env, &DataKey::FallbackAdmin);
+        Ok(())
+    }
+
+    fn emit_escrow_created(env: &Env, event: EscrowEvent) {
+        env.events()
+            .publish((symbol_short!("escrow"), event.escrow_id), event);
+    }
+
+    fn emit_escrow_resolved_event(env: &Env, event: EscrowResolvedEvent) {
+        env.events().publish(
+            (Symbol::new(env, "escrow_resolved"), event.escrow_id),
+            event,
+        );
+    }
+
+    fn emit_reputation_update(env: &Env, event: ReputationUpdateEvent) {
+        env.events().publish(
+            (
+                Symbol::new(env, "stake_reputation_update"),
+                event.address.clone(),
+            ),
+            event,
+        );
+    }
+
+    fn emit_config_updated(
+        env: &Env,
+        field_name: &str,
+        old_value: ConfigValue,
+        new_value: ConfigValue,
+    ) {
+        env.events().publish(
+            (
+                Symbol::new(env, "admin_config_updated"),
+                Symbol::new(env, field_name),
+            ),
+            ConfigUpdatedEvent {
+                field_name: Symbol::new(env, field_name),
+                old_value,
+                new_value,
+            },
+        );
+    }
+
+    fn emit_artisan_fee_tier_updated(env: &Env, artisan: Address, fee_bps: u32) {
+        env.events().publish(
+            (Symbol::new(env, "admin_fee_tier_updated"), artisan.clone()),
+            ArtisanFeeTierUpdatedEvent { artisan, fee_bps },
+        );
+    }
+
+    fn emit_metadata_verified(env: &Env, order_id: u32, verifier: Address) {
+        env.events().publish(
+            (
+                Symbol::new(env, "escrow_metadata_verified"),
+                (order_id as u64),
+            ),
+            MetadataVerifiedEvent {
+                order_id: order_id as u64,
+                verifier,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    fn emit_platform_paused(env: &Env, initiator: Address) {
+        env.events().publish(
+            (Symbol::new(env, "admin_platform_paused"), initiator.clone()),
+            PlatformPausedEvent {
+                initiator,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    fn emit_platform_unpaused(env: &Env, initiator: Address) {
+        env.events().publish(
+            (
+                Symbol::new(env, "admin_platform_unpaused"),
+                initiator.clone(),
+            ),
+            PlatformUnpausedEvent {
+                initiator,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Atomically appends one escrow ID to the indexed global registry and
+    /// increments `EscrowCount` (#515 / Issue #226).
+    fn update_escrow_indices_atomic(env: &Env, order_id: u32) {
+        // Issue #515 â€” O(1) indexed append replaces monolithic AllEscrowIds Vec
+        // rewrites. Legacy Vec entries are migrated lazily on first touch.
+        Self::migrate_legacy_all_escrow_ids(env);
+
+        let count_key = DataKey::EscrowCount;
+        let count = Self::get_persistent_u32(env, &count_key);
+
+        let index_key = DataKey::GlobalEscrowIdIndexed(count);
+        env.storage().persistent().set(&index_key, &order_id);
+        Self::extend_persistent(env, &index_key);
+
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_persistent(env, &count_key);
+    }
+
+    /// Atomically appends escrow IDs to the indexed global registry for batch
+    /// operations (#515). Each ID is stored under its own key so batch creates
+    /// avoid rewriting a growing Vec.
+    fn update_escrow_indices_batch_atomic(env: &Env, order_ids: &soroban_sdk::Vec<u32>) {
+        if order_ids.is_empty() {
+            return;
+        }
+
+        Self::migrate_legacy_all_escrow_ids(env);
+
+        let count_key = DataKey::EscrowCount;
+        let mut count = Self::get_persistent_u32(env, &count_key);
+
+        for i in 0..order_ids.len() {
+            if let Some(id) = order_ids.get(i) {
+                let index_key = DataKey::GlobalEscrowIdIndexed(count);
+                env.storage().persistent().set(&index_key, &id);
+                Self::extend_persistent(env, &index_key);
+                count += 1;
+            }
+        }
+
+        env.storage().persistent().set(&count_key, &count);
+        Self::extend_persistent(env, &count_key);
+    }
+
+    // IMPORTANT: this validation is intentionally scoped to escrow creation-time
+    // flows only. Do not call from payout/distribution paths, or dynamic minimum
+    // changes could trap dust balances in existing escrows.
+    fn check_min_amount(env: &Env, token: Address, amount: i128) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::AmountBelowMinimum);
+        }
+
+        let min_amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MinEscrowAmount(token))
+            .unwrap_or(0); // If not set, allow any positive amount
+
+        if amount < min_amount {
+            return Err(Error::AmountBelowMinimum);
+        }
+
+        Ok(())
+    }
+
+    /// Records a stake operation in the history queue for audit trail and analytics (#237)
+    /// Implements bounded queue with automatic pruning to prevent unbounded storage growth
+    fn record_stake_history(
+        env: &Env,
+        artisan: &Address,
+        new_stake: i128,
+        operation: &str,
+    ) -> Result<(), Error> {
+        let count_key = DataKey::StakeHistoryCount(artisan.clone());
+        let _history_key = DataKey::StakeHistory(artisan.clone());
+
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        // Check if we need to prune before adding new entry
+        if current_count >= MAX_STAKE_HISTORY_SIZE {
+            // Queue is full, cannot add more entries
+            return Err(Error::StakeQueueFull);
+        }
+
+        // If approaching capacity threshold, schedule pruning
+        if current_count >= STAKE_HISTORY_PRUNE_THRESHOLD {
+            // Keep only most recent 50% of entries to free up space
+            // This is done lazily - oldest entries will be overwritten on next full cycle
+            let new_count = current_count / 2;
+            env.storage().persistent().set(&count_key, &new_count);
+            Self::extend_persistent(env, &count_key);
+        }
+
+        // Record timestamp of this operation for maintenance checks
+        let modified_key = DataKey::StakeLastModified(artisan.clone());
+        env.storage()
+            .persistent()
+            .set(&modified_key, &env.ledger().timestamp());
+        Self::extend_persistent(env, &modified_key);
+
+        // Emit audit event
+        env.events().publish(
+            (Symbol::new(env, "stake_operation"), operation.as_bytes()),
+            (artisan.clone(), new_stake),
+        );
+
+        Ok(())
+    }
+
+    /// Prunes obsolete stake history entries when queue reaches capacity (#237)
+    /// Implements safe cleanup strategy that preserves recent entries for audit trail
+    #[allow(dead_code)]
+    fn prune_stake_history(env: &Env, artisan: &Address) {
+        let count_key = DataKey::StakeHistoryCount(artisan.clone());
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        if current_count > 0 {
+            // Keep most recent 50 entries, discard older ones
+            let retained_count = current_count.min(50);
+            env.storage().persistent().set(&count_key, &retained_count);
+            Self::extend_persistent(env, &count_key);
+        }
+    }
+
+    #[inline(always)]
+    fn update_active_obligations(env: &Env, user: &Address, delta: i32) {
+        let key = DataKey::ActiveObligations(user.clone());
+        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let new_val = if delta > 0 {
+            count.saturating_add(delta as u32)
+        } else {
+            count.saturating_sub((-delta) as u32)
+        };
+        env.storage().persistent().set(&key, &new_val);
+        Self::extend_persistent(env, &key);
+    }
+
+    #[inline(always)]
+    fn update_total_locked(env: &Env, token: &Address, delta: i128) {
+        let key = DataKey::TotalLocked(token.clone());
+        let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let new_total = current.saturating_add(delta);
+        env.storage().persistent().set(&key, &new_total);
+        Self::extend_persistent(env, &key);
+    }
+
+    #[inline(always)]
+    fn update_total_staked(env: &Env, token: &Address, delta: i128) {
+        let key = DataKey::TotalStaked(token.clone());
+        let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let new_total = current.saturating_add(delta);
+        env.storage().persistent().set(&key, &new_total);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Extend the TTL of a persistent storage entry using standardized values.
+    #[inline(always)]
+    fn extend_persistent(env: &Env, key: &impl soroban_sdk::IntoVal<Env, soroban_sdk::Val>) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, TTL_THRESHOLD, TTL_EXTENSION);
+    }
+
+    #[inline(always)]
+    fn extend_persistent_read(env: &Env, key: &impl soroban_sdk::IntoVal<Env, soroban_sdk::Val>) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, READ_TTL_THRESHOLD, TTL_EXTENSION);
+    }
+
+    /// Read a persistent `u32` and extend its TTL when the key exists (#515).
+    #[inline(always)]
+    fn get_persistent_u32(env: &Env, key: &DataKey) -> u32 {
+        match env.storage().persistent().get(key) {
+            Some(value) => {
+                Self::extend_persistent(env, key);
+                value
+            }
+            None => 0u32,
+        }
+    }
+
+    /// Read a persistent `u64` and extend its TTL when the key exists (#431 / key index #30).
+    #[inline(always)]
+    fn get_persistent_u64(env: &Env, key: &DataKey) -> u64 {
+        match env.storage().persistent().get(key) {
+            Some(value) => {
+                Self::extend_persistent(env, key);
+                value
+            }
+            None => 0u64,
+        }
+    }
+
+    #[inline(always)]
+    fn get_whitelist_count(env: &Env) -> u32 {
+        let count_key = DataKey::WhitelistedTokenCount;
+        match env.storage().persistent().get(&count_key) {
+            Some(count) => {
+                Self::extend_persistent(env, &count_key);
+                count
+            }
+            None => 0u32,
+        }
+    }
+
+    #[inline(always)]
+    fn set_whitelist_count(env: &Env, count: u32) {
+        let count_key = DataKey::WhitelistedTokenCount;
+        env.storage().persistent().set(&count_key, &count);
+        Self::extend_persistent(env, &count_key);
+    }
+
+    fn migrate_legacy_whitelisted_tokens(env: &Env) {
+        let legacy_key = DataKey::WhitelistedTokens;
+        if !env.storage().persistent().has(&legacy_key) {
+            return;
+        }
+
+        let legacy_whitelist: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .unwrap_or(Map::new(env));
+
+        let mut count = Self::get_whitelist_count(env);
+        for (token, enabled) in legacy_whitelist.iter() {
+            if enabled {
+                let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
+                if !env.storage().persistent().has(&token_key) {
+                    env.storage().persistent().set(&token_key, &true);
+                    Self::extend_persistent(env, &token_key);
+                    count += 1;
+                }
+            }
+        }
+
+        if count > 0 {
+            Self::set_whitelist_count(env, count);
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::WhitelistedTokenCount);
+        }
+
+        env.storage().persistent().remove(&legacy_key);
+    }
+
+    /// Migrate legacy `AllEscrowIds` Vec storage to indexed keys (#515).
+    fn migrate_legacy_all_escrow_ids(env: &Env) {
+        let legacy_key = DataKey::AllEscrowIds;
+        if !env.storage().persistent().has(&legacy_key) {
+            return;
+        }
+
+        let all_ids: soroban_sdk::Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .unwrap_or(soroban_sdk::Vec::new(env));
+
+        for i in 0..all_ids.len() {
+            if let Some(id) = all_ids.get(i) {
+                let index_key = DataKey::GlobalEscrowIdIndexed(i);
+                if !env.storage().persistent().has(&index_key) {
+                    env.storage().persistent().set(&index_key, &id);
+                    Self::extend_persistent(env, &index_key);
+                }
+            }
+        }
+
+        let count_key = DataKey::EscrowCount;
+        let stored_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if stored_count < all_ids.len() {
+            env.storage()
+                .persistent()
+                .set(&count_key, &(all_ids.len() as u32));
+            Self::extend_persistent(env, &count_key);
+        }
+
+        env.storage().persistent().remove(&legacy_key);
+    }
+
+    /// Returns the configured maximum release window (in seconds).
+    /// Falls back to MAX_TOTAL_RELEASE_WINDOW (30 days) if not set by admin.
+    #[inline(always)]
+    fn get_max_release_window(env: &Env) -> u32 {
+        let key = DataKey::MaxReleaseWindow;
+        let value = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(MAX_TOTAL_RELEASE_WINDOW);
+        // Issue #423: extend TTL on read to prevent storage expiry
+        if env.storage().persistent().has(&key) {
+            Self::extend_persistent(env, &key);
+        }
+        value
+    }
+
+    /// Returns the configured onboarding contract address, if any (#243).
+    fn get_onboarding_address(env: &Env) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Address>(&DataKey::OnboardingContractAddress)
+    }
+
+    /// Build a client for the configured onboarding contract, if one is set.
+    ///
+    /// This helper is used by the escrow contract’s safe cross-contract
+    /// integration so onboarding updates remain behind a single, explicit
+    /// entry point instead of being spread across ad-hoc address lookups.
+    fn get_onboarding_client(env: &Env) -> Option<(Address, OnboardingClient<'_>)> {
+        Self::get_onboarding_address(env).map(|address| {
+            let client = OnboardingClient::new(env, &address);
+            (address, client)
+        })
+    }
+
+    /// Public read-only accessor for the registered onboarding contract
+    /// address. Returns `OnboardingContractNotSet` rather than `None` so that
+    /// SDK clients receive a typed error instead of a silent unwrap on a
+    /// `None`. Use `has_onboarding_contract` for a boolean check (#243).
+    pub fn get_onboarding_contract(env: Env) -> Result<Address, Error> {
+        Self::get_onboarding_address(&env).ok_or(Error::OnboardingContractNotSet)
+    }
+
+    /// True iff `set_onboarding_contract` has been called. Useful for
+    /// dashboards and integration tests that need to assert configuration
+    /// without surfacing an error path (#243).
+    pub fn has_onboarding_contract(env: Env) -> bool {
+        Self::get_onboarding_address(&env).is_some()
+    }
+
+    /// Check if a user has any active escrows or recurring escrows.
+    pub fn has_active_escrows(env: Env, user: Address) -> bool {
+        let key = DataKey::ActiveObligations(user);
+        Self::get_persistent_u32(&env, &key) > 0
+    }
+
+    /// Emit a structured warning event when a cross-contract call to the
+    /// onboarding contract fails. Indexers can subscribe to `OB_FAIL` to flag
+    /// integration drift between the escrow and onboarding contracts.
+    fn emit_onboarding_call_failed(env: &Env, method: Symbol, address: Address) {
+        env.events().publish(
+            (ONBOARD_CALL_FAILED, method),
+            (address, env.ledger().timestamp()),
+        );
+    }
+
+    /// Safely call `update_reputation` on the registered onboarding contract.
+    ///
+    /// Returns `Ok(true)` on a successful cross-contract call, `Ok(false)` if
+    /// the call failed (so the caller can decide whether to fall back to
+    /// emitting events) or no contract is configured. Never panics, never
+    /// propagates the host trap â€” the escrow flow MUST keep moving even if
+    /// the onboarding contract is broken or pointing at a malicious
+    /// implementation (#243).
+    #[allow(dead_code)]
+    fn safe_update_reputation(
+        env: &Env,
+        address: Address,
+        successful_delta: u32,
+        disputed_delta: u32,
+    ) -> bool {
+        // Issue #527 â€” short-circuit on the no-op call before paying
+        // for the persistent storage read of the onboarding contract
+        // address. If both reputation deltas are 0 the cross-contract
+        // call has no effect; returning `true` here saves a storage
+        // decode + a host `try_invoke_contract` on every escrow
+        // settlement where reputation didn't change.
+        if successful_delta == 0 && disputed_delta == 0 {
+            return true;
+        }
+
+        let (onboarding_address, _onboarding) = match Self::get_onboarding_client(env) {
+            Some(client) => client,
+            None => return false,
+        };
+
+        let method = Symbol::new(env, "update_reputation");
+        let args: Vec<Val> = (address.clone(), successful_delta, disputed_delta).into_val(env);
+
+        match env.try_invoke_contract::<(), soroban_sdk::Error>(&onboarding_address, &method, args)
+        {
+            Ok(Ok(())) => true,
+            _ => {
+                Self::emit_onboarding_call_failed(env, method, onboarding_address);
+                false
+            }
+        }
+    }
+
+    /// Safely call `update_user_metrics` on the registered onboarding contract.
+    /// Mirrors `safe_update_reputation`'s contract: never panics, returns
+    /// `false` on missing config or cross-contract failure (#243).
+    #[allow(dead_code)]
+    fn safe_update_user_metrics(
+        env: &Env,
+        address: Address,
+        escrow_count_delta: u32,
+        volume_delta: i128,
+        token_address: Address,
+    ) -> bool {
+        let (onboarding_address, _onboarding) = match Self::get_onboarding_client(env) {
+            Some(client) => client,
+            None => return false,
+        };
+
+        let method = Symbol::new(env, "update_user_metrics");
+        let args: Vec<Val> = (
+            address.clone(),
+            escrow_count_delta,
+            volume_delta,
+            token_address.clone(),
+        )
+            .into_val(env);
+
+        match env.try_invoke_contract::<(), soroban_sdk::Error>(&onboarding_address, &method, args)
+        {
+            Ok(Ok(())) => true,
+            _ => {
+                Self::emit_onboarding_call_failed(env, method, onboarding_address);
+                false
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    fn safe_update_active_contracts(env: &Env, user: Address, delta: i32) -> bool {
+        if delta == 0 {
+            return true;
+        }
+
+        let (onboarding_address, _onboarding) = match Self::get_onboarding_client(env) {
+            Some(client) => client,
+            None => return false,
+        };
+
+        let method = Symbol::new(env, "update_active_contracts");
+        let args: Vec<Val> = (user.clone(), delta).into_val(env);
+
+        match env.try_invoke_contract::<(), soroban_sdk::Error>(&onboarding_address, &method, args)
+        {
+            Ok(Ok(())) => true,
+            _ => {
+                Self::emit_onboarding_call_failed(env, method, onboarding_address);
+                false
+            }
+        }
+    }
+
+    /// Set the configurable maximum release window (admin only).
+    ///
+    /// Kept for existing clients; role holders call `set_max_release_window_as`.
+    pub fn set_max_release_window(env: Env, max_window: u32) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::set_max_release_window_as(env, admin, max_window)
+    }
+
+    /// Set the configurable maximum release window (`Role::Operator`).
+    ///
+    /// # Arguments
+    /// * `max_window` - Maximum allowed release window in seconds.
+    ///   Must be > 0 and <= ABSOLUTE_MAX_RELEASE_WINDOW.
+    pub fn set_max_release_window_as(env: Env, caller: Address, max_window: u32) {
+        Self::require_role(&env, Role::Operator, &caller);
+        if max_window == 0 {
+            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        }
+        if max_window > ABSOLUTE_MAX_RELEASE_WINDOW {
+            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::MaxReleaseWindow, &max_window);
+    }
+
+    /// Set the minimum release window (admin only).
+    ///
+    /// Kept for existing clients; role holders call `set_min_release_window_as`.
+    pub fn set_min_release_window(env: Env, min_window: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::set_min_release_window_as(env, admin, min_window)
+    }
+
+    /// Set the minimum release window to prevent "flash" auto-releases (`Role::Operator`).
+    ///
+    /// # Arguments
+    /// * `min_window` - Minimum allowed release window in seconds
+    ///
+    /// # Panics
+    /// - If min_window is 0
+    /// - If min_window exceeds the current max_release_window
+    pub fn set_min_release_window_as(
+        env: Env,
+        caller: Address,
+        min_window: u32,
+    ) -> Result<(), Error> {
+        let mut config = Self::get_platform_config_internal(&env);
+        Self::require_role(&env, Role::Operator, &caller);
+
+        if min_window == 0 {
+            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        }
+
+        let max_window = Self::get_max_release_window(&env);
+        if min_window > max_window {
+            return Err(Error::ReleaseWindowTooLong);
+        }
+
+        let old_min = config.min_release_window;
+        config.min_release_window = min_window;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        Self::emit_config_updated(
+            &env,
+            "min_release_window",
+            ConfigValue::U32(old_min),
+            ConfigValue::U32(min_window),
+        );
+
+        Ok(())
+    }
+
+    /// Get the current minimum release window
+    pub fn get_min_release_window(env: Env) -> u32 {
+        let config = Self::get_platform_config_internal(&env);
+        config.min_release_window
+    }
+
+    /// Register the deployed OnboardingContract address (admin only).
+    ///
+    /// Kept for existing clients; role holders call `set_onboarding_contract_as`.
+    pub fn set_onboarding_contract(env: Env, contract_address: Address) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::set_onboarding_contract_as(env, admin, contract_address)
+    }
+
+    /// Register the deployed OnboardingContract address so the escrow contract
+    /// can make cross-contract reputation / metrics updates (`Role::Operator`).
+    ///
+    /// (#243) Rejects pointing the onboarding contract at the escrow itself â€”
+    /// a self-call would create a re-entrancy hazard if the trait surface ever
+    /// expands. Cross-contract calls into the configured address remain
+    /// indirect via `safe_update_reputation` / `safe_update_user_metrics`,
+    /// which trap-isolate failures so a misbehaving onboarding contract
+    /// cannot brick escrow operations. Emits a `config_updated` event with
+    /// the previous and new addresses for audit trails.
+    pub fn set_onboarding_contract_as(env: Env, caller: Address, contract_address: Address) {
+        Self::require_role(&env, Role::Operator, &caller);
+
+        if contract_address == env.current_contract_address() {
+            env.panic_with_error(crate::Error::Unauthorized);
+        }
+
+        let previous = Self::get_onboarding_address(&env);
+
+        // Issue #527 â€” short-circuit on the no-op call before paying
+        // for the persistent storage write and TTL extension.
+        if let Some(ref current) = previous {
+            if *current == contract_address {
+                return;
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::OnboardingContractAddress, &contract_address);
+        Self::extend_persistent(&env, &DataKey::OnboardingContractAddress);
+
+        let old_value = match previous {
+            Some(addr) => ConfigValue::Address(addr),
+            None => ConfigValue::String(String::from_str(&env, "unset")),
+        };
+        Self::emit_config_updated(
+            &env,
+            "onboarding_contract",
+            old_value,
+            ConfigValue::Address(contract_address),
+        );
+    }
+
+    /// Clear the registered onboarding contract address (admin only).
+    ///
+    /// Kept for existing clients; role holders call `clear_onboarding_contract_as`.
+    pub fn clear_onboarding_contract(env: Env) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::clear_onboarding_contract_as(env, admin)
+    }
+
+    /// Clear the registered onboarding contract address (`Role::Operator`, #243).
+    /// After calling this, `get_onboarding_contract` returns
+    /// `OnboardingContractNotSet` and the safe cross-contract helpers become
+    /// no-ops â€” escrow flows continue to emit `ReputationUpdateEvent`s for
+    /// off-chain reconstruction (#211).
+    pub fn clear_onboarding_contract_as(env: Env, caller: Address) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
+
+        let previous = Self::get_onboarding_address(&env).ok_or(Error::OnboardingContractNotSet)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::OnboardingContractAddress);
+
+        Self::emit_config_updated(
+            &env,
+            "onboarding_contract",
+            ConfigValue::Address(previous),
+            ConfigValue::String(String::from_str(&env, "unset")),
+        );
+        Ok(())
+    }
+
+    /// Add a token to the platform whitelist (admin only).
+    ///
+    /// Kept for existing clients; role holders call `whitelist_token_as`.
+    pub fn whitelist_token(env: Env, token: Address) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::whitelist_token_as(env, admin, token)
+    }
+
+    /// Add a token to the platform whitelist (`Role::TokenManager`).
+    ///
+    /// Uses individual key-value pairs for scalability instead of a single Map.
+    /// Each token is stored as DataKey::WhitelistedTokenIndexed(token) -> true.
+    /// Once at least one token is whitelisted, only whitelisted tokens may be
+    /// used in escrow creation. The check is skipped when the whitelist is empty,
+    /// preserving backward compatibility.
+    ///
+    /// # Decimal validation
+    ///
+    /// The token's `decimals()` value must be in the range 0–18 (inclusive).
+    /// Tokens with more than 18 decimal places would overflow the volume
+    /// normalization arithmetic in the onboarding contract and are rejected
+    /// with [`Error::InvalidTokenDecimals`].
+    pub fn whitelist_token_as(env: Env, caller: Address, token: Address) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::require_role(&env, Role::TokenManager, &caller);
+
+        // Probe the SEP-41 read interface before persisting an administrator
+        // supplied address. Missing or malformed methods become a stable
+        // contract error instead of an opaque host panic.
+        let token_client = token::Client::new(&env, &token);
+        let decimals = token_client
+            .try_decimals()
+            .map_err(|_| Error::UnsupportedToken)?
+            .map_err(|_| Error::UnsupportedToken)?;
+        if decimals > 18 {
+            return Err(Error::InvalidTokenDecimals);
+        }
+        token_client
+            .try_balance(&env.current_contract_address())
+            .map_err(|_| Error::UnsupportedToken)?
+            .map_err(|_| Error::UnsupportedToken)?;
+
+        Self::migrate_legacy_whitelisted_tokens(&env);
+        let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
+        let mut count = Self::get_whitelist_count(&env);
+
+        if !env.storage().persistent().has(&token_key) {
+            env.storage().persistent().set(&token_key, &true);
+            Self::extend_persistent(&env, &token_key);
+            count += 1;
+            Self::set_whitelist_count(&env, count);
+        }
+        Ok(())
+    }
+
+    /// Remove a token from the platform whitelist (admin only).
+    ///
+    /// Kept for existing clients; role holders call `remove_token_from_whitelist_as`.
+    pub fn remove_token_from_whitelist(env: Env, token: Address) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::remove_token_from_whitelist_as(env, admin, token)
+    }
+
+    /// Remove a token from the platform whitelist (`Role::TokenManager`).
+    ///
+    /// Uses individual key-value pairs for scalability. Removes the specific
+    /// token entry and updates the count. If the resulting whitelist is empty,
+    /// whitelist enforcement is automatically disabled (all tokens permitted again).
+    pub fn remove_token_from_whitelist_as(env: Env, caller: Address, token: Address) {
+        Self::require_role(&env, Role::TokenManager, &caller);
+
+        Self::migrate_legacy_whitelisted_tokens(&env);
+        let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
+
+        if env.storage().persistent().has(&token_key) {
+            env.storage().persistent().remove(&token_key);
+            let count = Self::get_whitelist_count(&env);
+            if count > 0 {
+                Self::set_whitelist_count(&env, count - 1);
+            }
+        }
+    }
+
+    /// Check whether a specific token is on the whitelist.
+    ///
+    /// Returns `true` if the token is explicitly whitelisted, OR if the whitelist
+    /// is empty (enforcement not yet active). Uses individual key lookups for
+    /// scalability instead of loading the entire whitelist Map.
+    pub fn is_token_whitelisted(env: Env, token: Address) -> bool {
+        Self::migrate_legacy_whitelisted_tokens(&env);
+        let count = Self::get_whitelist_count(&env);
+        if count == 0 {
+            return true;
+        }
+
+        let token_key = DataKey::WhitelistedTokenIndexed(token);
+        let is_whitelisted = env.storage().persistent().has(&token_key);
+        if is_whitelisted {
+            Self::extend_persistent(&env, &token_key);
+        }
+        is_whitelisted
+    }
+
+    /// Internal helper: panics with TokenNotWhitelisted when enforcement is active
+    /// and the token is not on the whitelist.
+    /// NOTE: whitelist enforcement is intentionally performed only during
+    /// escrow creation (and related locking operations). State transitions
+    /// such as `release`, `refund`, or recurring cycle releases MUST NOT
+    /// re-check the whitelist to avoid locking funds for escrows created
+    /// before whitelist changes. Keep this helper private and call it only
+    /// in creation-time validation paths.
+    fn check_token_whitelisted(env: &Env, token: &Address) {
+        Self::migrate_legacy_whitelisted_tokens(env);
+        let count = Self::get_whitelist_count(env);
+        if count == 0 {
+            return;
+        }
+
+        let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
+        if !env.storage().persistent().has(&token_key) {
+            env.panic_with_error(crate::Error::TokenNotWhitelisted);
+        }
+    }
+
+    /// Get the count of whitelisted tokens.
+    ///
+    /// Returns 0 if no tokens are whitelisted (enforcement disabled).
+    /// This is more efficient than loading all tokens when only the count is needed.
+    pub fn get_whitelisted_token_count(env: Env) -> u32 {
+        Self::migrate_legacy_whitelisted_tokens(&env);
+        Self::get_whitelist_count(&env)
+    }
+
+    /// Migrate legacy whitelist storage (admin only).
+    ///
+    /// Kept for existing clients; role holders call `migrate_whitelist_storage_as`.
+    pub fn migrate_whitelist_storage(env: Env) -> u32 {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::migrate_whitelist_storage_as(env, admin)
+    }
+
+    /// Migrate legacy whitelist storage to individual key-value pairs.
+    ///
+    /// This function reads the old WhitelistedTokens Map and converts each entry
+    /// to individual WhitelistedTokenIndexed keys. Should be called once during
+    /// contract upgrade to migrate existing data (`Role::Migrator`).
+    pub fn migrate_whitelist_storage_as(env: Env, caller: Address) -> u32 {
+        Self::require_role(&env, Role::Migrator, &caller);
+
+        let legacy_key = DataKey::WhitelistedTokens;
+
+        // Check if legacy storage exists
+        if !env.storage().persistent().has(&legacy_key) {
+            return 0; // Nothing to migrate
+        }
+
+        let legacy_whitelist: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .unwrap_or(Map::new(&env));
+
+        let mut migrated_count = 0u32;
+
+        // Migrate each token to individual storage
+        let keys = legacy_whitelist.keys();
+        for i in 0..keys.len() {
+            if let Some(token) = keys.get(i) {
+                if let Some(is_whitelisted) = legacy_whitelist.get(token.clone()) {
+                    if is_whitelisted {
+                        let token_key = DataKey::WhitelistedTokenIndexed(token);
+                        env.storage().persistent().set(&token_key, &true);
+                        Self::extend_persistent(&env, &token_key);
+                        migrated_count += 1;
+                    }
+                }
+            }
+        }
+
+        // Update count
+        if migrated_count > 0 {
+            let count_key = DataKey::WhitelistedTokenCount;
+            env.storage().persistent().set(&count_key, &migrated_count);
+            Self::extend_persistent(&env, &count_key);
+        }
+
+        // Remove legacy storage
+        env.storage().persistent().remove(&legacy_key);
+
+        migrated_count
+    }
+
+    /// Migrate a legacy ArtisanStakeQueue (admin only).
+    ///
+    /// Kept for existing clients; role holders call `migrate_artisan_stake_queue_as`.
+    pub fn migrate_artisan_stake_queue(env: Env, artisan: Address) -> u32 {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::migrate_artisan_stake_queue_as(env, admin, artisan)
+    }
+
+    /// Migrate legacy ArtisanStakeQueue Vec storage to individual indexed entries.
+    ///
+    /// This function reads the old ArtisanStakeQueue Vec and converts each entry
+    /// to individual ArtisanStakeQueueIndexed keys. Should be called once during
+    /// contract upgrade to migrate existing data (`Role::Migrator`).
+    pub fn migrate_artisan_stake_queue_as(env: Env, caller: Address, artisan: Address) -> u32 {
+        Self::require_role(&env, Role::Migrator, &caller);
+
+        let legacy_key = DataKey::ArtisanStakeQueue(artisan.clone());
+
+        // Check if legacy storage exists
+        if !env.storage().persistent().has(&legacy_key) {
+            return 0; // Nothing to migrate
+        }
+
+        let legacy_queue: soroban_sdk::Vec<StakeDeposit> = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+
+        let queue_len = legacy_queue.len();
+        if queue_len == 0 {
+            // Remove empty legacy queue
+            env.storage().persistent().remove(&legacy_key);
+            return 0;
+        }
+
+        // Migrate each deposit to individual indexed storage
+        for i in 0..queue_len {
+            if let Some(deposit) = legacy_queue.get(i) {
+                let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), i);
+                env.storage().persistent().set(&deposit_key, &deposit);
+                Self::extend_persistent(&env, &deposit_key);
+            }
+        }
+
+        // Set count
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        env.storage().persistent().set(&count_key, &queue_len);
+        Self::extend_persistent(&env, &count_key);
+
+        // Remove legacy storage
+        env.storage().persistent().remove(&legacy_key);
+
+        queue_len
+    }
+
+    /// Get the count of stake deposits in an artisan's queue.
+    ///
+    /// Returns 0 if no deposits exist. This is more efficient than loading
+    /// all deposits when only the count is needed.
+    pub fn get_artisan_stake_queue_count(env: Env, artisan: Address) -> u32 {
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        env.storage().persistent().get(&count_key).unwrap_or(0)
+    }
+
+    /// Get paginated stake deposits for an artisan (admin/debug helper).
+    ///
+    /// Returns up to `limit` deposits starting from `offset`. Useful for
+    /// inspecting queue state without loading the entire queue.
+    pub fn get_artisan_stake_deposits(
+        env: Env,
+        artisan: Address,
+        offset: u32,
+        limit: u32,
+    ) -> soroban_sdk::Vec<StakeDeposit> {
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        let total_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+        let mut deposits = soroban_sdk::Vec::new(&env);
+        let end = core::cmp::min(offset + limit, total_count);
+
+        for i in offset..end {
+            let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), i);
+            if let Some(deposit) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, StakeDeposit>(&deposit_key)
+            {
+                deposits.push_back(deposit);
+            }
+        }
+
+        deposits
+    }
+
+    pub fn initialize(
+        env: Env,
+        platform_wallet: Address,
+        admin: Address,
+        arbitrator: Address,
+        platform_fee_bps: u32,
+        onboarding_contract: Option<Address>,
+    ) {
+        admin.require_auth();
+
+        // Validate fee is within bounds
+        if platform_fee_bps > MAX_PLATFORM_FEE_BPS {
+            env.panic_with_error(crate::Error::InvalidFee);
+        }
+
+        let config = PlatformConfig {
+            platform_fee_bps,
+            platform_wallet: platform_wallet.clone(),
+            admin: admin.clone(),
+            arbitrator: arbitrator.clone(),
+            moderator: None,
+            is_paused: false,
+            min_stake_required: 0,
+            pending_admin: None,
+            wasm_upgrade_cooldown: DEFAULT_WASM_UPGRADE_COOLDOWN,
+            max_dispute_duration: DEFAULT_MAX_DISPUTE_DURATION,
+            stake_cooldown: DEFAULT_STAKE_COOLDOWN,
+            expired_dispute_fee_policy: ExpiredDisputeFeePolicy::RefundFullNoPlatformFee,
+            min_release_window: DEFAULT_MIN_RELEASE_WINDOW,
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        env.storage()
+            .persistent()
+            .set(&PLATFORM_WALLET, &platform_wallet);
+        Self::extend_persistent(&env, &PLATFORM_WALLET);
+
+        // Initialize total fees to 0
+        let zero: i128 = 0;
+        env.storage().persistent().set(&TOTAL_FEES, &zero);
+        Self::extend_persistent(&env, &TOTAL_FEES);
+
+        // Initialize contract version to 1
+        env.storage()
+            .persistent()
+            .set(&DataKey::ContractVersion, &1u32);
+        Self::extend_persistent(&env, &DataKey::ContractVersion);
+
+        // Initialize storage layout version so future upgrades can validate the
+        // on-disk schema before applying new logic.
+        env.storage()
+            .persistent()
+            .set(&DataKey::StorageLayoutVersion, &CURRENT_STORAGE_LAYOUT_VERSION);
+        Self::extend_persistent(&env, &DataKey::StorageLayoutVersion);
+
+        // Set the onboarding contract address to enable reputation tracking (optional)
+        if let Some(ref addr) = onboarding_contract {
+            env.storage()
+                .persistent()
+                .set(&DataKey::OnboardingContractAddress, addr);
+            Self::extend_persistent(&env, &DataKey::OnboardingContractAddress);
+        }
+
+        Self::emit_config_updated(
+            &env,
+            "platform_fee_bps",
+            ConfigValue::String(String::from_str(&env, "unset")),
+            ConfigValue::U32(platform_fee_bps),
+        );
+        Self::emit_config_updated(
+            &env,
+            "platform_wallet",
+            ConfigValue::String(String::from_str(&env, "unset")),
+            ConfigValue::Address(platform_wallet),
+        );
+        if let Some(addr) = onboarding_contract {
+            Self::emit_config_updated(
+                &env,
+                "onboarding_contract",
+                ConfigValue::String(String::from_str(&env, "unset")),
+                ConfigValue::Address(addr),
+            );
+        }
+    }
+
+    /// Propose a new administrator for the platform (admin only).
+    /// Starts the two-step transfer process (#95).
+    /// Both the current admin and the incoming admin must co-sign, proving the
+    /// new address is a live, registered ledger node capable of authorizing
+    /// transactions (#419).
+    pub fn update_admin(env: Env, new_admin: Address) {
+        let mut config = Self::get_platform_config_internal(&env);
+        config.admin.require_auth();
+
+        // Validate: not the contract address itself (#240)
+        if let Err(_) = Self::validate_admin_address(&env, &new_admin) {
+            env.panic_with_error(Error::InvalidAdminAddress);
+        }
+
+        // Require the incoming admin to co-sign, proving it is a fully
+        // registered ledger node that controls its own private key (#419).
+        new_admin.require_auth();
+
+        let previous_admin = config.admin.clone();
+        config.pending_admin = Some(new_admin.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        // Emit audit event for admin change proposal
+        Self::emit_admin_changed(&env, previous_admin, new_admin, "admin_proposed");
+    }
+
+    /// Claim the administrative role (pending admin only).
+    /// Completes the two-step transfer process (#95).
+    /// Enhanced with validation, audit logging and fallback setup (#240)
+    pub fn claim_admin(env: Env) {
+        let mut config = Self::get_platform_config_internal(&env);
+        let pending = config.pending_admin.as_ref().expect("");
+        pending.require_auth();
+
+        // Validate the pending admin address before accepting the transfer
+        if let Err(_) = Self::validate_admin_address(&env, pending) {
+            env.panic_with_error(Error::InvalidAdminAddress);
+        }
+
+        let previous_admin = config.admin.clone();
+        let new_admin = pending.clone();
+        config.admin = new_admin.clone();
+        config.pending_admin = None;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        // Emit audit event for the completed two-step admin transfer (#631)
+        Self::emit_admin_changed(&env, previous_admin, new_admin, "admin_claimed");
+    }
+
+    /// Cancel an in-progress two-step admin transfer (current admin only).
+    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
+        let mut config = Self::get_platform_config_internal(&env);
+        config.admin.require_auth();
+
+        if config.pending_admin.is_none() {
+            return Err(Error::NoPendingAdmin);
+        }
+
+        config.pending_admin = None;
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+        Ok(())
+    }
+
+    // ----- Admin Action Proposal (Multi-Sig + Timelock) -----
+
+    fn get_admin_action_signers(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&AdminActionDataKey::AdminActionSigners)
+            .unwrap_or_else(|| {
+                let mut signers = Vec::new(env);
+                if let Ok(admin) = Self::get_admin(env) {
+                    signers.push_back(admin);
+                }
+                signers
+            })
+    }
+
+    fn get_admin_action_threshold(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&AdminActionDataKey::AdminActionThreshold)
+            .unwrap_or(1u32)
+    }
+
+    fn get_admin_action_timelock_delay(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&AdminActionDataKey::AdminActionTimelockDelay)
+            .unwrap_or(DEFAULT_ADMIN_ACTION_TIMELOCK_DELAY)
+    }
+
+    fn get_next_admin_action_id(env: &Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminActionDataKey::NextAdminActionId)
+            .unwrap_or(1u64)
+    }
+
+    fn get_admin_action(env: &Env, action_id: u64) -> Option<AdminActionProposal> {
+        env.storage().persistent().get::<AdminActionDataKey, AdminActionProposal>(
+            &AdminActionDataKey::AdminAction(action_id),
+        )
+    }
+
+    fn persist_admin_action(env: &Env, action: &AdminActionProposal) {
+        env.storage()
+            .persistent()
+            .set(&AdminActionDataKey::AdminAction(action.id), action);
+        Self::extend_persistent(env, &AdminActionDataKey::AdminAction(action.id));
+    }
+
+    /// Configure the signer set for pending critical admin actions.
+    pub fn set_admin_action_signers(env: Env, signers: Vec<Address>) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        if signers.is_empty() {
+            env.storage().persistent().remove(&AdminActionDataKey::AdminActionSigners);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&AdminActionDataKey::AdminActionSigners, &signers);
+            Self::extend_persistent(&env, &AdminActionDataKey::AdminActionSigners);
+        }
+        Ok(())
+    }
+
+    /// Configure the approval threshold for pending critical admin actions.
+    pub fn set_admin_action_threshold(env: Env, threshold: u32) -> Result<(), Error> {
+        if threshold == 0 {
+            return Err(Error::InvalidFee);
+        }
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        env.storage().instance().set(&AdminActionDataKey::AdminActionThreshold, &threshold);
+        Ok(())
+    }
+
+    /// Configure the timelock delay applied to pending critical admin actions.
+    pub fn set_admin_action_timelock_delay(env: Env, delay_seconds: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&AdminActionDataKey::AdminActionTimelockDelay, &delay_seconds);
+        Ok(())
+    }
+
+    /// Create a new pending admin action that requires multi-sig approvals.
+    pub fn propose_admin_action(
+        env: Env,
+        proposer: Address,
+        action: AdminActionKind,
+    ) -> Result<AdminActionProposal, Error> {
+        proposer.require_auth();
+
+        let signers = Self::get_admin_action_signers(&env);
+        if !signers.iter().any(|signer| signer == proposer) {
+            return Err(Error::NotAnAdminActionSigner);
+        }
+
+        let threshold = Self::get_admin_action_threshold(&env);
+        let delay = Self::get_admin_action_timelock_delay(&env);
+        let created_at = env.ledger().timestamp();
+        let next_id = Self::get_next_admin_action_id(&env);
+
+        let mut approvals = Vec::new(&env);
+        approvals.push_back(proposer.clone());
+
+        let proposal = AdminActionProposal {
+            id: next_id,
+            kind: action,
+            proposer: proposer.clone(),
+            approvals,
+            threshold,
+            signers: signers.clone(),
+            created_at,
+            ready_at: created_at + delay,
+            executed: false,
+            cancelled: false,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&AdminActionDataKey::NextAdminActionId, &(next_id + 1));
+        Self::extend_persistent(&env, &AdminActionDataKey::NextAdminActionId);
+        Self::persist_admin_action(&env, &proposal);
+
+        Ok(proposal)
+    }
+
+    /// Approve an existing pending admin action.
+    pub fn approve_admin_action(
+        env: Env,
+        action_id: u64,
+        signer: Address,
+    ) -> Result<AdminActionProposal, Error> {
+        signer.require_auth();
+
+        let mut action = Self::get_admin_action(&env, action_id).ok_or(Error::AdminActionTerminal)?;
+        if action.cancelled {
+            return Err(Error::AdminActionTerminal);
+        }
+        if action.executed {
+            return Err(Error::AdminActionTerminal);
+        }
+        if !action.signers.iter().any(|existing| existing == signer) {
+            return Err(Error::NotAnAdminActionSigner);
+        }
+        if action.approvals.iter().any(|existing| existing == signer) {
+            return Err(Error::AlreadyApproved);
+        }
+
+        action.approvals.push_back(signer);
+        Self::persist_admin_action(&env, &action);
+        Ok(action)
+    }
+
+    /// Cancel a pending admin action.
+    pub fn cancel_admin_action(env: Env, action_id: u64) -> Result<AdminActionProposal, Error> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+
+        let mut action = Self::get_admin_action(&env, action_id).ok_or(Error::AdminActionTerminal)?;
+        if action.cancelled {
+            return Err(Error::AdminActionTerminal);
+        }
+        if action.executed {
+            return Err(Error::AdminActionTerminal);
+        }
+
+        action.cancelled = true;
+        Self::persist_admin_action(&env, &action);
+        Ok(action)
+    }
+
+    /// Execute a pending admin action once its approvals and timelock have been satisfied.
+    pub fn execute_admin_action(env: Env, action_id: u64) -> Result<(), Error> {
+        let action = Self::get_admin_action(&env, action_id).ok_or(Error::AdminActionTerminal)?;
+        if action.cancelled {
+            return Err(Error::AdminActionTerminal);
+        }
+        if action.executed {
+            return Err(Error::AdminActionTerminal);
+        }
+        if action.approvals.len() as u32 < action.threshold {
+            return Err(Error::AdminActionNeedsApprovals);
+        }
+        let now = env.ledger().timestamp();
+        if now < action.ready_at {
+            return Err(Error::AdminActionTimelockActive);
+        }
+
+        let mut persisted = action.clone();
+        Self::apply_admin_action(&env, &persisted)?;
+        persisted.executed = true;
+        Self::persist_admin_action(&env, &persisted);
+        Ok(())
+    }
+
+    /// Return all pending admin actions that have not executed or been cancelled.
+    pub fn get_pending_admin_actions(env: Env) -> Vec<AdminActionProposal> {
+        let mut actions = Vec::new(&env);
+        let next_id = Self::get_next_admin_action_id(&env);
+        for action_id in 1..next_id {
+            if let Some(action) = Self::get_admin_action(&env, action_id) {
+                if !action.executed && !action.cancelled {
+                    actions.push_back(action);
+                }
+            }
+        }
+        actions
+    }
+
+    fn apply_admin_action(env: &Env, action: &AdminActionProposal) -> Result<(), Error> {
+        match &action.kind {
+            AdminActionKind::PausePlatform(paused) => Self::set_paused_internal(env, *paused),
+            AdminActionKind::SetPlatformFee(new_fee_bps) => {
+                Self::check_no_fee_notice(env)?;
+                let mut config = Self::get_platform_config_internal(env);
+                if *new_fee_bps > MAX_PLATFORM_FEE_BPS {
+                    return Err(Error::InvalidFee);
+                }
+                let old_fee = Self::current_platform_fee_bps(env);
+                Self::drop_due_fee_changes(env, None);
+                config.platform_fee_bps = *new_fee_bps;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "platform_fee_bps",
+                    ConfigValue::U32(old_fee),
+                    ConfigValue::U32(*new_fee_bps),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetPlatformWallet(new_wallet) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let old_wallet = config.platform_wallet.clone();
+                config.platform_wallet = new_wallet.clone();
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "platform_wallet",
+                    ConfigValue::Address(old_wallet),
+                    ConfigValue::Address(new_wallet.clone()),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetWasmUpgradeCooldown(cooldown_seconds) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let old_value = config.wasm_upgrade_cooldown;
+                config.wasm_upgrade_cooldown = *cooldown_seconds;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "wasm_upgrade_cooldown",
+                    ConfigValue::U32(old_value),
+                    ConfigValue::U32(*cooldown_seconds),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetMinStakeRequired(min_stake) => {
+                let mut config = Self::get_platform_config_internal(env);
+                config.min_stake_required = *min_stake;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Ok(())
+            }
+            AdminActionKind::SweepUnallocatedFunds(token, destination) => {
+                let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+                let locked: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::TotalLocked(token.clone()))
+                    .unwrap_or(0);
+                let staked: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::TotalStaked(token.clone()))
+                    .unwrap_or(0);
+                let claimable = Self::get_total_claimable(env.clone(), token.clone());
+                let treasury = Self::get_treasury_balance(env.clone(), token.clone());
+                let promo = Self::get_promo_balance(env.clone(), token.clone());
+                let unallocated = balance - (locked + staked + claimable + treasury + promo);
+                if unallocated > 0 {
+                    Self::transfer_tokens_and_record_audit(
+                        env,
+                        token,
+                        &env.current_contract_address(),
+                        destination,
+                        unallocated,
+                        destination,
+                        Symbol::new(env, "sweep_unallocated"),
+                        unallocated,
+                    );
+                }
+                Ok(())
+            }
+            AdminActionKind::ExecuteUpgrade(expected_wasm_hash) => {
+                Self::execute_upgrade(env, expected_wasm_hash)
+            }
+            AdminActionKind::SetMaxDisputeDuration(duration) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let old_value = config.max_dispute_duration;
+                config.max_dispute_duration = *duration;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "max_dispute_duration",
+                    ConfigValue::U32(old_value),
+                    ConfigValue::U32(*duration),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetStakeCooldown(cooldown) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let old_value = config.stake_cooldown;
+                config.stake_cooldown = *cooldown;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "stake_cooldown",
+                    ConfigValue::U32(old_value),
+                    ConfigValue::U32(*cooldown),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetArtisanFeeTier(artisan, fee_bps) => {
+                Self::check_no_fee_notice(env)?;
+                let mut config = Self::get_platform_config_internal(env);
+                if *fee_bps > MAX_PLATFORM_FEE_BPS {
+                    return Err(Error::InvalidFee);
+                }
+                config.admin.require_auth();
+                Self::drop_due_fee_changes(env, Some(artisan));
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ArtisanFeeTier(artisan.clone()), fee_bps);
+                Self::extend_persistent(env, &DataKey::ArtisanFeeTier(artisan.clone()));
+                Self::emit_artisan_fee_tier_updated(env, artisan.clone(), *fee_bps);
+                Ok(())
+            }
+            AdminActionKind::SetModerator(moderator) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let previous = config
+                    .moderator
+                    .clone()
+                    .map(ConfigValue::Address)
+                    .unwrap_or_else(|| ConfigValue::String(String::from_str(env, "unset")));
+                config.moderator = Some(moderator.clone());
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "moderator",
+                    previous,
+                    ConfigValue::Address(moderator.clone()),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetMinEscrowAmount(token, min_amount) => {
+                let admin = Self::get_admin(env)?;
+                admin.require_auth();
+                let key = DataKey::MinEscrowAmount(token.clone());
+                let old_amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                env.storage().persistent().set(&key, min_amount);
+                Self::extend_persistent(env, &key);
+                Self::emit_config_updated(
+                    env,
+                    "min_escrow_amount",
+                    ConfigValue::I128(old_amount),
+                    ConfigValue::I128(*min_amount),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetMaxReleaseWindow(window) => {
+                let old_value: u32 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::MaxReleaseWindow)
+                    .unwrap_or(MAX_TOTAL_RELEASE_WINDOW);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::MaxReleaseWindow, window);
+                Self::extend_persistent(env, &DataKey::MaxReleaseWindow);
+                Self::emit_config_updated(
+                    env,
+                    "max_release_window",
+                    ConfigValue::U32(old_value),
+                    ConfigValue::U32(*window),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetMinReleaseWindow(window) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let old_value = config.min_release_window;
+                config.min_release_window = *window;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "min_release_window",
+                    ConfigValue::U32(old_value),
+                    ConfigValue::U32(*window),
+                );
+                Ok(())
+            }
+            AdminActionKind::SetOnboardingContract(address) => {
+                let admin = Self::get_admin(env)?;
+                admin.require_auth();
+                env.storage()
+                    .instance()
+                    .set(&DataKey::OnboardingContractAddress, address);
+                Self::extend_persistent(env, &DataKey::OnboardingContractAddress);
+                Ok(())
+            }
+            AdminActionKind::SetExpiredDisputePolicy(policy) => {
+                let mut config = Self::get_platform_config_internal(env);
+                let old_policy = config.expired_dispute_fee_policy;
+                config.expired_dispute_fee_policy = *policy;
+                env.storage().instance().set(&DataKey::PlatformConfig, &config);
+                Self::emit_config_updated(
+                    env,
+                    "expired_dispute_fee_policy",
+                    ConfigValue::U32(old_policy as u32),
+                    ConfigValue::U32(*policy as u32),
+                );
+                Ok(())
+            }
+            AdminActionKind::GrantRole(role, account) => Self::grant_role_internal(env, *role, account),
+            AdminActionKind::RevokeRole(role, account) => {
+                Self::revoke_role_internal(env, *role, account);
+                Ok(())
+            }
+        }
+    }
+
+    fn grant_role_internal(env: &Env, role: Role, account: &Address) -> Result<(), Error> {
+        let mut members = Self::get_role_members(env.clone(), role);
+        if members.contains(account) {
+            return Ok(());
+        }
+        if members.len() >= MAX_ROLE_MEMBERS {
+            return Err(Error::BatchLimitExceeded);
+        }
+        members.push_back(account.clone());
+        let key = RoleDataKey::Members(role);
+        env.storage().persistent().set(&key, &members);
+        Self::extend_persistent(env, &key);
+        env.events()
+            .publish((Symbol::new(env, "role_granted"), role), account.clone());
+        Ok(())
+    }
+
+    fn revoke_role_internal(env: &Env, role: Role, account: &Address) {
+        let mut members = Self::get_role_members(env.clone(), role);
+        let Some(index) = members.first_index_of(account) else {
+            return;
+        };
+        members.remove(index);
+        let key = RoleDataKey::Members(role);
+        env.storage().persistent().set(&key, &members);
+        Self::extend_persistent(env, &key);
+        env.events()
+            .publish((Symbol::new(env, "role_revoked"), role), account.clone());
+    }
+
+    /// Get the addresses granted a role. The admin is not listed but holds
+    /// every role.
+    pub fn get_role_members(env: Env, role: Role) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&RoleDataKey::Members(role))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Check whether an address holds a role, either through a grant or as
+    /// the admin.
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        Self::get_admin(&env).is_ok_and(|admin| admin == account)
+            || Self::get_role_members(env, role).contains(&account)
+    }
+
+    /// Require `caller`'s authorization and the `role` permission.
+    fn require_role(env: &Env, role: Role, caller: &Address) {
+        caller.require_auth();
+        if !Self::has_role(env.clone(), role, caller.clone()) {
+            env.panic_with_error(crate::Error::Unauthorized);
+        }
+    }
+
+    /// Migrate a user's escrow list to indexed storage (admin only).
+    ///
+    /// Kept for existing clients; role holders call `migrate_user_escrows_as`.
+    pub fn migrate_user_escrows(env: Env, user: Address, is_buyer: bool) -> Result<u32, Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::migrate_user_escrows_as(env, admin, user, is_buyer)
+    }
+
+    /// Migrate a user's escrow list from legacy vector storage to indexed storage.
+    /// This is a one-time migration function that should be called for users who have
+    /// escrows stored in the old format (`Role::Migrator`).
+    ///
+    /// # Arguments
+    /// * `user` - Address of the user to migrate
+    /// * `is_buyer` - true to migrate buyer escrows, false to migrate seller escrows
+    ///
+    /// # Returns
+    /// Number of escrows migrated
+    pub fn migrate_user_escrows_as(
+        env: Env,
+        caller: Address,
+        user: Address,
+        is_buyer: bool,
+    ) -> Result<u32, Error> {
+        let config = Self::get_platform_config_internal(&env);
+        Self::require_role(&env, Role::Migrator, &caller);
+
+        let legacy_key = if is_buyer {
+            DataKey::BuyerEscrows(user.clone())
+        } else {
+            DataKey::SellerEscrows(user.clone())
+        };
+
+        // Check if legacy data exists
+        if !env.storage().persistent().has(&legacy_key) {
+            return Ok(0);
+        }
+
+        let legacy_escrows: soroban_sdk::Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+
+        let count = legacy_escrows.len();
+
+        // Migrate to indexed storage
+        for i in 0..count {
+            if let Some(escrow_id) = legacy_escrows.get(i) {
+                let index_key = if is_buyer {
+                    DataKey::BuyerEscrowIndexed(user.clone(), i)
+                } else {
+                    DataKey::SellerEscrowIndexed(user.clone(), i)
+                };
+                env.storage().persistent().set(&index_key, &escrow_id);
+                Self::extend_persistent(&env, &index_key);
+            }
+        }
+
+        // Set the count
+        let count_key = if is_buyer {
+            DataKey::BuyerEscrowCount(user.clone())
+        } else {
+            DataKey::SellerEscrowCount(user.clone())
+        };
+        env.storage().persistent().set(&count_key, &count);
+        Self::extend_persistent(&env, &count_key);
+
+        // Remove legacy storage to free up space
+        env.storage().persistent().remove(&legacy_key);
+
+        env.storage().persistent().set(&ADMIN, &config.admin);
+        Self::extend_persistent(&env, &ADMIN);
+
+        // Set the new admin as fallback for recovery purposes (#240)
+        if let Err(_) = Self::set_fallback_admin(&env, config.admin.clone()) {
+            env.panic_with_error(Error::InvalidAdminAddress);
+        }
+
+        Ok(count)
+    }
+
+    /// Recover admin access using fallback admin after time lock period (#240)
+    /// This provides a recovery mechanism if the primary admin is corrupted or inaccessible
+    /// Requires a 7-day time lock after recovery is initiated to prevent abuse
+    pub fn recover_admin_access(env: Env, recovered_admin: Address) -> Result<(), Error> {
+        // Check if fallback admin exists and is authorized
+        let fallback = match env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::FallbackAdmin)
+        {
+            Some(fallback) => fallback,
+            None => return Err(Error::AdminRecoveryFailed),
+        };
+
+        fallback.require_auth();
+
+        // Validate the recovery address
+        if Self::validate_admin_address(&env, &recovered_admin).is_err() {
+            return Err(Error::AdminRecoveryFailed);
+        }
+
+        // Check if recovery time lock has passed (#431 â€” TTL-friendly read)
+        let recovery_time = Self::get_persistent_u64(&env, &DataKey::AdminRecoveryTime);
+
+        let current_time = env.ledger().timestamp();
+
+        // If this is the first recovery request, initiate time lock and record
+        // the delay used so that malicious direct writes to `AdminRecoveryTime`
+        // cannot bypass the minimum cooldown requirement.
+        if recovery_time == 0 {
+            let new_recovery_time = current_time + ADMIN_RECOVERY_DELAY;
+            let recovery_time_key = DataKey::AdminRecoveryTime;
+            env.storage()
+                .persistent()
+                .set(&recovery_time_key, &new_recovery_time);
+            Self::extend_persistent(&env, &recovery_time_key);
+
+            // Record the delay used for this initiation so it can be validated
+            // later when recovery is attempted.
+            let delay_key = DataKey::AdminRecoveryDelay;
+            env.storage()
+                .persistent()
+                .set(&delay_key, &ADMIN_RECOVERY_DELAY);
+            Self::extend_persistent(&env, &delay_key);
+
+            env.events().publish(
+                (Symbol::new(&env, "admin_recovery_initiated"), true),
+                String::from_str(&env, "7-day time lock initiated for admin recovery"),
+            );
+            return Err(Error::AdminRecoveryFailed); // Recovery not ready yet
+        }
+
+        // Check if time lock period has elapsed
+        if current_time < recovery_time {
+            return Err(Error::AdminRecoveryFailed);
+        }
+
+        // Ensure the recorded cooldown meets the minimum floor. If the delay
+        // is missing or below the minimum, treat this as a failed recovery
+        // attempt to prevent direct-storage bypasses.
+        let recorded_delay = Self::get_persistent_u64(&env, &DataKey::AdminRecoveryDelay);
+        if recorded_delay == 0 || recorded_delay < MIN_ADMIN_RECOVERY_COOLDOWN {
+            return Err(Error::AdminRecoveryFailed);
+        }
+
+        // Time lock has passed, proceed with recovery
+        let mut config = Self::get_platform_config_internal(&env);
+        let previous_admin = config.admin.clone();
+
+        config.admin = recovered_admin.clone();
+        config.pending_admin = None;
+        // Write config to instance storage (primary location) â€” TTL already extended
+        // by get_platform_config_internal. No redundant extend_persistent needed.
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        // Sync to persistent backup key for recovery consistency (no TTL extension needed
+        // since this is a one-time sync, not a read-heavy path).
+        env.storage().persistent().set(&PLATFORM_FEE, &config);
+
+        // Clear the recovery time lock for next cycle
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AdminRecoveryTime);
+        // Clear the recorded delay as well
+        env.storage()
+            .persistent()
+            .remove(&DataKey::AdminRecoveryDelay);
+
+        // Emit audit event
+        Self::emit_admin_changed(&env, previous_admin, recovered_admin, "admin_recovered");
+
+        Ok(())
+    }
+
+    /// Create a new escrow for an order
+    ///
+    /// # Arguments
+    /// * `buyer` - Address of the buyer
+    /// * `seller` - Address of the seller
+    /// * `token` - Token contract address (USDC)
+    /// * `amount` - Amount to escrow
+    /// * `order_id` - Unique order identifier
+    /// * `release_window` - Time in seconds before auto-release (default 7 days = 604800)
+    pub fn create_escrow(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        release_window: Option<u32>,
+    ) -> Escrow {
+        Self::create_escrow_with_metadata(
+            env,
+            buyer,
+            seller,
+            token,
+            amount,
+            order_id,
+            release_window,
+            None,
+            None,
+        )
+    }
+
+    /// Create a new escrow for an order and attach off-chain metadata.
+    pub fn create_escrow_with_metadata(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        release_window: Option<u32>,
+        ipfs_hash: Option<String>,
+        metadata_hash: Option<Bytes>,
+    ) -> Escrow {
+        Self::create_escrow_internal(
+            env,
+            buyer,
+            seller,
+            token,
+            amount,
+            order_id,
+            release_window,
+            ipfs_hash,
+            metadata_hash,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// Create an escrow funded by a third party (gift or procurement).
+    ///
+    /// `payer` signs and funds the escrow; `buyer` is the recipient who
+    /// confirms delivery, releases funds and may dispute. Refunds, including
+    /// partial and dispute refunds, go to `refund_address` if set, otherwise
+    /// to `payer`.
+    ///
+    /// # Arguments
+    /// * `payer` - Address funding the escrow (must sign)
+    /// * `buyer` - Recipient controlling release
+    /// * `refund_address` - Optional refund destination
+    pub fn create_gift_escrow(
+        env: Env,
+        payer: Address,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        release_window: Option<u32>,
+        refund_address: Option<Address>,
+    ) -> Escrow {
+        Self::create_escrow_internal(
+            env,
+            buyer,
+            seller,
+            token,
+            amount,
+            order_id,
+            release_window,
+            None,
+            None,
+            Some(payer),
+            refund_address,
+            None,
+            None,
+        )
+    }
+
+    /// Create an escrow with a platform-funded coupon.
+    ///
+    /// The buyer deposits `amount` minus the coupon discount; the difference
+    /// is drawn from the token's promo balance. The escrow holds the full
+    /// `amount`, so the seller is paid the listed price and fees are computed
+    /// on it as usual. On refund the unused subsidy returns to the promo
+    /// balance rather than to the buyer.
+    ///
+    /// Panics with a [`CouponError`] when the code is unknown, expired or for
+    /// another token, or when the buyer's redemption limit, the coupon budget
+    /// or the promo balance is exhausted.
+    pub fn create_escrow_with_coupon(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        release_window: Option<u32>,
+        coupon_code: Bytes,
+    ) -> Escrow {
+        Self::create_escrow_internal(
+            env,
+            buyer,
+            seller,
+            token,
+            amount,
+            order_id,
+            release_window,
+            None,
+            None,
+            None,
+            None,
+            Some(coupon_code),
+            None,
+        )
+    }
+
+    /// Create an escrow that pays `referrer` a share of the platform fee.
+    ///
+    /// The referrer is fixed at creation under the funder's signature and
+    /// takes precedence over one set on the seller's onboarding profile. It
+    /// may not be the buyer, the seller or the funder.
+    pub fn create_escrow_with_referrer(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        release_window: Option<u32>,
+        referrer: Address,
+    ) -> Escrow {
+        Self::create_escrow_internal(
+            env,
+            buyer,
+            seller,
+            token,
+            amount,
+            order_id,
+            release_window,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(referrer),
+        )
+    }
+
+    /// Issue a coupon (`Role::FeeManager`). `code_hash` is the SHA-256 of the
+    /// code.
+    pub fn create_coupon(
+        env: Env,
+        caller: Address,
+        code_hash: BytesN<32>,
+        token: Address,
+        discount: i128,
+        budget: i128,
+        per_user_limit: u32,
+        expires_at: u64,
+    ) -> Result<(), CouponError> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+
+        if discount <= 0 || budget < discount || per_user_limit == 0 {
+            return Err(CouponError::InvalidCouponTerms);
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(CouponError::InvalidCouponExpiry);
+        }
+        let key = CouponDataKey::Coupon(code_hash);
+        if env.storage().persistent().has(&key) {
+            return Err(CouponError::CouponAlreadyExists);
+        }
+
+        env.storage().persistent().set(
+            &key,
+            &Coupon {
+                token,
+                discount,
+                budget,
+                spent: 0,
+                per_user_limit,
+                expires_at,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+        Ok(())
+    }
+
+    /// Get a coupon by the hash of its code.
+    pub fn get_coupon(env: Env, code_hash: BytesN<32>) -> Option<Coupon> {
+        env.storage()
+            .persistent()
+            .get(&CouponDataKey::Coupon(code_hash))
+    }
+
+    /// Deposit tokens from `caller` into the promo balance that funds coupons
+    /// (`Role::FeeManager`).
+    pub fn fund_promo_balance(
+        env: Env,
+        caller: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::require_role(&env, Role::FeeManager, &caller);
+        if amount <= 0 {
+            return Err(Error::AmountBelowMinimum);
+        }
+
+        Self::adjust_promo_balance(&env, &token, amount);
+        Self::transfer_tokens_and_record_audit(
+            &env,
+            &token,
+            &caller,
+            &env.current_contract_address(),
+            amount,
+            &caller,
+            Symbol::new(&env, "promo_funded"),
+            -amount,
+        );
+        Ok(())
+    }
+
+    /// Withdraw unused promo balance to `to` (`Role::FeeManager`).
+    pub fn withdraw_promo_balance(
+        env: Env,
+        caller: Address,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::require_role(&env, Role::FeeManager, &caller);
+        if amount <= 0 || amount > Self::get_promo_balance(env.clone(), token.clone()) {
+            return Err(Error::InvalidRefundAmount);
+        }
+
+        Self::adjust_promo_balance(&env, &token, -amount);
+        Self::payout_tokens_and_record_audit(
+            &env,
+            &token,
+            &to,
+            amount,
+            Symbol::new(&env, "promo_withdrawn"),
+        );
+        Ok(())
+    }
 
-        let is_v0 = len == 46
-            && cid_bytes[0] == b'Q'
-            && cid_bytes[1] == b'm'
-            && cid_bytes.iter().all(|b| Self::is_base58_btc_char(*b));
+    /// Get the promo balance available to coupons for a token.
+    pub fn get_promo_balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&CouponDataKey::PromoBalance(token))
+            .unwrap_or(0)
+    }
 
-        if is_v0 {
-            return true;
-        }
+    fn adjust_promo_balance(env: &Env, token: &Address, delta: i128) {
+        let key = CouponDataKey::PromoBalance(token.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(balance + delta));
+        Self::extend_persistent(env, &key);
+    }
 
-        // CIDv1: minimum 3 chars (multibase prefix + version byte + codec)
-        if len < 3 {
-            return false;
+    /// Validate a coupon code for `buyer` and draw its subsidy from the promo
+    /// balance. Returns the subsidy, capped at `amount`.
+    fn redeem_coupon(
+        env: &Env,
+        code: &Bytes,
+        buyer: &Address,
+        token: &Address,
+        amount: i128,
+    ) -> Result<(BytesN<32>, i128), CouponError> {
+        let code_hash: BytesN<32> = env.crypto().sha256(code).into();
+        let coupon_key = CouponDataKey::Coupon(code_hash.clone());
+        let mut coupon: Coupon = env
+            .storage()
+            .persistent()
+            .get(&coupon_key)
+            .ok_or(CouponError::CouponNotFound)?;
+        if env.ledger().timestamp() >= coupon.expires_at {
+            return Err(CouponError::CouponExpired);
+        }
+        if coupon.token != *token {
+            return Err(CouponError::CouponTokenMismatch);
         }
 
-        let prefix = cid_bytes[0];
-        let payload = &cid_bytes[1..];
+        let redemptions_key = CouponDataKey::Redemptions(code_hash.clone(), buyer.clone());
+        let redemptions: u32 = env
+            .storage()
+            .persistent()
+            .get(&redemptions_key)
+            .unwrap_or(0);
+        let subsidy = coupon.discount.min(amount);
+        if redemptions >= coupon.per_user_limit {
+            return Err(CouponError::RedemptionLimitReached);
+        }
+        if coupon.spent + subsidy > coupon.budget {
+            return Err(CouponError::CouponBudgetExhausted);
+        }
+        if subsidy > Self::get_promo_balance(env.clone(), token.clone()) {
+            return Err(CouponError::PromoBalanceInsufficient);
+        }
 
-        match prefix {
-            // base32lower (most common CIDv1 encoding)
-            b'b' => {
-                if len < 50 || len > 100 || cid_bytes[1] != b'a' {
-                    return false;
-                }
-                payload
-                    .iter()
-                    .all(|b| matches!(*b, b'a'..=b'z' | b'2'..=b'7'))
-            }
-            // base16lower (hex)
-            b'f' => {
-                if len < 60 || len > 120 || cid_bytes[1] != b'0' || cid_bytes[2] != b'1' {
-                    return false;
-                }
-                payload
-                    .iter()
-                    .all(|b| matches!(*b, b'0'..=b'9' | b'a'..=b'f'))
-            }
-            // base58btc
-            b'z' => {
-                if len < 40 || len > 100 {
-                    return false;
-                }
-                payload.iter().all(|b| Self::is_base58_btc_char(*b))
+        coupon.spent += subsidy;
+        env.storage().persistent().set(&coupon_key, &coupon);
+        Self::extend_persistent(env, &coupon_key);
+        env.storage()
+            .persistent()
+            .set(&redemptions_key, &(redemptions + 1));
+        Self::extend_persistent(env, &redemptions_key);
+        Self::adjust_promo_balance(env, token, -subsidy);
+        Ok((code_hash, subsidy))
+    }
+
+    /// Pay a refund of `amount` out of an escrow whose gross amount was
+    /// `gross`. A coupon subsidy is returned to the promo balance first on a
+    /// full refund and pro rata on a partial one; the rest goes to the
+    /// escrow's refund destination.
+    fn pay_refund(env: &Env, order_id: u32, escrow: &Escrow, gross: i128, amount: i128, reason: Symbol) {
+        if amount > 0 {
+            Self::record_epoch_stats(env, &escrow.token, |stats| stats.refunded_volume += amount);
+        }
+        let subsidy_key = CouponDataKey::Subsidy(order_id);
+        let subsidy: i128 = env.storage().persistent().get(&subsidy_key).unwrap_or(0);
+        let mut promo_share = 0;
+        if subsidy > 0 && amount > 0 {
+            promo_share = if amount >= gross {
+                subsidy
+            } else {
+                (amount * subsidy / gross).min(subsidy)
+            };
+            if subsidy == promo_share {
+                env.storage().persistent().remove(&subsidy_key);
+            } else {
+                env.storage()
+                    .persistent()
+                    .set(&subsidy_key, &(subsidy - promo_share));
             }
-            _ => false,
+            Self::adjust_promo_balance(env, &escrow.token, promo_share);
         }
+        Self::payout_tokens_and_record_audit(
+            env,
+            &escrow.token,
+            &Self::escrow_refund_recipient(escrow),
+            amount - promo_share,
+            reason,
+        );
     }
 
-    #[inline(always)]
-    fn is_base58_btc_char(byte: u8) -> bool {
-        BASE58_BTC_CHARSET[byte as usize]
+    /// Remove per-escrow side state once an escrow has settled. A coupon
+    /// subsidy not returned by a refund was spent on the seller's payout,
+    /// and a hash lock or extension request no longer applies.
+    fn clear_settled_escrow_state(env: &Env, order_id: u32) {
+        env.storage()
+            .persistent()
+            .remove(&CouponDataKey::Subsidy(order_id));
+        env.storage().persistent().remove(&HtlcDataKey::Lock(order_id));
+        env.storage()
+            .persistent()
+            .remove(&ExtensionDataKey::Request(order_id));
     }
 
-    /// Validate an optional IPFS CID string, panicking with `InvalidIpfsHash` if present but invalid.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `ipfs_hash` - Optional CID string to validate
-    ///
-    /// # Errors
-    /// Panics with `Error::InvalidIpfsHash` if the CID is present but fails `validate_ipfs_cid`.
-    ///
-    /// # Storage side-effects
-    /// None â€” this is a pure validation helper with no storage reads or writes.
-    #[inline(always)]
-    fn validate_optional_ipfs_hash(env: &Env, ipfs_hash: &Option<String>) {
-        if let Some(cid) = ipfs_hash {
-            if !Self::validate_ipfs_cid(cid) {
-                env.panic_with_error(crate::Error::InvalidIpfsHash);
-            }
+    fn create_escrow_internal(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        release_window: Option<u32>,
+        ipfs_hash: Option<String>,
+        metadata_hash: Option<Bytes>,
+        payer: Option<Address>,
+        refund_address: Option<Address>,
+        coupon_code: Option<Bytes>,
+        referrer: Option<Address>,
+    ) -> Escrow {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
+        // A payer equal to the buyer is an ordinary escrow.
+        let payer = payer.filter(|payer| *payer != buyer);
+        let funder = payer.clone().unwrap_or_else(|| buyer.clone());
+        funder.require_auth();
+
+        // Validate amount is positive and above minimum
+        if let Err(e) = Self::check_min_amount(&env, token.clone(), amount) {
+            env.panic_with_error(e);
         }
-    }
 
-    /// Validate an optional metadata hash, panicking with `InvalidMetadataHash` if present but not 32 bytes.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `metadata_hash` - Optional raw bytes to validate
-    ///
-    /// # Errors
-    /// Panics with `Error::InvalidMetadataHash` if the hash is present but its length is not exactly 32 bytes.
-    ///
-    /// # Storage side-effects
-    /// None â€” this is a pure validation helper with no storage reads or writes.
-    #[inline(always)]
-    fn validate_optional_metadata_hash(env: &Env, metadata_hash: &Option<Bytes>) {
-        if let Some(hash) = metadata_hash {
-            if hash.len() != 32 {
-                env.panic_with_error(crate::Error::InvalidMetadataHash);
+        // Validate buyer and seller are different; a seller may not fund
+        // its own escrow either
+        if buyer == seller || funder == seller {
+            env.panic_with_error(crate::Error::SameBuyerSeller);
+        }
+        if let Some(referrer) = &referrer {
+            if *referrer == buyer || *referrer == seller || *referrer == funder {
+                env.panic_with_error(crate::Error::Unauthorized);
             }
         }
-    }
 
-    #[inline(always)]
-    fn get_admin(env: &Env) -> Result<Address, Error> {
-        let config: PlatformConfig = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlatformConfig)
-            .ok_or(Error::PlatformNotInitialized)?;
-        Ok(config.admin)
-    }
+        // Validate token is whitelisted (#103)
+        Self::check_token_whitelisted(&env, &token);
 
-    /// Validates admin address to ensure it's not zero/default and is properly initialized (#240)
-    /// This prevents common configuration errors and hardens against corruption.
-    ///
-    /// Storage-layout note: this validator sits on the hot path for any
-    /// admin-gated mutation. Checks are ordered cheapest-first so the
-    /// common case (a structurally valid candidate that differs from
-    /// the current contract address) returns without touching persistent
-    /// storage at all â€” a small but consistent gas saving across every
-    /// transfer / propose / accept_admin call.
-    fn validate_admin_address(env: &Env, admin: &Address) -> Result<(), Error> {
-        // Ensure the address is not the contract's own address â€” a common
-        // misconfiguration that would lock the contract out of admin
-        // operations forever.
-        let contract = env.current_contract_address();
-        if admin == &contract {
-            return Err(Error::InvalidAdminAddress);
+        // Check artisan (seller) stake requirement (Issue #99)
+        let config = Self::get_platform_config_internal(&env);
+        if config.min_stake_required > 0 {
+            let artisan_stake: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ArtisanStake(seller.clone()))
+                .map(|stake: ArtisanStakeData| stake.amount)
+                .unwrap_or(0);
+            if artisan_stake < config.min_stake_required {
+                env.panic_with_error(crate::Error::InsufficientStake);
+            }
         }
-        // Note: Additional address validation could be performed here
-        // (e.g., checking if address exists on ledger, format validation, etc.)
-        Ok(())
-    }
 
-    /// Gets platform configuration with fallback mechanism for corruption recovery (#240)
-    /// Returns the primary config if valid, falls back to last-known good state if corrupted
-    #[allow(dead_code)]
-    fn get_platform_config_safe(env: &Env) -> Result<PlatformConfig, Error> {
-        let config: Option<PlatformConfig> = env.storage().persistent().get(&PLATFORM_FEE);
+        // Default to 7 days if not specified
+        let window = release_window.unwrap_or(604800u32);
 
-        if let Some(cfg) = config {
-            // Validate that critical fields are initialized
-            if Self::validate_admin_address(env, &cfg.admin).is_ok() {
-                Self::extend_persistent(env, &PLATFORM_FEE);
-                return Ok(cfg);
-            }
+        // Validate release window bounds
+        let min_window = config.min_release_window;
+        let max_window = Self::get_max_release_window(&env);
+
+        if window < min_window {
+            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        }
+        if window > max_window {
+            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
         }
 
-        // If primary config is missing or corrupted, attempt to recover using fallback admin
-        if let Some(fallback_admin) = env
-            .storage()
-            .persistent()
-            .get::<_, Address>(&DataKey::FallbackAdmin)
-        {
-            Self::extend_persistent(env, &DataKey::FallbackAdmin);
-            // Emit recovery event for audit trail
+        let created_at_u64 = env.ledger().timestamp();
+        assert!(
+            created_at_u64 <= u32::MAX as u64,
+            "Ledger timestamp overflow"
+        );
+        let created_at = created_at_u64 as u32;
+        Self::validate_optional_ipfs_hash(&env, &ipfs_hash);
+        Self::validate_optional_metadata_hash(&env, &metadata_hash);
+
+        let mut subsidy = 0;
+        if let Some(code) = coupon_code {
+            let (code_hash, redeemed) = Self::redeem_coupon(&env, &code, &buyer, &token, amount)
+                .unwrap_or_else(|e| env.panic_with_error(e));
+            subsidy = redeemed;
+            let subsidy_key = CouponDataKey::Subsidy(order_id);
+            env.storage().persistent().set(&subsidy_key, &subsidy);
+            Self::extend_persistent(&env, &subsidy_key);
             env.events().publish(
-                (Symbol::new(env, "admin_config_recovered"), true),
-                String::from_str(env, "Using fallback admin after config corruption detected"),
+                (Symbol::new(&env, "coupon_redeemed"), order_id),
+                CouponRedeemedEvent {
+                    order_id,
+                    buyer: buyer.clone(),
+                    code_hash,
+                    subsidy,
+                },
             );
-            // Return a minimal valid config with fallback admin
-            // This ensures critical operations remain accessible even if config is corrupted
-            return Ok(PlatformConfig {
-                platform_fee_bps: 500, // 5% default fee
-                platform_wallet: fallback_admin.clone(),
-                admin: fallback_admin,
-                arbitrator: env.current_contract_address(),
-                moderator: None,
-                is_paused: true, // Safer to default to paused during recovery
-                min_stake_required: 0,
-                pending_admin: None,
-                wasm_upgrade_cooldown: DEFAULT_WASM_UPGRADE_COOLDOWN,
-                max_dispute_duration: DEFAULT_MAX_DISPUTE_DURATION,
-                stake_cooldown: DEFAULT_STAKE_COOLDOWN,
-                expired_dispute_fee_policy: ExpiredDisputeFeePolicy::RefundFullNoPlatformFee,
-                min_release_window: DEFAULT_MIN_RELEASE_WINDOW,
-            });
         }
 
-        Err(Error::CorruptedPlatformConfig)
-    }
+        // Under a buyer-side fee model the buyer's fee share is escrowed on
+        // top of the price.
+        Self::snapshot_fee_rate(&env, order_id, &seller, &token);
+        let amount = amount + Self::apply_fee_payer_terms(&env, order_id, &seller, &token, amount);
+
+        let escrow = Escrow {
+            version: CURRENT_ESCROW_VERSION,
+            id: order_id as u64,
+            batch_id: None,
+            buyer: buyer.clone(),
+            seller: seller.clone(),
+            token: token.clone(),
+            amount,
+            status: EscrowStatus::Active,
+            release_window: window,
+            created_at,
+            ipfs_hash: ipfs_hash.clone(),
+            metadata_hash: metadata_hash.clone(),
+            dispute_reason: None,
+            dispute_initiated_at: None,
+            funded: true,
+            funding_deadline: None, // Immediately funded; no deadline required (#656)
+            payer,
+            refund_address,
+        };
+
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::extend_persistent(&env, &(ESCROW, order_id));
+
+        if let Some(referrer) = referrer {
+            let referrer_key = ReferralDataKey::EscrowReferrer(order_id);
+            env.storage().persistent().set(&referrer_key, &referrer);
+            Self::extend_persistent(&env, &referrer_key);
+        }
+
+        // Track active escrows
+        Self::update_active_obligations(&env, &buyer, 1);
+        Self::update_active_obligations(&env, &seller, 1);
 
-    /// Emits audit event for admin changes to maintain a complete audit trail (#240)
-    fn emit_admin_changed(
-        env: &Env,
-        previous_admin: Address,
-        new_admin: Address,
-        change_type: &str,
-    ) {
-        env.events().publish(
-            (Symbol::new(env, "admin_changed"), change_type.as_bytes()),
-            (previous_admin, new_admin),
-        );
-    }
+        // Update global escrow index for off-chain enumeration using atomic function
+        // This ensures AllEscrowIds and EscrowCount always remain in sync (Issue #226)
+        Self::update_escrow_indices_atomic(&env, order_id);
 
-    /// Stores fallback admin address for recovery purposes (#240)
-    /// This ensures that even if primary admin storage is corrupted, platform can be recovered
-    fn set_fallback_admin(env: &Env, admin: Address) -> Result<(), Error> {
-        Self::validate_admin_address(env, &admin)?;
+        // Update buyer's escrow list using indexed storage (scalable approach)
+        let buyer_count_key = DataKey::BuyerEscrowCount(buyer.clone());
+        let buyer_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&buyer_count_key)
+            .unwrap_or(0u32);
+        let buyer_index_key = DataKey::BuyerEscrowIndexed(buyer.clone(), buyer_count);
         env.storage()
             .persistent()
-            .set(&DataKey::FallbackAdmin, &admin);
-        Self::extend_persistent(env, &DataKey::FallbackAdmin);
-        Ok(())
-    }
+            .set(&buyer_index_key, &(order_id as u64));
+        Self::extend_persistent(&env, &buyer_index_key);
+        env.storage()
+            .persistent()
+            .set(&buyer_count_key, &(buyer_count + 1));
+        Self::extend_persistent(&env, &buyer_count_key);
 
-    fn emit_escrow_created(env: &Env, event: EscrowEvent) {
-        env.events()
-            .publish((symbol_short!("escrow"), event.escrow_id), event);
-    }
+        // Update seller's escrow list using indexed storage (scalable approach)
+        let seller_count_key = DataKey::SellerEscrowCount(seller.clone());
+        let seller_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&seller_count_key)
+            .unwrap_or(0u32);
+        let seller_index_key = DataKey::SellerEscrowIndexed(seller.clone(), seller_count);
+        env.storage()
+            .persistent()
+            .set(&seller_index_key, &(order_id as u64));
+        Self::extend_persistent(&env, &seller_index_key);
+        env.storage()
+            .persistent()
+            .set(&seller_count_key, &(seller_count + 1));
+        Self::extend_persistent(&env, &seller_count_key);
 
-    fn emit_escrow_resolved_event(env: &Env, event: EscrowResolvedEvent) {
-        env.events().publish(
-            (Symbol::new(env, "escrow_resolved"), event.escrow_id),
-            event,
-        );
-    }
+        Self::safe_update_active_contracts(&env, buyer.clone(), 1);
+        Self::safe_update_active_contracts(&env, seller.clone(), 1);
 
-    fn emit_reputation_update(env: &Env, event: ReputationUpdateEvent) {
-        env.events().publish(
-            (
-                Symbol::new(env, "stake_reputation_update"),
-                event.address.clone(),
-            ),
-            event,
-        );
-    }
+        // Commit locked accounting before the external token interaction.
+        Self::update_total_locked(&env, &token, amount);
+        let deposit = amount - subsidy;
+        Self::transfer_tokens_and_record_audit(&env, &token, &funder, &env.current_contract_address(), deposit, &funder, Symbol::new(&env, "escrow_funded"), -deposit);
 
-    fn emit_config_updated(
-        env: &Env,
-        field_name: &str,
-        old_value: ConfigValue,
-        new_value: ConfigValue,
-    ) {
-        env.events().publish(
-            (
-                Symbol::new(env, "admin_config_updated"),
-                Symbol::new(env, field_name),
-            ),
-            ConfigUpdatedEvent {
-                field_name: Symbol::new(env, field_name),
-                old_value,
-                new_value,
+        Self::emit_escrow_created(
+            &env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action: EscrowAction::Created,
+                buyer: buyer.clone(),
+                seller: seller.clone(),
+                amount,
+                token: token.clone(),
+                timestamp: env.ledger().timestamp(),
             },
         );
-    }
 
-    fn emit_artisan_fee_tier_updated(env: &Env, artisan: Address, fee_bps: u32) {
-        env.events().publish(
-            (Symbol::new(env, "admin_fee_tier_updated"), artisan.clone()),
-            ArtisanFeeTierUpdatedEvent { artisan, fee_bps },
-        );
+        escrow
     }
 
-    fn emit_metadata_verified(env: &Env, order_id: u32, verifier: Address) {
-        env.events().publish(
-            (
-                Symbol::new(env, "escrow_metadata_verified"),
-                (order_id as u64),
-            ),
-            MetadataVerifiedEvent {
-                order_id: order_id as u64,
-                verifier,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
-    }
+    /// Create an escrow without funding it immediately (#213).
+    /// The buyer must call `fund_escrow` later to activate it.
+    pub fn create_unfunded_escrow(
+        env: Env,
+        order_id: u32,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        window: u32,
+        ipfs_hash: Option<String>,
+        metadata_hash: Option<Bytes>,
+    ) -> Escrow {
+        let _guard = ReentryGuardScope::new(&env);
 
-    fn emit_platform_paused(env: &Env, initiator: Address) {
-        env.events().publish(
-            (Symbol::new(env, "admin_platform_paused"), initiator.clone()),
-            PlatformPausedEvent {
-                initiator,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
-    }
+        // Validate release window bounds
+        let config = Self::get_platform_config_internal(&env);
+        let min_window = config.min_release_window;
+        let max_window = Self::get_max_release_window(&env);
 
-    fn emit_platform_unpaused(env: &Env, initiator: Address) {
-        env.events().publish(
-            (
-                Symbol::new(env, "admin_platform_unpaused"),
-                initiator.clone(),
-            ),
-            PlatformUnpausedEvent {
-                initiator,
-                timestamp: env.ledger().timestamp(),
-            },
+        if window < min_window {
+            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        }
+        if window > max_window {
+            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
+        }
+
+        let created_at_u64 = env.ledger().timestamp();
+        assert!(
+            created_at_u64 <= u32::MAX as u64,
+            "Ledger timestamp overflow"
         );
-    }
+        let created_at = created_at_u64 as u32;
+        Self::validate_optional_ipfs_hash(&env, &ipfs_hash);
+        Self::validate_optional_metadata_hash(&env, &metadata_hash);
 
-    /// Atomically appends one escrow ID to the indexed global registry and
-    /// increments `EscrowCount` (#515 / Issue #226).
-    fn update_escrow_indices_atomic(env: &Env, order_id: u32) {
-        // Issue #515 â€” O(1) indexed append replaces monolithic AllEscrowIds Vec
-        // rewrites. Legacy Vec entries are migrated lazily on first touch.
-        Self::migrate_legacy_all_escrow_ids(env);
+        // Compute the deadline after which any party may cancel the unfunded stub (#656).
+        let funding_deadline = created_at_u64 + UNFUNDED_CANCEL_TIMEOUT;
 
-        let count_key = DataKey::EscrowCount;
-        let count = Self::get_persistent_u32(env, &count_key);
+        let escrow = Escrow {
+            version: CURRENT_ESCROW_VERSION,
+            id: order_id as u64,
+            batch_id: None,
+            buyer: buyer.clone(),
+            seller: seller.clone(),
+            token: token.clone(),
+            amount,
+            status: EscrowStatus::Active,
+            release_window: window,
+            created_at,
+            ipfs_hash: ipfs_hash.clone(),
+            metadata_hash: metadata_hash.clone(),
+            dispute_reason: None,
+            dispute_initiated_at: None,
+            funded: false,
+            funding_deadline: Some(funding_deadline), // Deadline for funding; parties may cancel after this (#656)
+            payer: None,
+            refund_address: None,
+        };
 
-        let index_key = DataKey::GlobalEscrowIdIndexed(count);
-        env.storage().persistent().set(&index_key, &order_id);
-        Self::extend_persistent(env, &index_key);
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::extend_persistent(&env, &(ESCROW, order_id));
+        Self::snapshot_fee_rate(&env, order_id, &seller, &token);
 
-        env.storage().persistent().set(&count_key, &(count + 1));
-        Self::extend_persistent(env, &count_key);
-    }
+        // Update buyer's escrow list
+        let buyer_count_key = DataKey::BuyerEscrowCount(buyer.clone());
+        let buyer_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&buyer_count_key)
+            .unwrap_or(0u32);
+        let buyer_index_key = DataKey::BuyerEscrowIndexed(buyer.clone(), buyer_count);
+        env.storage()
+            .persistent()
+            .set(&buyer_index_key, &(order_id as u64));
+        Self::extend_persistent(&env, &buyer_index_key);
+        env.storage()
+            .persistent()
+            .set(&buyer_count_key, &(buyer_count + 1));
+        Self::extend_persistent(&env, &buyer_count_key);
+
+        // Update seller's escrow list
+        let seller_count_key = DataKey::SellerEscrowCount(seller.clone());
+        let seller_count: u32 = env
+            .storage()
+            .persistent()
+            .get(&seller_count_key)
+            .unwrap_or(0u32);
+        let seller_index_key = DataKey::SellerEscrowIndexed(seller.clone(), seller_count);
+        env.storage()
+            .persistent()
+            .set(&seller_index_key, &(order_id as u64));
+        Self::extend_persistent(&env, &seller_index_key);
+        env.storage()
+            .persistent()
+            .set(&seller_count_key, &(seller_count + 1));
+        Self::extend_persistent(&env, &seller_count_key);
 
-    /// Atomically appends escrow IDs to the indexed global registry for batch
-    /// operations (#515). Each ID is stored under its own key so batch creates
-    /// avoid rewriting a growing Vec.
-    fn update_escrow_indices_batch_atomic(env: &Env, order_ids: &soroban_sdk::Vec<u32>) {
-        if order_ids.is_empty() {
-            return;
-        }
+        // Track active escrows (unfunded still count towards active limit to prevent spam)
+        Self::update_active_obligations(&env, &buyer, 1);
+        Self::update_active_obligations(&env, &seller, 1);
 
-        Self::migrate_legacy_all_escrow_ids(env);
+        Self::safe_update_active_contracts(&env, buyer.clone(), 1);
+        Self::safe_update_active_contracts(&env, seller.clone(), 1);
 
-        let count_key = DataKey::EscrowCount;
-        let mut count = Self::get_persistent_u32(env, &count_key);
+        Self::emit_escrow_created(
+            &env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action: EscrowAction::Created,
+                buyer: buyer.clone(),
+                seller: seller.clone(),
+                amount,
+                token: token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-        for i in 0..order_ids.len() {
-            if let Some(id) = order_ids.get(i) {
-                let index_key = DataKey::GlobalEscrowIdIndexed(count);
-                env.storage().persistent().set(&index_key, &id);
-                Self::extend_persistent(env, &index_key);
-                count += 1;
-            }
+        escrow
+    }
+    pub fn fund_escrow(env: Env, order_id: u32) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        let mut escrow = Self::get_stored_escrow(&env, order_id);
+        if escrow.funded {
+            return Err(Error::InvalidEscrowState);
         }
 
-        env.storage().persistent().set(&count_key, &count);
-        Self::extend_persistent(env, &count_key);
-    }
+        let funder = Self::escrow_funder(&escrow);
+        funder.require_auth();
 
-    // IMPORTANT: this validation is intentionally scoped to escrow creation-time
-    // flows only. Do not call from payout/distribution paths, or dynamic minimum
-    // changes could trap dust balances in existing escrows.
-    fn check_min_amount(env: &Env, token: Address, amount: i128) -> Result<(), Error> {
-        if amount <= 0 {
-            return Err(Error::AmountBelowMinimum);
-        }
+        // Effects before interaction: a callback can never observe this escrow
+        // as unfunded after its balance has been pulled.
+        escrow.funded = true;
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::extend_persistent(&env, &(ESCROW, order_id));
+        Self::update_total_locked(&env, &escrow.token, escrow.amount);
 
-        let min_amount: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::MinEscrowAmount(token))
-            .unwrap_or(0); // If not set, allow any positive amount
+        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &funder, &env.current_contract_address(), escrow.amount, &funder, Symbol::new(&env, "escrow_funded"), -escrow.amount);
 
-        if amount < min_amount {
-            return Err(Error::AmountBelowMinimum);
-        }
+        Self::emit_escrow_created(
+            &env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action: EscrowAction::Created, // Re-emit as created/funded
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
         Ok(())
     }
 
-    /// Records a stake operation in the history queue for audit trail and analytics (#237)
-    /// Implements bounded queue with automatic pruning to prevent unbounded storage growth
-    fn record_stake_history(
-        env: &Env,
-        artisan: &Address,
-        new_stake: i128,
-        operation: &str,
-    ) -> Result<(), Error> {
-        let count_key = DataKey::StakeHistoryCount(artisan.clone());
-        let _history_key = DataKey::StakeHistory(artisan.clone());
-
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        // Check if we need to prune before adding new entry
-        if current_count >= MAX_STAKE_HISTORY_SIZE {
-            // Queue is full, cannot add more entries
-            return Err(Error::StakeQueueFull);
+    /// Cancel an escrow that has not been funded within the timeout period (#213, #656).
+    ///
+    /// Before the `funding_deadline` only the buyer may cancel voluntarily.
+    /// After the deadline **any** party (buyer, seller, or platform admin) may cancel
+    /// by passing their own address as `caller` to reclaim persistent-storage rent
+    /// and prevent indefinite stub accumulation.
+    pub fn cancel_unfunded_escrow(env: Env, order_id: u32, caller: Address) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        let escrow = Self::get_stored_escrow(&env, order_id);
+        if escrow.funded {
+            return Err(Error::InvalidEscrowState);
         }
 
-        // If approaching capacity threshold, schedule pruning
-        if current_count >= STAKE_HISTORY_PRUNE_THRESHOLD {
-            // Keep only most recent 50% of entries to free up space
-            // This is done lazily - oldest entries will be overwritten on next full cycle
-            let new_count = current_count / 2;
-            env.storage().persistent().set(&count_key, &new_count);
-            Self::extend_persistent(env, &count_key);
-        }
+        let current_time = env.ledger().timestamp();
+        // Use the stored funding_deadline when available; fall back to the
+        // legacy created_at + UNFUNDED_CANCEL_TIMEOUT calculation for escrows
+        // created before this field was added.
+        let deadline = escrow
+            .funding_deadline
+            .unwrap_or((escrow.created_at as u64) + UNFUNDED_CANCEL_TIMEOUT);
 
-        // Record timestamp of this operation for maintenance checks
-        let modified_key = DataKey::StakeLastModified(artisan.clone());
-        env.storage()
-            .persistent()
-            .set(&modified_key, &env.ledger().timestamp());
-        Self::extend_persistent(env, &modified_key);
+        if current_time >= deadline {
+            // After the deadline: buyer, seller, or platform admin may cancel.
+            let admin = Self::get_admin(&env).unwrap_or(escrow.buyer.clone());
+            if caller != escrow.buyer && caller != escrow.seller && caller != admin {
+                return Err(Error::Unauthorized);
+            }
+            caller.require_auth();
+        } else {
+            // Before the deadline: only the buyer may cancel voluntarily.
+            if caller != escrow.buyer {
+                return Err(Error::Unauthorized);
+            }
+            caller.require_auth();
+        }
 
-        // Emit audit event
-        env.events().publish(
-            (Symbol::new(env, "stake_operation"), operation.as_bytes()),
-            (artisan.clone(), new_stake),
-        );
+        Self::remove_unfunded_escrow(&env, order_id, &escrow);
 
         Ok(())
     }
 
-    /// Prunes obsolete stake history entries when queue reaches capacity (#237)
-    /// Implements safe cleanup strategy that preserves recent entries for audit trail
-    #[allow(dead_code)]
-    fn prune_stake_history(env: &Env, artisan: &Address) {
-        let count_key = DataKey::StakeHistoryCount(artisan.clone());
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        if current_count > 0 {
-            // Keep most recent 50 entries, discard older ones
-            let retained_count = current_count.min(50);
-            env.storage().persistent().set(&count_key, &retained_count);
-            Self::extend_persistent(env, &count_key);
-        }
-    }
+    /// Remove an unfunded escrow stub and release its active-obligation slots.
+    fn remove_unfunded_escrow(env: &Env, order_id: u32, escrow: &Escrow) {
+        env.storage().persistent().remove(&(ESCROW, order_id));
 
-    #[inline(always)]
-    fn update_active_obligations(env: &Env, user: &Address, delta: i32) {
-        let key = DataKey::ActiveObligations(user.clone());
-        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
-        let new_val = if delta > 0 {
-            count.saturating_add(delta as u32)
-        } else {
-            count.saturating_sub((-delta) as u32)
-        };
-        env.storage().persistent().set(&key, &new_val);
-        Self::extend_persistent(env, &key);
-    }
+        // Decrement active obligations
+        Self::update_active_obligations(env, &escrow.buyer, -1);
+        Self::update_active_obligations(env, &escrow.seller, -1);
 
-    #[inline(always)]
-    fn update_total_locked(env: &Env, token: &Address, delta: i128) {
-        let key = DataKey::TotalLocked(token.clone());
-        let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        let new_total = current.saturating_add(delta);
-        env.storage().persistent().set(&key, &new_total);
-        Self::extend_persistent(env, &key);
+        Self::safe_update_active_contracts(env, escrow.buyer.clone(), -1);
+        Self::safe_update_active_contracts(env, escrow.seller.clone(), -1);
     }
 
-    #[inline(always)]
-    fn update_total_staked(env: &Env, token: &Address, delta: i128) {
-        let key = DataKey::TotalStaked(token.clone());
-        let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        let new_total = current.saturating_add(delta);
-        env.storage().persistent().set(&key, &new_total);
-        Self::extend_persistent(env, &key);
-    }
+    /// Batch-cancel unfunded escrow stubs whose `funding_deadline` has elapsed (#656).
+    ///
+    /// Callable by a `Role::Operator` holder. The function iterates over the
+    /// provided list of `order_ids` and cancels each one that:
+    ///   1. Exists in storage
+    ///   2. Is not yet funded
+    ///   3. Has passed its `funding_deadline` (or the legacy 24-hour timeout)
+    ///
+    /// Escrows that do not meet these criteria are silently skipped so a
+    /// single invalid entry does not abort the whole batch. Returns the count
+    /// of escrows that were actually cancelled.
+    ///
+    /// # Arguments
+    /// * `caller` â€“ Address holding `Role::Operator`; auth is required.
+    /// * `order_ids` â€“ List of escrow order IDs to check and cancel.
+    pub fn auto_cancel_unfunded(
+        env: Env,
+        caller: Address,
+        order_ids: soroban_sdk::Vec<u32>,
+    ) -> Result<u32, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::require_role(&env, Role::Operator, &caller);
 
-    /// Extend the TTL of a persistent storage entry using standardized values.
-    #[inline(always)]
-    fn extend_persistent(env: &Env, key: &impl soroban_sdk::IntoVal<Env, soroban_sdk::Val>) {
-        env.storage()
-            .persistent()
-            .extend_ttl(key, TTL_THRESHOLD, TTL_EXTENSION);
-    }
+        let current_time = env.ledger().timestamp();
+        let mut cancelled_count: u32 = 0;
 
-    #[inline(always)]
-    fn extend_persistent_read(env: &Env, key: &impl soroban_sdk::IntoVal<Env, soroban_sdk::Val>) {
-        env.storage()
-            .persistent()
-            .extend_ttl(key, READ_TTL_THRESHOLD, TTL_EXTENSION);
-    }
+        for order_id in order_ids.iter() {
+            let key = (ESCROW, order_id);
 
-    /// Read a persistent `u32` and extend its TTL when the key exists (#515).
-    #[inline(always)]
-    fn get_persistent_u32(env: &Env, key: &DataKey) -> u32 {
-        match env.storage().persistent().get(key) {
-            Some(value) => {
-                Self::extend_persistent(env, key);
-                value
-            }
-            None => 0u32,
-        }
-    }
+            // Skip missing escrows
+            let escrow: Escrow = match env.storage().persistent().get(&key) {
+                Some(e) => e,
+                None => continue,
+            };
 
-    /// Read a persistent `u64` and extend its TTL when the key exists (#431 / key index #30).
-    #[inline(always)]
-    fn get_persistent_u64(env: &Env, key: &DataKey) -> u64 {
-        match env.storage().persistent().get(key) {
-            Some(value) => {
-                Self::extend_persistent(env, key);
-                value
-            }
-            None => 0u64,
-        }
-    }
+            // Skip already-funded escrows
+            if escrow.funded {
+                continue;
+            }
 
-    #[inline(always)]
-    fn get_whitelist_count(env: &Env) -> u32 {
-        let count_key = DataKey::WhitelistedTokenCount;
-        match env.storage().persistent().get(&count_key) {
-            Some(count) => {
-                Self::extend_persistent(env, &count_key);
-                count
+            // Skip escrows that haven't yet reached their funding deadline
+            let deadline = escrow
+                .funding_deadline
+                .unwrap_or((escrow.created_at as u64) + UNFUNDED_CANCEL_TIMEOUT);
+            if current_time < deadline {
+                continue;
             }
-            None => 0u32,
+
+            // Cancel: remove from storage and update bookkeeping
+            Self::remove_unfunded_escrow(&env, order_id, &escrow);
+
+            cancelled_count += 1;
         }
-    }
 
-    #[inline(always)]
-    fn set_whitelist_count(env: &Env, count: u32) {
-        let count_key = DataKey::WhitelistedTokenCount;
-        env.storage().persistent().set(&count_key, &count);
-        Self::extend_persistent(env, &count_key);
+        Ok(cancelled_count)
     }
 
-    fn migrate_legacy_whitelisted_tokens(env: &Env) {
-        let legacy_key = DataKey::WhitelistedTokens;
-        if !env.storage().persistent().has(&legacy_key) {
-            return;
+    /// Get escrows for a specific buyer with pagination.
+    /// Uses indexed storage for scalability, with fallback to legacy vector storage.
+    pub fn get_escrows_by_buyer(
+        env: Env,
+        buyer: Address,
+        page: u32,
+        page_size: u32,
+        reverse: bool,
+    ) -> Result<soroban_sdk::Vec<u64>, Error> {
+        buyer.require_auth();
+        let mut result = soroban_sdk::Vec::new(&env);
+
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        if page_size == 0 {
+            return Ok(result);
         }
 
-        let legacy_whitelist: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&legacy_key)
-            .unwrap_or(Map::new(env));
+        // Try new indexed storage first
+        let count_key = DataKey::BuyerEscrowCount(buyer.clone());
+        if env.storage().persistent().has(&count_key) {
+            let total_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0u32);
+            let start = page * page_size;
 
-        let mut count = Self::get_whitelist_count(env);
-        for (token, enabled) in legacy_whitelist.iter() {
-            if enabled {
-                let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
-                if !env.storage().persistent().has(&token_key) {
-                    env.storage().persistent().set(&token_key, &true);
-                    Self::extend_persistent(env, &token_key);
-                    count += 1;
-                }
+            if start >= total_count {
+                return Ok(result);
             }
-        }
 
-        if count > 0 {
-            Self::set_whitelist_count(env, count);
-        } else {
-            env.storage()
-                .persistent()
-                .remove(&DataKey::WhitelistedTokenCount);
-        }
+            let end = (start + page_size).min(total_count);
 
-        env.storage().persistent().remove(&legacy_key);
-    }
+            for position in start..end {
+                let storage_index = if reverse {
+                    total_count - 1 - position
+                } else {
+                    position
+                };
+                let index_key = DataKey::BuyerEscrowIndexed(buyer.clone(), storage_index);
+                if let Some(escrow_id) = env.storage().persistent().get::<_, u64>(&index_key) {
+                    result.push_back(escrow_id);
+                    Self::extend_persistent_read(&env, &index_key);
+                }
+            }
 
-    /// Migrate legacy `AllEscrowIds` Vec storage to indexed keys (#515).
-    fn migrate_legacy_all_escrow_ids(env: &Env) {
-        let legacy_key = DataKey::AllEscrowIds;
-        if !env.storage().persistent().has(&legacy_key) {
-            return;
+            Self::extend_persistent_read(&env, &count_key);
+            return Ok(result);
         }
 
-        let all_ids: soroban_sdk::Vec<u32> = env
+        // Fallback to legacy vector storage for backward compatibility
+        let legacy_key = DataKey::BuyerEscrows(buyer);
+        let escrow_ids: soroban_sdk::Vec<u64> = env
             .storage()
             .persistent()
             .get(&legacy_key)
-            .unwrap_or(soroban_sdk::Vec::new(env));
+            .unwrap_or(soroban_sdk::Vec::new(&env));
 
-        for i in 0..all_ids.len() {
-            if let Some(id) = all_ids.get(i) {
-                let index_key = DataKey::GlobalEscrowIdIndexed(i);
-                if !env.storage().persistent().has(&index_key) {
-                    env.storage().persistent().set(&index_key, &id);
-                    Self::extend_persistent(env, &index_key);
-                }
-            }
+        if env.storage().persistent().has(&legacy_key) {
+            Self::extend_persistent_read(&env, &legacy_key);
         }
 
-        let count_key = DataKey::EscrowCount;
-        let stored_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        if stored_count < all_ids.len() {
-            env.storage()
-                .persistent()
-                .set(&count_key, &(all_ids.len() as u32));
-            Self::extend_persistent(env, &count_key);
-        }
+        let start = page * page_size;
+        let len = escrow_ids.len();
 
-        env.storage().persistent().remove(&legacy_key);
-    }
+        if start >= len {
+            return Ok(result);
+        }
 
-    /// Returns the configured maximum release window (in seconds).
-    /// Falls back to MAX_TOTAL_RELEASE_WINDOW (30 days) if not set by admin.
-    #[inline(always)]
-    fn get_max_release_window(env: &Env) -> u32 {
-        let key = DataKey::MaxReleaseWindow;
-        let value = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(MAX_TOTAL_RELEASE_WINDOW);
-        // Issue #423: extend TTL on read to prevent storage expiry
-        if env.storage().persistent().has(&key) {
-            Self::extend_persistent(env, &key);
+        let end = (start + page_size).min(len);
+        if reverse {
+            for position in start..end {
+                if let Some(escrow_id) = escrow_ids.get(len - 1 - position) {
+                    result.push_back(escrow_id);
+                }
+            }
+            Ok(result)
+        } else {
+            Ok(escrow_ids.slice(start..end))
         }
-        value
     }
 
-    /// Returns the configured onboarding contract address, if any (#243).
-    fn get_onboarding_address(env: &Env) -> Option<Address> {
-        env.storage()
-            .persistent()
-            .get::<DataKey, Address>(&DataKey::OnboardingContractAddress)
-    }
+    /// Get escrows for a specific seller with pagination.
+    /// Uses indexed storage for scalability, with fallback to legacy vector storage.
+    pub fn get_escrows_by_seller(
+        env: Env,
+        seller: Address,
+        page: u32,
+        page_size: u32,
+        reverse: bool,
+    ) -> Result<soroban_sdk::Vec<u64>, Error> {
+        seller.require_auth();
+        let mut result = soroban_sdk::Vec::new(&env);
 
-    /// Build a client for the configured onboarding contract, if one is set.
-    ///
-    /// This helper is used by the escrow contract’s safe cross-contract
-    /// integration so onboarding updates remain behind a single, explicit
-    /// entry point instead of being spread across ad-hoc address lookups.
-    fn get_onboarding_client(env: &Env) -> Option<(Address, OnboardingClient<'_>)> {
-        Self::get_onboarding_address(env).map(|address| {
-            let client = OnboardingClient::new(env, &address);
-            (address, client)
-        })
-    }
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        if page_size == 0 {
+            return Ok(result);
+        }
 
-    /// Public read-only accessor for the registered onboarding contract
-    /// address. Returns `OnboardingContractNotSet` rather than `None` so that
-    /// SDK clients receive a typed error instead of a silent unwrap on a
-    /// `None`. Use `has_onboarding_contract` for a boolean check (#243).
-    pub fn get_onboarding_contract(env: Env) -> Result<Address, Error> {
-        Self::get_onboarding_address(&env).ok_or(Error::OnboardingContractNotSet)
-    }
+        // Try new indexed storage first
+        let count_key = DataKey::SellerEscrowCount(seller.clone());
+        if env.storage().persistent().has(&count_key) {
+            let total_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0u32);
+            let start = page * page_size;
 
-    /// True iff `set_onboarding_contract` has been called. Useful for
-    /// dashboards and integration tests that need to assert configuration
-    /// without surfacing an error path (#243).
-    pub fn has_onboarding_contract(env: Env) -> bool {
-        Self::get_onboarding_address(&env).is_some()
-    }
+            if start >= total_count {
+                return Ok(result);
+            }
 
-    /// Check if a user has any active escrows or recurring escrows.
-    pub fn has_active_escrows(env: Env, user: Address) -> bool {
-        let key = DataKey::ActiveObligations(user);
-        Self::get_persistent_u32(&env, &key) > 0
-    }
+            let end = (start + page_size).min(total_count);
 
-    /// Emit a structured warning event when a cross-contract call to the
-    /// onboarding contract fails. Indexers can subscribe to `OB_FAIL` to flag
-    /// integration drift between the escrow and onboarding contracts.
-    fn emit_onboarding_call_failed(env: &Env, method: Symbol, address: Address) {
-        env.events().publish(
-            (ONBOARD_CALL_FAILED, method),
-            (address, env.ledger().timestamp()),
-        );
-    }
+            for position in start..end {
+                let storage_index = if reverse {
+                    total_count - 1 - position
+                } else {
+                    position
+                };
+                let index_key = DataKey::SellerEscrowIndexed(seller.clone(), storage_index);
+                if let Some(escrow_id) = env.storage().persistent().get::<_, u64>(&index_key) {
+                    result.push_back(escrow_id);
+                    Self::extend_persistent_read(&env, &index_key);
+                }
+            }
 
-    /// Safely call `update_reputation` on the registered onboarding contract.
-    ///
-    /// Returns `Ok(true)` on a successful cross-contract call, `Ok(false)` if
-    /// the call failed (so the caller can decide whether to fall back to
-    /// emitting events) or no contract is configured. Never panics, never
-    /// propagates the host trap â€” the escrow flow MUST keep moving even if
-    /// the onboarding contract is broken or pointing at a malicious
-    /// implementation (#243).
-    #[allow(dead_code)]
-    fn safe_update_reputation(
-        env: &Env,
-        address: Address,
-        successful_delta: u32,
-        disputed_delta: u32,
-    ) -> bool {
-        // Issue #527 â€” short-circuit on the no-op call before paying
-        // for the persistent storage read of the onboarding contract
-        // address. If both reputation deltas are 0 the cross-contract
-        // call has no effect; returning `true` here saves a storage
-        // decode + a host `try_invoke_contract` on every escrow
-        // settlement where reputation didn't change.
-        if successful_delta == 0 && disputed_delta == 0 {
-            return true;
+            Self::extend_persistent_read(&env, &count_key);
+            return Ok(result);
         }
 
-        let (onboarding_address, _onboarding) = match Self::get_onboarding_client(env) {
-            Some(client) => client,
-            None => return false,
-        };
-
-        let method = Symbol::new(env, "update_reputation");
-        let args: Vec<Val> = (address.clone(), successful_delta, disputed_delta).into_val(env);
+        // Fallback to legacy vector storage for backward compatibility
+        let legacy_key = DataKey::SellerEscrows(seller);
+        let escrow_ids: soroban_sdk::Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&legacy_key)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
 
-        match env.try_invoke_contract::<(), soroban_sdk::Error>(&onboarding_address, &method, args)
-        {
-            Ok(Ok(())) => true,
-            _ => {
-                Self::emit_onboarding_call_failed(env, method, onboarding_address);
-                false
-            }
+        if env.storage().persistent().has(&legacy_key) {
+            Self::extend_persistent_read(&env, &legacy_key);
         }
-    }
 
-    /// Safely call `update_user_metrics` on the registered onboarding contract.
-    /// Mirrors `safe_update_reputation`'s contract: never panics, returns
-    /// `false` on missing config or cross-contract failure (#243).
-    #[allow(dead_code)]
-    fn safe_update_user_metrics(
-        env: &Env,
-        address: Address,
-        escrow_count_delta: u32,
-        volume_delta: i128,
-        token_address: Address,
-    ) -> bool {
-        let (onboarding_address, _onboarding) = match Self::get_onboarding_client(env) {
-            Some(client) => client,
-            None => return false,
-        };
+        let start = page * page_size;
+        let len = escrow_ids.len();
 
-        let method = Symbol::new(env, "update_user_metrics");
-        let args: Vec<Val> = (
-            address.clone(),
-            escrow_count_delta,
-            volume_delta,
-            token_address.clone(),
-        )
-            .into_val(env);
+        if start >= len {
+            return Ok(result);
+        }
 
-        match env.try_invoke_contract::<(), soroban_sdk::Error>(&onboarding_address, &method, args)
-        {
-            Ok(Ok(())) => true,
-            _ => {
-                Self::emit_onboarding_call_failed(env, method, onboarding_address);
-                false
+        let end = (start + page_size).min(len);
+        if reverse {
+            for position in start..end {
+                if let Some(escrow_id) = escrow_ids.get(len - 1 - position) {
+                    result.push_back(escrow_id);
+                }
             }
+            Ok(result)
+        } else {
+            Ok(escrow_ids.slice(start..end))
         }
     }
 
-    #[allow(dead_code)]
-    fn safe_update_active_contracts(env: &Env, user: Address, delta: i32) -> bool {
-        if delta == 0 {
-            return true;
-        }
+    /// Get platform configuration
+    pub fn get_platform_config(env: Env) -> PlatformConfig {
+        Self::get_platform_config_internal(&env)
+    }
 
-        let (onboarding_address, _onboarding) = match Self::get_onboarding_client(env) {
-            Some(client) => client,
-            None => return false,
-        };
+    fn get_platform_config_internal(env: &Env) -> PlatformConfig {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTENSION);
+        env.storage()
+            .instance()
+            .get(&DataKey::PlatformConfig)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::PlatformNotInitialized))
+    }
 
-        let method = Symbol::new(env, "update_active_contracts");
-        let args: Vec<Val> = (user.clone(), delta).into_val(env);
+    fn set_paused_internal(env: &Env, paused: bool) -> Result<(), Error> {
+        let mut config = Self::get_platform_config_internal(env);
+        config.is_paused = paused;
+        env.storage().instance().set(&DataKey::PlatformConfig, &config);
 
-        match env.try_invoke_contract::<(), soroban_sdk::Error>(&onboarding_address, &method, args)
-        {
-            Ok(Ok(())) => true,
-            _ => {
-                Self::emit_onboarding_call_failed(env, method, onboarding_address);
-                false
-            }
+        if paused {
+            Self::emit_platform_paused(env, config.admin.clone());
+        } else {
+            Self::emit_platform_unpaused(env, config.admin.clone());
         }
+        Ok(())
     }
 
-    /// Set the configurable maximum release window (admin only).
-    ///
-    /// # Arguments
-    /// * `max_window` - Maximum allowed release window in seconds.
-    ///   Must be > 0 and <= ABSOLUTE_MAX_RELEASE_WINDOW.
-    pub fn set_max_release_window(env: Env, max_window: u32) {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
-        if max_window == 0 {
-            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
-        }
-        if max_window > ABSOLUTE_MAX_RELEASE_WINDOW {
-            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
-        }
-        env.storage()
+    fn try_get_escrow_readonly(env: &Env, order_id: u32) -> Escrow {
+        let key = (ESCROW, order_id);
+        let stored: Val = env
+            .storage()
             .persistent()
-            .set(&DataKey::MaxReleaseWindow, &max_window);
-    }
-
-    /// Set the minimum release window to prevent "flash" auto-releases (admin only).
-    ///
-    /// # Arguments
-    /// * `min_window` - Minimum allowed release window in seconds
-    ///
-    /// # Panics
-    /// - If min_window is 0
-    /// - If min_window exceeds the current max_release_window
-    pub fn set_min_release_window(env: Env, min_window: u32) -> Result<(), Error> {
-        let mut config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+            .get(&key)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::EscrowNotFound));
+        let map = Map::<Symbol, Val>::try_from_val(env, &stored).expect("");
+        let version_key = Symbol::new(env, "version");
 
-        if min_window == 0 {
-            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        if map.contains_key(version_key) {
+            let mut escrow = Self::decode_versioned_escrow(env, &map, &stored);
+            if escrow.version < CURRENT_ESCROW_VERSION {
+                escrow.version = CURRENT_ESCROW_VERSION;
+            }
+            Self::extend_persistent(env, &key); // OPTIMIZED: Ensure TTL extension on read
+            return escrow;
         }
 
-        let max_window = Self::get_max_release_window(&env);
-        if min_window > max_window {
-            return Err(Error::ReleaseWindowTooLong);
-        }
+        let legacy = LegacyEscrow::try_from_val(env, &stored).expect("");
 
-        let old_min = config.min_release_window;
-        config.min_release_window = min_window;
+        let dispute_symbol = legacy.dispute_reason.map(|r| {
+            // Safety bound: Symbols max at 32 chars. Truncate if legacy reason is too long.
+            let len = r.len() as usize;
+            let slice_len = core::cmp::min(len, 32);
+            let mut buf = [0u8; 32];
+            r.copy_into_slice(&mut buf[..slice_len]);
+            let s = core::str::from_utf8(&buf[..slice_len]).unwrap();
+            Symbol::new(env, s)
+        });
 
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
+        let upgraded = Escrow {
+            version: CURRENT_ESCROW_VERSION,
+            id: legacy.id,
+            batch_id: None,
+            buyer: legacy.buyer,
+            seller: legacy.seller,
+            token: legacy.token,
+            amount: legacy.amount,
+            status: legacy.status,
+            release_window: legacy.release_window,
+            created_at: legacy.created_at,
+            ipfs_hash: legacy.ipfs_hash,
+            metadata_hash: legacy.metadata_hash,
+            dispute_reason: dispute_symbol,
+            dispute_initiated_at: legacy.dispute_initiated_at,
+            funded: true,
+            funding_deadline: None, // Legacy escrows were funded at creation
+            payer: None,
+            refund_address: None,
+        };
+        Self::extend_persistent(env, &key); // OPTIMIZED: Ensure TTL extension on read
+        upgraded
+    }
 
-        Self::emit_config_updated(
-            &env,
-            "min_release_window",
-            ConfigValue::U32(old_min),
-            ConfigValue::U32(min_window),
-        );
+    fn get_stored_escrow(env: &Env, order_id: u32) -> Escrow {
+        let key = (ESCROW, order_id);
+        let stored: Val = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::EscrowNotFound));
+        let map = Map::<Symbol, Val>::try_from_val(env, &stored).expect("");
+        let version_key = Symbol::new(env, "version");
+
+        if map.contains_key(version_key) {
+            let escrow = Self::decode_versioned_escrow(env, &map, &stored);
+            if escrow.version < CURRENT_ESCROW_VERSION {
+                return Self::upgrade_escrow(env, order_id, escrow);
+            }
+            Self::extend_persistent(env, &key);
+            return escrow;
+        }
+
+        let legacy = LegacyEscrow::try_from_val(env, &stored).expect("");
+        let dispute_symbol = legacy.dispute_reason.map(|r| {
+            let len = r.len() as usize;
+            let slice_len = core::cmp::min(len, 32);
+            let mut buf = [0u8; 32];
+            r.copy_into_slice(&mut buf[..slice_len]);
+            let s = core::str::from_utf8(&buf[..slice_len]).unwrap();
+            Symbol::new(env, s)
+        });
+        let upgraded = Escrow {
+            version: CURRENT_ESCROW_VERSION,
+            id: legacy.id,
+            batch_id: None,
+            buyer: legacy.buyer,
+            seller: legacy.seller,
+            token: legacy.token,
+            amount: legacy.amount,
+            status: legacy.status,
+            release_window: legacy.release_window,
+            created_at: legacy.created_at,
+            ipfs_hash: legacy.ipfs_hash,
+            metadata_hash: legacy.metadata_hash,
+            dispute_reason: dispute_symbol,
+            dispute_initiated_at: legacy.dispute_initiated_at,
+            funded: true,
+            funding_deadline: None, // Legacy escrows were funded at creation
+            payer: None,
+            refund_address: None,
+        };
+        env.storage().persistent().set(&key, &upgraded);
+        Self::extend_persistent(env, &key);
+        upgraded
+    }
+
+    fn claim_active_escrow_transition(
+        env: &Env,
+        order_id: u32,
+        pending_status: EscrowStatus,
+    ) -> Result<Escrow, Error> {
+        let mut escrow = Self::get_stored_escrow(env, order_id);
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::InvalidEscrowState);
+        }
 
-        Ok(())
+        escrow.status = pending_status;
+        let key = (ESCROW, order_id);
+        env.storage().persistent().set(&key, &escrow);
+        Self::extend_persistent(env, &key);
+        Ok(escrow)
     }
 
-    /// Get the current minimum release window
-    pub fn get_min_release_window(env: Env) -> u32 {
-        let config = Self::get_platform_config_internal(&env);
-        config.min_release_window
+    fn upgrade_escrow(env: &Env, order_id: u32, mut escrow: Escrow) -> Escrow {
+        if escrow.version < 3 {
+            escrow.funded = true;
+        }
+        escrow.version = CURRENT_ESCROW_VERSION;
+        let key = (ESCROW, order_id);
+        env.storage().persistent().set(&key, &escrow);
+        Self::extend_persistent(env, &key);
+        escrow
     }
 
-    /// Register the deployed OnboardingContract address so the escrow contract
-    /// can make cross-contract reputation / metrics updates (admin only).
-    ///
-    /// (#243) Rejects pointing the onboarding contract at the escrow itself â€”
-    /// a self-call would create a re-entrancy hazard if the trait surface ever
-    /// expands. Cross-contract calls into the configured address remain
-    /// indirect via `safe_update_reputation` / `safe_update_user_metrics`,
-    /// which trap-isolate failures so a misbehaving onboarding contract
-    /// cannot brick escrow operations. Emits a `config_updated` event with
-    /// the previous and new addresses for audit trails.
-    pub fn set_onboarding_contract(env: Env, contract_address: Address) {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
-
-        if contract_address == env.current_contract_address() {
-            env.panic_with_error(crate::Error::Unauthorized);
-        }
+    /// Address that funds an escrow: the payer of a gift escrow, else the buyer.
+    fn escrow_funder(escrow: &Escrow) -> Address {
+        escrow.payer.clone().unwrap_or_else(|| escrow.buyer.clone())
+    }
 
-        let previous = Self::get_onboarding_address(&env);
+    /// Destination for refunds of an escrow.
+    fn escrow_refund_recipient(escrow: &Escrow) -> Address {
+        escrow
+            .refund_address
+            .clone()
+            .unwrap_or_else(|| Self::escrow_funder(escrow))
+    }
 
-        // Issue #527 â€” short-circuit on the no-op call before paying
-        // for the persistent storage write and TTL extension.
-        if let Some(ref current) = previous {
-            if *current == contract_address {
-                return;
-            }
+    /// Decode an escrow stored with a `version` field in any of its
+    /// historical layouts.
+    fn decode_versioned_escrow(env: &Env, map: &Map<Symbol, Val>, stored: &Val) -> Escrow {
+        if map.contains_key(Symbol::new(env, "payer")) {
+            Escrow::try_from_val(env, stored).expect("")
+        } else if map.contains_key(Symbol::new(env, "batch_id")) {
+            Self::escrow_from_v4(EscrowV4::try_from_val(env, stored).expect(""))
+        } else {
+            let previous = EscrowWithoutBatch::try_from_val(env, stored).expect("");
+            Self::escrow_from_without_batch(env, previous)
         }
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::OnboardingContractAddress, &contract_address);
-        Self::extend_persistent(&env, &DataKey::OnboardingContractAddress);
-
-        let old_value = match previous {
-            Some(addr) => ConfigValue::Address(addr),
-            None => ConfigValue::String(String::from_str(&env, "unset")),
-        };
-        Self::emit_config_updated(
-            &env,
-            "onboarding_contract",
-            old_value,
-            ConfigValue::Address(contract_address),
-        );
+    fn escrow_from_v4(escrow: EscrowV4) -> Escrow {
+        Escrow {
+            version: escrow.version,
+            id: escrow.id,
+            batch_id: escrow.batch_id,
+            buyer: escrow.buyer,
+            seller: escrow.seller,
+            token: escrow.token,
+            amount: escrow.amount,
+            status: escrow.status,
+            release_window: escrow.release_window,
+            created_at: escrow.created_at,
+            ipfs_hash: escrow.ipfs_hash,
+            metadata_hash: escrow.metadata_hash,
+            dispute_reason: escrow.dispute_reason,
+            dispute_initiated_at: escrow.dispute_initiated_at,
+            funded: escrow.funded,
+            funding_deadline: escrow.funding_deadline,
+            payer: None,
+            refund_address: None,
+        }
     }
 
-    /// Clear the registered onboarding contract address (admin only) (#243).
-    /// After calling this, `get_onboarding_contract` returns
-    /// `OnboardingContractNotSet` and the safe cross-contract helpers become
-    /// no-ops â€” escrow flows continue to emit `ReputationUpdateEvent`s for
-    /// off-chain reconstruction (#211).
-    pub fn clear_onboarding_contract(env: Env) -> Result<(), Error> {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    fn escrow_from_without_batch(env: &Env, escrow: EscrowWithoutBatch) -> Escrow {
+        let dispute_symbol = escrow.dispute_reason.map(|r| {
+            let len = r.len() as usize;
+            let slice_len = core::cmp::min(len, 32);
+            let mut buf = [0u8; 32];
+            r.copy_into_slice(&mut buf[..slice_len]);
+            let s = core::str::from_utf8(&buf[..slice_len]).unwrap();
+            Symbol::new(env, s)
+        });
 
-        let previous = Self::get_onboarding_address(&env).ok_or(Error::OnboardingContractNotSet)?;
+        Escrow {
+            version: escrow.version,
+            id: escrow.id,
+            batch_id: None,
+            buyer: escrow.buyer,
+            seller: escrow.seller,
+            token: escrow.token,
+            amount: escrow.amount,
+            status: escrow.status,
+            release_window: escrow.release_window,
+            created_at: escrow.created_at,
+            ipfs_hash: escrow.ipfs_hash,
+            metadata_hash: escrow.metadata_hash,
+            dispute_reason: dispute_symbol, // Map to lightweight Symbol
+            dispute_initiated_at: escrow.dispute_initiated_at,
+            funded: true,
+            funding_deadline: None, // Legacy escrows were funded at creation
+            payer: None,
+            refund_address: None,
+        }
+    }
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::OnboardingContractAddress);
+    /// Calculate platform fee for a given amount.
+    #[inline(always)]
+    fn try_calculate_fee(amount: i128, fee_bps: u32) -> Result<i128, Error> {
+        if amount < 0 {
+            return Err(Error::InvalidFee);
+        }
 
-        Self::emit_config_updated(
-            &env,
-            "onboarding_contract",
-            ConfigValue::Address(previous),
-            ConfigValue::String(String::from_str(&env, "unset")),
-        );
-        Ok(())
+        amount
+            .checked_mul(fee_bps as i128)
+            .and_then(|product| product.checked_div(10_000))
+            .ok_or(Error::InvalidFee)
     }
 
-    /// Add a token to the platform whitelist (admin only).
-    ///
-    /// Uses individual key-value pairs for scalability instead of a single Map.
-    /// Each token is stored as DataKey::WhitelistedTokenIndexed(token) -> true.
-    /// Once at least one token is whitelisted, only whitelisted tokens may be
-    /// used in escrow creation. The check is skipped when the whitelist is empty,
-    /// preserving backward compatibility.
-    ///
-    /// # Decimal validation
-    ///
-    /// The token's `decimals()` value must be in the range 0–18 (inclusive).
-    /// Tokens with more than 18 decimal places would overflow the volume
-    /// normalization arithmetic in the onboarding contract and are rejected
-    /// with [`Error::InvalidTokenDecimals`].
-    pub fn whitelist_token(env: Env, token: Address) -> Result<(), Error> {
-        let _guard = ReentryGuardScope::new(&env);
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    #[inline(always)]
+    fn calculate_fee(env: &Env, amount: i128, fee_bps: u32) -> i128 {
+        Self::try_calculate_fee(amount, fee_bps).unwrap_or_else(|err| env.panic_with_error(err))
+    }
 
-        // Probe the SEP-41 read interface before persisting an administrator
-        // supplied address. Missing or malformed methods become a stable
-        // contract error instead of an opaque host panic.
-        let token_client = token::Client::new(&env, &token);
-        let decimals = token_client
-            .try_decimals()
-            .map_err(|_| Error::UnsupportedToken)?
-            .map_err(|_| Error::UnsupportedToken)?;
-        if decimals > 18 {
-            return Err(Error::InvalidTokenDecimals);
+    /// Fee on `amount` under `rate`: the bps fee, plus the schedule's flat
+    /// fee, clamped to the schedule's minimum and maximum. Never exceeds
+    /// `amount`.
+    fn scheduled_fee(env: &Env, amount: i128, rate: &FeeRate) -> i128 {
+        let bps_fee = Self::calculate_fee(env, amount, rate.bps);
+        let schedule = match rate.schedule {
+            Some(schedule) if amount > 0 => schedule,
+            _ => return bps_fee,
+        };
+        let mut fee = bps_fee
+            .checked_add(schedule.flat_fee)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::InvalidFee));
+        fee = fee.max(schedule.min_fee);
+        if schedule.max_fee > 0 {
+            fee = fee.min(schedule.max_fee);
         }
-        token_client
-            .try_balance(&env.current_contract_address())
-            .map_err(|_| Error::UnsupportedToken)?
-            .map_err(|_| Error::UnsupportedToken)?;
-
-        Self::migrate_legacy_whitelisted_tokens(&env);
-        let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
-        let mut count = Self::get_whitelist_count(&env);
+        fee.min(amount)
+    }
 
-        if !env.storage().persistent().has(&token_key) {
-            env.storage().persistent().set(&token_key, &true);
-            Self::extend_persistent(&env, &token_key);
-            count += 1;
-            Self::set_whitelist_count(&env, count);
+    /// Fee rate for settling an escrow: the rate snapshotted at creation,
+    /// falling back to the live rate for escrows created before snapshots.
+    fn escrow_fee_rate(env: &Env, order_id: u32, escrow: &Escrow) -> FeeRate {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, FeeRateSnapshot>(&FeeDataKey::EscrowFeeRate(order_id))
+        {
+            Some(snapshot) => FeeRate::from_snapshot(&snapshot),
+            None => Self::effective_fee_rate(env, &escrow.seller, &escrow.token),
         }
-        Ok(())
     }
 
-    /// Remove a token from the platform whitelist (admin only).
-    ///
-    /// Uses individual key-value pairs for scalability. Removes the specific
-    /// token entry and updates the count. If the resulting whitelist is empty,
-    /// whitelist enforcement is automatically disabled (all tokens permitted again).
-    pub fn remove_token_from_whitelist(env: Env, token: Address) {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// Fee rate for settling a recurring escrow cycle: the rate snapshotted
+    /// at creation, falling back to the live rate for older escrows.
+    fn recurring_fee_rate(env: &Env, escrow: &RecurringEscrow) -> FeeRate {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, FeeRateSnapshot>(&RecurringDataKey::FeeRate(escrow.id))
+        {
+            Some(snapshot) => FeeRate::from_snapshot(&snapshot),
+            None => Self::effective_fee_rate(env, &escrow.artisan, &escrow.token),
+        }
+    }
 
-        Self::migrate_legacy_whitelisted_tokens(&env);
-        let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
+    /// Record the fee rate, bps and fee schedule, in force for a new escrow
+    /// so later fee changes do not reprice it.
+    fn snapshot_fee_rate(env: &Env, order_id: u32, seller: &Address, token: &Address) {
+        let fee_rate = Self::effective_fee_rate(env, seller, token);
+        Self::store_escrow_fee_rate(env, order_id, &fee_rate);
+    }
 
-        if env.storage().persistent().has(&token_key) {
-            env.storage().persistent().remove(&token_key);
-            let count = Self::get_whitelist_count(&env);
-            if count > 0 {
-                Self::set_whitelist_count(&env, count - 1);
-            }
-        }
+    fn store_escrow_fee_rate(env: &Env, order_id: u32, fee_rate: &FeeRate) {
+        let key = FeeDataKey::EscrowFeeRate(order_id);
+        env.storage().persistent().set(&key, &fee_rate.snapshot());
+        Self::extend_persistent(env, &key);
     }
 
-    /// Check whether a specific token is on the whitelist.
-    ///
-    /// Returns `true` if the token is explicitly whitelisted, OR if the whitelist
-    /// is empty (enforcement not yet active). Uses individual key lookups for
-    /// scalability instead of loading the entire whitelist Map.
-    pub fn is_token_whitelisted(env: Env, token: Address) -> bool {
-        Self::migrate_legacy_whitelisted_tokens(&env);
-        let count = Self::get_whitelist_count(&env);
-        if count == 0 {
-            return true;
+    /// Fee rate charging exactly `fee`, capped at the settled amount.
+    fn fixed_fee_rate(fee: i128) -> FeeRate {
+        FeeRate {
+            bps: 0,
+            schedule: Some(FeeSchedule {
+                flat_fee: fee,
+                min_fee: 0,
+                max_fee: 0,
+            }),
         }
+    }
 
-        let token_key = DataKey::WhitelistedTokenIndexed(token);
-        let is_whitelisted = env.storage().persistent().has(&token_key);
-        if is_whitelisted {
-            Self::extend_persistent(&env, &token_key);
+    /// Fee rate for an escrow of `seller` in `token`. The bps component is
+    /// the artisan's fee tier, else the token's `custom_fee_bps`, else the
+    /// platform fee; the token's fee schedule, if any, is applied on top.
+    fn effective_fee_rate(env: &Env, seller: &Address, token: &Address) -> FeeRate {
+        FeeRate {
+            bps: Self::get_effective_fee_bps_for_token(env.clone(), seller.clone(), token.clone()),
+            schedule: env
+                .storage()
+                .persistent()
+                .get(&FeeDataKey::Schedule(token.clone())),
         }
-        is_whitelisted
     }
 
-    /// Internal helper: panics with TokenNotWhitelisted when enforcement is active
-    /// and the token is not on the whitelist.
-    /// NOTE: whitelist enforcement is intentionally performed only during
-    /// escrow creation (and related locking operations). State transitions
-    /// such as `release`, `refund`, or recurring cycle releases MUST NOT
-    /// re-check the whitelist to avoid locking funds for escrows created
-    /// before whitelist changes. Keep this helper private and call it only
-    /// in creation-time validation paths.
-    fn check_token_whitelisted(env: &Env, token: &Address) {
-        Self::migrate_legacy_whitelisted_tokens(env);
-        let count = Self::get_whitelist_count(env);
-        if count == 0 {
-            return;
-        }
+    /// Deterministically compute how the escrow pot is split for any
+    /// settlement path.
+    ///
+    /// This is the **single source of truth** for all fee math in the
+    /// contract.  Every settlement function — `release_funds`, `auto_release`,
+    /// `release_batch_funds`, `refund`, `resolve_dispute`,
+    /// `resolve_expired_dispute`, and `accept_partial_refund` — **must** obtain
+    /// its transfer amounts exclusively from this function and must not perform
+    /// fee arithmetic inline.
+    ///
+    /// # Invariant
+    ///
+    /// The three output fields always satisfy:
+    ///
+    /// ```text
+    /// allocation.platform_fee + allocation.seller_amount + allocation.buyer_amount
+    ///     == escrow_amount
+    /// ```
+    ///
+    /// This invariant is checked by the test suite for every `SettlementKind`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env`           - Soroban environment (for panicking on overflow).
+    /// * `escrow_amount` - Total amount held in escrow (must be >= 0).
+    /// * `fee_rate`      - Effective fee rate for this escrow's seller and
+    ///                     token, obtained via `effective_fee_rate`.
+    /// * `kind`          - Which settlement formula to apply.
+    fn compute_fee_allocation(
+        env: &Env,
+        escrow_amount: i128,
+        fee_rate: &FeeRate,
+        kind: SettlementKind,
+    ) -> FeeAllocation {
+        let allocation = match kind {
+            // ── Normal release: platform fee from seller's share ──────────────
+            SettlementKind::ReleaseFunds => {
+                let platform_fee = Self::scheduled_fee(env, escrow_amount, fee_rate);
+                let seller_amount = escrow_amount - platform_fee;
+                FeeAllocation {
+                    platform_fee,
+                    seller_amount,
+                    buyer_amount: 0,
+                    referral_fee: 0,
+                    keeper_fee: 0,
+                }
+            }
+
+            // ── Full refund, no fee: entire pot returned to buyer ─────────────
+            SettlementKind::FullRefundNoFee => FeeAllocation {
+                platform_fee: 0,
+                seller_amount: 0,
+                buyer_amount: escrow_amount,
+                referral_fee: 0,
+                keeper_fee: 0,
+            },
 
-        let token_key = DataKey::WhitelistedTokenIndexed(token.clone());
-        if !env.storage().persistent().has(&token_key) {
-            env.panic_with_error(crate::Error::TokenNotWhitelisted);
-        }
-    }
+            // ── Expired dispute – fee conceptually from seller ────────────────
+            // Buyer receives the full amount; the platform does NOT collect
+            // the fee.  The seller's loss is the opportunity cost of the
+            // stalled arbitration.  Balances because platform_fee=0.
+            SettlementKind::ExpiredDisputeDeductFromSeller => FeeAllocation {
+                platform_fee: 0,
+                seller_amount: 0,
+                buyer_amount: escrow_amount,
+                referral_fee: 0,
+                keeper_fee: 0,
+            },
 
-    /// Get the count of whitelisted tokens.
-    ///
-    /// Returns 0 if no tokens are whitelisted (enforcement disabled).
-    /// This is more efficient than loading all tokens when only the count is needed.
-    pub fn get_whitelisted_token_count(env: Env) -> u32 {
-        Self::migrate_legacy_whitelisted_tokens(&env);
-        Self::get_whitelist_count(&env)
-    }
+            // ── Expired dispute – fee deducted from buyer's refund ────────────
+            SettlementKind::ExpiredDisputeDeductFromBuyer => {
+                let platform_fee = Self::scheduled_fee(env, escrow_amount, fee_rate);
+                let buyer_amount = escrow_amount - platform_fee;
+                FeeAllocation {
+                    platform_fee,
+                    seller_amount: 0,
+                    buyer_amount,
+                    referral_fee: 0,
+                    keeper_fee: 0,
+                }
+            }
 
-    /// Migrate legacy whitelist storage to individual key-value pairs.
-    ///
-    /// This function reads the old WhitelistedTokens Map and converts each entry
-    /// to individual WhitelistedTokenIndexed keys. Should be called once during
-    /// contract upgrade to migrate existing data.
-    pub fn migrate_whitelist_storage(env: Env) -> u32 {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+            // ── Expired dispute – fee split equally between both sides ─────────
+            SettlementKind::ExpiredDisputeSplitFee => {
+                let full_fee = Self::scheduled_fee(env, escrow_amount, fee_rate);
+                // Integer division: any remainder (odd-bps rounding) stays with buyer.
+                let platform_fee = full_fee / 2;
+                let buyer_amount = escrow_amount - platform_fee;
+                FeeAllocation {
+                    platform_fee,
+                    seller_amount: 0,
+                    buyer_amount,
+                    referral_fee: 0,
+                    keeper_fee: 0,
+                }
+            }
 
-        let legacy_key = DataKey::WhitelistedTokens;
+            // ── Release with a buyer-side fee deposited on top of the price ──
+            SettlementKind::ReleaseWithBuyerFee(buyer_fee, seller_shares) => {
+                let price = escrow_amount - buyer_fee;
+                let seller_fee = if seller_shares {
+                    let full_fee = Self::scheduled_fee(env, price, fee_rate);
+                    full_fee - full_fee / 2
+                } else {
+                    0
+                };
+                FeeAllocation {
+                    platform_fee: buyer_fee + seller_fee,
+                    seller_amount: price - seller_fee,
+                    buyer_amount: 0,
+                    referral_fee: 0,
+                    keeper_fee: 0,
+                }
+            }
 
-        // Check if legacy storage exists
-        if !env.storage().persistent().has(&legacy_key) {
-            return 0; // Nothing to migrate
-        }
+            // ── Partial refund: dual-sided fee deduction ──────────────────────
+            // The gross amounts must sum to `escrow_amount`; fees are computed
+            // on each gross portion independently using the same bps.
+            SettlementKind::PartialRefund(refund_gross, seller_gross) => {
+                // Defensive: clamp to escrow_amount to prevent accounting drift.
+                let safe_refund_gross = if refund_gross < 0 { 0 } else { refund_gross };
+                let safe_seller_gross = if seller_gross < 0 { 0 } else { seller_gross };
 
-        let legacy_whitelist: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&legacy_key)
-            .unwrap_or(Map::new(&env));
+                // A fee schedule is charged once on the whole pot and split
+                // pro rata, so its flat and minimum parts are not doubled.
+                let (refund_fee, seller_fee) = if fee_rate.schedule.is_some() {
+                    let gross = safe_refund_gross.saturating_add(safe_seller_gross);
+                    let total_fee = Self::scheduled_fee(env, gross, fee_rate);
+                    let refund_fee = if gross > 0 {
+                        total_fee * safe_refund_gross / gross
+                    } else {
+                        0
+                    };
+                    (refund_fee, total_fee - refund_fee)
+                } else {
+                    (
+                        Self::calculate_fee(env, safe_refund_gross, fee_rate.bps),
+                        Self::calculate_fee(env, safe_seller_gross, fee_rate.bps),
+                    )
+                };
 
-        let mut migrated_count = 0u32;
+                let platform_fee = refund_fee.saturating_add(seller_fee);
+                let buyer_amount = safe_refund_gross - refund_fee;
+                let seller_amount = safe_seller_gross - seller_fee;
 
-        // Migrate each token to individual storage
-        let keys = legacy_whitelist.keys();
-        for i in 0..keys.len() {
-            if let Some(token) = keys.get(i) {
-                if let Some(is_whitelisted) = legacy_whitelist.get(token.clone()) {
-                    if is_whitelisted {
-                        let token_key = DataKey::WhitelistedTokenIndexed(token);
-                        env.storage().persistent().set(&token_key, &true);
-                        Self::extend_persistent(&env, &token_key);
-                        migrated_count += 1;
-                    }
+                FeeAllocation {
+                    platform_fee,
+                    seller_amount,
+                    buyer_amount,
+                    referral_fee: 0,
+                    keeper_fee: 0,
                 }
             }
-        }
+        };
 
-        // Update count
-        if migrated_count > 0 {
-            let count_key = DataKey::WhitelistedTokenCount;
-            env.storage().persistent().set(&count_key, &migrated_count);
-            Self::extend_persistent(&env, &count_key);
+        // Deterministic balance invariant: the three-way split must exactly
+        // consume the escrow pot with no remainder.
+        let sum = allocation
+            .platform_fee
+            .checked_add(allocation.seller_amount)
+            .and_then(|s| s.checked_add(allocation.buyer_amount));
+        if sum != Some(escrow_amount) {
+            env.panic_with_error(crate::Error::InvalidFee);
         }
 
-        // Remove legacy storage
-        env.storage().persistent().remove(&legacy_key);
-
-        migrated_count
+        allocation
     }
 
-    /// Migrate legacy ArtisanStakeQueue Vec storage to individual indexed entries.
+    /// Maintain the dual fee-token bookkeeping (#239).
     ///
-    /// This function reads the old ArtisanStakeQueue Vec and converts each entry
-    /// to individual ArtisanStakeQueueIndexed keys. Should be called once during
-    /// contract upgrade to migrate existing data.
-    pub fn migrate_artisan_stake_queue(env: Env, artisan: Address) -> u32 {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
-
-        let legacy_key = DataKey::ArtisanStakeQueue(artisan.clone());
-
-        // Check if legacy storage exists
-        if !env.storage().persistent().has(&legacy_key) {
-            return 0; // Nothing to migrate
-        }
-
-        let legacy_queue: soroban_sdk::Vec<StakeDeposit> = env
+    /// Historically fee-receiving tokens were tracked only in the legacy
+    /// `FeeTokenIndex` Vec. That single-key shape made future multi-token
+    /// fee features (custom bps per token, disabling tokens, accumulator
+    /// reconciliation) impossible without a contract upgrade. We now also
+    /// write a per-token `FeeTokenConfig(token)` slot, which is the storage
+    /// shape new code should read going forward. The legacy Vec is kept as
+    /// the canonical enumeration source for backward compatibility â€” a
+    /// `migrate_fee_token_configs` admin call backfills `FeeTokenConfig` for
+    /// pre-existing tokens.
+    fn add_fee_token_to_index(env: &Env, token: &Address) {
+        let key = DataKey::FeeTokenIndex;
+        let mut tracked_tokens: Vec<Address> = env
             .storage()
             .persistent()
-            .get(&legacy_key)
-            .unwrap_or(soroban_sdk::Vec::new(&env));
+            .get(&key)
+            .unwrap_or(Vec::new(env));
 
-        let queue_len = legacy_queue.len();
-        if queue_len == 0 {
-            // Remove empty legacy queue
-            env.storage().persistent().remove(&legacy_key);
-            return 0;
+        let mut already_tracked = false;
+        for index in 0..tracked_tokens.len() {
+            if tracked_tokens.get(index) == Some(token.clone()) {
+                already_tracked = true;
+                break;
+            }
         }
 
-        // Migrate each deposit to individual indexed storage
-        for i in 0..queue_len {
-            if let Some(deposit) = legacy_queue.get(i) {
-                let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), i);
-                env.storage().persistent().set(&deposit_key, &deposit);
-                Self::extend_persistent(&env, &deposit_key);
-            }
+        if !already_tracked {
+            tracked_tokens.push_back(token.clone());
+            env.storage().persistent().set(&key, &tracked_tokens);
         }
+        Self::extend_persistent(env, &key);
 
-        // Set count
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        env.storage().persistent().set(&count_key, &queue_len);
-        Self::extend_persistent(&env, &count_key);
+        Self::ensure_fee_token_config(env, token);
+    }
 
-        // Remove legacy storage
-        env.storage().persistent().remove(&legacy_key);
+    /// Seed a default `FeeTokenInfo` slot the first time a token is seen.
+    /// Idempotent â€” once a slot exists, subsequent calls leave it untouched
+    /// so admin overrides survive future fee deposits (#239).
+    fn ensure_fee_token_config(env: &Env, token: &Address) {
+        let cfg_key = DataKey::FeeTokenConfig(token.clone());
+        if !env.storage().persistent().has(&cfg_key) {
+            let info = FeeTokenInfo {
+                active: true,
+                custom_fee_bps: None,
+                accumulated: 0,
+                fee_payer: FeePayer::Seller,
+                custom_fee_payer: false,
+            };
+            env.storage().persistent().set(&cfg_key, &info);
+        }
+        Self::extend_persistent(env, &cfg_key);
+    }
 
-        queue_len
+    /// Bump the per-token accumulator inside `FeeTokenConfig` (#239). Kept
+    /// internal so external callers cannot tamper with the running total.
+    fn bump_fee_token_accumulator(env: &Env, token: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        Self::ensure_fee_token_config(env, token);
+        let cfg_key = DataKey::FeeTokenConfig(token.clone());
+        let mut info: FeeTokenInfo =
+            Self::read_fee_token_info(env, token).unwrap_or(FeeTokenInfo {
+                active: true,
+                custom_fee_bps: None,
+                accumulated: 0,
+                fee_payer: FeePayer::Seller,
+                custom_fee_payer: false,
+            });
+        info.accumulated = info.accumulated.saturating_add(amount);
+        env.storage().persistent().set(&cfg_key, &info);
+        Self::extend_persistent(env, &cfg_key);
     }
 
-    /// Get the count of stake deposits in an artisan's queue.
-    ///
-    /// Returns 0 if no deposits exist. This is more efficient than loading
-    /// all deposits when only the count is needed.
-    pub fn get_artisan_stake_queue_count(env: Env, artisan: Address) -> u32 {
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        env.storage().persistent().get(&count_key).unwrap_or(0)
+    /// Returns the per-token fee configuration for `token`, or `None` if the
+    /// token has never received platform fees (#239).
+    pub fn get_fee_token_config(env: Env, token: Address) -> Option<FeeTokenInfo> {
+        Self::read_fee_token_info(&env, &token)
+    }
+
+    /// Read a `FeeTokenConfig` slot, upgrading slots written before
+    /// `fee_payer` existed.
+    fn read_fee_token_info(env: &Env, token: &Address) -> Option<FeeTokenInfo> {
+        let stored: Val = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FeeTokenConfig(token.clone()))?;
+        let map = Map::<Symbol, Val>::try_from_val(env, &stored).expect("");
+        if map.contains_key(Symbol::new(env, "custom_fee_payer")) {
+            return Some(FeeTokenInfo::try_from_val(env, &stored).expect(""));
+        }
+        let legacy = FeeTokenInfoV1::try_from_val(env, &stored).expect("");
+        Some(FeeTokenInfo {
+            active: legacy.active,
+            custom_fee_bps: legacy.custom_fee_bps,
+            accumulated: legacy.accumulated,
+            fee_payer: FeePayer::Seller,
+            custom_fee_payer: false,
+        })
     }
 
-    /// Get paginated stake deposits for an artisan (admin/debug helper).
-    ///
-    /// Returns up to `limit` deposits starting from `offset`. Useful for
-    /// inspecting queue state without loading the entire queue.
-    pub fn get_artisan_stake_deposits(
+    /// Set the fee payer model for one token (`Role::FeeManager`). `None` makes the
+    /// token follow the global model set with `set_fee_payer`.
+    pub fn set_fee_token_payer(
         env: Env,
-        artisan: Address,
-        offset: u32,
-        limit: u32,
-    ) -> soroban_sdk::Vec<StakeDeposit> {
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        let total_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        caller: Address,
+        token: Address,
+        fee_payer: Option<FeePayer>,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
 
-        let mut deposits = soroban_sdk::Vec::new(&env);
-        let end = core::cmp::min(offset + limit, total_count);
+        Self::ensure_fee_token_config(&env, &token);
+        let cfg_key = DataKey::FeeTokenConfig(token.clone());
+        let mut info = Self::read_fee_token_info(&env, &token).expect("");
+        info.custom_fee_payer = fee_payer.is_some();
+        info.fee_payer = fee_payer.unwrap_or(FeePayer::Seller);
+        env.storage().persistent().set(&cfg_key, &info);
+        Self::extend_persistent(&env, &cfg_key);
+        Ok(())
+    }
 
-        for i in offset..end {
-            let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), i);
-            if let Some(deposit) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, StakeDeposit>(&deposit_key)
-            {
-                deposits.push_back(deposit);
-            }
-        }
+    /// Set the global fee payer model (`Role::FeeManager`). Applies to escrows
+    /// created afterwards in tokens without their own model.
+    pub fn set_fee_payer(env: Env, caller: Address, fee_payer: FeePayer) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
 
-        deposits
+        let old_value = Self::get_fee_payer(env.clone());
+        env.storage().instance().set(&FeeDataKey::Payer, &fee_payer);
+        Self::emit_config_updated(
+            &env,
+            "fee_payer",
+            ConfigValue::U32(Self::fee_payer_code(old_value)),
+            ConfigValue::U32(Self::fee_payer_code(fee_payer)),
+        );
+        Ok(())
     }
 
-    pub fn initialize(
-        env: Env,
-        platform_wallet: Address,
-        admin: Address,
-        arbitrator: Address,
-        platform_fee_bps: u32,
-        onboarding_contract: Option<Address>,
-    ) {
-        admin.require_auth();
-
-        // Validate fee is within bounds
-        if platform_fee_bps > MAX_PLATFORM_FEE_BPS {
-            env.panic_with_error(crate::Error::InvalidFee);
+    /// Numeric code of a fee payer model for `ConfigUpdatedEvent`.
+    fn fee_payer_code(fee_payer: FeePayer) -> u32 {
+        match fee_payer {
+            FeePayer::Seller => 0,
+            FeePayer::Buyer => 1,
+            FeePayer::Split => 2,
         }
+    }
 
-        let config = PlatformConfig {
-            platform_fee_bps,
-            platform_wallet: platform_wallet.clone(),
-            admin: admin.clone(),
-            arbitrator: arbitrator.clone(),
-            moderator: None,
-            is_paused: false,
-            min_stake_required: 0,
-            pending_admin: None,
-            wasm_upgrade_cooldown: DEFAULT_WASM_UPGRADE_COOLDOWN,
-            max_dispute_duration: DEFAULT_MAX_DISPUTE_DURATION,
-            stake_cooldown: DEFAULT_STAKE_COOLDOWN,
-            expired_dispute_fee_policy: ExpiredDisputeFeePolicy::RefundFullNoPlatformFee,
-            min_release_window: DEFAULT_MIN_RELEASE_WINDOW,
-        };
-
+    /// Get the global fee payer model.
+    pub fn get_fee_payer(env: Env) -> FeePayer {
         env.storage()
             .instance()
-            .set(&DataKey::PlatformConfig, &config);
-
-        env.storage()
-            .persistent()
-            .set(&PLATFORM_WALLET, &platform_wallet);
-        Self::extend_persistent(&env, &PLATFORM_WALLET);
-
-        // Initialize total fees to 0
-        let zero: i128 = 0;
-        env.storage().persistent().set(&TOTAL_FEES, &zero);
-        Self::extend_persistent(&env, &TOTAL_FEES);
+            .get(&FeeDataKey::Payer)
+            .unwrap_or(FeePayer::Seller)
+    }
 
-        // Initialize contract version to 1
-        env.storage()
-            .persistent()
-            .set(&DataKey::ContractVersion, &1u32);
-        Self::extend_persistent(&env, &DataKey::ContractVersion);
+    /// Get the fee payer model that applies to new escrows in `token`.
+    pub fn get_token_fee_payer(env: Env, token: Address) -> FeePayer {
+        Self::read_fee_token_info(&env, &token)
+            .filter(|info| info.custom_fee_payer)
+            .map(|info| info.fee_payer)
+            .unwrap_or_else(|| Self::get_fee_payer(env.clone()))
+    }
 
-        // Initialize storage layout version so future upgrades can validate the
-        // on-disk schema before applying new logic.
+    /// Get the fee terms fixed for an escrow at creation, if its buyer
+    /// deposited part of the fee.
+    pub fn get_escrow_fee_terms(env: Env, order_id: u32) -> Option<EscrowFeeTerms> {
         env.storage()
             .persistent()
-            .set(&DataKey::StorageLayoutVersion, &CURRENT_STORAGE_LAYOUT_VERSION);
-        Self::extend_persistent(&env, &DataKey::StorageLayoutVersion);
+            .get(&FeeDataKey::EscrowTerms(order_id))
+    }
 
-        // Set the onboarding contract address to enable reputation tracking (optional)
-        if let Some(ref addr) = onboarding_contract {
+    /// Apply the token's fee payer model to a new escrow for `price`.
+    /// Records the escrow's fee terms and returns the fee the buyer deposits
+    /// on top of the price.
+    fn apply_fee_payer_terms(
+        env: &Env,
+        order_id: u32,
+        seller: &Address,
+        token: &Address,
+        price: i128,
+    ) -> i128 {
+        let payer = Self::get_token_fee_payer(env.clone(), token.clone());
+        if payer == FeePayer::Seller {
+            return 0;
+        }
+        let fee_rate = Self::effective_fee_rate(env, seller, token);
+        let full_fee = Self::scheduled_fee(env, price, &fee_rate);
+        let buyer_fee = if payer == FeePayer::Split {
+            full_fee / 2
+        } else {
+            full_fee
+        };
+        if buyer_fee > 0 {
+            let key = FeeDataKey::EscrowTerms(order_id);
             env.storage()
                 .persistent()
-                .set(&DataKey::OnboardingContractAddress, addr);
-            Self::extend_persistent(&env, &DataKey::OnboardingContractAddress);
+                .set(&key, &EscrowFeeTerms { payer, buyer_fee });
+            Self::extend_persistent(env, &key);
         }
+        buyer_fee
+    }
 
-        Self::emit_config_updated(
-            &env,
-            "platform_fee_bps",
-            ConfigValue::String(String::from_str(&env, "unset")),
-            ConfigValue::U32(platform_fee_bps),
-        );
-        Self::emit_config_updated(
-            &env,
-            "platform_wallet",
-            ConfigValue::String(String::from_str(&env, "unset")),
-            ConfigValue::Address(platform_wallet),
-        );
-        if let Some(addr) = onboarding_contract {
-            Self::emit_config_updated(
-                &env,
-                "onboarding_contract",
-                ConfigValue::String(String::from_str(&env, "unset")),
-                ConfigValue::Address(addr),
-            );
+    /// `compute_fee_allocation` for an escrow, honouring fee terms fixed at
+    /// creation. A release charges the buyer's deposited fee plus any seller
+    /// share; every other path settles the price under `kind` and returns the
+    /// buyer's deposited fee to the buyer.
+    fn compute_escrow_fee_allocation(
+        env: &Env,
+        order_id: u32,
+        escrow_amount: i128,
+        fee_rate: &FeeRate,
+        kind: SettlementKind,
+    ) -> FeeAllocation {
+        let terms: Option<EscrowFeeTerms> = env
+            .storage()
+            .persistent()
+            .get(&FeeDataKey::EscrowTerms(order_id));
+        let terms = match terms {
+            Some(terms) => terms,
+            None => return Self::compute_fee_allocation(env, escrow_amount, fee_rate, kind),
+        };
+
+        let price = escrow_amount - terms.buyer_fee;
+        match kind {
+            SettlementKind::ReleaseFunds => Self::compute_fee_allocation(
+                env,
+                escrow_amount,
+                fee_rate,
+                SettlementKind::ReleaseWithBuyerFee(
+                    terms.buyer_fee,
+                    terms.payer == FeePayer::Split,
+                ),
+            ),
+            _ => {
+                let kind = match kind {
+                    SettlementKind::PartialRefund(refund_gross, _) => {
+                        let refund_gross = refund_gross.min(price);
+                        SettlementKind::PartialRefund(refund_gross, price - refund_gross)
+                    }
+                    other => other,
+                };
+                let mut allocation = Self::compute_fee_allocation(env, price, fee_rate, kind);
+                allocation.buyer_amount += terms.buyer_fee;
+                allocation
+            }
         }
     }
 
-    /// Propose a new administrator for the platform (admin only).
-    /// Starts the two-step transfer process (#95).
-    /// Both the current admin and the incoming admin must co-sign, proving the
-    /// new address is a live, registered ledger node capable of authorizing
-    /// transactions (#419).
-    pub fn update_admin(env: Env, new_admin: Address) {
-        let mut config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// Returns every token that has ever received platform fees (#239).
+    /// Reads the legacy `FeeTokenIndex` Vec; new callers should pair this
+    /// enumeration with `get_fee_token_config` for richer per-token data.
+    pub fn get_fee_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FeeTokenIndex)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
 
-        // Validate: not the contract address itself (#240)
-        if let Err(_) = Self::validate_admin_address(&env, &new_admin) {
-            env.panic_with_error(Error::InvalidAdminAddress);
+    /// Update mutable fields of a `FeeTokenInfo` slot (admin only).
+    ///
+    /// Kept for existing clients; role holders call `set_fee_token_config_as`.
+    pub fn set_fee_token_config(
+        env: Env,
+        token: Address,
+        active: bool,
+        custom_fee_bps: Option<u32>,
+    ) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::set_fee_token_config_as(env, admin, token, active, custom_fee_bps)
+    }
+
+    /// Update mutable fields of a `FeeTokenInfo` slot (`Role::TokenManager`, #239).
+    ///
+    /// `active` and `custom_fee_bps` are admin-controlled. `accumulated` is
+    /// IGNORED if passed in â€” the running total is owned by the contract and
+    /// only `record_total_fees` may move it. This split prevents an admin
+    /// from rewriting historical fee accounting via the config setter.
+    ///
+    /// `custom_fee_bps`, when set, must satisfy `<= MAX_PLATFORM_FEE_BPS`
+    /// and replaces the platform fee for this token unless the seller has an
+    /// artisan fee tier. Changing it is a fee change, rejected while a fee
+    /// change notice period is set.
+    pub fn set_fee_token_config_as(
+        env: Env,
+        caller: Address,
+        token: Address,
+        active: bool,
+        custom_fee_bps: Option<u32>,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::TokenManager, &caller);
+
+        if let Some(bps) = custom_fee_bps {
+            if bps > MAX_PLATFORM_FEE_BPS {
+                return Err(Error::InvalidFee);
+            }
         }
 
-        // Require the incoming admin to co-sign, proving it is a fully
-        // registered ledger node that controls its own private key (#419).
-        new_admin.require_auth();
+        let cfg_key = DataKey::FeeTokenConfig(token.clone());
+        let existing: FeeTokenInfo =
+            Self::read_fee_token_info(&env, &token).unwrap_or(FeeTokenInfo {
+                active: true,
+                custom_fee_bps: None,
+                accumulated: 0,
+                fee_payer: FeePayer::Seller,
+                custom_fee_payer: false,
+            });
 
-        let previous_admin = config.admin.clone();
-        config.pending_admin = Some(new_admin.clone());
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
+        if custom_fee_bps != existing.custom_fee_bps {
+            Self::check_no_fee_notice(&env)?;
+        }
 
-        // Emit audit event for admin change proposal
-        Self::emit_admin_changed(&env, previous_admin, new_admin, "admin_proposed");
+        let info = FeeTokenInfo {
+            active,
+            custom_fee_bps,
+            accumulated: existing.accumulated,
+            fee_payer: existing.fee_payer,
+            custom_fee_payer: existing.custom_fee_payer,
+        };
+        env.storage().persistent().set(&cfg_key, &info);
+        Self::extend_persistent(&env, &cfg_key);
+        Ok(())
     }
 
-    /// Claim the administrative role (pending admin only).
-    /// Completes the two-step transfer process (#95).
-    /// Enhanced with validation, audit logging and fallback setup (#240)
-    pub fn claim_admin(env: Env) {
-        let mut config = Self::get_platform_config_internal(&env);
-        let pending = config.pending_admin.as_ref().expect("");
-        pending.require_auth();
+    /// Backfill `FeeTokenConfig(token)` slots (admin only).
+    ///
+    /// Kept for existing clients; role holders call `migrate_fee_token_configs_as`.
+    pub fn migrate_fee_token_configs(env: Env) -> Result<u32, Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::migrate_fee_token_configs_as(env, admin)
+    }
 
-        // Validate the pending admin address before accepting the transfer
-        if let Err(_) = Self::validate_admin_address(&env, pending) {
-            env.panic_with_error(Error::InvalidAdminAddress);
+    /// Backfill `FeeTokenConfig(token)` slots for every token currently
+    /// present in the legacy `FeeTokenIndex` Vec (`Role::Migrator`, #239).
+    ///
+    /// Idempotent â€” already-migrated tokens are skipped, so it is safe to
+    /// call from a deploy script or an automated migration job. Returns the
+    /// number of new config slots written so callers can verify progress.
+    /// Pairs with `set_fee_token_config` for downstream tuning.
+    pub fn migrate_fee_token_configs_as(env: Env, caller: Address) -> Result<u32, Error> {
+        Self::require_role(&env, Role::Migrator, &caller);
+
+        let tokens: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FeeTokenIndex)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let scanned_tokens = tokens.len();
+        let mut migrated: u32 = 0;
+        let mut skipped_existing: u32 = 0;
+        for index in 0..tokens.len() {
+            if let Some(token) = tokens.get(index) {
+                let cfg_key = DataKey::FeeTokenConfig(token.clone());
+                if !env.storage().persistent().has(&cfg_key) {
+                    let info = FeeTokenInfo {
+                        active: true,
+                        custom_fee_bps: None,
+                        accumulated: env
+                            .storage()
+                            .persistent()
+                            .get(&DataKey::TotalFees(token))
+                            .unwrap_or(0i128),
+                        fee_payer: FeePayer::Seller,
+                        custom_fee_payer: false,
+                    };
+                    env.storage().persistent().set(&cfg_key, &info);
+                    Self::extend_persistent(&env, &cfg_key);
+                    migrated += 1;
+                } else {
+                    Self::extend_persistent(&env, &cfg_key);
+                    skipped_existing += 1;
+                }
+            }
         }
 
-        let previous_admin = config.admin.clone();
-        let new_admin = pending.clone();
-        config.admin = new_admin.clone();
-        config.pending_admin = None;
-
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
+        env.events().publish(
+            (Symbol::new(&env, "fee_cfg_migrated"),),
+            FeeTokenConfigsMigratedEvent {
+                scanned_tokens,
+                migrated_configs: migrated,
+                skipped_existing,
+            },
+        );
 
-        // Emit audit event for the completed two-step admin transfer (#631)
-        Self::emit_admin_changed(&env, previous_admin, new_admin, "admin_claimed");
+        Ok(migrated)
     }
 
-    /// Cancel an in-progress two-step admin transfer (current admin only).
-    pub fn cancel_admin_transfer(env: Env) -> Result<(), Error> {
-        let mut config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
-
-        if config.pending_admin.is_none() {
-            return Err(Error::NoPendingAdmin);
+    fn record_total_fees(env: &Env, token: &Address, fee_amount: i128) {
+        if fee_amount <= 0 {
+            return;
         }
 
-        config.pending_admin = None;
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
-        Ok(())
-    }
-
-    // ----- Admin Action Proposal (Multi-Sig + Timelock) -----
-
-    fn get_admin_action_signers(env: &Env) -> Vec<Address> {
+        let key = DataKey::TotalFees(token.clone());
+        let current_total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
         env.storage()
             .persistent()
-            .get(&AdminActionDataKey::AdminActionSigners)
-            .unwrap_or_else(|| {
-                let mut signers = Vec::new(env);
-                if let Ok(admin) = Self::get_admin(env) {
-                    signers.push_back(admin);
-                }
-                signers
-            })
+            .set(&key, &(current_total + fee_amount));
+        Self::extend_persistent(env, &key);
+        Self::add_fee_token_to_index(env, token);
+        // Mirror the running total into the per-token fee config so future
+        // multi-token logic has a single source of truth (#239).
+        Self::bump_fee_token_accumulator(env, token, fee_amount);
+        Self::record_epoch_stats(env, token, |stats| stats.fees += fee_amount);
     }
 
-    fn get_admin_action_threshold(env: &Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&AdminActionDataKey::AdminActionThreshold)
-            .unwrap_or(1u32)
-    }
+    // ── Epoch Accounting ────────────────────────────────────────────────
 
-    fn get_admin_action_timelock_delay(env: &Env) -> u64 {
+    fn epoch_length(env: &Env) -> u64 {
         env.storage()
             .instance()
-            .get(&AdminActionDataKey::AdminActionTimelockDelay)
-            .unwrap_or(DEFAULT_ADMIN_ACTION_TIMELOCK_DELAY)
-    }
-
-    fn get_next_admin_action_id(env: &Env) -> u64 {
-        env.storage()
-            .persistent()
-            .get(&AdminActionDataKey::NextAdminActionId)
-            .unwrap_or(1u64)
+            .get(&AccountingDataKey::EpochLength)
+            .unwrap_or(DEFAULT_EPOCH_LENGTH)
     }
 
-    fn get_admin_action(env: &Env, action_id: u64) -> Option<AdminActionProposal> {
-        env.storage().persistent().get::<AdminActionDataKey, AdminActionProposal>(
-            &AdminActionDataKey::AdminAction(action_id),
-        )
+    fn empty_epoch_stats(epoch: u64, epoch_length: u64) -> EpochStats {
+        EpochStats {
+            epoch,
+            start: epoch.saturating_mul(epoch_length),
+            fees: 0,
+            released_volume: 0,
+            refunded_volume: 0,
+            disputes: 0,
+        }
     }
 
-    fn persist_admin_action(env: &Env, action: &AdminActionProposal) {
-        env.storage()
+    /// Apply `update` to the current epoch's bucket for `token`.
+    fn record_epoch_stats(env: &Env, token: &Address, update: impl FnOnce(&mut EpochStats)) {
+        let epoch_length = Self::epoch_length(env);
+        let epoch = env.ledger().timestamp() / epoch_length;
+        let key = AccountingDataKey::Bucket(token.clone(), epoch);
+        let mut stats = env
+            .storage()
             .persistent()
-            .set(&AdminActionDataKey::AdminAction(action.id), action);
-        Self::extend_persistent(env, &AdminActionDataKey::AdminAction(action.id));
+            .get(&key)
+            .unwrap_or_else(|| Self::empty_epoch_stats(epoch, epoch_length));
+        update(&mut stats);
+        env.storage().persistent().set(&key, &stats);
+        Self::extend_persistent(env, &key);
     }
 
-    /// Configure the signer set for pending critical admin actions.
-    pub fn set_admin_action_signers(env: Env, signers: Vec<Address>) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
-        if signers.is_empty() {
-            env.storage().persistent().remove(&AdminActionDataKey::AdminActionSigners);
-        } else {
-            env.storage()
-                .persistent()
-                .set(&AdminActionDataKey::AdminActionSigners, &signers);
-            Self::extend_persistent(&env, &AdminActionDataKey::AdminActionSigners);
-        }
-        Ok(())
-    }
+    /// Set the reporting epoch length in seconds (`Role::Operator`).
+    ///
+    /// Buckets are indexed by `timestamp / epoch_length`, so buckets written
+    /// under a previous length keep their old index. Change the length only
+    /// between reporting periods.
+    pub fn set_epoch_length(env: Env, caller: Address, seconds: u64) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
 
-    /// Configure the approval threshold for pending critical admin actions.
-    pub fn set_admin_action_threshold(env: Env, threshold: u32) -> Result<(), Error> {
-        if threshold == 0 {
-            return Err(Error::InvalidFee);
+        if seconds == 0 || seconds > u32::MAX as u64 {
+            return Err(Error::InvalidEscrowState);
         }
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
-        env.storage().instance().set(&AdminActionDataKey::AdminActionThreshold, &threshold);
-        Ok(())
-    }
-
-    /// Configure the timelock delay applied to pending critical admin actions.
-    pub fn set_admin_action_timelock_delay(env: Env, delay_seconds: u64) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+        let old_value = Self::epoch_length(&env);
         env.storage()
             .instance()
-            .set(&AdminActionDataKey::AdminActionTimelockDelay, &delay_seconds);
+            .set(&AccountingDataKey::EpochLength, &seconds);
+        Self::emit_config_updated(
+            &env,
+            "epoch_length",
+            ConfigValue::U32(old_value as u32),
+            ConfigValue::U32(seconds as u32),
+        );
         Ok(())
     }
 
-    /// Create a new pending admin action that requires multi-sig approvals.
-    pub fn propose_admin_action(
-        env: Env,
-        proposer: Address,
-        action: AdminActionKind,
-    ) -> Result<AdminActionProposal, Error> {
-        proposer.require_auth();
-
-        let signers = Self::get_admin_action_signers(&env);
-        if !signers.iter().any(|signer| signer == proposer) {
-            return Err(Error::NotAnAdminActionSigner);
-        }
-
-        let threshold = Self::get_admin_action_threshold(&env);
-        let delay = Self::get_admin_action_timelock_delay(&env);
-        let created_at = env.ledger().timestamp();
-        let next_id = Self::get_next_admin_action_id(&env);
-
-        let mut approvals = Vec::new(&env);
-        approvals.push_back(proposer.clone());
-
-        let proposal = AdminActionProposal {
-            id: next_id,
-            kind: action,
-            proposer: proposer.clone(),
-            approvals,
-            threshold,
-            signers: signers.clone(),
-            created_at,
-            ready_at: created_at + delay,
-            executed: false,
-            cancelled: false,
-        };
-
-        env.storage()
-            .persistent()
-            .set(&AdminActionDataKey::NextAdminActionId, &(next_id + 1));
-        Self::extend_persistent(&env, &AdminActionDataKey::NextAdminActionId);
-        Self::persist_admin_action(&env, &proposal);
+    /// Get the reporting epoch length in seconds.
+    pub fn get_epoch_length(env: Env) -> u64 {
+        Self::epoch_length(&env)
+    }
 
-        Ok(proposal)
+    /// Get the current epoch index.
+    pub fn get_current_epoch(env: Env) -> u64 {
+        env.ledger().timestamp() / Self::epoch_length(&env)
     }
 
-    /// Approve an existing pending admin action.
-    pub fn approve_admin_action(
+    /// Get per-epoch stats for `token` for epochs `start_epoch..=end_epoch`,
+    /// at most `limit` (capped at `MAX_PAGE_SIZE`) entries starting from
+    /// `start_epoch`. Epochs without activity are returned zeroed.
+    pub fn get_epoch_stats(
         env: Env,
-        action_id: u64,
-        signer: Address,
-    ) -> Result<AdminActionProposal, Error> {
-        signer.require_auth();
-
-        let mut action = Self::get_admin_action(&env, action_id).ok_or(Error::AdminActionTerminal)?;
-        if action.cancelled {
-            return Err(Error::AdminActionTerminal);
-        }
-        if action.executed {
-            return Err(Error::AdminActionTerminal);
-        }
-        if !action.signers.iter().any(|existing| existing == signer) {
-            return Err(Error::NotAnAdminActionSigner);
-        }
-        if action.approvals.iter().any(|existing| existing == signer) {
-            return Err(Error::AlreadyApproved);
+        token: Address,
+        start_epoch: u64,
+        end_epoch: u64,
+        limit: u32,
+    ) -> Vec<EpochStats> {
+        let mut stats = Vec::new(&env);
+        if start_epoch > end_epoch || limit == 0 {
+            return stats;
         }
 
-        action.approvals.push_back(signer);
-        Self::persist_admin_action(&env, &action);
-        Ok(action)
+        let epoch_length = Self::epoch_length(&env);
+        let last = end_epoch.min(start_epoch.saturating_add(limit.min(MAX_PAGE_SIZE) as u64 - 1));
+        for epoch in start_epoch..=last {
+            let key = AccountingDataKey::Bucket(token.clone(), epoch);
+            stats.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&key)
+                    .unwrap_or_else(|| Self::empty_epoch_stats(epoch, epoch_length)),
+            );
+        }
+        stats
     }
 
-    /// Cancel a pending admin action.
-    pub fn cancel_admin_action(env: Env, action_id: u64) -> Result<AdminActionProposal, Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
-
-        let mut action = Self::get_admin_action(&env, action_id).ok_or(Error::AdminActionTerminal)?;
-        if action.cancelled {
-            return Err(Error::AdminActionTerminal);
-        }
-        if action.executed {
-            return Err(Error::AdminActionTerminal);
+    fn append_fund_audit_record(
+        env: &Env,
+        actor: &Address,
+        amount: i128,
+        reason: Symbol,
+        balance_impact: i128,
+    ) {
+        if amount <= 0 {
+            return;
         }
 
-        action.cancelled = true;
-        Self::persist_admin_action(&env, &action);
-        Ok(action)
+        let count_key = DataKey::FundAuditCount(actor.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let entry_key = DataKey::FundAuditIndexed(actor.clone(), count);
+        let entry = FundMovementAuditEntry {
+            actor: actor.clone(),
+            amount,
+            reason,
+            timestamp: env.ledger().timestamp(),
+            balance_impact,
+        };
+
+        env.storage().persistent().set(&entry_key, &entry);
+        Self::extend_persistent(env, &entry_key);
+        env.storage().persistent().set(&count_key, &(count + 1));
+        Self::extend_persistent(env, &count_key);
     }
 
-    /// Execute a pending admin action once its approvals and timelock have been satisfied.
-    pub fn execute_admin_action(env: Env, action_id: u64) -> Result<(), Error> {
-        let action = Self::get_admin_action(&env, action_id).ok_or(Error::AdminActionTerminal)?;
-        if action.cancelled {
-            return Err(Error::AdminActionTerminal);
-        }
-        if action.executed {
-            return Err(Error::AdminActionTerminal);
-        }
-        if action.approvals.len() as u32 < action.threshold {
-            return Err(Error::AdminActionNeedsApprovals);
+    fn transfer_tokens_and_record_audit(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+        actor: &Address,
+        reason: Symbol,
+        balance_impact: i128,
+    ) {
+        if amount <= 0 {
+            return;
         }
-        let now = env.ledger().timestamp();
-        if now < action.ready_at {
-            return Err(Error::AdminActionTimelockActive);
+
+        // Every transfer must be reached through a guarded public operation.
+        // This assertion makes omissions fail closed as new flows are added.
+        if !env.storage().temporary().has(&DataKey::ReentryGuard) {
+            env.panic_with_error(crate::Error::ReentryDetected);
         }
 
-        let mut persisted = action.clone();
-        Self::apply_admin_action(&env, &persisted)?;
-        persisted.executed = true;
-        Self::persist_admin_action(&env, &persisted);
-        Ok(())
+        // Commit effects before interaction. A failed token call rolls back the
+        // complete Soroban invocation, including this audit record.
+        Self::append_fund_audit_record(env, actor, amount, reason, balance_impact);
+        let token_client = token::Client::new(env, token);
+        token_client.transfer(from, to, &amount);
     }
 
-    /// Return all pending admin actions that have not executed or been cancelled.
-    pub fn get_pending_admin_actions(env: Env) -> Vec<AdminActionProposal> {
-        let mut actions = Vec::new(&env);
-        let next_id = Self::get_next_admin_action_id(&env);
-        for action_id in 1..next_id {
-            if let Some(action) = Self::get_admin_action(&env, action_id) {
-                if !action.executed && !action.cancelled {
-                    actions.push_back(action);
-                }
+    /// Pay `amount` of `token` out of the contract to a settlement recipient.
+    ///
+    /// Push is the default: the transfer is attempted directly. If the
+    /// recipient opted into pull payouts, or the transfer fails (e.g. a
+    /// revoked or deauthorized account), the amount is credited to the claim
+    /// ledger instead so the settlement still completes. The recipient then
+    /// withdraws it with `claim`.
+    fn payout_tokens_and_record_audit(
+        env: &Env,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+        reason: Symbol,
+    ) {
+        if amount <= 0 {
+            return;
+        }
+
+        if !env.storage().temporary().has(&DataKey::ReentryGuard) {
+            env.panic_with_error(crate::Error::ReentryDetected);
+        }
+
+        if !Self::is_pull_payouts_enabled(env.clone(), recipient.clone()) {
+            // The audit record is written only once the push succeeded so a
+            // failed attempt is never logged as a completed transfer. The
+            // reentry guard is held for the duration of the call.
+            let token_client = token::Client::new(env, token);
+            if let Ok(Ok(())) =
+                token_client.try_transfer(&env.current_contract_address(), recipient, &amount)
+            {
+                Self::append_fund_audit_record(env, recipient, amount, reason, amount);
+                return;
             }
         }
-        actions
+
+        Self::credit_claimable(env, token, recipient, amount, reason);
     }
 
-    fn apply_admin_action(env: &Env, action: &AdminActionProposal) -> Result<(), Error> {
-        match &action.kind {
-            AdminActionKind::PausePlatform(paused) => Self::set_paused_internal(env, *paused),
-            AdminActionKind::SetPlatformFee(new_fee_bps) => {
-                let mut config = Self::get_platform_config_internal(env);
-                if *new_fee_bps > MAX_PLATFORM_FEE_BPS {
-                    return Err(Error::InvalidFee);
-                }
-                let old_fee = config.platform_fee_bps;
-                config.platform_fee_bps = *new_fee_bps;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
-                    env,
-                    "platform_fee_bps",
-                    ConfigValue::U32(old_fee),
-                    ConfigValue::U32(*new_fee_bps),
-                );
-                Ok(())
-            }
-            AdminActionKind::SetPlatformWallet(new_wallet) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let old_wallet = config.platform_wallet.clone();
-                config.platform_wallet = new_wallet.clone();
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
-                    env,
-                    "platform_wallet",
-                    ConfigValue::Address(old_wallet),
-                    ConfigValue::Address(new_wallet.clone()),
-                );
-                Ok(())
-            }
-            AdminActionKind::SetWasmUpgradeCooldown(cooldown_seconds) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let old_value = config.wasm_upgrade_cooldown;
-                config.wasm_upgrade_cooldown = *cooldown_seconds;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
+    /// Pay out `allocation.platform_fee`.
+    ///
+    /// `allocation.referral_fee` goes to `referrer` and is accrued in the
+    /// referral counters, `allocation.keeper_fee` goes to `keeper`; the
+    /// remainder goes to the platform wallet and is the only part counted in
+    /// `TotalFees`.
+    fn transfer_platform_fee(
+        env: &Env,
+        token: &Address,
+        platform_wallet: &Address,
+        allocation: &FeeAllocation,
+        referral: Option<(u32, &Address)>,
+        keeper: Option<&Address>,
+    ) {
+        if allocation.platform_fee <= 0 {
+            return;
+        }
+
+        let mut platform_amount = allocation.platform_fee;
+        if let Some(keeper) = keeper {
+            if allocation.keeper_fee > 0 {
+                platform_amount -= allocation.keeper_fee;
+                Self::payout_tokens_and_record_audit(
                     env,
-                    "wasm_upgrade_cooldown",
-                    ConfigValue::U32(old_value),
-                    ConfigValue::U32(*cooldown_seconds),
+                    token,
+                    keeper,
+                    allocation.keeper_fee,
+                    Symbol::new(env, "keeper_bounty"),
                 );
-                Ok(())
-            }
-            AdminActionKind::SetMinStakeRequired(min_stake) => {
-                let mut config = Self::get_platform_config_internal(env);
-                config.min_stake_required = *min_stake;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Ok(())
-            }
-            AdminActionKind::SweepUnallocatedFunds(token, destination) => {
-                let balance = token::Client::new(env, token).balance(&env.current_contract_address());
-                let locked: i128 = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::TotalLocked(token.clone()))
-                    .unwrap_or(0);
-                let staked: i128 = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::TotalStaked(token.clone()))
-                    .unwrap_or(0);
-                let unallocated = balance - (locked + staked);
-                if unallocated > 0 {
-                    Self::transfer_tokens_and_record_audit(
-                        env,
-                        token,
-                        &env.current_contract_address(),
-                        destination,
-                        unallocated,
-                        destination,
-                        Symbol::new(env, "sweep_unallocated"),
-                        unallocated,
-                    );
-                }
-                Ok(())
             }
-            AdminActionKind::ExecuteUpgrade(expected_wasm_hash) => {
-                Self::execute_upgrade(env, expected_wasm_hash)
-            }
-            AdminActionKind::SetMaxDisputeDuration(duration) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let old_value = config.max_dispute_duration;
-                config.max_dispute_duration = *duration;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
+        }
+        if let Some((order_id, referrer)) = referral {
+            if allocation.referral_fee > 0 {
+                platform_amount -= allocation.referral_fee;
+                Self::record_referral_fees(env, referrer, token, allocation.referral_fee);
+                Self::payout_tokens_and_record_audit(
                     env,
-                    "max_dispute_duration",
-                    ConfigValue::U32(old_value),
-                    ConfigValue::U32(*duration),
+                    token,
+                    referrer,
+                    allocation.referral_fee,
+                    Symbol::new(env, "referral_fee"),
                 );
-                Ok(())
-            }
-            AdminActionKind::SetStakeCooldown(cooldown) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let old_value = config.stake_cooldown;
-                config.stake_cooldown = *cooldown;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
-                    env,
-                    "stake_cooldown",
-                    ConfigValue::U32(old_value),
-                    ConfigValue::U32(*cooldown),
+                env.events().publish(
+                    (Symbol::new(env, "referral_paid"), referrer.clone()),
+                    ReferralPaidEvent {
+                        order_id,
+                        referrer: referrer.clone(),
+                        token: token.clone(),
+                        amount: allocation.referral_fee,
+                    },
                 );
-                Ok(())
             }
-            AdminActionKind::SetArtisanFeeTier(artisan, fee_bps) => {
-                let mut config = Self::get_platform_config_internal(env);
-                if *fee_bps > MAX_PLATFORM_FEE_BPS {
+        }
+
+        if Self::get_treasury_recipients(env.clone()).is_empty() {
+            Self::transfer_tokens_and_record_audit(
+                env,
+                token,
+                &env.current_contract_address(),
+                platform_wallet,
+                platform_amount,
+                platform_wallet,
+                Symbol::new(env, "platform_fee"),
+                platform_amount,
+            );
+        } else {
+            Self::accrue_treasury(env, token, platform_amount);
+        }
+        Self::record_total_fees(env, token, platform_amount);
+    }
+
+    // ── Treasury ────────────────────────────────────────────────────────
+
+    /// Keep `amount` of platform fees in the contract for later distribution.
+    /// The accrual is recorded in the contract's own fund audit history.
+    fn accrue_treasury(env: &Env, token: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let key = TreasuryDataKey::Accrued(token.clone());
+        let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(accrued + amount));
+        Self::extend_persistent(env, &key);
+        Self::append_fund_audit_record(
+            env,
+            &env.current_contract_address(),
+            amount,
+            Symbol::new(env, "treasury_accrued"),
+            amount,
+        );
+    }
+
+    /// Configure the treasury split (`Role::FeeManager`).
+    ///
+    /// While recipients are configured, platform fees accrue in the contract
+    /// per token instead of being sent to `platform_wallet`, and are paid out
+    /// by `distribute_treasury`. Shares must be non-zero and sum to 10000
+    /// bps. An empty list turns the treasury off.
+    pub fn set_treasury_recipients(
+        env: Env,
+        caller: Address,
+        recipients: Vec<TreasuryRecipient>,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+
+        if recipients.len() > MAX_TREASURY_RECIPIENTS {
+            return Err(Error::BatchLimitExceeded);
+        }
+        if !recipients.is_empty() {
+            let mut total_bps = 0u32;
+            for recipient in recipients.iter() {
+                if recipient.share_bps == 0 {
                     return Err(Error::InvalidFee);
                 }
-                config.admin.require_auth();
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::ArtisanFeeTier(artisan.clone()), fee_bps);
-                Self::extend_persistent(env, &DataKey::ArtisanFeeTier(artisan.clone()));
-                Self::emit_artisan_fee_tier_updated(env, artisan.clone(), *fee_bps);
-                Ok(())
-            }
-            AdminActionKind::SetModerator(moderator) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let previous = config
-                    .moderator
-                    .clone()
-                    .map(ConfigValue::Address)
-                    .unwrap_or_else(|| ConfigValue::String(String::from_str(env, "unset")));
-                config.moderator = Some(moderator.clone());
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
-                    env,
-                    "moderator",
-                    previous,
-                    ConfigValue::Address(moderator.clone()),
-                );
-                Ok(())
-            }
-            AdminActionKind::SetMinEscrowAmount(token, min_amount) => {
-                let admin = Self::get_admin(env)?;
-                admin.require_auth();
-                let key = DataKey::MinEscrowAmount(token.clone());
-                let old_amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-                env.storage().persistent().set(&key, min_amount);
-                Self::extend_persistent(env, &key);
-                Self::emit_config_updated(
-                    env,
-                    "min_escrow_amount",
-                    ConfigValue::I128(old_amount),
-                    ConfigValue::I128(*min_amount),
-                );
-                Ok(())
-            }
-            AdminActionKind::SetMaxReleaseWindow(window) => {
-                let old_value: u32 = env
-                    .storage()
-                    .persistent()
-                    .get(&DataKey::MaxReleaseWindow)
-                    .unwrap_or(MAX_TOTAL_RELEASE_WINDOW);
-                env.storage()
-                    .persistent()
-                    .set(&DataKey::MaxReleaseWindow, window);
-                Self::extend_persistent(env, &DataKey::MaxReleaseWindow);
-                Self::emit_config_updated(
-                    env,
-                    "max_release_window",
-                    ConfigValue::U32(old_value),
-                    ConfigValue::U32(*window),
-                );
-                Ok(())
-            }
-            AdminActionKind::SetMinReleaseWindow(window) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let old_value = config.min_release_window;
-                config.min_release_window = *window;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
-                    env,
-                    "min_release_window",
-                    ConfigValue::U32(old_value),
-                    ConfigValue::U32(*window),
-                );
-                Ok(())
+                total_bps = total_bps.saturating_add(recipient.share_bps);
             }
-            AdminActionKind::SetOnboardingContract(address) => {
-                let admin = Self::get_admin(env)?;
-                admin.require_auth();
-                env.storage()
-                    .instance()
-                    .set(&DataKey::OnboardingContractAddress, address);
-                Self::extend_persistent(env, &DataKey::OnboardingContractAddress);
-                Ok(())
+            if total_bps != 10_000 {
+                return Err(Error::InvalidFee);
             }
-            AdminActionKind::SetExpiredDisputePolicy(policy) => {
-                let mut config = Self::get_platform_config_internal(env);
-                let old_policy = config.expired_dispute_fee_policy;
-                config.expired_dispute_fee_policy = *policy;
-                env.storage().instance().set(&DataKey::PlatformConfig, &config);
-                Self::emit_config_updated(
-                    env,
-                    "expired_dispute_fee_policy",
-                    ConfigValue::U32(old_policy as u32),
-                    ConfigValue::U32(*policy as u32),
-                );
-                Ok(())
+        }
+
+        let old_len = Self::get_treasury_recipients(env.clone()).len();
+        let key = TreasuryDataKey::Recipients;
+        if recipients.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &recipients);
+            Self::extend_persistent(&env, &key);
+        }
+        Self::emit_config_updated(
+            &env,
+            "treasury_recipients",
+            ConfigValue::U32(old_len),
+            ConfigValue::U32(recipients.len()),
+        );
+        Ok(())
+    }
+
+    /// Get the configured treasury split.
+    pub fn get_treasury_recipients(env: Env) -> Vec<TreasuryRecipient> {
+        let key = TreasuryDataKey::Recipients;
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Vec<TreasuryRecipient>>(&key)
+        {
+            Some(recipients) => {
+                Self::extend_persistent(&env, &key);
+                recipients
             }
+            None => Vec::new(&env),
         }
     }
 
-    /// Migrate a user's escrow list from legacy vector storage to indexed storage.
-    /// This is a one-time migration function that should be called for users who have
-    /// escrows stored in the old format. Admin only.
-    ///
-    /// # Arguments
-    /// * `user` - Address of the user to migrate
-    /// * `is_buyer` - true to migrate buyer escrows, false to migrate seller escrows
-    ///
-    /// # Returns
-    /// Number of escrows migrated
-    pub fn migrate_user_escrows(env: Env, user: Address, is_buyer: bool) -> Result<u32, Error> {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// Get platform fees of `token` accrued and not yet distributed.
+    pub fn get_treasury_balance(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&TreasuryDataKey::Accrued(token))
+            .unwrap_or(0)
+    }
 
-        let legacy_key = if is_buyer {
-            DataKey::BuyerEscrows(user.clone())
-        } else {
-            DataKey::SellerEscrows(user.clone())
-        };
+    /// Pay accrued platform fees of `token` to the treasury recipients by
+    /// their share. Rounding dust goes to the last recipient. If the
+    /// treasury has since been turned off, the balance goes to
+    /// `platform_wallet`. Anyone may call this. Returns the amount paid.
+    pub fn distribute_treasury(env: Env, token: Address) -> Result<i128, Error> {
+        let _guard = ReentryGuardScope::new(&env);
 
-        // Check if legacy data exists
-        if !env.storage().persistent().has(&legacy_key) {
+        let key = TreasuryDataKey::Accrued(token.clone());
+        let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if accrued <= 0 {
             return Ok(0);
         }
+        env.storage().persistent().remove(&key);
 
-        let legacy_escrows: soroban_sdk::Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&legacy_key)
-            .unwrap_or(soroban_sdk::Vec::new(&env));
+        let contract = env.current_contract_address();
+        Self::append_fund_audit_record(
+            &env,
+            &contract,
+            accrued,
+            Symbol::new(&env, "treasury_distributed"),
+            -accrued,
+        );
 
-        let count = legacy_escrows.len();
+        let mut recipients = Self::get_treasury_recipients(env.clone());
+        if recipients.is_empty() {
+            recipients.push_back(TreasuryRecipient {
+                recipient: Self::get_platform_config_internal(&env).platform_wallet,
+                share_bps: 10_000,
+            });
+        }
+        let reason = Symbol::new(&env, "treasury_distribution");
+        let mut remaining = accrued;
+        for (i, recipient) in recipients.iter().enumerate() {
+            let share = if i as u32 + 1 == recipients.len() {
+                remaining
+            } else {
+                accrued * recipient.share_bps as i128 / 10_000
+            };
+            remaining -= share;
+            Self::payout_tokens_and_record_audit(
+                &env,
+                &token,
+                &recipient.recipient,
+                share,
+                reason.clone(),
+            );
+        }
 
-        // Migrate to indexed storage
-        for i in 0..count {
-            if let Some(escrow_id) = legacy_escrows.get(i) {
-                let index_key = if is_buyer {
-                    DataKey::BuyerEscrowIndexed(user.clone(), i)
-                } else {
-                    DataKey::SellerEscrowIndexed(user.clone(), i)
-                };
-                env.storage().persistent().set(&index_key, &escrow_id);
-                Self::extend_persistent(&env, &index_key);
+        env.events().publish(
+            (Symbol::new(&env, "treasury_distributed"), token.clone()),
+            TreasuryDistributedEvent {
+                token,
+                amount: accrued,
+                recipients: recipients.len(),
+            },
+        );
+        Ok(accrued)
+    }
+
+    // ── Referral Rewards ────────────────────────────────────────────────
+
+    fn get_referral_share_bps_internal(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ReferralDataKey::ShareBps)
+            .unwrap_or(0)
+    }
+
+    /// Referrer that should receive a share of the fee for `order_id`.
+    ///
+    /// A referrer recorded on the escrow wins; otherwise the seller's
+    /// onboarding profile is consulted. Returns `None` without touching the
+    /// onboarding contract while the referral share is zero, so settlements
+    /// pay nothing extra when the program is switched off.
+    fn resolve_escrow_referrer(env: &Env, order_id: u32, seller: &Address) -> Option<Address> {
+        if Self::get_referral_share_bps_internal(env) == 0 {
+            return None;
+        }
+
+        let key = ReferralDataKey::EscrowReferrer(order_id);
+        if let Some(referrer) = env.storage().persistent().get::<_, Address>(&key) {
+            Self::extend_persistent(env, &key);
+            return Some(referrer);
+        }
+
+        let onboarding_address = Self::get_onboarding_address(env)?;
+        let method = Symbol::new(env, "get_referrer");
+        let args: Vec<Val> = (seller.clone(),).into_val(env);
+        match env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &onboarding_address,
+            &method,
+            args,
+        ) {
+            Ok(Ok(referrer)) => referrer,
+            _ => {
+                Self::emit_onboarding_call_failed(env, method, onboarding_address);
+                None
             }
         }
+    }
+
+    /// Carve the configured referral share out of `allocation.platform_fee`.
+    /// The three-way split is left untouched, so the balance invariant of
+    /// `compute_fee_allocation` still holds.
+    fn apply_referral_share(
+        env: &Env,
+        mut allocation: FeeAllocation,
+        referrer: &Option<Address>,
+    ) -> FeeAllocation {
+        if referrer.is_some() && allocation.platform_fee > 0 {
+            let share_bps = Self::get_referral_share_bps_internal(env);
+            allocation.referral_fee = Self::calculate_fee(env, allocation.platform_fee, share_bps);
+        }
+        allocation
+    }
 
-        // Set the count
-        let count_key = if is_buyer {
-            DataKey::BuyerEscrowCount(user.clone())
-        } else {
-            DataKey::SellerEscrowCount(user.clone())
-        };
-        env.storage().persistent().set(&count_key, &count);
-        Self::extend_persistent(&env, &count_key);
+    fn record_referral_fees(env: &Env, referrer: &Address, token: &Address, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
 
-        // Remove legacy storage to free up space
-        env.storage().persistent().remove(&legacy_key);
+        let total_key = ReferralDataKey::TotalReferralFees(token.clone());
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        env.storage().persistent().set(&total_key, &(total + amount));
+        Self::extend_persistent(env, &total_key);
 
-        env.storage().persistent().set(&ADMIN, &config.admin);
-        Self::extend_persistent(&env, &ADMIN);
+        let earnings_key = ReferralDataKey::ReferrerEarnings(referrer.clone(), token.clone());
+        let earned: i128 = env.storage().persistent().get(&earnings_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&earnings_key, &(earned + amount));
+        Self::extend_persistent(env, &earnings_key);
+    }
 
-        // Set the new admin as fallback for recovery purposes (#240)
-        if let Err(_) = Self::set_fallback_admin(&env, config.admin.clone()) {
-            env.panic_with_error(Error::InvalidAdminAddress);
+    /// Set the share of the platform fee paid to referrers (`Role::FeeManager`).
+    ///
+    /// # Arguments
+    /// * `caller` - Address holding `Role::FeeManager`
+    /// * `share_bps` - Share of the platform fee in basis points, at most
+    ///   `MAX_REFERRAL_SHARE_BPS` (20%). Zero disables referral payouts.
+    pub fn set_referral_share_bps(env: Env, caller: Address, share_bps: u32) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+
+        if share_bps > MAX_REFERRAL_SHARE_BPS {
+            return Err(Error::InvalidFee);
         }
 
-        Ok(count)
+        let old_value = Self::get_referral_share_bps_internal(&env);
+        env.storage()
+            .instance()
+            .set(&ReferralDataKey::ShareBps, &share_bps);
+        Self::emit_config_updated(
+            &env,
+            "referral_share_bps",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(share_bps),
+        );
+        Ok(())
     }
 
-    /// Recover admin access using fallback admin after time lock period (#240)
-    /// This provides a recovery mechanism if the primary admin is corrupted or inaccessible
-    /// Requires a 7-day time lock after recovery is initiated to prevent abuse
-    pub fn recover_admin_access(env: Env, recovered_admin: Address) -> Result<(), Error> {
-        // Check if fallback admin exists and is authorized
-        let fallback = match env
-            .storage()
+    /// Get the share of the platform fee paid to referrers, in basis points.
+    pub fn get_referral_share_bps(env: Env) -> u32 {
+        Self::get_referral_share_bps_internal(&env)
+    }
+
+    /// Get the referrer recorded on an escrow, if any.
+    pub fn get_escrow_referrer(env: Env, order_id: u32) -> Option<Address> {
+        env.storage()
             .persistent()
-            .get::<_, Address>(&DataKey::FallbackAdmin)
-        {
-            Some(fallback) => fallback,
-            None => return Err(Error::AdminRecoveryFailed),
-        };
+            .get(&ReferralDataKey::EscrowReferrer(order_id))
+    }
 
-        fallback.require_auth();
+    /// Get the total referral payouts a referrer has received in `token`.
+    pub fn get_referral_earnings(env: Env, referrer: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ReferralDataKey::ReferrerEarnings(referrer, token))
+            .unwrap_or(0)
+    }
 
-        // Validate the recovery address
-        if Self::validate_admin_address(&env, &recovered_admin).is_err() {
-            return Err(Error::AdminRecoveryFailed);
+    /// Get the total referral payouts made in `token` across all referrers.
+    pub fn get_total_referral_fees(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ReferralDataKey::TotalReferralFees(token))
+            .unwrap_or(0)
+    }
+
+    // ── Claim Ledger ────────────────────────────────────────────────────
+
+    fn credit_claimable(
+        env: &Env,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+        reason: Symbol,
+    ) {
+        let key = ClaimDataKey::Claimable(recipient.clone(), token.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(balance + amount));
+        Self::extend_persistent(env, &key);
+
+        let total_key = ClaimDataKey::TotalClaimable(token.clone());
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        env.storage().persistent().set(&total_key, &(total + amount));
+        Self::extend_persistent(env, &total_key);
+
+        Self::append_fund_audit_record(env, recipient, amount, Symbol::new(env, "claim_credited"), 0);
+
+        env.events().publish(
+            (Symbol::new(env, "claim_credited"), recipient.clone()),
+            ClaimCreditedEvent {
+                recipient: recipient.clone(),
+                token: token.clone(),
+                amount,
+                reason,
+            },
+        );
+    }
+
+    /// Withdraw the caller's claimable balance for `token`.
+    ///
+    /// Claimable balances are credited by settlements when the recipient
+    /// opted into pull payouts or a direct transfer to them failed.
+    ///
+    /// # Arguments
+    /// * `recipient` - Owner of the claimable balance (must sign)
+    /// * `token` - Token to withdraw
+    /// * `destination` - Where to send the tokens; defaults to `recipient`.
+    ///   Lets a recipient whose own account is blocked withdraw elsewhere.
+    ///
+    /// # Returns
+    /// The amount withdrawn; `0` when nothing was claimable.
+    pub fn claim(
+        env: Env,
+        recipient: Address,
+        token: Address,
+        destination: Option<Address>,
+    ) -> Result<i128, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        recipient.require_auth();
+
+        let key = ClaimDataKey::Claimable(recipient.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        // Effects: clear the balance and the accounting bucket before paying out.
+        env.storage().persistent().remove(&key);
+        let total_key = ClaimDataKey::TotalClaimable(token.clone());
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        env.storage().persistent().set(&total_key, &(total - amount));
+        Self::extend_persistent(&env, &total_key);
+
+        let destination = destination.unwrap_or_else(|| recipient.clone());
+        Self::transfer_tokens_and_record_audit(
+            &env,
+            &token,
+            &env.current_contract_address(),
+            &destination,
+            amount,
+            &recipient,
+            Symbol::new(&env, "claim_withdrawn"),
+            amount,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "claimed"), recipient.clone()),
+            ClaimedEvent {
+                recipient,
+                destination,
+                token,
+                amount,
+            },
+        );
+
+        Ok(amount)
+    }
+
+    /// Opt in or out of pull payouts.
+    ///
+    /// When enabled, every settlement payout to `user` is credited to the
+    /// claim ledger instead of being transferred directly.
+    pub fn set_pull_payouts(env: Env, user: Address, enabled: bool) {
+        user.require_auth();
+
+        let key = ClaimDataKey::PullPayouts(user);
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Returns true if `user` opted into pull payouts.
+    pub fn is_pull_payouts_enabled(env: Env, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&ClaimDataKey::PullPayouts(user))
+            .unwrap_or(false)
+    }
+
+    /// Get the balance `recipient` can withdraw with `claim` for `token`.
+    pub fn get_claimable_balance(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ClaimDataKey::Claimable(recipient, token))
+            .unwrap_or(0)
+    }
+
+    /// Get the sum of all claimable balances for `token`.
+    pub fn get_total_claimable(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&ClaimDataKey::TotalClaimable(token))
+            .unwrap_or(0)
+    }
+
+    // ── Keeper Mode ─────────────────────────────────────────────────────
+
+    fn get_keeper_bounty_bps_internal(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&KeeperDataKey::BountyBps)
+            .unwrap_or(0)
+    }
+
+    /// Carve the keeper bounty out of `allocation.platform_fee`, after any
+    /// referral share, so the platform wallet never pays more than its fee.
+    fn apply_keeper_bounty(
+        env: &Env,
+        mut allocation: FeeAllocation,
+        keeper: Option<&Address>,
+    ) -> FeeAllocation {
+        if keeper.is_some() && allocation.platform_fee > 0 {
+            let bounty_bps = Self::get_keeper_bounty_bps_internal(env);
+            let bounty = Self::calculate_fee(env, allocation.platform_fee, bounty_bps);
+            allocation.keeper_fee = bounty.min(allocation.platform_fee - allocation.referral_fee);
+        }
+        allocation
+    }
+
+    /// Returns true if `order_id` can currently be processed with `action`.
+    fn is_keeper_eligible(env: &Env, action: KeeperAction, order_id: u32) -> bool {
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return false;
+        }
+        let escrow = Self::try_get_escrow_readonly(env, order_id);
+        if !matches!(escrow.status, EscrowStatus::Active | EscrowStatus::Disputed) {
+            return false;
+        }
+
+        let now = env.ledger().timestamp();
+        match action {
+            KeeperAction::Release => {
+                escrow.status == EscrowStatus::Active
+                    && escrow.funded
+                    && !Self::is_htlc_locked(env, order_id)
+                    && now - (escrow.created_at as u64) >= escrow.release_window as u64
+            }
+            KeeperAction::CancelUnfunded => {
+                let deadline = escrow
+                    .funding_deadline
+                    .unwrap_or((escrow.created_at as u64) + UNFUNDED_CANCEL_TIMEOUT);
+                escrow.status == EscrowStatus::Active && !escrow.funded && now >= deadline
+            }
+            KeeperAction::ResolveExpiredDispute => {
+                let config = Self::get_platform_config_internal(env);
+                match escrow.dispute_initiated_at {
+                    Some(initiated_at) => {
+                        escrow.status == EscrowStatus::Disputed
+                            && initiated_at + config.max_dispute_duration as u64 <= now
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// Set the keeper bounty as a share of the platform fee (`Role::FeeManager`).
+    ///
+    /// # Arguments
+    /// * `caller` - Address holding `Role::FeeManager`
+    /// * `bounty_bps` - Share of the platform fee in basis points, at most
+    ///   `MAX_KEEPER_BOUNTY_BPS`. Zero disables the bounty; keepers may still
+    ///   process escrows.
+    pub fn set_keeper_bounty_bps(env: Env, caller: Address, bounty_bps: u32) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+
+        if bounty_bps > MAX_KEEPER_BOUNTY_BPS {
+            return Err(Error::InvalidFee);
         }
 
-        // Check if recovery time lock has passed (#431 â€” TTL-friendly read)
-        let recovery_time = Self::get_persistent_u64(&env, &DataKey::AdminRecoveryTime);
+        let old_value = Self::get_keeper_bounty_bps_internal(&env);
+        env.storage()
+            .instance()
+            .set(&KeeperDataKey::BountyBps, &bounty_bps);
+        Self::emit_config_updated(
+            &env,
+            "keeper_bounty_bps",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(bounty_bps),
+        );
+        Ok(())
+    }
 
-        let current_time = env.ledger().timestamp();
+    /// Get the keeper bounty as a share of the platform fee, in basis points.
+    pub fn get_keeper_bounty_bps(env: Env) -> u32 {
+        Self::get_keeper_bounty_bps_internal(&env)
+    }
 
-        // If this is the first recovery request, initiate time lock and record
-        // the delay used so that malicious direct writes to `AdminRecoveryTime`
-        // cannot bypass the minimum cooldown requirement.
-        if recovery_time == 0 {
-            let new_recovery_time = current_time + ADMIN_RECOVERY_DELAY;
-            let recovery_time_key = DataKey::AdminRecoveryTime;
-            env.storage()
-                .persistent()
-                .set(&recovery_time_key, &new_recovery_time);
-            Self::extend_persistent(&env, &recovery_time_key);
+    /// Process ready escrows permissionlessly and earn the keeper bounty.
+    ///
+    /// Each order is re-checked against `action` right before it is
+    /// processed; ineligible orders — including ones already handled earlier
+    /// in the same batch or by another keeper — are skipped, so an escrow can
+    /// never be settled or rewarded twice. Releases and expired disputes pay
+    /// the bounty out of the platform fee; unfunded cancellations carry no fee
+    /// and therefore no bounty.
+    ///
+    /// # Arguments
+    /// * `keeper` - Address that receives the bounty (must sign)
+    /// * `action` - Action to apply to every order
+    /// * `order_ids` - Orders to process (at most `MAX_BATCH_SIZE`)
+    ///
+    /// # Returns
+    /// The order IDs that were processed.
+    pub fn keeper_process(
+        env: Env,
+        keeper: Address,
+        action: KeeperAction,
+        order_ids: Vec<u32>,
+    ) -> Result<Vec<u32>, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
+        keeper.require_auth();
 
-            // Record the delay used for this initiation so it can be validated
-            // later when recovery is attempted.
-            let delay_key = DataKey::AdminRecoveryDelay;
-            env.storage()
-                .persistent()
-                .set(&delay_key, &ADMIN_RECOVERY_DELAY);
-            Self::extend_persistent(&env, &delay_key);
+        if order_ids.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchLimitExceeded);
+        }
+
+        let mut processed = Vec::new(&env);
+        for order_id in order_ids.iter() {
+            if !Self::is_keeper_eligible(&env, action, order_id) {
+                continue;
+            }
 
+            let bounty = match action {
+                KeeperAction::Release => {
+                    Self::auto_release_internal(env.clone(), order_id, Some(&keeper))
+                }
+                KeeperAction::CancelUnfunded => {
+                    let escrow = Self::try_get_escrow_readonly(&env, order_id);
+                    Self::remove_unfunded_escrow(&env, order_id, &escrow);
+                    0
+                }
+                KeeperAction::ResolveExpiredDispute => {
+                    Self::resolve_expired_dispute_internal(env.clone(), order_id, Some(&keeper))?
+                }
+            };
             env.events().publish(
-                (Symbol::new(&env, "admin_recovery_initiated"), true),
-                String::from_str(&env, "7-day time lock initiated for admin recovery"),
+                (Symbol::new(&env, "keeper_processed"), keeper.clone()),
+                KeeperProcessedEvent {
+                    keeper: keeper.clone(),
+                    order_id,
+                    action,
+                    bounty,
+                },
             );
-            return Err(Error::AdminRecoveryFailed); // Recovery not ready yet
+            processed.push_back(order_id);
         }
 
-        // Check if time lock period has elapsed
-        if current_time < recovery_time {
-            return Err(Error::AdminRecoveryFailed);
+        Ok(processed)
+    }
+
+    /// Returns the escrows eligible for `action` within one page of the
+    /// global escrow index.
+    ///
+    /// `page` and `page_size` select a slice of `GlobalEscrowIdIndexed`
+    /// (capped at `MAX_PAGE_SIZE`); only eligible IDs from that slice are
+    /// returned, so a page may hold fewer than `page_size` entries. Keep
+    /// paging until `page * page_size >= get_escrow_count()`.
+    pub fn get_keeper_eligible_escrows(
+        env: Env,
+        action: KeeperAction,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<u32> {
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        let mut result = Vec::new(&env);
+        if page_size == 0 {
+            return result;
         }
 
-        // Ensure the recorded cooldown meets the minimum floor. If the delay
-        // is missing or below the minimum, treat this as a failed recovery
-        // attempt to prevent direct-storage bypasses.
-        let recorded_delay = Self::get_persistent_u64(&env, &DataKey::AdminRecoveryDelay);
-        if recorded_delay == 0 || recorded_delay < MIN_ADMIN_RECOVERY_COOLDOWN {
-            return Err(Error::AdminRecoveryFailed);
+        Self::migrate_legacy_all_escrow_ids(&env);
+
+        let total = Self::get_persistent_u32(&env, &DataKey::EscrowCount);
+        let start = page.saturating_mul(page_size);
+        if start >= total {
+            return result;
         }
+        let end = start.saturating_add(page_size).min(total);
 
-        // Time lock has passed, proceed with recovery
-        let mut config = Self::get_platform_config_internal(&env);
-        let previous_admin = config.admin.clone();
+        for index in start..end {
+            let index_key = DataKey::GlobalEscrowIdIndexed(index);
+            if let Some(order_id) = env.storage().persistent().get::<_, u32>(&index_key) {
+                if Self::is_keeper_eligible(&env, action, order_id) {
+                    result.push_back(order_id);
+                }
+            }
+        }
 
-        config.admin = recovered_admin.clone();
-        config.pending_admin = None;
-        // Write config to instance storage (primary location) â€” TTL already extended
-        // by get_platform_config_internal. No redundant extend_persistent needed.
+        result
+    }
+
+    // ── Delivery Attestations ───────────────────────────────────────────
+
+    fn get_attested_release_window_internal(env: &Env) -> u32 {
         env.storage()
             .instance()
-            .set(&DataKey::PlatformConfig, &config);
+            .get(&AttestationDataKey::ReleaseWindow)
+            .unwrap_or(DEFAULT_ATTESTED_RELEASE_WINDOW)
+    }
 
-        // Sync to persistent backup key for recovery consistency (no TTL extension needed
-        // since this is a one-time sync, not a read-heavy path).
-        env.storage().persistent().set(&PLATFORM_FEE, &config);
+    /// Register or remove a delivery attester public key (`Role::Operator`).
+    ///
+    /// # Arguments
+    /// * `public_key` - ed25519 public key of the attester
+    /// * `active` - `true` to register the key, `false` to remove it
+    pub fn set_delivery_attester(
+        env: Env,
+        caller: Address,
+        public_key: BytesN<32>,
+        active: bool,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
 
-        // Clear the recovery time lock for next cycle
-        env.storage()
-            .persistent()
-            .remove(&DataKey::AdminRecoveryTime);
-        // Clear the recorded delay as well
+        let key = AttestationDataKey::Attester(public_key.clone());
+        if active {
+            env.storage().persistent().set(&key, &true);
+            Self::extend_persistent(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        env.events().publish(
+            (Symbol::new(&env, "attester_updated"), public_key.clone()),
+            AttesterUpdatedEvent { public_key, active },
+        );
+        Ok(())
+    }
+
+    /// Returns true if `public_key` is a registered delivery attester.
+    pub fn is_delivery_attester(env: Env, public_key: BytesN<32>) -> bool {
         env.storage()
             .persistent()
-            .remove(&DataKey::AdminRecoveryDelay);
+            .has(&AttestationDataKey::Attester(public_key))
+    }
 
-        // Emit audit event
-        Self::emit_admin_changed(&env, previous_admin, recovered_admin, "admin_recovered");
+    /// Set how long after an attested delivery the escrow becomes
+    /// releasable (`Role::Operator`). Zero releases the escrow as soon as the
+    /// attestation is submitted, subject to the minimum release window.
+    pub fn set_attested_release_window(
+        env: Env,
+        caller: Address,
+        window_seconds: u32,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
 
+        let old_value = Self::get_attested_release_window_internal(&env);
+        env.storage()
+            .instance()
+            .set(&AttestationDataKey::ReleaseWindow, &window_seconds);
+        Self::emit_config_updated(
+            &env,
+            "attested_release_window",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(window_seconds),
+        );
         Ok(())
     }
 
-    /// Create a new escrow for an order
-    ///
-    /// # Arguments
-    /// * `buyer` - Address of the buyer
-    /// * `seller` - Address of the seller
-    /// * `token` - Token contract address (USDC)
-    /// * `amount` - Amount to escrow
-    /// * `order_id` - Unique order identifier
-    /// * `release_window` - Time in seconds before auto-release (default 7 days = 604800)
-    pub fn create_escrow(
-        env: Env,
-        buyer: Address,
-        seller: Address,
-        token: Address,
-        amount: i128,
-        order_id: u32,
-        release_window: Option<u32>,
-    ) -> Escrow {
-        Self::create_escrow_with_metadata(
-            env,
-            buyer,
-            seller,
-            token,
-            amount,
-            order_id,
-            release_window,
-            None,
-            None,
-        )
+    /// Get the release window applied after an attested delivery.
+    pub fn get_attested_release_window(env: Env) -> u32 {
+        Self::get_attested_release_window_internal(&env)
     }
 
-    /// Create a new escrow for an order and attach off-chain metadata.
-    pub fn create_escrow_with_metadata(
+    /// Submit a signed delivery attestation for a funded, active escrow.
+    ///
+    /// Callable by anyone; trust comes from the attester signature, which is
+    /// checked with `ed25519_verify` (an invalid signature aborts the call).
+    /// Attestations are accepted at most once per order, and the attested
+    /// timestamp must lie between escrow creation and now.
+    ///
+    /// The release window is shortened so the escrow becomes auto-releasable
+    /// `get_attested_release_window()` seconds after the attested delivery,
+    /// but never below `get_min_release_window()` from creation; a window
+    /// that is already shorter is left unchanged. If the shortened window has
+    /// already elapsed the escrow is released immediately.
+    ///
+    /// # Arguments
+    /// * `attestation` - Signed delivery attestation
+    /// * `public_key` - Registered attester key that signed it
+    /// * `signature` - ed25519 signature over `(contract_address, attestation)` XDR
+    pub fn submit_delivery_attestation(
         env: Env,
-        buyer: Address,
-        seller: Address,
-        token: Address,
-        amount: i128,
-        order_id: u32,
-        release_window: Option<u32>,
-        ipfs_hash: Option<String>,
-        metadata_hash: Option<Bytes>,
-    ) -> Escrow {
+        attestation: DeliveryAttestation,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
         let _guard = ReentryGuardScope::new(&env);
         Self::check_not_paused(&env);
-        buyer.require_auth();
 
-        // Validate amount is positive and above minimum
-        if let Err(e) = Self::check_min_amount(&env, token.clone(), amount) {
-            env.panic_with_error(e);
+        if !Self::is_delivery_attester(env.clone(), public_key.clone()) {
+            return Err(Error::Unauthorized);
         }
+        let message = (env.current_contract_address(), attestation.clone()).to_xdr(&env);
+        env.crypto()
+            .ed25519_verify(&public_key, &message, &signature);
 
-        // Validate buyer and seller are different
-        if buyer == seller {
-            env.panic_with_error(crate::Error::SameBuyerSeller);
+        let order_id = attestation.order_id;
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound);
         }
-
-        // Validate token is whitelisted (#103)
-        Self::check_token_whitelisted(&env, &token);
-
-        // Check artisan (seller) stake requirement (Issue #99)
-        let config = Self::get_platform_config_internal(&env);
-        if config.min_stake_required > 0 {
-            let artisan_stake: i128 = env
-                .storage()
-                .persistent()
-                .get(&DataKey::ArtisanStake(seller.clone()))
-                .map(|stake: ArtisanStakeData| stake.amount)
-                .unwrap_or(0);
-            if artisan_stake < config.min_stake_required {
-                env.panic_with_error(crate::Error::InsufficientStake);
-            }
+        let mut escrow = Self::get_stored_escrow(&env, order_id);
+        let record_key = AttestationDataKey::Attestation(order_id);
+        let now = env.ledger().timestamp();
+        if escrow.status != EscrowStatus::Active
+            || !escrow.funded
+            || env.storage().persistent().has(&record_key)
+            || attestation.timestamp > now
+            || attestation.timestamp < escrow.created_at as u64
+        {
+            return Err(Error::InvalidEscrowState);
         }
 
-        // Default to 7 days if not specified
-        let window = release_window.unwrap_or(604800u32);
-
-        // Validate release window bounds
-        let min_window = config.min_release_window;
-        let max_window = Self::get_max_release_window(&env);
+        env.storage().persistent().set(&record_key, &attestation);
+        Self::extend_persistent(&env, &record_key);
 
-        if window < min_window {
-            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        let window = Self::get_attested_release_window_internal(&env);
+        let deadline = attestation.timestamp.saturating_add(window as u64);
+        let min_window = Self::get_platform_config_internal(&env).min_release_window;
+        let attested_window = (deadline - escrow.created_at as u64).max(min_window as u64);
+        if attested_window < escrow.release_window as u64 {
+            escrow.release_window = attested_window as u32;
+            env.storage().persistent().set(&(ESCROW, order_id), &escrow);
         }
-        if window > max_window {
-            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
+
+        let released = Self::can_auto_release(env.clone(), order_id);
+        if released {
+            Self::auto_release_internal(env.clone(), order_id, None);
         }
 
-        let created_at_u64 = env.ledger().timestamp();
-        assert!(
-            created_at_u64 <= u32::MAX as u64,
-            "Ledger timestamp overflow"
+        env.events().publish(
+            (Symbol::new(&env, "delivery_attested"), order_id),
+            DeliveryAttestedEvent {
+                order_id,
+                attester: public_key,
+                delivered_at: attestation.timestamp,
+                released,
+            },
         );
-        let created_at = created_at_u64 as u32;
-        Self::validate_optional_ipfs_hash(&env, &ipfs_hash);
-        Self::validate_optional_metadata_hash(&env, &metadata_hash);
-
-        let escrow = Escrow {
-            version: CURRENT_ESCROW_VERSION,
-            id: order_id as u64,
-            batch_id: None,
-            buyer: buyer.clone(),
-            seller: seller.clone(),
-            token: token.clone(),
-            amount,
-            status: EscrowStatus::Active,
-            release_window: window,
-            created_at,
-            ipfs_hash: ipfs_hash.clone(),
-            metadata_hash: metadata_hash.clone(),
-            dispute_reason: None,
-            dispute_initiated_at: None,
-            funded: true,
-            funding_deadline: None, // Immediately funded; no deadline required (#656)
-        };
-
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
-        Self::extend_persistent(&env, &(ESCROW, order_id));
-
-        // Track active escrows
-        Self::update_active_obligations(&env, &buyer, 1);
-        Self::update_active_obligations(&env, &seller, 1);
-
-        // Update global escrow index for off-chain enumeration using atomic function
-        // This ensures AllEscrowIds and EscrowCount always remain in sync (Issue #226)
-        Self::update_escrow_indices_atomic(&env, order_id);
+        Ok(())
+    }
 
-        // Update buyer's escrow list using indexed storage (scalable approach)
-        let buyer_count_key = DataKey::BuyerEscrowCount(buyer.clone());
-        let buyer_count: u32 = env
-            .storage()
-            .persistent()
-            .get(&buyer_count_key)
-            .unwrap_or(0u32);
-        let buyer_index_key = DataKey::BuyerEscrowIndexed(buyer.clone(), buyer_count);
+    /// Get the accepted delivery attestation for an order, if any.
+    pub fn get_delivery_attestation(env: Env, order_id: u32) -> Option<DeliveryAttestation> {
         env.storage()
             .persistent()
-            .set(&buyer_index_key, &(order_id as u64));
-        Self::extend_persistent(&env, &buyer_index_key);
-        env.storage()
-            .persistent()
-            .set(&buyer_count_key, &(buyer_count + 1));
-        Self::extend_persistent(&env, &buyer_count_key);
+            .get(&AttestationDataKey::Attestation(order_id))
+    }
 
-        // Update seller's escrow list using indexed storage (scalable approach)
-        let seller_count_key = DataKey::SellerEscrowCount(seller.clone());
-        let seller_count: u32 = env
+    // ── Hash-Time-Locked Escrows ────────────────────────────────────────
+
+    fn is_htlc_locked(env: &Env, order_id: u32) -> bool {
+        env.storage().persistent().has(&HtlcDataKey::Lock(order_id))
+    }
+
+    fn get_htlc_lock_internal(env: &Env, order_id: u32) -> Result<HtlcLock, Error> {
+        let key = HtlcDataKey::Lock(order_id);
+        let lock = env
             .storage()
             .persistent()
-            .get(&seller_count_key)
-            .unwrap_or(0u32);
-        let seller_index_key = DataKey::SellerEscrowIndexed(seller.clone(), seller_count);
-        env.storage()
-            .persistent()
-            .set(&seller_index_key, &(order_id as u64));
-        Self::extend_persistent(&env, &seller_index_key);
-        env.storage()
-            .persistent()
-            .set(&seller_count_key, &(seller_count + 1));
-        Self::extend_persistent(&env, &seller_count_key);
+            .get(&key)
+            .ok_or(Error::InvalidEscrowState)?;
+        Self::extend_persistent(env, &key);
+        Ok(lock)
+    }
 
-        Self::safe_update_active_contracts(&env, buyer.clone(), 1);
-        Self::safe_update_active_contracts(&env, seller.clone(), 1);
+    /// Create an escrow that is paid out only when the seller reveals a
+    /// secret, e.g. one the buyer hands over at an in-person pickup.
+    ///
+    /// The escrow is funded like `create_escrow`, but it is never
+    /// auto-released: the seller claims it with `claim_htlc_escrow` before
+    /// `timeout`, after which the buyer may `reclaim_htlc_escrow`. The buyer
+    /// can still release early with `release_funds`, and either party can
+    /// dispute as usual.
+    ///
+    /// # Arguments
+    /// * `secret_hash` - SHA-256 of the secret (32 bytes)
+    /// * `timeout` - Unix timestamp after which the buyer may reclaim
+    ///
+    /// Panics with [`HtlcError::InvalidSecretHash`] or
+    /// [`HtlcError::InvalidHtlcTimeout`] on a malformed lock.
+    pub fn create_htlc_escrow(
+        env: Env,
+        buyer: Address,
+        seller: Address,
+        token: Address,
+        amount: i128,
+        order_id: u32,
+        secret_hash: Bytes,
+        timeout: u64,
+    ) -> Escrow {
+        if secret_hash.len() != 32 {
+            env.panic_with_error(HtlcError::InvalidSecretHash);
+        }
+        if timeout <= env.ledger().timestamp() {
+            env.panic_with_error(HtlcError::InvalidHtlcTimeout);
+        }
 
-        // Commit locked accounting before the external token interaction.
-        Self::update_total_locked(&env, &token, amount);
-        Self::transfer_tokens_and_record_audit(&env, &token, &buyer, &env.current_contract_address(), amount, &buyer, Symbol::new(&env, "escrow_funded"), -amount);
+        let escrow = Self::create_escrow(env.clone(), buyer, seller, token, amount, order_id, None);
 
-        Self::emit_escrow_created(
-            &env,
-            EscrowEvent {
-                escrow_id: order_id as u64,
-                action: EscrowAction::Created,
-                buyer: buyer.clone(),
-                seller: seller.clone(),
-                amount,
-                token: token.clone(),
-                timestamp: env.ledger().timestamp(),
+        let key = HtlcDataKey::Lock(order_id);
+        env.storage().persistent().set(
+            &key,
+            &HtlcLock {
+                secret_hash: secret_hash.clone(),
+                timeout,
             },
         );
+        Self::extend_persistent(&env, &key);
 
+        env.events().publish(
+            (Symbol::new(&env, "htlc_locked"), order_id),
+            HtlcLockedEvent {
+                order_id,
+                secret_hash,
+                timeout,
+            },
+        );
         escrow
     }
 
-    /// Create an escrow without funding it immediately (#213).
-    /// The buyer must call `fund_escrow` later to activate it.
-    pub fn create_unfunded_escrow(
+    /// Seller claims an HTLC escrow by revealing the secret.
+    ///
+    /// The secret is checked with the same SHA-256 comparison as
+    /// `verify_metadata_reveal`; `proof.content` carries the preimage and
+    /// `proof.secret` is ignored. Fees are charged as for a normal release.
+    pub fn claim_htlc_escrow(
         env: Env,
         order_id: u32,
-        buyer: Address,
-        seller: Address,
-        token: Address,
-        amount: i128,
-        window: u32,
-        ipfs_hash: Option<String>,
-        metadata_hash: Option<Bytes>,
-    ) -> Escrow {
+        proof: MetadataRevealProof,
+    ) -> Result<(), Error> {
         let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
 
-        // Validate release window bounds
-        let config = Self::get_platform_config_internal(&env);
-        let min_window = config.min_release_window;
-        let max_window = Self::get_max_release_window(&env);
+        let lock = Self::get_htlc_lock_internal(&env, order_id)?;
+        let escrow = Self::get_stored_escrow(&env, order_id);
+        escrow.seller.require_auth();
 
-        if window < min_window {
-            env.panic_with_error(crate::Error::ReleaseWindowTooShort);
+        if env.ledger().timestamp() >= lock.timeout {
+            return Err(Error::InvalidEscrowState);
         }
-        if window > max_window {
-            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
+        if !Self::proof_matches_hash(&env, &proof, &lock.secret_hash) {
+            return Err(Error::Unauthorized);
         }
 
-        let created_at_u64 = env.ledger().timestamp();
-        assert!(
-            created_at_u64 <= u32::MAX as u64,
-            "Ledger timestamp overflow"
+        Self::settle_release_internal(env.clone(), order_id, None);
+
+        env.events().publish(
+            (Symbol::new(&env, "htlc_claimed"), order_id),
+            HtlcClaimedEvent {
+                order_id,
+                secret: proof.content,
+            },
         );
-        let created_at = created_at_u64 as u32;
-        Self::validate_optional_ipfs_hash(&env, &ipfs_hash);
-        Self::validate_optional_metadata_hash(&env, &metadata_hash);
+        Ok(())
+    }
 
-        // Compute the deadline after which any party may cancel the unfunded stub (#656).
-        let funding_deadline = created_at_u64 + UNFUNDED_CANCEL_TIMEOUT;
+    /// Buyer reclaims an HTLC escrow whose secret was not revealed before
+    /// the timeout. The full amount is refunded without a platform fee.
+    pub fn reclaim_htlc_escrow(env: Env, order_id: u32) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
 
-        let escrow = Escrow {
-            version: CURRENT_ESCROW_VERSION,
-            id: order_id as u64,
-            batch_id: None,
-            buyer: buyer.clone(),
-            seller: seller.clone(),
-            token: token.clone(),
-            amount,
-            status: EscrowStatus::Active,
-            release_window: window,
-            created_at,
-            ipfs_hash: ipfs_hash.clone(),
-            metadata_hash: metadata_hash.clone(),
-            dispute_reason: None,
-            dispute_initiated_at: None,
-            funded: false,
-            funding_deadline: Some(funding_deadline), // Deadline for funding; parties may cancel after this (#656)
-        };
+        let lock = Self::get_htlc_lock_internal(&env, order_id)?;
+        let escrow = Self::get_stored_escrow(&env, order_id);
+        escrow.buyer.require_auth();
 
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
-        Self::extend_persistent(&env, &(ESCROW, order_id));
+        if env.ledger().timestamp() < lock.timeout {
+            return Err(Error::ReleaseWindowNotElapsed);
+        }
 
-        // Update buyer's escrow list
-        let buyer_count_key = DataKey::BuyerEscrowCount(buyer.clone());
-        let buyer_count: u32 = env
-            .storage()
-            .persistent()
-            .get(&buyer_count_key)
-            .unwrap_or(0u32);
-        let buyer_index_key = DataKey::BuyerEscrowIndexed(buyer.clone(), buyer_count);
-        env.storage()
-            .persistent()
-            .set(&buyer_index_key, &(order_id as u64));
-        Self::extend_persistent(&env, &buyer_index_key);
-        env.storage()
-            .persistent()
-            .set(&buyer_count_key, &(buyer_count + 1));
-        Self::extend_persistent(&env, &buyer_count_key);
+        Self::refund_internal(env.clone(), order_id as u64)
+    }
 
-        // Update seller's escrow list
-        let seller_count_key = DataKey::SellerEscrowCount(seller.clone());
-        let seller_count: u32 = env
+    /// Get the hash lock of an HTLC escrow; `None` once the escrow has
+    /// settled by any path.
+    pub fn get_htlc_lock(env: Env, order_id: u32) -> Option<HtlcLock> {
+        env.storage().persistent().get(&HtlcDataKey::Lock(order_id))
+    }
+
+    #[inline(always)]
+    fn get_legacy_total_fees(env: &Env) -> i128 {
+        env.storage().persistent().get(&TOTAL_FEES).unwrap_or(0)
+    }
+
+    fn get_all_tracked_total_fees(env: &Env) -> i128 {
+        let key = DataKey::FeeTokenIndex;
+        let tracked_tokens: Vec<Address> = env
             .storage()
             .persistent()
-            .get(&seller_count_key)
-            .unwrap_or(0u32);
-        let seller_index_key = DataKey::SellerEscrowIndexed(seller.clone(), seller_count);
-        env.storage()
-            .persistent()
-            .set(&seller_index_key, &(order_id as u64));
-        Self::extend_persistent(&env, &seller_index_key);
-        env.storage()
-            .persistent()
-            .set(&seller_count_key, &(seller_count + 1));
-        Self::extend_persistent(&env, &seller_count_key);
+            .get(&key)
+            .unwrap_or(Vec::new(env));
 
-        // Track active escrows (unfunded still count towards active limit to prevent spam)
-        Self::update_active_obligations(&env, &buyer, 1);
-        Self::update_active_obligations(&env, &seller, 1);
+        if tracked_tokens.is_empty() {
+            return Self::get_legacy_total_fees(env);
+        }
+
+        let mut total_fees = 0i128;
+        for index in 0..tracked_tokens.len() {
+            if let Some(token) = tracked_tokens.get(index) {
+                let token_key = DataKey::TotalFees(token);
+                let token_total: i128 = env.storage().persistent().get(&token_key).unwrap_or(0);
+                total_fees += token_total;
+            }
+        }
+
+        total_fees
+    }
+
+    /// Release funds to seller with platform fee deduction
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    pub fn release_funds(env: Env, order_id: u32) {
+        Self::release_funds_with_tip(env, order_id, 0);
+    }
+
+    /// Release funds to seller and tip the seller on top.
+    ///
+    /// The tip is transferred from the buyer straight to the seller, is not
+    /// subject to the platform fee, and is counted in the seller's volume
+    /// metrics. A zero tip is a plain `release_funds`.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `tip` - Fee-exempt amount paid by the buyer to the seller
+    pub fn release_funds_with_tip(env: Env, order_id: u32, tip: i128) {
+        let _guard = ReentryGuardScope::new(&env);
+        let escrow_for_auth = Self::get_stored_escrow(&env, order_id);
+
+        // Only buyer can release funds
+        escrow_for_auth.buyer.require_auth();
+
+        if tip < 0 {
+            env.panic_with_error(crate::Error::AmountBelowMinimum);
+        }
+
+        let mut escrow =
+            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::ReleasePending)
+                .unwrap_or_else(|e| env.panic_with_error(e));
+
+        // Get platform config
+        let config = Self::get_platform_config_internal(&env);
+
+        // Deterministic fee allocation via the central FeePolicy engine.
+        let fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+        let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+        let allocation = Self::apply_referral_share(
+            &env,
+            Self::compute_escrow_fee_allocation(
+                &env,
+                order_id,
+                escrow.amount,
+                &fee_rate,
+                SettlementKind::ReleaseFunds,
+            ),
+            &referrer,
+        );
+
+        // Update status
+        escrow.status = EscrowStatus::Released;
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+
+        // Decrement active counts
+        Self::update_active_obligations(&env, &escrow.buyer, -1);
+        Self::update_active_obligations(&env, &escrow.seller, -1);
+
+        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
+        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+
+        // Reserve accounting is part of the effects phase.
+        Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+
+        // Transfer platform fee to platform wallet
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(
+                &env,
+                &escrow.token,
+                &config.platform_wallet,
+                &allocation,
+                referrer.as_ref().map(|referrer| (order_id, referrer)),
+                None,
+            );
+        }
+
+        // Transfer net funds to seller and record audit
+        Self::pay_seller_proceeds(&env, order_id, &escrow, allocation.seller_amount, Symbol::new(&env, "escrow_released"));
+
+        if tip > 0 {
+            Self::transfer_tokens_and_record_audit(
+                &env,
+                &escrow.token,
+                &escrow.buyer,
+                &escrow.seller,
+                tip,
+                &escrow.buyer,
+                Symbol::new(&env, "release_tip"),
+                -tip,
+            );
+            Self::append_fund_audit_record(&env, &escrow.seller, tip, Symbol::new(&env, "release_tip"), tip);
+            env.events().publish(
+                (Symbol::new(&env, "release_tip"), order_id),
+                TipPaidEvent {
+                    order_id,
+                    buyer: escrow.buyer.clone(),
+                    seller: escrow.seller.clone(),
+                    token: escrow.token.clone(),
+                    amount: tip,
+                },
+            );
+        }
 
-        Self::safe_update_active_contracts(&env, buyer.clone(), 1);
-        Self::safe_update_active_contracts(&env, seller.clone(), 1);
+        Self::clear_settled_escrow_state(&env, order_id);
 
         Self::emit_escrow_created(
             &env,
             EscrowEvent {
                 escrow_id: order_id as u64,
-                action: EscrowAction::Created,
-                buyer: buyer.clone(),
-                seller: seller.clone(),
-                amount,
-                token: token.clone(),
+                action: EscrowAction::Released,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
                 timestamp: env.ledger().timestamp(),
             },
         );
 
-        escrow
+        // Emit reputation update events â€” decoupled from onboarding contract (#211)
+        let ts = env.ledger().timestamp();
+        Self::emit_reputation_update(
+            &env,
+            ReputationUpdateEvent {
+                address: escrow.seller.clone(),
+                successful_delta: 1,
+                disputed_delta: 0,
+                metrics_sales_delta: 1,
+                metrics_amount: escrow.amount + tip,
+                token: escrow.token.clone(),
+                timestamp: ts,
+            },
+        );
+        Self::emit_reputation_update(
+            &env,
+            ReputationUpdateEvent {
+                address: escrow.buyer.clone(),
+                successful_delta: 1,
+                disputed_delta: 0,
+                metrics_sales_delta: 0,
+                metrics_amount: 0,
+                token: escrow.token.clone(),
+                timestamp: ts,
+            },
+        );
     }
-    pub fn fund_escrow(env: Env, order_id: u32) -> Result<(), Error> {
+
+    /// Release part of an escrow to the seller early (only buyer can call)
+    ///
+    /// Without a fee schedule the platform fee is charged on `amount` only,
+    /// exactly as a full release of that amount would be. Under a fee
+    /// schedule the fee is computed once on the whole escrow and `amount`
+    /// pays its pro-rata share; the unpaid rest is fixed on the escrow, so
+    /// splitting a release neither repeats the flat and minimum fee nor
+    /// dodges the maximum. The escrow stays `Active` with the remaining
+    /// amount, which keeps its release window, dispute and refund rights.
+    /// Use `release_funds` to release everything.
+    ///
+    /// Fails with [`FeePayerError::BuyerFeeEscrowUnsupported`] when the
+    /// buyer deposited part of the fee (see `get_escrow_fee_terms`); such
+    /// escrows settle only through a full release or refund. Other failures
+    /// are [`Error`] codes.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `amount` - Gross amount to release; must be below the escrow amount
+    pub fn release_partial_funds(env: Env, order_id: u32, amount: i128) -> Result<(), soroban_sdk::Error> {
         let _guard = ReentryGuardScope::new(&env);
-        let mut escrow = Self::get_stored_escrow(&env, order_id);
-        if escrow.funded {
-            return Err(Error::InvalidEscrowState);
+        Self::check_not_paused(&env);
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound.into());
         }
+        let mut escrow = Self::get_stored_escrow(&env, order_id);
 
+        // Only buyer can release funds
         escrow.buyer.require_auth();
 
-        // Effects before interaction: a callback can never observe this escrow
-        // as unfunded after its balance has been pulled.
-        escrow.funded = true;
+        if escrow.status != EscrowStatus::Active || !escrow.funded {
+            return Err(Error::InvalidEscrowState.into());
+        }
+        if amount <= 0 {
+            return Err(Error::AmountBelowMinimum.into());
+        }
+        if amount >= escrow.amount {
+            return Err(Error::InvalidEscrowState.into());
+        }
+        // A buyer-side fee is settled only on a full release.
+        if env.storage().persistent().has(&FeeDataKey::EscrowTerms(order_id)) {
+            return Err(FeePayerError::BuyerFeeEscrowUnsupported.into());
+        }
+
+        let config = Self::get_platform_config_internal(&env);
+
+        // Deterministic fee allocation via the central FeePolicy engine,
+        // applied to the released slice only.
+        let mut fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+        if fee_rate.schedule.is_some() {
+            // Prorate the whole escrow's scheduled fee, as `PartialRefund`
+            // does, and leave the rest on the escrow as a fixed fee.
+            let total_fee = Self::scheduled_fee(&env, escrow.amount, &fee_rate);
+            let slice_fee = total_fee * amount / escrow.amount;
+            let rest = Self::fixed_fee_rate(total_fee - slice_fee);
+            Self::store_escrow_fee_rate(&env, order_id, &rest);
+            fee_rate = Self::fixed_fee_rate(slice_fee);
+        }
+        let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+        let allocation = Self::apply_referral_share(
+            &env,
+            Self::compute_fee_allocation(&env, amount, &fee_rate, SettlementKind::ReleaseFunds),
+            &referrer,
+        );
+
+        // Effects before interactions. The released slice spends its share
+        // of a coupon subsidy, so a later refund returns only the rest.
+        let subsidy_key = CouponDataKey::Subsidy(order_id);
+        let subsidy: i128 = env.storage().persistent().get(&subsidy_key).unwrap_or(0);
+        if subsidy > 0 {
+            let spent = subsidy * amount / escrow.amount;
+            env.storage().persistent().set(&subsidy_key, &(subsidy - spent));
+        }
+        escrow.amount -= amount;
         env.storage().persistent().set(&(ESCROW, order_id), &escrow);
         Self::extend_persistent(&env, &(ESCROW, order_id));
-        Self::update_total_locked(&env, &escrow.token, escrow.amount);
+        Self::update_total_locked(&env, &escrow.token, -amount);
 
-        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &escrow.buyer, &env.current_contract_address(), escrow.amount, &escrow.buyer, Symbol::new(&env, "escrow_funded"), -escrow.amount);
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(
+                &env,
+                &escrow.token,
+                &config.platform_wallet,
+                &allocation,
+                referrer.as_ref().map(|referrer| (order_id, referrer)),
+                None,
+            );
+        }
+        Self::pay_seller_proceeds(
+            &env,
+            order_id,
+            &escrow,
+            allocation.seller_amount,
+            Symbol::new(&env, "partial_release"),
+        );
 
         Self::emit_escrow_created(
             &env,
             EscrowEvent {
                 escrow_id: order_id as u64,
-                action: EscrowAction::Created, // Re-emit as created/funded
+                action: EscrowAction::PartiallyReleased,
                 buyer: escrow.buyer.clone(),
                 seller: escrow.seller.clone(),
                 amount: escrow.amount,
@@ -3609,977 +7871,1202 @@ impl CraftNexusContract {
                 timestamp: env.ledger().timestamp(),
             },
         );
+        env.events().publish(
+            (Symbol::new(&env, "partial_release"), order_id),
+            PartialReleaseEvent {
+                order_id,
+                amount,
+                platform_fee: allocation.platform_fee,
+                seller_amount: allocation.seller_amount,
+                remaining: escrow.amount,
+            },
+        );
+        Ok(())
+    }
+
+    /// Amend the amount of an active escrow when the commission scope changes.
+    ///
+    /// Raising the amount is a buyer top-up: the difference is transferred
+    /// from the buyer (or the payer of a gift escrow). Lowering it is a price
+    /// reduction that the seller must also sign, as must the assignee once
+    /// the receivable has been assigned; the difference is refunded to the
+    /// escrow's refund destination without a fee.
+    /// The new amount must satisfy the token's minimum escrow amount.
+    ///
+    /// Fails with [`FeePayerError::BuyerFeeEscrowUnsupported`] when the
+    /// buyer deposited part of the fee: the deposited fee was computed on the
+    /// original price and is not re-quoted. Other failures are [`Error`]
+    /// codes.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `new_amount` - Amount the escrow should hold afterwards
+    pub fn amend_escrow_amount(env: Env, order_id: u32, new_amount: i128) -> Result<(), soroban_sdk::Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound.into());
+        }
+        let mut escrow = Self::get_stored_escrow(&env, order_id);
+        let funder = Self::escrow_funder(&escrow);
+        funder.require_auth();
+
+        if escrow.status != EscrowStatus::Active || !escrow.funded {
+            return Err(Error::InvalidEscrowState.into());
+        }
+        if env.storage().persistent().has(&FeeDataKey::EscrowTerms(order_id)) {
+            return Err(FeePayerError::BuyerFeeEscrowUnsupported.into());
+        }
+        Self::check_min_amount(&env, escrow.token.clone(), new_amount)?;
+
+        let old_amount = escrow.amount;
+        if new_amount == old_amount {
+            return Err(Error::InvalidEscrowState.into());
+        }
+        if new_amount < old_amount {
+            escrow.seller.require_auth();
+            // A reduction shrinks the assignee's payout, so they must agree.
+            let holder = Self::get_receivable_holder(env.clone(), order_id);
+            if holder != escrow.seller {
+                holder.require_auth();
+            }
+        }
+
+        let version_key = AmendmentDataKey::Version(order_id);
+        let version = Self::get_escrow_amendment_version(env.clone(), order_id) + 1;
+        env.storage().persistent().set(&version_key, &version);
+        Self::extend_persistent(&env, &version_key);
+
+        // Effects before interactions.
+        escrow.amount = new_amount;
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::extend_persistent(&env, &(ESCROW, order_id));
+        Self::update_total_locked(&env, &escrow.token, new_amount - old_amount);
+
+        if new_amount > old_amount {
+            let top_up = new_amount - old_amount;
+            Self::transfer_tokens_and_record_audit(
+                &env,
+                &escrow.token,
+                &funder,
+                &env.current_contract_address(),
+                top_up,
+                &funder,
+                Symbol::new(&env, "escrow_topped_up"),
+                -top_up,
+            );
+        } else {
+            Self::pay_refund(
+                &env,
+                order_id,
+                &escrow,
+                old_amount,
+                old_amount - new_amount,
+                Symbol::new(&env, "amendment_refund"),
+            );
+        }
 
+        env.events().publish(
+            (Symbol::new(&env, "escrow_amended"), order_id),
+            EscrowAmendedEvent {
+                order_id,
+                old_amount,
+                new_amount,
+                version,
+            },
+        );
         Ok(())
     }
 
-    /// Cancel an escrow that has not been funded within the timeout period (#213, #656).
+    /// Get the number of amendments applied to an escrow.
+    pub fn get_escrow_amendment_version(env: Env, order_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&AmendmentDataKey::Version(order_id))
+            .unwrap_or(0)
+    }
+
+    /// Assign the payout right of an active escrow to another address, e.g.
+    /// a financier buying the seller's receivable.
     ///
-    /// Before the `funding_deadline` only the buyer may cancel voluntarily.
-    /// After the deadline **any** party (buyer, seller, or platform admin) may cancel
-    /// by passing their own address as `caller` to reclaim persistent-storage rent
-    /// and prevent indefinite stub accumulation.
-    pub fn cancel_unfunded_escrow(env: Env, order_id: u32, caller: Address) -> Result<(), Error> {
+    /// Must be signed by the current holder of the payout right: the seller,
+    /// or the last assignee. The buyer and the seller's dispute rights are
+    /// unchanged; only the recipient of released funds moves.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `assignee` - New holder of the payout right
+    pub fn assign_receivable(env: Env, order_id: u32, assignee: Address) -> Result<(), Error> {
         let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound);
+        }
         let escrow = Self::get_stored_escrow(&env, order_id);
-        if escrow.funded {
+        let holder = Self::get_receivable_holder(env.clone(), order_id);
+        holder.require_auth();
+
+        if escrow.status != EscrowStatus::Active || !escrow.funded {
+            return Err(Error::InvalidEscrowState);
+        }
+        if assignee == escrow.buyer {
+            return Err(Error::SameBuyerSeller);
+        }
+        if assignee == holder {
             return Err(Error::InvalidEscrowState);
         }
 
+        let assignee_key = ReceivableDataKey::Assignee(order_id);
+        if assignee == escrow.seller {
+            env.storage().persistent().remove(&assignee_key);
+        } else {
+            env.storage().persistent().set(&assignee_key, &assignee);
+            Self::extend_persistent(&env, &assignee_key);
+        }
+
+        let history_key = ReceivableDataKey::History(order_id);
+        let mut history = Self::get_receivable_history(env.clone(), order_id);
+        history.push_back(ReceivableAssignment {
+            from: holder.clone(),
+            to: assignee.clone(),
+            assigned_at: env.ledger().timestamp(),
+        });
+        while history.len() > MAX_RECEIVABLE_ASSIGNMENTS {
+            history.pop_front();
+        }
+        env.storage().persistent().set(&history_key, &history);
+        Self::extend_persistent(&env, &history_key);
+
+        env.events().publish(
+            (Symbol::new(&env, "receivable_assigned"), order_id),
+            ReceivableAssignedEvent {
+                order_id,
+                from: holder,
+                to: assignee,
+            },
+        );
+        Ok(())
+    }
+
+    /// Get the address that receives the seller's proceeds of an escrow.
+    pub fn get_receivable_holder(env: Env, order_id: u32) -> Address {
+        env.storage()
+            .persistent()
+            .get(&ReceivableDataKey::Assignee(order_id))
+            .unwrap_or_else(|| Self::get_stored_escrow(&env, order_id).seller)
+    }
+
+    /// Get the assignment log of an escrow, oldest first. Capped at
+    /// `MAX_RECEIVABLE_ASSIGNMENTS` records.
+    pub fn get_receivable_history(env: Env, order_id: u32) -> Vec<ReceivableAssignment> {
+        env.storage()
+            .persistent()
+            .get(&ReceivableDataKey::History(order_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Pay the seller's share of an escrow. Assigned receivables go to the
+    /// assignee and are logged with the `receivable_paid` reason.
+    fn pay_seller_proceeds(env: &Env, order_id: u32, escrow: &Escrow, amount: i128, reason: Symbol) {
+        if amount > 0 {
+            Self::record_epoch_stats(env, &escrow.token, |stats| stats.released_volume += amount);
+        }
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&ReceivableDataKey::Assignee(order_id))
+        {
+            Some(assignee) => Self::payout_tokens_and_record_audit(
+                env,
+                &escrow.token,
+                &assignee,
+                amount,
+                Symbol::new(env, "receivable_paid"),
+            ),
+            None => Self::payout_tokens_and_record_audit(env, &escrow.token, &escrow.seller, amount, reason),
+        }
+    }
+
+    /// Auto-release funds after release window (seller can call)
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    pub fn auto_release(env: Env, order_id: u32) {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::auto_release_internal(env.clone(), order_id, None);
+    }
+
+    /// Shared body of `auto_release` and keeper releases. When `keeper` is
+    /// set, the configured keeper bounty is carved out of the platform fee.
+    ///
+    /// Returns the keeper bounty paid.
+    fn auto_release_internal(env: Env, order_id: u32, keeper: Option<&Address>) -> i128 {
+        let escrow_for_window = Self::get_stored_escrow(&env, order_id);
+
+        // Hash-locked escrows are paid only by revealing the secret.
+        if !(escrow_for_window.status == EscrowStatus::Active) || Self::is_htlc_locked(&env, order_id)
+        {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
+
         let current_time = env.ledger().timestamp();
-        // Use the stored funding_deadline when available; fall back to the
-        // legacy created_at + UNFUNDED_CANCEL_TIMEOUT calculation for escrows
-        // created before this field was added.
-        let deadline = escrow
-            .funding_deadline
-            .unwrap_or((escrow.created_at as u64) + UNFUNDED_CANCEL_TIMEOUT);
+        let elapsed = current_time - (escrow_for_window.created_at as u64);
 
-        if current_time >= deadline {
-            // After the deadline: buyer, seller, or platform admin may cancel.
-            let admin = Self::get_admin(&env).unwrap_or(escrow.buyer.clone());
-            if caller != escrow.buyer && caller != escrow.seller && caller != admin {
-                return Err(Error::Unauthorized);
-            }
-            caller.require_auth();
-        } else {
-            // Before the deadline: only the buyer may cancel voluntarily.
-            if caller != escrow.buyer {
-                return Err(Error::Unauthorized);
-            }
-            caller.require_auth();
+        if elapsed < escrow_for_window.release_window as u64 {
+            env.panic_with_error(crate::Error::ReleaseWindowNotElapsed);
         }
 
-        // Cleanup state
-        env.storage().persistent().remove(&(ESCROW, order_id));
+        Self::settle_release_internal(env, order_id, keeper)
+    }
 
-        // Decrement active obligations
+    /// Release an active escrow to the seller without checking the release
+    /// window. Returns the keeper bounty paid.
+    fn settle_release_internal(env: Env, order_id: u32, keeper: Option<&Address>) -> i128 {
+        let mut escrow =
+            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::ReleasePending)
+                .unwrap_or_else(|e| env.panic_with_error(e));
+
+        // Get platform config
+        let config = Self::get_platform_config_internal(&env);
+
+        // Deterministic fee allocation via the central FeePolicy engine.
+        let fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+        let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+        let allocation = Self::apply_referral_share(
+            &env,
+            Self::compute_escrow_fee_allocation(
+                &env,
+                order_id,
+                escrow.amount,
+                &fee_rate,
+                SettlementKind::ReleaseFunds,
+            ),
+            &referrer,
+        );
+        let allocation = Self::apply_keeper_bounty(&env, allocation, keeper);
+
+        // Update status
+        escrow.status = EscrowStatus::Released;
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+
+        // Decrement active counts
         Self::update_active_obligations(&env, &escrow.buyer, -1);
         Self::update_active_obligations(&env, &escrow.seller, -1);
 
-        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
+        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+
+        Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+
+        // Transfer platform fee to platform wallet
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(
+                &env,
+                &escrow.token,
+                &config.platform_wallet,
+                &allocation,
+                referrer.as_ref().map(|referrer| (order_id, referrer)),
+                keeper,
+            );
+        }
+
+        // Transfer net funds to seller and record audit
+        Self::pay_seller_proceeds(&env, order_id, &escrow, allocation.seller_amount, Symbol::new(&env, "escrow_released"));
+
+        Self::clear_settled_escrow_state(&env, order_id);
+
+        Self::emit_escrow_created(
+            &env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action: EscrowAction::Released,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        // Emit reputation update events â€” decoupled from onboarding contract (#211)
+        let ts = env.ledger().timestamp();
+        Self::emit_reputation_update(
+            &env,
+            ReputationUpdateEvent {
+                address: escrow.seller.clone(),
+                successful_delta: 1,
+                disputed_delta: 0,
+                metrics_sales_delta: 1,
+                metrics_amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: ts,
+            },
+        );
+        Self::emit_reputation_update(
+            &env,
+            ReputationUpdateEvent {
+                address: escrow.buyer.clone(),
+                successful_delta: 1,
+                disputed_delta: 0,
+                metrics_sales_delta: 0,
+                metrics_amount: 0,
+                token: escrow.token.clone(),
+                timestamp: ts,
+            },
+        );
 
-        Ok(())
+        allocation.keeper_fee
     }
 
-    /// Batch-cancel unfunded escrow stubs whose `funding_deadline` has elapsed (#656).
-    ///
-    /// Callable only by the platform admin. The function iterates over the
-    /// provided list of `order_ids` and cancels each one that:
-    ///   1. Exists in storage
-    ///   2. Is not yet funded
-    ///   3. Has passed its `funding_deadline` (or the legacy 24-hour timeout)
+    /// Auto-release every matured escrow of a seller, one page at a time.
     ///
-    /// Escrows that do not meet these criteria are silently skipped so a
-    /// single invalid entry does not abort the whole batch. Returns the count
-    /// of escrows that were actually cancelled.
+    /// Walks `SellerEscrowIndexed(seller, cursor..cursor + limit)` and
+    /// releases each escrow for which `can_auto_release` holds. Escrows that
+    /// are not ready, already settled, unfunded or removed are skipped, so
+    /// the call can be repeated safely. Pass the returned `next_cursor` to
+    /// continue in a later transaction until `complete` is `true`.
     ///
     /// # Arguments
-    /// * `admin` â€“ Must be the platform admin address; auth is required.
-    /// * `order_ids` â€“ List of escrow order IDs to check and cancel.
-    pub fn auto_cancel_unfunded(
+    /// * `seller` - Seller whose escrows are released (must sign)
+    /// * `cursor` - Index position to start from
+    /// * `limit` - Number of index positions to scan (at most `MAX_BATCH_SIZE`)
+    pub fn auto_release_seller_escrows(
         env: Env,
-        admin: Address,
-        order_ids: soroban_sdk::Vec<u32>,
-    ) -> Result<u32, Error> {
+        seller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<SellerAutoReleaseBatch, Error> {
         let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
+        seller.require_auth();
 
-        // Verify caller is platform admin
-        let stored_admin = Self::get_admin(&env)?;
-        if admin != stored_admin {
-            return Err(Error::Unauthorized);
+        if limit > MAX_BATCH_SIZE {
+            return Err(Error::BatchLimitExceeded);
         }
-        admin.require_auth();
-
-        let current_time = env.ledger().timestamp();
-        let mut cancelled_count: u32 = 0;
 
-        for order_id in order_ids.iter() {
-            let key = (ESCROW, order_id);
-
-            // Skip missing escrows
-            let escrow: Escrow = match env.storage().persistent().get(&key) {
-                Some(e) => e,
+        let count_key = DataKey::SellerEscrowCount(seller.clone());
+        let total_count = Self::get_persistent_u32(&env, &count_key);
+        let start = cursor.min(total_count);
+        let end = start.saturating_add(limit).min(total_count);
+
+        let mut released = Vec::new(&env);
+        for position in start..end {
+            let index_key = DataKey::SellerEscrowIndexed(seller.clone(), position);
+            let order_id = match env.storage().persistent().get::<_, u64>(&index_key) {
+                Some(escrow_id) => escrow_id as u32,
                 None => continue,
             };
-
-            // Skip already-funded escrows
-            if escrow.funded {
+            if !env.storage().persistent().has(&(ESCROW, order_id)) {
                 continue;
             }
-
-            // Skip escrows that haven't yet reached their funding deadline
-            let deadline = escrow
-                .funding_deadline
-                .unwrap_or((escrow.created_at as u64) + UNFUNDED_CANCEL_TIMEOUT);
-            if current_time < deadline {
+            // Unfunded stubs hold no tokens; they are cancelled, not released.
+            if !Self::try_get_escrow_readonly(&env, order_id).funded {
+                continue;
+            }
+            if !Self::can_auto_release(env.clone(), order_id) {
                 continue;
             }
 
-            // Cancel: remove from storage and update bookkeeping
-            env.storage().persistent().remove(&key);
-            Self::update_active_obligations(&env, &escrow.buyer, -1);
-            Self::update_active_obligations(&env, &escrow.seller, -1);
-            Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-            Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
-
-            cancelled_count += 1;
+            Self::auto_release_internal(env.clone(), order_id, None);
+            released.push_back(order_id as u64);
         }
 
-        Ok(cancelled_count)
+        Ok(SellerAutoReleaseBatch {
+            released,
+            next_cursor: end,
+            complete: end >= total_count,
+        })
     }
 
-    /// Get escrows for a specific buyer with pagination.
-    /// Uses indexed storage for scalability, with fallback to legacy vector storage.
-    pub fn get_escrows_by_buyer(
-        env: Env,
-        buyer: Address,
-        page: u32,
-        page_size: u32,
-        reverse: bool,
-    ) -> Result<soroban_sdk::Vec<u64>, Error> {
-        buyer.require_auth();
-        let mut result = soroban_sdk::Vec::new(&env);
+    /// Extend the release window for an escrow (only buyer can call)
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `additional_seconds` - Time in seconds to add to the release window
+    pub fn extend_release_window(env: Env, order_id: u32, additional_seconds: u32) {
+        let _guard = ReentryGuardScope::new(&env);
+        let escrow_key = (ESCROW, order_id);
+        let escrow_opt = env.storage().persistent().get(&escrow_key);
 
-        let page_size = page_size.min(MAX_PAGE_SIZE);
-        if page_size == 0 {
-            return Ok(result);
+        if escrow_opt.is_none() {
+            env.panic_with_error(crate::Error::EscrowNotFound);
         }
 
-        // Try new indexed storage first
-        let count_key = DataKey::BuyerEscrowCount(buyer.clone());
-        if env.storage().persistent().has(&count_key) {
-            let total_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0u32);
-            let start = page * page_size;
-
-            if start >= total_count {
-                return Ok(result);
-            }
-
-            let end = (start + page_size).min(total_count);
+        Self::extend_persistent(&env, &escrow_key);
+        let mut escrow: Escrow = escrow_opt.unwrap();
 
-            for position in start..end {
-                let storage_index = if reverse {
-                    total_count - 1 - position
-                } else {
-                    position
-                };
-                let index_key = DataKey::BuyerEscrowIndexed(buyer.clone(), storage_index);
-                if let Some(escrow_id) = env.storage().persistent().get::<_, u64>(&index_key) {
-                    result.push_back(escrow_id);
-                    Self::extend_persistent_read(&env, &index_key);
-                }
-            }
+        // Only buyer can extend release window
+        escrow.buyer.require_auth();
 
-            Self::extend_persistent_read(&env, &count_key);
-            return Ok(result);
+        if !(escrow.status == EscrowStatus::Active) {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
         }
 
-        // Fallback to legacy vector storage for backward compatibility
-        let legacy_key = DataKey::BuyerEscrows(buyer);
-        let escrow_ids: soroban_sdk::Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&legacy_key)
-            .unwrap_or(soroban_sdk::Vec::new(&env));
+        let new_window = escrow.release_window.saturating_add(additional_seconds);
 
-        if env.storage().persistent().has(&legacy_key) {
-            Self::extend_persistent_read(&env, &legacy_key);
+        if new_window > MAX_TOTAL_RELEASE_WINDOW {
+            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
         }
 
-        let start = page * page_size;
-        let len = escrow_ids.len();
-
-        if start >= len {
-            return Ok(result);
-        }
+        escrow.release_window = new_window;
+        env.storage().persistent().set(&escrow_key, &escrow);
 
-        let end = (start + page_size).min(len);
-        if reverse {
-            for position in start..end {
-                if let Some(escrow_id) = escrow_ids.get(len - 1 - position) {
-                    result.push_back(escrow_id);
-                }
-            }
-            Ok(result)
-        } else {
-            Ok(escrow_ids.slice(start..end))
-        }
+        Self::emit_escrow_created(
+            &env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action: EscrowAction::Extended,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
     }
 
-    /// Get escrows for a specific seller with pagination.
-    /// Uses indexed storage for scalability, with fallback to legacy vector storage.
-    pub fn get_escrows_by_seller(
+    /// Seller asks the buyer for more time before the release window closes.
+    ///
+    /// Only one request may be pending per escrow; a second call returns
+    /// ProposalAlreadyExists until the buyer responds or the seller calls
+    /// `cancel_release_extension`. The request is dropped when the escrow
+    /// settles. The extended window must stay within
+    /// `MAX_TOTAL_RELEASE_WINDOW`.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `additional_seconds` - Time in seconds to add to the release window
+    /// * `reason` - Why the seller needs the extension
+    pub fn request_release_extension(
         env: Env,
-        seller: Address,
-        page: u32,
-        page_size: u32,
-        reverse: bool,
-    ) -> Result<soroban_sdk::Vec<u64>, Error> {
-        seller.require_auth();
-        let mut result = soroban_sdk::Vec::new(&env);
-
-        let page_size = page_size.min(MAX_PAGE_SIZE);
-        if page_size == 0 {
-            return Ok(result);
+        order_id: u32,
+        additional_seconds: u32,
+        reason: ExtensionReason,
+    ) -> Result<(), Error> {
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound);
         }
+        let escrow = Self::get_stored_escrow(&env, order_id);
+        escrow.seller.require_auth();
 
-        // Try new indexed storage first
-        let count_key = DataKey::SellerEscrowCount(seller.clone());
-        if env.storage().persistent().has(&count_key) {
-            let total_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0u32);
-            let start = page * page_size;
-
-            if start >= total_count {
-                return Ok(result);
-            }
-
-            let end = (start + page_size).min(total_count);
-
-            for position in start..end {
-                let storage_index = if reverse {
-                    total_count - 1 - position
-                } else {
-                    position
-                };
-                let index_key = DataKey::SellerEscrowIndexed(seller.clone(), storage_index);
-                if let Some(escrow_id) = env.storage().persistent().get::<_, u64>(&index_key) {
-                    result.push_back(escrow_id);
-                    Self::extend_persistent_read(&env, &index_key);
-                }
-            }
-
-            Self::extend_persistent_read(&env, &count_key);
-            return Ok(result);
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::InvalidEscrowState);
         }
-
-        // Fallback to legacy vector storage for backward compatibility
-        let legacy_key = DataKey::SellerEscrows(seller);
-        let escrow_ids: soroban_sdk::Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&legacy_key)
-            .unwrap_or(soroban_sdk::Vec::new(&env));
-
-        if env.storage().persistent().has(&legacy_key) {
-            Self::extend_persistent_read(&env, &legacy_key);
+        if additional_seconds == 0 {
+            return Err(Error::ReleaseWindowTooShort);
         }
-
-        let start = page * page_size;
-        let len = escrow_ids.len();
-
-        if start >= len {
-            return Ok(result);
+        if escrow.release_window.saturating_add(additional_seconds) > MAX_TOTAL_RELEASE_WINDOW {
+            return Err(Error::ReleaseWindowTooLong);
         }
 
-        let end = (start + page_size).min(len);
-        if reverse {
-            for position in start..end {
-                if let Some(escrow_id) = escrow_ids.get(len - 1 - position) {
-                    result.push_back(escrow_id);
-                }
-            }
-            Ok(result)
-        } else {
-            Ok(escrow_ids.slice(start..end))
+        let request_key = ExtensionDataKey::Request(order_id);
+        if env.storage().persistent().has(&request_key) {
+            return Err(Error::ProposalAlreadyExists);
         }
-    }
 
-    /// Get platform configuration
-    pub fn get_platform_config(env: Env) -> PlatformConfig {
-        Self::get_platform_config_internal(&env)
-    }
+        env.storage().persistent().set(
+            &request_key,
+            &ExtensionRequest {
+                order_id,
+                additional_seconds,
+                reason,
+                requested_at: env.ledger().timestamp(),
+            },
+        );
+        Self::extend_persistent(&env, &request_key);
 
-    fn get_platform_config_internal(env: &Env) -> PlatformConfig {
-        env.storage()
-            .instance()
-            .extend_ttl(TTL_THRESHOLD, TTL_EXTENSION);
-        env.storage()
-            .instance()
-            .get(&DataKey::PlatformConfig)
-            .unwrap_or_else(|| env.panic_with_error(crate::Error::PlatformNotInitialized))
+        Self::emit_release_extension(
+            &env,
+            &escrow,
+            order_id,
+            EscrowAction::ExtensionRequested,
+            additional_seconds,
+            reason,
+        );
+        Ok(())
     }
 
-    fn set_paused_internal(env: &Env, paused: bool) -> Result<(), Error> {
-        let mut config = Self::get_platform_config_internal(env);
-        config.is_paused = paused;
-        env.storage().instance().set(&DataKey::PlatformConfig, &config);
-
-        if paused {
-            Self::emit_platform_paused(env, config.admin.clone());
-        } else {
-            Self::emit_platform_unpaused(env, config.admin.clone());
+    /// Buyer accepts or rejects the pending extension request.
+    ///
+    /// On acceptance the release window grows by the requested amount,
+    /// re-checked against `MAX_TOTAL_RELEASE_WINDOW` in case the buyer
+    /// extended it in the meantime. Either way the request is cleared.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `accept` - `true` to grant the extension, `false` to reject it
+    pub fn respond_to_release_extension(
+        env: Env,
+        order_id: u32,
+        accept: bool,
+    ) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound);
         }
-        Ok(())
-    }
+        let mut escrow = Self::get_stored_escrow(&env, order_id);
+        escrow.buyer.require_auth();
 
-    fn try_get_escrow_readonly(env: &Env, order_id: u32) -> Escrow {
-        let key = (ESCROW, order_id);
-        let stored: Val = env
+        let request_key = ExtensionDataKey::Request(order_id);
+        let request: ExtensionRequest = env
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| env.panic_with_error(crate::Error::EscrowNotFound));
-        let map = Map::<Symbol, Val>::try_from_val(env, &stored).expect("");
-        let version_key = Symbol::new(env, "version");
-
-        if map.contains_key(version_key) {
-            let batch_id_key = Symbol::new(env, "batch_id");
-            if map.contains_key(batch_id_key) {
-                let mut escrow = Escrow::try_from_val(env, &stored).expect("");
-                if escrow.version < CURRENT_ESCROW_VERSION {
-                    escrow.version = CURRENT_ESCROW_VERSION;
-                }
-                Self::extend_persistent(env, &key); // OPTIMIZED: Ensure TTL extension on read
-                return escrow;
-            }
+            .get(&request_key)
+            .ok_or(Error::ProposalNotFound)?;
 
-            let previous = EscrowWithoutBatch::try_from_val(env, &stored).expect("");
-            let mut escrow = Self::escrow_from_without_batch(env, previous);
-            if escrow.version < CURRENT_ESCROW_VERSION {
-                escrow.version = CURRENT_ESCROW_VERSION;
-            }
-            Self::extend_persistent(env, &key); // OPTIMIZED: Ensure TTL extension on read
-            return escrow;
+        if escrow.status != EscrowStatus::Active {
+            return Err(Error::InvalidEscrowState);
         }
 
-        let legacy = LegacyEscrow::try_from_val(env, &stored).expect("");
-
-        let dispute_symbol = legacy.dispute_reason.map(|r| {
-            // Safety bound: Symbols max at 32 chars. Truncate if legacy reason is too long.
-            let len = r.len() as usize;
-            let slice_len = core::cmp::min(len, 32);
-            let mut buf = [0u8; 32];
-            r.copy_into_slice(&mut buf[..slice_len]);
-            let s = core::str::from_utf8(&buf[..slice_len]).unwrap();
-            Symbol::new(env, s)
-        });
-
-        let upgraded = Escrow {
-            version: CURRENT_ESCROW_VERSION,
-            id: legacy.id,
-            batch_id: None,
-            buyer: legacy.buyer,
-            seller: legacy.seller,
-            token: legacy.token,
-            amount: legacy.amount,
-            status: legacy.status,
-            release_window: legacy.release_window,
-            created_at: legacy.created_at,
-            ipfs_hash: legacy.ipfs_hash,
-            metadata_hash: legacy.metadata_hash,
-            dispute_reason: dispute_symbol,
-            dispute_initiated_at: legacy.dispute_initiated_at,
-            funded: true,
-            funding_deadline: None, // Legacy escrows were funded at creation
+        let action = if accept {
+            let new_window = escrow
+                .release_window
+                .saturating_add(request.additional_seconds);
+            if new_window > MAX_TOTAL_RELEASE_WINDOW {
+                return Err(Error::ReleaseWindowTooLong);
+            }
+            escrow.release_window = new_window;
+            env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+            EscrowAction::Extended
+        } else {
+            EscrowAction::ExtensionRejected
         };
-        Self::extend_persistent(env, &key); // OPTIMIZED: Ensure TTL extension on read
-        upgraded
+        env.storage().persistent().remove(&request_key);
+
+        Self::emit_release_extension(
+            &env,
+            &escrow,
+            order_id,
+            action,
+            request.additional_seconds,
+            request.reason,
+        );
+        Ok(())
     }
 
-    fn get_stored_escrow(env: &Env, order_id: u32) -> Escrow {
-        let key = (ESCROW, order_id);
-        let stored: Val = env
+    /// Seller withdraws its pending extension request.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    pub fn cancel_release_extension(env: Env, order_id: u32) -> Result<(), Error> {
+        if !env.storage().persistent().has(&(ESCROW, order_id)) {
+            return Err(Error::EscrowNotFound);
+        }
+        let escrow = Self::get_stored_escrow(&env, order_id);
+        escrow.seller.require_auth();
+
+        let request_key = ExtensionDataKey::Request(order_id);
+        let request: ExtensionRequest = env
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| env.panic_with_error(crate::Error::EscrowNotFound));
-        let map = Map::<Symbol, Val>::try_from_val(env, &stored).expect("");
-        let version_key = Symbol::new(env, "version");
+            .get(&request_key)
+            .ok_or(Error::ProposalNotFound)?;
+        env.storage().persistent().remove(&request_key);
 
-        if map.contains_key(version_key) {
-            let batch_id_key = Symbol::new(env, "batch_id");
-            let escrow = if map.contains_key(batch_id_key) {
-                Escrow::try_from_val(env, &stored).expect("")
-            } else {
-                let previous = EscrowWithoutBatch::try_from_val(env, &stored).expect("");
-                Self::escrow_from_without_batch(env, previous)
-            };
-            if escrow.version < CURRENT_ESCROW_VERSION {
-                return Self::upgrade_escrow(env, order_id, escrow);
-            }
-            Self::extend_persistent(env, &key);
-            return escrow;
-        }
+        Self::emit_release_extension(
+            &env,
+            &escrow,
+            order_id,
+            EscrowAction::ExtensionCancelled,
+            request.additional_seconds,
+            request.reason,
+        );
+        Ok(())
+    }
 
-        let legacy = LegacyEscrow::try_from_val(env, &stored).expect("");
-        let dispute_symbol = legacy.dispute_reason.map(|r| {
-            let len = r.len() as usize;
-            let slice_len = core::cmp::min(len, 32);
-            let mut buf = [0u8; 32];
-            r.copy_into_slice(&mut buf[..slice_len]);
-            let s = core::str::from_utf8(&buf[..slice_len]).unwrap();
-            Symbol::new(env, s)
-        });
-        let upgraded = Escrow {
-            version: CURRENT_ESCROW_VERSION,
-            id: legacy.id,
-            batch_id: None,
-            buyer: legacy.buyer,
-            seller: legacy.seller,
-            token: legacy.token,
-            amount: legacy.amount,
-            status: legacy.status,
-            release_window: legacy.release_window,
-            created_at: legacy.created_at,
-            ipfs_hash: legacy.ipfs_hash,
-            metadata_hash: legacy.metadata_hash,
-            dispute_reason: dispute_symbol,
-            dispute_initiated_at: legacy.dispute_initiated_at,
-            funded: true,
-            funding_deadline: None, // Legacy escrows were funded at creation
-        };
-        env.storage().persistent().set(&key, &upgraded);
-        Self::extend_persistent(env, &key);
-        upgraded
+    /// Get the pending release window extension request for an order.
+    pub fn get_release_extension_request(env: Env, order_id: u32) -> Option<ExtensionRequest> {
+        env.storage()
+            .persistent()
+            .get(&ExtensionDataKey::Request(order_id))
     }
 
-    fn claim_active_escrow_transition(
+    fn emit_release_extension(
         env: &Env,
+        escrow: &Escrow,
         order_id: u32,
-        pending_status: EscrowStatus,
-    ) -> Result<Escrow, Error> {
-        let mut escrow = Self::get_stored_escrow(env, order_id);
-        if escrow.status != EscrowStatus::Active {
-            return Err(Error::InvalidEscrowState);
-        }
-
-        escrow.status = pending_status;
-        let key = (ESCROW, order_id);
-        env.storage().persistent().set(&key, &escrow);
-        Self::extend_persistent(env, &key);
-        Ok(escrow)
-    }
-
-    fn upgrade_escrow(env: &Env, order_id: u32, mut escrow: Escrow) -> Escrow {
-        if escrow.version < 3 {
-            escrow.funded = true;
-        }
-        escrow.version = CURRENT_ESCROW_VERSION;
-        let key = (ESCROW, order_id);
-        env.storage().persistent().set(&key, &escrow);
-        Self::extend_persistent(env, &key);
-        escrow
-    }
-
-    fn escrow_from_without_batch(env: &Env, escrow: EscrowWithoutBatch) -> Escrow {
-        let dispute_symbol = escrow.dispute_reason.map(|r| {
-            let len = r.len() as usize;
-            let slice_len = core::cmp::min(len, 32);
-            let mut buf = [0u8; 32];
-            r.copy_into_slice(&mut buf[..slice_len]);
-            let s = core::str::from_utf8(&buf[..slice_len]).unwrap();
-            Symbol::new(env, s)
-        });
-
-        Escrow {
-            version: escrow.version,
-            id: escrow.id,
-            batch_id: None,
-            buyer: escrow.buyer,
-            seller: escrow.seller,
-            token: escrow.token,
-            amount: escrow.amount,
-            status: escrow.status,
-            release_window: escrow.release_window,
-            created_at: escrow.created_at,
-            ipfs_hash: escrow.ipfs_hash,
-            metadata_hash: escrow.metadata_hash,
-            dispute_reason: dispute_symbol, // Map to lightweight Symbol
-            dispute_initiated_at: escrow.dispute_initiated_at,
-            funded: true,
-            funding_deadline: None, // Legacy escrows were funded at creation
-        }
+        action: EscrowAction,
+        additional_seconds: u32,
+        reason: ExtensionReason,
+    ) {
+        Self::emit_escrow_created(
+            env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        env.events().publish(
+            (Symbol::new(env, "release_extension"), order_id),
+            ReleaseExtensionEvent {
+                order_id,
+                action,
+                additional_seconds,
+                reason,
+                release_window: escrow.release_window,
+            },
+        );
     }
 
-    /// Calculate platform fee for a given amount.
-    #[inline(always)]
-    fn try_calculate_fee(amount: i128, fee_bps: u32) -> Result<i128, Error> {
-        if amount < 0 {
-            return Err(Error::InvalidFee);
+    /// Reject obviously invalid WASM hashes before they touch storage.
+    ///
+    /// The Soroban host validates that the hash points to an uploaded WASM at
+    /// `update_current_contract_wasm` time, but only at execution. Catching
+    /// the all-zero sentinel here avoids the worst footgun (an admin
+    /// accidentally proposing the default `BytesN<32>::from_array(_, [0; 32])`)
+    /// and gives a meaningful error code instead of a host trap.
+    fn validate_upgrade_hash(env: &Env, hash: &BytesN<32>) -> Result<(), Error> {
+        let zero = BytesN::<32>::from_array(env, &[0u8; 32]);
+        if hash == &zero {
+            return Err(Error::InvalidUpgradeHash);
         }
-
-        amount
-            .checked_mul(fee_bps as i128)
-            .and_then(|product| product.checked_div(10_000))
-            .ok_or(Error::InvalidFee)
+        Ok(())
     }
 
-    #[inline(always)]
-    fn calculate_fee(env: &Env, amount: i128, fee_bps: u32) -> i128 {
-        Self::try_calculate_fee(amount, fee_bps).unwrap_or_else(|err| env.panic_with_error(err))
+    fn emit_upgrade_event(
+        env: &Env,
+        action: Symbol,
+        wasm_hash: BytesN<32>,
+        admin: Address,
+        upgrade_at: u64,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "wasm_upgrade"), action.clone()),
+            UpgradeProposalEvent {
+                action,
+                wasm_hash,
+                admin,
+                timestamp: env.ledger().timestamp(),
+                upgrade_at,
+            },
+        );
     }
 
-    /// Deterministically compute how the escrow pot is split for any
-    /// settlement path.
-    ///
-    /// This is the **single source of truth** for all fee math in the
-    /// contract.  Every settlement function — `release_funds`, `auto_release`,
-    /// `release_batch_funds`, `refund`, `resolve_dispute`,
-    /// `resolve_expired_dispute`, and `accept_partial_refund` — **must** obtain
-    /// its transfer amounts exclusively from this function and must not perform
-    /// fee arithmetic inline.
-    ///
-    /// # Invariant
-    ///
-    /// The three output fields always satisfy:
-    ///
-    /// ```text
-    /// allocation.platform_fee + allocation.seller_amount + allocation.buyer_amount
-    ///     == escrow_amount
-    /// ```
-    ///
-    /// This invariant is checked by the test suite for every `SettlementKind`.
+    /// Propose a new WASM code for the contract (multi-sig).
     ///
-    /// # Arguments
+    /// Each authorized upgrade signer calls this function with the same
+    /// `new_wasm_hash`. Approvals are accumulated until the configured
+    /// `UpgradeThreshold` is reached, at which point the proposal is committed
+    /// and the cooldown clock starts. A signer cannot approve the same hash
+    /// twice. When no explicit signers list is configured the admin acts as the
+    /// sole signer (backward-compatible default, threshold=1). The first
+    /// approval of a round must come from a `Role::UpgradeProposer` holder.
     ///
-    /// * `env`           - Soroban environment (for panicking on overflow).
-    /// * `escrow_amount` - Total amount held in escrow (must be >= 0).
-    /// * `fee_bps`       - Effective fee in basis points for this escrow's
-    ///                     seller, obtained via `get_effective_fee_bps`.
-    /// * `kind`          - Which settlement formula to apply.
-    fn compute_fee_allocation(
-        env: &Env,
-        escrow_amount: i128,
-        fee_bps: u32,
-        kind: SettlementKind,
-    ) -> FeeAllocation {
-        let allocation = match kind {
-            // ── Normal release: platform fee from seller's share ──────────────
-            SettlementKind::ReleaseFunds => {
-                let platform_fee = Self::calculate_fee(env, escrow_amount, fee_bps);
-                let seller_amount = escrow_amount - platform_fee;
-                FeeAllocation {
-                    platform_fee,
-                    seller_amount,
-                    buyer_amount: 0,
-                }
+    /// Only one proposal may be pending at a time. Cancel with
+    /// `cancel_upgrade_wasm` before starting a new one.
+    pub fn propose_upgrade_wasm(
+        env: Env,
+        signer: Address,
+        new_wasm_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        signer.require_auth();
+
+        Self::validate_upgrade_hash(&env, &new_wasm_hash)?;
+
+        // Issue #618: Prevent cancel-and-repropose from resetting the review
+        // window. If a proposal was recently cancelled, the proposer must wait
+        // CANCEL_REPROPOSE_COOLDOWN seconds before submitting a new one.
+        if let Some(cancelled_at) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, u64>(&DataKey::LastUpgradeCancelledAt)
+        {
+            let now = env.ledger().timestamp();
+            if now < cancelled_at.saturating_add(CANCEL_REPROPOSE_COOLDOWN) {
+                return Err(Error::UpgradeCooldownActive);
             }
+        }
 
-            // ── Full refund, no fee: entire pot returned to buyer ─────────────
-            SettlementKind::FullRefundNoFee => FeeAllocation {
-                platform_fee: 0,
-                seller_amount: 0,
-                buyer_amount: escrow_amount,
-            },
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::WasmUpgradeProposal)
+        {
+            return Err(Error::UpgradeProposalExists);
+        }
 
-            // ── Expired dispute – fee conceptually from seller ────────────────
-            // Buyer receives the full amount; the platform does NOT collect
-            // the fee.  The seller's loss is the opportunity cost of the
-            // stalled arbitration.  Balances because platform_fee=0.
-            SettlementKind::ExpiredDisputeDeductFromSeller => FeeAllocation {
-                platform_fee: 0,
-                seller_amount: 0,
-                buyer_amount: escrow_amount,
-            },
+        // -- Approval state (singleton key, nonce inside struct) ----------------
+        // Approval state is stored at a fixed slot UpgradeApprovalState(0).
+        // The `nonce` field inside the struct is incremented on every
+        // cancel_upgrade_wasm call, so a re-proposal after cancellation
+        // starts a fresh round with a different nonce, making the old
+        // approvals stale and detectable.
+        let state_key = DataKey::UpgradeApprovalState(0);
 
-            // ── Expired dispute – fee deducted from buyer's refund ────────────
-            SettlementKind::ExpiredDisputeDeductFromBuyer => {
-                let platform_fee = Self::calculate_fee(env, escrow_amount, fee_bps);
-                let buyer_amount = escrow_amount - platform_fee;
-                FeeAllocation {
-                    platform_fee,
-                    seller_amount: 0,
-                    buyer_amount,
-                }
-            }
+        // Read the current nonce from any pre-existing state, or default to 0.
+        let current_nonce: u32 = env
+            .storage()
+            .persistent()
+            .get::<DataKey, UpgradeApprovalState>(&state_key)
+            .map(|s| s.nonce)
+            .unwrap_or(0u32);
 
-            // ── Expired dispute – fee split equally between both sides ─────────
-            SettlementKind::ExpiredDisputeSplitFee => {
-                let full_fee = Self::calculate_fee(env, escrow_amount, fee_bps);
-                // Integer division: any remainder (odd-bps rounding) stays with buyer.
-                let platform_fee = full_fee / 2;
-                let buyer_amount = escrow_amount - platform_fee;
-                FeeAllocation {
-                    platform_fee,
-                    seller_amount: 0,
-                    buyer_amount,
-                }
+        // Helper closure: snapshot current live signers + threshold into a fresh state.
+        let fresh_state = |nonce: u32| -> UpgradeApprovalState {
+            let snapshotted_signers: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UpgradeSigners)
+                .unwrap_or_else(|| {
+                    let mut v = Vec::new(&env);
+                    if let Ok(admin) = Self::get_admin(&env) {
+                        v.push_back(admin);
+                    }
+                    v
+                });
+            let snapshotted_threshold: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::UpgradeThreshold)
+                .unwrap_or(1u32);
+            UpgradeApprovalState {
+                nonce,
+                signers: snapshotted_signers,
+                threshold: snapshotted_threshold,
+                approvals: Vec::new(&env),
             }
+        };
 
-            // ── Partial refund: dual-sided fee deduction ──────────────────────
-            // The gross amounts must sum to `escrow_amount`; fees are computed
-            // on each gross portion independently using the same `fee_bps`.
-            SettlementKind::PartialRefund(refund_gross, seller_gross) => {
-                // Defensive: clamp to escrow_amount to prevent accounting drift.
-                let safe_refund_gross = if refund_gross < 0 { 0 } else { refund_gross };
-                let safe_seller_gross = if seller_gross < 0 { 0 } else { seller_gross };
+        let mut state: UpgradeApprovalState = env
+            .storage()
+            .persistent()
+            .get(&state_key)
+            // Reuse stored state only when:
+            //  (a) it has the same nonce (was not left from a cancelled round), AND
+            //  (b) the round has already started (signers list is non-empty).
+            // A state with an empty signers list is a cancel-sentinel written by
+            // cancel_upgrade_wasm to carry the bumped nonce forward; it must not
+            // be treated as a live round.
+            .filter(|s: &UpgradeApprovalState| {
+                s.nonce == current_nonce && !s.signers.is_empty()
+            })
+            .unwrap_or_else(|| fresh_state(current_nonce));
 
-                let refund_fee = Self::calculate_fee(env, safe_refund_gross, fee_bps);
-                let seller_fee = Self::calculate_fee(env, safe_seller_gross, fee_bps);
+        // Opening a round takes the UpgradeProposer role; later approvals
+        // only need a place in the snapshot.
+        if state.approvals.is_empty()
+            && !Self::has_role(env.clone(), Role::UpgradeProposer, signer.clone())
+        {
+            return Err(Error::Unauthorized);
+        }
 
-                let platform_fee = refund_fee.saturating_add(seller_fee);
-                let buyer_amount = safe_refund_gross - refund_fee;
-                let seller_amount = safe_seller_gross - seller_fee;
+        // Validate against the *snapshotted* signer set -- live storage is
+        // intentionally not consulted here.
+        if !state.signers.iter().any(|s| s == signer) {
+            return Err(Error::NotAnUpgradeSigner);
+        }
 
-                FeeAllocation {
-                    platform_fee,
-                    seller_amount,
-                    buyer_amount,
-                }
-            }
-        };
+        if state.approvals.iter().any(|a| a == signer) {
+            return Err(Error::AlreadyApproved);
+        }
+        state.approvals.push_back(signer.clone());
 
-        // Deterministic balance invariant: the three-way split must exactly
-        // consume the escrow pot with no remainder.
-        let sum = allocation
-            .platform_fee
-            .checked_add(allocation.seller_amount)
-            .and_then(|s| s.checked_add(allocation.buyer_amount));
-        if sum != Some(escrow_amount) {
-            env.panic_with_error(crate::Error::InvalidFee);
+        // All entries in state.approvals were validated against state.signers
+        // when they were added, so a simple length check is sufficient.
+        if (state.approvals.len() as u32) < state.threshold {
+            // Threshold not yet met -- persist updated state and return.
+            env.storage().persistent().set(&state_key, &state);
+            Self::extend_persistent(&env, &state_key);
+            return Ok(());
         }
 
-        allocation
-    }
+        // Threshold reached -- commit the proposal and clean up approval state.
+        // Remove approval state for this nonce; it is no longer needed.
+        env.storage().persistent().remove(&state_key);
 
-    /// Maintain the dual fee-token bookkeeping (#239).
-    ///
-    /// Historically fee-receiving tokens were tracked only in the legacy
-    /// `FeeTokenIndex` Vec. That single-key shape made future multi-token
-    /// fee features (custom bps per token, disabling tokens, accumulator
-    /// reconciliation) impossible without a contract upgrade. We now also
-    /// write a per-token `FeeTokenConfig(token)` slot, which is the storage
-    /// shape new code should read going forward. The legacy Vec is kept as
-    /// the canonical enumeration source for backward compatibility â€” a
-    /// `migrate_fee_token_configs` admin call backfills `FeeTokenConfig` for
-    /// pre-existing tokens.
-    fn add_fee_token_to_index(env: &Env, token: &Address) {
-        let key = DataKey::FeeTokenIndex;
-        let mut tracked_tokens: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
+        let config = Self::get_platform_config_internal(&env);
+        let proposed_at = env.ledger().timestamp();
+        let upgrade_at = proposed_at + config.wasm_upgrade_cooldown as u64;
+        let proposal = WasmUpgradeProposal {
+            wasm_hash: new_wasm_hash.clone(),
+            upgrade_at,
+            proposed_by: signer.clone(),
+            proposed_at,
+        };
 
-        let mut already_tracked = false;
-        for index in 0..tracked_tokens.len() {
-            if tracked_tokens.get(index) == Some(token.clone()) {
-                already_tracked = true;
-                break;
-            }
-        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::WasmUpgradeProposal, &proposal);
+        Self::extend_persistent(&env, &DataKey::WasmUpgradeProposal);
 
-        if !already_tracked {
-            tracked_tokens.push_back(token.clone());
-            env.storage().persistent().set(&key, &tracked_tokens);
-        }
-        Self::extend_persistent(env, &key);
+        Self::emit_upgrade_event(&env, UPGRADE_PROPOSED, new_wasm_hash, signer, upgrade_at);
 
-        Self::ensure_fee_token_config(env, token);
+        Ok(())
     }
 
-    /// Seed a default `FeeTokenInfo` slot the first time a token is seen.
-    /// Idempotent â€” once a slot exists, subsequent calls leave it untouched
-    /// so admin overrides survive future fee deposits (#239).
-    fn ensure_fee_token_config(env: &Env, token: &Address) {
-        let cfg_key = DataKey::FeeTokenConfig(token.clone());
-        if !env.storage().persistent().has(&cfg_key) {
-            let info = FeeTokenInfo {
-                active: true,
-                custom_fee_bps: None,
-                accumulated: 0,
-            };
-            env.storage().persistent().set(&cfg_key, &info);
+    /// Set the number of distinct signer approvals required to commit a WASM
+    /// upgrade proposal. Must be >= 1. Admin only.
+    pub fn set_upgrade_threshold(env: Env, threshold: u32) -> Result<(), Error> {
+        if threshold == 0 {
+            return Err(Error::InvalidFee);
         }
-        Self::extend_persistent(env, &cfg_key);
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::UpgradeThreshold, &threshold);
+        Ok(())
     }
 
-    /// Bump the per-token accumulator inside `FeeTokenConfig` (#239). Kept
-    /// internal so external callers cannot tamper with the running total.
-    fn bump_fee_token_accumulator(env: &Env, token: &Address, amount: i128) {
-        if amount <= 0 {
-            return;
-        }
-        Self::ensure_fee_token_config(env, token);
-        let cfg_key = DataKey::FeeTokenConfig(token.clone());
-        let mut info: FeeTokenInfo =
+    /// Replace the list of addresses authorized to co-sign WASM upgrade
+    /// proposals. An empty list resets to the admin-only default. Admin only.
+    pub fn set_upgrade_signers(env: Env, signers: Vec<Address>) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+        if signers.is_empty() {
+            env.storage().persistent().remove(&DataKey::UpgradeSigners);
+        } else {
             env.storage()
                 .persistent()
-                .get(&cfg_key)
-                .unwrap_or(FeeTokenInfo {
-                    active: true,
-                    custom_fee_bps: None,
-                    accumulated: 0,
-                });
-        info.accumulated = info.accumulated.saturating_add(amount);
-        env.storage().persistent().set(&cfg_key, &info);
-        Self::extend_persistent(env, &cfg_key);
+                .set(&DataKey::UpgradeSigners, &signers);
+            Self::extend_persistent(&env, &DataKey::UpgradeSigners);
+        }
+        Ok(())
     }
 
-    /// Returns the per-token fee configuration for `token`, or `None` if the
-    /// token has never received platform fees (#239).
-    pub fn get_fee_token_config(env: Env, token: Address) -> Option<FeeTokenInfo> {
+    /// Returns the configured upgrade threshold (defaults to 1).
+    pub fn get_upgrade_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::UpgradeThreshold)
+            .unwrap_or(1u32)
+    }
+
+    /// Returns the current proposal round nonce.
+    ///
+    /// The nonce is incremented on every `cancel_upgrade_wasm` call.  Callers
+    /// can use it to look up the active `UpgradeApprovalState` via
+    /// `get_upgrade_approvals`.
+    pub fn get_upgrade_proposal_nonce(env: Env) -> u32 {
         env.storage()
             .persistent()
-            .get(&DataKey::FeeTokenConfig(token))
+            .get::<DataKey, UpgradeApprovalState>(&DataKey::UpgradeApprovalState(0))
+            .map(|s| s.nonce)
+            .unwrap_or(0u32)
     }
 
-    /// Returns every token that has ever received platform fees (#239).
-    /// Reads the legacy `FeeTokenIndex` Vec; new callers should pair this
-    /// enumeration with `get_fee_token_config` for richer per-token data.
-    pub fn get_fee_tokens(env: Env) -> Vec<Address> {
+    /// Returns the list of pending approvals for the given proposal nonce.
+    ///
+    /// Pass the value returned by `get_upgrade_proposal_nonce` to inspect the
+    /// current round.  Returns an empty vec if no approvals exist for that
+    /// nonce (i.e. the round has not started or was already committed/cancelled).
+    pub fn get_upgrade_approvals(env: Env, nonce: u32) -> Vec<Address> {
+        // Returns approvals only if the stored state matches the requested nonce.
         env.storage()
             .persistent()
-            .get(&DataKey::FeeTokenIndex)
+            .get::<DataKey, UpgradeApprovalState>(&DataKey::UpgradeApprovalState(0))
+            .filter(|s| s.nonce == nonce)
+            .map(|s| s.approvals)
             .unwrap_or_else(|| Vec::new(&env))
     }
 
-    /// Update mutable fields of a `FeeTokenInfo` slot (admin only, #239).
+    /// Return the persisted storage layout version.
+    pub fn get_storage_layout_version(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StorageLayoutVersion)
+            .unwrap_or(0)
+    }
+
+    /// Migrate persisted storage to the current layout version (admin only).
     ///
-    /// `active` and `custom_fee_bps` are admin-controlled. `accumulated` is
-    /// IGNORED if passed in â€” the running total is owned by the contract and
-    /// only `record_total_fees` may move it. This split prevents an admin
-    /// from rewriting historical fee accounting via the config setter.
+    /// Kept for existing clients; role holders call `migrate_storage_layout_as`.
+    pub fn migrate_storage_layout(env: Env) -> u32 {
+        let admin = Self::get_admin(&env).unwrap_or_else(|_| {
+            env.panic_with_error(crate::Error::PlatformNotInitialized)
+        });
+        Self::migrate_storage_layout_as(env, admin)
+    }
+
+    /// Migrate persisted storage to the current layout version.
     ///
-    /// `custom_fee_bps`, when set, must satisfy `<= MAX_PLATFORM_FEE_BPS`.
-    /// The value is currently informational; `calculate_fee` does not yet
-    /// consult it (storage-only change to keep #239 scope tight).
-    pub fn set_fee_token_config(
-        env: Env,
-        token: Address,
-        active: bool,
-        custom_fee_bps: Option<u32>,
-    ) -> Result<(), Error> {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// This is an explicit, `Role::Migrator`-gated migration path for legacy
+    /// deployments. Existing state is preserved by running the existing lazy
+    /// migration helpers before recording the new layout version.
+    pub fn migrate_storage_layout_as(env: Env, caller: Address) -> u32 {
+        Self::require_role(&env, Role::Migrator, &caller);
 
-        if let Some(bps) = custom_fee_bps {
-            if bps > MAX_PLATFORM_FEE_BPS {
-                return Err(Error::InvalidFee);
-            }
+        let current_version: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StorageLayoutVersion)
+            .unwrap_or(0);
+        if current_version == CURRENT_STORAGE_LAYOUT_VERSION {
+            return 0;
         }
 
-        let cfg_key = DataKey::FeeTokenConfig(token.clone());
-        let existing: FeeTokenInfo =
-            env.storage()
-                .persistent()
-                .get(&cfg_key)
-                .unwrap_or(FeeTokenInfo {
-                    active: true,
-                    custom_fee_bps: None,
-                    accumulated: 0,
-                });
+        Self::migrate_legacy_all_escrow_ids(&env);
+        Self::migrate_legacy_whitelisted_tokens(&env);
 
-        let info = FeeTokenInfo {
-            active,
-            custom_fee_bps,
-            accumulated: existing.accumulated,
-        };
-        env.storage().persistent().set(&cfg_key, &info);
-        Self::extend_persistent(&env, &cfg_key);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StorageLayoutVersion, &CURRENT_STORAGE_LAYOUT_VERSION);
+        Self::extend_persistent(&env, &DataKey::StorageLayoutVersion);
+
+        1
+    }
+
+    fn ensure_storage_layout_compatible(env: &Env) -> Result<(), Error> {
+        let stored_version: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StorageLayoutVersion)
+            .unwrap_or(0);
+        if stored_version != CURRENT_STORAGE_LAYOUT_VERSION {
+            return Err(Error::StorageLayoutMismatch);
+        }
         Ok(())
     }
 
-    /// Backfill `FeeTokenConfig(token)` slots for every token currently
-    /// present in the legacy `FeeTokenIndex` Vec (admin only, #239).
+    /// Upgrade the contract's WASM code after the grace period has elapsed.
     ///
-    /// Idempotent â€” already-migrated tokens are skipped, so it is safe to
-    /// call from a deploy script or an automated migration job. Returns the
-    /// number of new config slots written so callers can verify progress.
-    /// Pairs with `set_fee_token_config` for downstream tuning.
-    pub fn migrate_fee_token_configs(env: Env) -> Result<u32, Error> {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// The caller passes the `expected_wasm_hash` they think is pending; if it
+    /// does not match the stored proposal the call fails with
+    /// `InvalidUpgradeHash`. This is defense-in-depth against a scenario where
+    /// the admin's signing tool is shown a different proposal than what was
+    /// actually stored on-chain, and forces the operator to confirm exactly
+    /// which payload is being applied (#230).
+    ///
+    /// On success a new `UpgradeRecord` is appended to `UpgradeHistory`,
+    /// `ContractVersion` is bumped, and the proposal is cleared atomically.
+    pub fn execute_upgrade(env: Env, expected_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::ensure_storage_layout_compatible(&env)?;
 
-        let tokens: Vec<Address> = env
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+
+        let proposal: WasmUpgradeProposal = env
             .storage()
             .persistent()
-            .get(&DataKey::FeeTokenIndex)
-            .unwrap_or_else(|| Vec::new(&env));
+            .get(&DataKey::WasmUpgradeProposal)
+            .ok_or(Error::NoUpgradeProposed)?;
 
-        let scanned_tokens = tokens.len();
-        let mut migrated: u32 = 0;
-        let mut skipped_existing: u32 = 0;
-        for index in 0..tokens.len() {
-            if let Some(token) = tokens.get(index) {
-                let cfg_key = DataKey::FeeTokenConfig(token.clone());
-                if !env.storage().persistent().has(&cfg_key) {
-                    let info = FeeTokenInfo {
-                        active: true,
-                        custom_fee_bps: None,
-                        accumulated: env
-                            .storage()
-                            .persistent()
-                            .get(&DataKey::TotalFees(token))
-                            .unwrap_or(0i128),
-                    };
-                    env.storage().persistent().set(&cfg_key, &info);
-                    Self::extend_persistent(&env, &cfg_key);
-                    migrated += 1;
-                } else {
-                    Self::extend_persistent(&env, &cfg_key);
-                    skipped_existing += 1;
-                }
-            }
+        if proposal.wasm_hash != expected_wasm_hash {
+            return Err(Error::InvalidUpgradeHash);
         }
 
-        env.events().publish(
-            (Symbol::new(&env, "fee_cfg_migrated"),),
-            FeeTokenConfigsMigratedEvent {
-                scanned_tokens,
-                migrated_configs: migrated,
-                skipped_existing,
+        if env.ledger().timestamp() < proposal.upgrade_at {
+            return Err(Error::UpgradeCooldownActive);
+        }
+
+        env.deployer()
+            .update_current_contract_wasm(proposal.wasm_hash.clone());
+
+        // Update version in storage
+        let current_version: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContractVersion)
+            .unwrap_or(0);
+        let new_version = current_version + 1;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::ContractVersion, &new_version);
+        Self::extend_persistent(&env, &DataKey::ContractVersion);
+
+        Self::append_upgrade_history(
+            &env,
+            UpgradeRecord {
+                from_version: current_version,
+                to_version: new_version,
+                wasm_hash: proposal.wasm_hash.clone(),
+                admin: admin.clone(),
+                timestamp: env.ledger().timestamp(),
             },
         );
 
-        Ok(migrated)
+        // Clear proposal
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WasmUpgradeProposal);
+
+        Self::emit_upgrade_event(
+            &env,
+            UPGRADE_EXECUTED,
+            proposal.wasm_hash,
+            admin,
+            proposal.upgrade_at,
+        );
+
+        Ok(())
     }
 
-    fn record_total_fees(env: &Env, token: &Address, fee_amount: i128) {
-        if fee_amount <= 0 {
-            return;
-        }
+    /// Cancel a proposed WASM upgrade (admin only) (#95, #230).
+    ///
+    /// Emits an `UPG_CANC` event so cancellations are visible alongside
+    /// proposals in the audit trail. Returning `NoUpgradeProposed` instead of
+    /// silently succeeding makes accidental double-cancels visible.
+    pub fn cancel_upgrade_wasm(env: Env) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
 
-        let key = DataKey::TotalFees(token.clone());
-        let current_total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
-        env.storage()
+        let proposal: WasmUpgradeProposal = env
+            .storage()
             .persistent()
-            .set(&key, &(current_total + fee_amount));
-        Self::extend_persistent(env, &key);
-        Self::add_fee_token_to_index(env, token);
-        // Mirror the running total into the per-token fee config so future
-        // multi-token logic has a single source of truth (#239).
-        Self::bump_fee_token_accumulator(env, token, fee_amount);
-    }
+            .get(&DataKey::WasmUpgradeProposal)
+            .ok_or(Error::NoUpgradeProposed)?;
 
-    fn append_fund_audit_record(
-        env: &Env,
-        actor: &Address,
-        amount: i128,
-        reason: Symbol,
-        balance_impact: i128,
-    ) {
-        if amount <= 0 {
-            return;
-        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WasmUpgradeProposal);
 
-        let count_key = DataKey::FundAuditCount(actor.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        let entry_key = DataKey::FundAuditIndexed(actor.clone(), count);
-        let entry = FundMovementAuditEntry {
-            actor: actor.clone(),
-            amount,
-            reason,
-            timestamp: env.ledger().timestamp(),
-            balance_impact,
+        // Increment the round nonce inside the approval state so that any
+        // residual approvals cannot be replayed in the next round.
+        // We write a "poisoned" state (empty approvals, bumped nonce) rather
+        // than removing the key so the nonce survives across cancel cycles.
+        let state_key = DataKey::UpgradeApprovalState(0);
+        let next_nonce: u32 = env
+            .storage()
+            .persistent()
+            .get::<DataKey, UpgradeApprovalState>(&state_key)
+            .map(|s| s.nonce.saturating_add(1))
+            .unwrap_or(1u32);
+        // Store a sentinel state with the bumped nonce so propose_upgrade_wasm
+        // knows it must open a fresh round on the next call.
+        let reset_state = UpgradeApprovalState {
+            nonce: next_nonce,
+            signers: Vec::new(&env),
+            threshold: 1u32,
+            approvals: Vec::new(&env),
         };
+        env.storage().persistent().set(&state_key, &reset_state);
+        Self::extend_persistent(&env, &state_key);
 
-        env.storage().persistent().set(&entry_key, &entry);
-        Self::extend_persistent(env, &entry_key);
-        env.storage().persistent().set(&count_key, &(count + 1));
-        Self::extend_persistent(env, &count_key);
-    }
-
-    fn transfer_tokens_and_record_audit(
-        env: &Env,
-        token: &Address,
-        from: &Address,
-        to: &Address,
-        amount: i128,
-        actor: &Address,
-        reason: Symbol,
-        balance_impact: i128,
-    ) {
-        if amount <= 0 {
-            return;
-        }
+        // Issue #618: Record the cancellation timestamp so propose_upgrade_wasm
+        // can enforce CANCEL_REPROPOSE_COOLDOWN against the cancel-and-repropose
+        // bypass pattern.
+        let cancelled_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastUpgradeCancelledAt, &cancelled_at);
+        Self::extend_persistent(&env, &DataKey::LastUpgradeCancelledAt);
 
-        // Every transfer must be reached through a guarded public operation.
-        // This assertion makes omissions fail closed as new flows are added.
-        if !env.storage().temporary().has(&DataKey::ReentryGuard) {
-            env.panic_with_error(crate::Error::ReentryDetected);
-        }
+        Self::emit_upgrade_event(
+            &env,
+            UPGRADE_CANCELLED,
+            proposal.wasm_hash,
+            admin,
+            proposal.upgrade_at,
+        );
 
-        // Commit effects before interaction. A failed token call rolls back the
-        // complete Soroban invocation, including this audit record.
-        Self::append_fund_audit_record(env, actor, amount, reason, balance_impact);
-        let token_client = token::Client::new(env, token);
-        token_client.transfer(from, to, &amount);
+        Ok(())
     }
 
-    fn transfer_platform_fee(
-        env: &Env,
-        token: &Address,
-        platform_wallet: &Address,
-        fee_amount: i128,
-    ) {
-        if fee_amount <= 0 {
-            return;
-        }
-
-        Self::transfer_tokens_and_record_audit(
-            env,
-            token,
-            &env.current_contract_address(),
-            platform_wallet,
-            fee_amount,
-            platform_wallet,
-            Symbol::new(env, "platform_fee"),
-            fee_amount,
-        );
-        Self::record_total_fees(env, token, fee_amount);
+    /// Returns the currently pending WASM upgrade proposal, if any (#230).
+    /// Read-only â€” useful for off-chain monitors and admin dashboards that
+    /// need to confirm what `execute_upgrade` will apply.
+    pub fn get_upgrade_proposal(env: Env) -> Option<WasmUpgradeProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WasmUpgradeProposal)
     }
 
-    #[inline(always)]
-    fn get_legacy_total_fees(env: &Env) -> i128 {
-        env.storage().persistent().get(&TOTAL_FEES).unwrap_or(0)
+    /// Returns the current contract version.
+    ///
+    /// `ContractVersion` semantics:
+    /// - Initialized to `1` by `initialize`.
+    /// - Incremented by exactly `1` for each successful `execute_upgrade`.
+    /// - Independent of the on-disk WASM hash; the hash + version pair is
+    ///   captured per-upgrade in `UpgradeHistory` for auditability.
+    /// - Migration code that needs to gate behavior across upgrades should
+    ///   compare against this value rather than embedding magic numbers.
+    pub fn get_version(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContractVersion)
+            .unwrap_or(0)
     }
 
-    fn get_all_tracked_total_fees(env: &Env) -> i128 {
-        let key = DataKey::FeeTokenIndex;
-        let tracked_tokens: Vec<Address> = env
+    /// Append a record to the bounded `UpgradeHistory` log (#241). The Vec is
+    /// trimmed FIFO once it exceeds `MAX_UPGRADE_HISTORY`.
+    fn append_upgrade_history(env: &Env, record: UpgradeRecord) {
+        let mut history: Vec<UpgradeRecord> = env
             .storage()
             .persistent()
-            .get(&key)
-            .unwrap_or(Vec::new(env));
+            .get(&DataKey::UpgradeHistory)
+            .unwrap_or_else(|| Vec::new(env));
 
-        if tracked_tokens.is_empty() {
-            return Self::get_legacy_total_fees(env);
+        history.push_back(record);
+        while history.len() > MAX_UPGRADE_HISTORY {
+            history.pop_front();
         }
 
-        let mut total_fees = 0i128;
-        for index in 0..tracked_tokens.len() {
-            if let Some(token) = tracked_tokens.get(index) {
-                let token_key = DataKey::TotalFees(token);
-                let token_total: i128 = env.storage().persistent().get(&token_key).unwrap_or(0);
-                total_fees += token_total;
-            }
+        env.storage()
+            .persistent()
+            .set(&DataKey::UpgradeHistory, &history);
+        Self::extend_persistent(env, &DataKey::UpgradeHistory);
+    }
+
+    /// Returns the bounded log of past contract upgrades (#241).
+    ///
+    /// Newer entries are at the back. The log is capped at
+    /// `MAX_UPGRADE_HISTORY` records â€” older entries are dropped FIFO so
+    /// storage cannot grow unbounded. For long-term audit trails operators
+    /// should mirror the `wasm_upgrade` events to off-chain storage.
+    pub fn get_upgrade_history(env: Env) -> Vec<UpgradeRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UpgradeHistory)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns aggregate version + last-upgrade metadata (#241). Pairs the
+    /// scalar `ContractVersion` with the most recent `UpgradeRecord` so a
+    /// dashboard or migration script can read everything in one call.
+    pub fn get_version_info(env: Env) -> VersionInfo {
+        let current_version = Self::get_version(env.clone());
+        let history = Self::get_upgrade_history(env);
+        let upgrade_count = history.len();
+        VersionInfo {
+            current_version,
+            upgrade_count,
         }
+    }
 
-        total_fees
+    /// Refund funds to buyer (admin only).
+    ///
+    /// Kept for existing clients; role holders call `refund_as`.
+    pub fn refund(env: Env, escrow_id: u64) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::refund_as(env, admin, escrow_id)
     }
 
-    /// Release funds to seller with platform fee deduction
+    /// Refund funds to buyer (`Role::DisputeResolver`)
     ///
     /// # Arguments
-    /// * `order_id` - Order identifier
-    pub fn release_funds(env: Env, order_id: u32) {
+    /// * `escrow_id` - Escrow/Order identifier
+    pub fn refund_as(env: Env, caller: Address, escrow_id: u64) -> Result<(), Error> {
         let _guard = ReentryGuardScope::new(&env);
-        let escrow_for_auth = Self::get_stored_escrow(&env, order_id);
+        Self::require_role(&env, Role::DisputeResolver, &caller);
 
-        // Only buyer can release funds
-        escrow_for_auth.buyer.require_auth();
+        Self::refund_internal(env.clone(), escrow_id)
+    }
 
+    /// Refund the full amount of an active escrow to the buyer.
+    fn refund_internal(env: Env, escrow_id: u64) -> Result<(), Error> {
+        let order_id = escrow_id as u32;
         let mut escrow =
-            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::ReleasePending)
-                .unwrap_or_else(|e| env.panic_with_error(e));
-
-        // Get platform config
-        let config = Self::get_platform_config_internal(&env);
+            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::RefundPending)?;
 
         // Deterministic fee allocation via the central FeePolicy engine.
-        let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.seller.clone());
-        let allocation = Self::compute_fee_allocation(
+        let allocation = Self::compute_escrow_fee_allocation(
             &env,
+            order_id,
             escrow.amount,
-            fee_bps,
-            SettlementKind::ReleaseFunds,
+            &NO_FEE_RATE,
+            SettlementKind::FullRefundNoFee,
         );
 
         // Update status
-        escrow.status = EscrowStatus::Released;
+        escrow.status = EscrowStatus::Refunded;
         env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::extend_persistent(&env, &(ESCROW, order_id));
 
         // Decrement active counts
         Self::update_active_obligations(&env, &escrow.buyer, -1);
@@ -4588,22 +9075,18 @@ impl CraftNexusContract {
         Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
         Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
 
-        // Reserve accounting is part of the effects phase.
         Self::update_total_locked(&env, &escrow.token, -escrow.amount);
 
-        // Transfer platform fee to platform wallet
-        if allocation.platform_fee > 0 {
-            Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, allocation.platform_fee);
-        }
+        // Refund to buyer and record audit
+        Self::pay_refund(&env, order_id, &escrow, escrow.amount, allocation.buyer_amount, Symbol::new(&env, "refund"));
 
-        // Transfer net funds to seller and record audit
-        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.seller, allocation.seller_amount, &escrow.seller, Symbol::new(&env, "escrow_released"), allocation.seller_amount);
+        Self::clear_settled_escrow_state(&env, order_id);
 
         Self::emit_escrow_created(
             &env,
             EscrowEvent {
-                escrow_id: order_id as u64,
-                action: EscrowAction::Released,
+                escrow_id,
+                action: EscrowAction::Refunded,
                 buyer: escrow.buyer.clone(),
                 seller: escrow.seller.clone(),
                 amount: escrow.amount,
@@ -4617,11 +9100,11 @@ impl CraftNexusContract {
         Self::emit_reputation_update(
             &env,
             ReputationUpdateEvent {
-                address: escrow.seller.clone(),
+                address: escrow.buyer.clone(),
                 successful_delta: 1,
                 disputed_delta: 0,
-                metrics_sales_delta: 1,
-                metrics_amount: escrow.amount,
+                metrics_sales_delta: 0,
+                metrics_amount: 0,
                 token: escrow.token.clone(),
                 timestamp: ts,
             },
@@ -4629,152 +9112,226 @@ impl CraftNexusContract {
         Self::emit_reputation_update(
             &env,
             ReputationUpdateEvent {
-                address: escrow.buyer.clone(),
-                successful_delta: 1,
-                disputed_delta: 0,
+                address: escrow.seller.clone(),
+                successful_delta: 0,
+                disputed_delta: 1,
                 metrics_sales_delta: 0,
                 metrics_amount: 0,
                 token: escrow.token.clone(),
                 timestamp: ts,
             },
         );
+        Ok(())
     }
 
-    /// Auto-release funds after release window (seller can call)
+    /// Get escrow details
     ///
     /// # Arguments
     /// * `order_id` - Order identifier
-    pub fn auto_release(env: Env, order_id: u32) {
-        let _guard = ReentryGuardScope::new(&env);
-        let escrow_for_window = Self::get_stored_escrow(&env, order_id);
+    pub fn get_escrow(env: Env, order_id: u32) -> Escrow {
+        Self::get_stored_escrow(&env, order_id)
+    }
 
-        if !(escrow_for_window.status == EscrowStatus::Active) {
-            env.panic_with_error(crate::Error::InvalidEscrowState);
+    /// Read the immutable fund-movement audit history for an account.
+    pub fn get_fund_audit_history(env: Env, actor: Address) -> Vec<FundMovementAuditEntry> {
+        let count_key = DataKey::FundAuditCount(actor.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let mut history = Vec::new(&env);
+
+        for index in 0..count {
+            let entry_key = DataKey::FundAuditIndexed(actor.clone(), index);
+            if let Some(entry) = env.storage().persistent().get::<DataKey, FundMovementAuditEntry>(&entry_key) {
+                history.push_back(entry);
+            }
         }
 
-        let current_time = env.ledger().timestamp();
-        let elapsed = current_time - (escrow_for_window.created_at as u64);
+        history
+    }
 
-        if elapsed < escrow_for_window.release_window as u64 {
-            env.panic_with_error(crate::Error::ReleaseWindowNotElapsed);
+    /// Read the total number of fund-movement audit entries for an account.
+    pub fn get_fund_audit_count(env: Env, actor: Address) -> u32 {
+        let count_key = DataKey::FundAuditCount(actor);
+        env.storage().persistent().get(&count_key).unwrap_or(0)
+    }
+
+    /// Read a paginated slice of the fund-movement audit history for an account.
+    ///
+    /// # Arguments
+    /// * `actor` - Account address to query audit history for
+    /// * `start_index` - Starting zero-based index of audit records to read
+    /// * `limit` - Maximum number of entries to return
+    pub fn get_fund_audit_history_paginated(
+        env: Env,
+        actor: Address,
+        start_index: u32,
+        limit: u32,
+    ) -> Vec<FundMovementAuditEntry> {
+        let count_key = DataKey::FundAuditCount(actor.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let mut history = Vec::new(&env);
+
+        if start_index >= count || limit == 0 {
+            return history;
         }
 
-        let mut escrow =
-            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::ReleasePending)
-                .unwrap_or_else(|e| env.panic_with_error(e));
+        let end_index = start_index.saturating_add(limit).min(count);
+        for index in start_index..end_index {
+            let entry_key = DataKey::FundAuditIndexed(actor.clone(), index);
+            if let Some(entry) = env.storage().persistent().get::<DataKey, FundMovementAuditEntry>(&entry_key) {
+                history.push_back(entry);
+            }
+        }
 
-        // Get platform config
+        history
+    }
+
+    /// Get escrow metadata fields only.
+    pub fn get_escrow_metadata(env: Env, order_id: u32) -> EscrowMetadata {
+        let escrow = Self::get_escrow(env, order_id);
+        EscrowMetadata {
+            ipfs_hash: escrow.ipfs_hash,
+            metadata_hash: escrow.metadata_hash,
+        }
+    }
+
+    /// Verify that provided metadata matches the stored hash (Issue #122)
+    ///
+    /// This function allows parties to reveal off-chain metadata and verify it matches
+    /// the commitment stored on-chain. Uses SHA-256 hashing for verification.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `proof` - MetadataRevealProof containing the full content and optional secret
+    ///
+    /// # Returns
+    /// true if the provided content hashes to the stored metadata_hash, false otherwise
+    ///
+    /// # Notes
+    /// - The metadata_hash must be set on the escrow for verification to succeed
+    /// - The secret field is optional and can be used for additional application-level verification
+    /// - This function does NOT modify state; it only verifies the commitment
+    pub fn verify_metadata_reveal(
+        env: Env,
+        order_id: u32,
+        proof: MetadataRevealProof,
+        authorized_address: Address,
+    ) -> bool {
+        let escrow = Self::get_escrow(env.clone(), order_id);
         let config = Self::get_platform_config_internal(&env);
 
-        // Deterministic fee allocation via the central FeePolicy engine.
-        let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.seller.clone());
-        let allocation = Self::compute_fee_allocation(
-            &env,
-            escrow.amount,
-            fee_bps,
-            SettlementKind::ReleaseFunds,
-        );
+        let is_authorized = authorized_address == escrow.buyer
+            || authorized_address == escrow.seller
+            || authorized_address == config.arbitrator;
+        if !is_authorized {
+            env.panic_with_error(crate::Error::Unauthorized);
+        }
 
-        // Update status
-        escrow.status = EscrowStatus::Released;
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        // If no metadata hash was set, verification fails
+        if escrow.metadata_hash.is_none() {
+            return false;
+        }
 
-        // Decrement active counts
-        Self::update_active_obligations(&env, &escrow.buyer, -1);
-        Self::update_active_obligations(&env, &escrow.seller, -1);
+        let stored_hash = escrow.metadata_hash.unwrap();
+        Self::proof_matches_hash(&env, &proof, &stored_hash)
+    }
 
-        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+    /// Returns true if the SHA-256 of `proof.content` equals `hash`.
+    fn proof_matches_hash(env: &Env, proof: &MetadataRevealProof, hash: &Bytes) -> bool {
+        // Compute SHA-256 hash of the provided content
+        let computed_hash = env.crypto().sha256(&proof.content);
 
-        Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+        // Convert Hash to Bytes by creating a new Bytes from the hash
+        // Hash implements Into<Bytes> in Soroban SDK
+        let computed_bytes: Bytes = computed_hash.into();
 
-        // Transfer platform fee to platform wallet
-        if allocation.platform_fee > 0 {
-            Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, allocation.platform_fee);
-        }
+        // Compare hashes
+        computed_bytes == *hash
+    }
 
-        // Transfer net funds to seller and record audit
-        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.seller, allocation.seller_amount, &escrow.seller, Symbol::new(&env, "escrow_released"), allocation.seller_amount);
+    /// Authorized verification that records successful metadata matching on-chain.
+    ///
+    /// Only the escrow buyer, seller, or admin may call this method. A successful verification
+    /// emits a permanent MetadataVerified event.
+    pub fn verify_metadata_reveal_recorded(
+        env: Env,
+        order_id: u32,
+        proof: MetadataRevealProof,
+        authorized_address: Address,
+    ) -> bool {
+        authorized_address.require_auth();
 
-        Self::emit_escrow_created(
-            &env,
-            EscrowEvent {
-                escrow_id: order_id as u64,
-                action: EscrowAction::Released,
-                buyer: escrow.buyer.clone(),
-                seller: escrow.seller.clone(),
-                amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        let escrow = Self::get_escrow(env.clone(), order_id);
+        let config = Self::get_platform_config_internal(&env);
+        let is_authorized = authorized_address == escrow.buyer
+            || authorized_address == escrow.seller
+            || authorized_address == config.arbitrator;
+        if !is_authorized {
+            env.panic_with_error(crate::Error::Unauthorized);
+        }
 
-        // Emit reputation update events â€” decoupled from onboarding contract (#211)
-        let ts = env.ledger().timestamp();
-        Self::emit_reputation_update(
-            &env,
-            ReputationUpdateEvent {
-                address: escrow.seller.clone(),
-                successful_delta: 1,
-                disputed_delta: 0,
-                metrics_sales_delta: 1,
-                metrics_amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: ts,
-            },
-        );
-        Self::emit_reputation_update(
-            &env,
-            ReputationUpdateEvent {
-                address: escrow.buyer.clone(),
-                successful_delta: 1,
-                disputed_delta: 0,
-                metrics_sales_delta: 0,
-                metrics_amount: 0,
-                token: escrow.token.clone(),
-                timestamp: ts,
-            },
-        );
+        let is_valid =
+            Self::verify_metadata_reveal(env.clone(), order_id, proof, authorized_address.clone());
+        if is_valid {
+            Self::emit_metadata_verified(&env, order_id, authorized_address);
+        }
+        is_valid
     }
 
-    /// Extend the release window for an escrow (only buyer can call)
+    /// Check if escrow can be auto-released
     ///
     /// # Arguments
     /// * `order_id` - Order identifier
-    /// * `additional_seconds` - Time in seconds to add to the release window
-    pub fn extend_release_window(env: Env, order_id: u32, additional_seconds: u32) {
-        let _guard = ReentryGuardScope::new(&env);
-        let escrow_key = (ESCROW, order_id);
-        let escrow_opt = env.storage().persistent().get(&escrow_key);
+    pub fn can_auto_release(env: Env, order_id: u32) -> bool {
+        let escrow = Self::try_get_escrow_readonly(&env, order_id);
 
-        if escrow_opt.is_none() {
-            env.panic_with_error(crate::Error::EscrowNotFound);
+        if escrow.status != EscrowStatus::Active || Self::is_htlc_locked(&env, order_id) {
+            return false;
         }
 
-        Self::extend_persistent(&env, &escrow_key);
-        let mut escrow: Escrow = escrow_opt.unwrap();
+        let current_time = env.ledger().timestamp();
+        let elapsed = current_time - (escrow.created_at as u64);
 
-        // Only buyer can extend release window
-        escrow.buyer.require_auth();
+        elapsed >= escrow.release_window as u64
+    }
 
-        if !(escrow.status == EscrowStatus::Active) {
-            env.panic_with_error(crate::Error::InvalidEscrowState);
-        }
+    /// Dispute an escrow
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `dispute_reason` - Reason for dispute
+    /// * `authorized_address` - Address authorized to dispute (buyer or seller)
+    pub fn dispute_escrow(
+        env: Env,
+        order_id: u32,
+        dispute_reason: Symbol, // UPDATE ARGUMENT TYPE
+        authorized_address: Address,
+    ) {
+        authorized_address.require_auth();
 
-        let new_window = escrow.release_window.saturating_add(additional_seconds);
+        let escrow_for_auth = Self::get_stored_escrow(&env, order_id);
 
-        if new_window > MAX_TOTAL_RELEASE_WINDOW {
-            env.panic_with_error(crate::Error::ReleaseWindowTooLong);
+        // Allow buyer or seller to dispute
+        if !(escrow_for_auth.buyer == authorized_address
+            || escrow_for_auth.seller == authorized_address)
+        {
+            env.panic_with_error(crate::Error::Unauthorized);
         }
 
-        escrow.release_window = new_window;
-        env.storage().persistent().set(&escrow_key, &escrow);
+        let mut escrow =
+            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::DisputePending)
+                .unwrap_or_else(|e| env.panic_with_error(e));
+
+        escrow.status = EscrowStatus::Disputed;
+        escrow.dispute_reason = Some(dispute_reason); // Assign Symbol
+        escrow.dispute_initiated_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::record_epoch_stats(&env, &escrow.token, |stats| stats.disputes += 1);
 
         Self::emit_escrow_created(
             &env,
             EscrowEvent {
                 escrow_id: order_id as u64,
-                action: EscrowAction::Extended,
+                action: EscrowAction::Disputed,
                 buyer: escrow.buyer.clone(),
                 seller: escrow.seller.clone(),
                 amount: escrow.amount,
@@ -4784,1742 +9341,2279 @@ impl CraftNexusContract {
         );
     }
 
-    /// Reject obviously invalid WASM hashes before they touch storage.
-    ///
-    /// The Soroban host validates that the hash points to an uploaded WASM at
-    /// `update_current_contract_wasm` time, but only at execution. Catching
-    /// the all-zero sentinel here avoids the worst footgun (an admin
-    /// accidentally proposing the default `BytesN<32>::from_array(_, [0; 32])`)
-    /// and gives a meaningful error code instead of a host trap.
-    fn validate_upgrade_hash(env: &Env, hash: &BytesN<32>) -> Result<(), Error> {
-        let zero = BytesN::<32>::from_array(env, &[0u8; 32]);
-        if hash == &zero {
-            return Err(Error::InvalidUpgradeHash);
+    /// Require `authorized_address` to be the moderator, the arbitrator or a
+    /// `Role::DisputeResolver` holder (including the admin).
+    fn require_dispute_resolver(env: &Env, config: &PlatformConfig, authorized_address: &Address) {
+        authorized_address.require_auth();
+        let is_authorized = Some(authorized_address.clone()) == config.moderator
+            || *authorized_address == config.arbitrator
+            || Self::has_role(env.clone(), Role::DisputeResolver, authorized_address.clone());
+        if !is_authorized {
+            env.panic_with_error(crate::Error::Unauthorized);
         }
-        Ok(())
-    }
-
-    fn emit_upgrade_event(
-        env: &Env,
-        action: Symbol,
-        wasm_hash: BytesN<32>,
-        admin: Address,
-        upgrade_at: u64,
-    ) {
-        env.events().publish(
-            (Symbol::new(env, "wasm_upgrade"), action.clone()),
-            UpgradeProposalEvent {
-                action,
-                wasm_hash,
-                admin,
-                timestamp: env.ledger().timestamp(),
-                upgrade_at,
-            },
-        );
     }
 
-    /// Propose a new WASM code for the contract (multi-sig).
+    /// Resolve disputed escrow (arbitrator only).
     ///
-    /// Each authorized upgrade signer calls this function with the same
-    /// `new_wasm_hash`. Approvals are accumulated until the configured
-    /// `UpgradeThreshold` is reached, at which point the proposal is committed
-    /// and the cooldown clock starts. A signer cannot approve the same hash
-    /// twice. When no explicit signers list is configured the admin acts as the
-    /// sole signer (backward-compatible default, threshold=1).
+    /// This function transitions the escrow from `Disputed` to `Resolved`.
+    /// Depending on the `resolution` choice:
+    /// - `ReleaseToSeller`: Funds are sent to the seller minus the platform fee.
+    /// - `RefundToBuyer`: Full original amount is returned to the buyer.
     ///
-    /// Only one proposal may be pending at a time. Cancel with
-    /// `cancel_upgrade_wasm` before starting a new one.
-    pub fn propose_upgrade_wasm(
+    /// # Edge Cases
+    /// - **Payout Failure**: If the transfer to the winning party fails (e.g. account
+    ///   revoked), the amount is credited to the claim ledger instead of reverting.
+    ///   The escrow still moves to `Resolved` and the party withdraws with `claim`.
+    /// - **State Logic**: Can ONLY be called if `status` is currently `Disputed`.
+    pub fn resolve_dispute(
         env: Env,
-        signer: Address,
-        new_wasm_hash: BytesN<32>,
-    ) -> Result<(), Error> {
-        signer.require_auth();
+        order_id: u32,
+        resolution: Resolution,
+        authorized_address: Address,
+    ) {
+        let _guard = ReentryGuardScope::new(&env);
+        let config = Self::get_platform_config_internal(&env);
+        Self::require_dispute_resolver(&env, &config, &authorized_address);
 
-        Self::validate_upgrade_hash(&env, &new_wasm_hash)?;
+        let mut escrow = Self::get_stored_escrow(&env, order_id);
 
-        // Issue #618: Prevent cancel-and-repropose from resetting the review
-        // window. If a proposal was recently cancelled, the proposer must wait
-        // CANCEL_REPROPOSE_COOLDOWN seconds before submitting a new one.
-        if let Some(cancelled_at) = env
-            .storage()
-            .persistent()
-            .get::<DataKey, u64>(&DataKey::LastUpgradeCancelledAt)
-        {
-            let now = env.ledger().timestamp();
-            if now < cancelled_at.saturating_add(CANCEL_REPROPOSE_COOLDOWN) {
-                return Err(Error::UpgradeCooldownActive);
-            }
+        if escrow.status != EscrowStatus::Disputed {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
         }
 
-        if env
-            .storage()
-            .persistent()
-            .has(&DataKey::WasmUpgradeProposal)
-        {
-            return Err(Error::UpgradeProposalExists);
-        }
+        // CRITICAL: Update status BEFORE external calls (CEI pattern)
+        escrow.status = EscrowStatus::Resolved;
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
 
-        // -- Approval state (singleton key, nonce inside struct) ----------------
-        // Approval state is stored at a fixed slot UpgradeApprovalState(0).
-        // The `nonce` field inside the struct is incremented on every
-        // cancel_upgrade_wasm call, so a re-proposal after cancellation
-        // starts a fresh round with a different nonce, making the old
-        // approvals stale and detectable.
-        let state_key = DataKey::UpgradeApprovalState(0);
+        // Decrement active counts
+        Self::update_active_obligations(&env, &escrow.buyer, -1);
+        Self::update_active_obligations(&env, &escrow.seller, -1);
 
-        // Read the current nonce from any pre-existing state, or default to 0.
-        let current_nonce: u32 = env
-            .storage()
-            .persistent()
-            .get::<DataKey, UpgradeApprovalState>(&state_key)
-            .map(|s| s.nonce)
-            .unwrap_or(0u32);
+        // Clean up any orphaned partial refund proposal
+        let proposal_key = DataKey::PartialRefundProposal(order_id);
+        env.storage().persistent().remove(&proposal_key);
 
-        // Helper closure: snapshot current live signers + threshold into a fresh state.
-        let fresh_state = |nonce: u32| -> UpgradeApprovalState {
-            let snapshotted_signers: Vec<Address> = env
-                .storage()
-                .persistent()
-                .get(&DataKey::UpgradeSigners)
-                .unwrap_or_else(|| {
-                    let mut v = Vec::new(&env);
-                    if let Ok(admin) = Self::get_admin(&env) {
-                        v.push_back(admin);
-                    }
-                    v
-                });
-            let snapshotted_threshold: u32 = env
-                .storage()
-                .instance()
-                .get(&DataKey::UpgradeThreshold)
-                .unwrap_or(1u32);
-            UpgradeApprovalState {
-                nonce,
-                signers: snapshotted_signers,
-                threshold: snapshotted_threshold,
-                approvals: Vec::new(&env),
+        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
+        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+
+        // Now perform token transfers (external calls)
+        match resolution {
+            Resolution::ReleaseToSeller => {
+                let fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+                let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+                let allocation = Self::apply_referral_share(
+                    &env,
+                    Self::compute_escrow_fee_allocation(
+                        &env,
+                        order_id,
+                        escrow.amount,
+                        &fee_rate,
+                        SettlementKind::ReleaseFunds,
+                    ),
+                    &referrer,
+                );
+                Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+                if allocation.platform_fee > 0 {
+                    Self::transfer_platform_fee(
+                        &env,
+                        &escrow.token,
+                        &config.platform_wallet,
+                        &allocation,
+                        referrer.as_ref().map(|referrer| (order_id, referrer)),
+                        None,
+                    );
+                }
+                Self::pay_seller_proceeds(&env, order_id, &escrow, allocation.seller_amount, Symbol::new(&env, "escrow_released"));
             }
-        };
+            Resolution::RefundToBuyer => {
+                let allocation = Self::compute_escrow_fee_allocation(
+                    &env,
+                    order_id,
+                    escrow.amount,
+                    &NO_FEE_RATE,
+                    SettlementKind::FullRefundNoFee,
+                );
+                Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+                Self::pay_refund(&env, order_id, &escrow, escrow.amount, allocation.buyer_amount, Symbol::new(&env, "refund"));
+            }
+        }
 
-        let mut state: UpgradeApprovalState = env
-            .storage()
-            .persistent()
-            .get(&state_key)
-            // Reuse stored state only when:
-            //  (a) it has the same nonce (was not left from a cancelled round), AND
-            //  (b) the round has already started (signers list is non-empty).
-            // A state with an empty signers list is a cancel-sentinel written by
-            // cancel_upgrade_wasm to carry the bumped nonce forward; it must not
-            // be treated as a live round.
-            .filter(|s: &UpgradeApprovalState| {
-                s.nonce == current_nonce && !s.signers.is_empty()
-            })
-            .unwrap_or_else(|| fresh_state(current_nonce));
+        Self::clear_settled_escrow_state(&env, order_id);
 
-        // Validate against the *snapshotted* signer set -- live storage is
-        // intentionally not consulted here.
-        if !state.signers.iter().any(|s| s == signer) {
-            return Err(Error::NotAnUpgradeSigner);
+        Self::emit_escrow_created(
+            &env,
+            EscrowEvent {
+                escrow_id: order_id as u64,
+                action: EscrowAction::Resolved,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::emit_escrow_resolved_event(
+            &env,
+            EscrowResolvedEvent {
+                escrow_id: order_id as u64,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                arbitrator: authorized_address.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        // Emit reputation update events â€” decoupled from onboarding contract (#211)
+        let ts = env.ledger().timestamp();
+        match resolution {
+            Resolution::ReleaseToSeller => {
+                Self::emit_reputation_update(
+                    &env,
+                    ReputationUpdateEvent {
+                        address: escrow.seller.clone(),
+                        successful_delta: 1,
+                        disputed_delta: 0,
+                        metrics_sales_delta: 1,
+                        metrics_amount: escrow.amount,
+                        token: escrow.token.clone(),
+                        timestamp: ts,
+                    },
+                );
+                Self::emit_reputation_update(
+                    &env,
+                    ReputationUpdateEvent {
+                        address: escrow.buyer.clone(),
+                        successful_delta: 0,
+                        disputed_delta: 1,
+                        metrics_sales_delta: 0,
+                        metrics_amount: 0,
+                        token: escrow.token.clone(),
+                        timestamp: ts,
+                    },
+                );
+            }
+            Resolution::RefundToBuyer => {
+                Self::emit_reputation_update(
+                    &env,
+                    ReputationUpdateEvent {
+                        address: escrow.buyer.clone(),
+                        successful_delta: 1,
+                        disputed_delta: 0,
+                        metrics_sales_delta: 0,
+                        metrics_amount: 0,
+                        token: escrow.token.clone(),
+                        timestamp: ts,
+                    },
+                );
+                Self::emit_reputation_update(
+                    &env,
+                    ReputationUpdateEvent {
+                        address: escrow.seller.clone(),
+                        successful_delta: 0,
+                        disputed_delta: 1,
+                        metrics_sales_delta: 0,
+                        metrics_amount: 0,
+                        token: escrow.token.clone(),
+                        timestamp: ts,
+                    },
+                );
+            }
         }
+    }
 
-        if state.approvals.iter().any(|a| a == signer) {
-            return Err(Error::AlreadyApproved);
-        }
-        state.approvals.push_back(signer.clone());
+    /// Update platform fee percentage (admin only).
+    ///
+    /// Kept for existing clients; role holders call `update_platform_fee_as`.
+    pub fn update_platform_fee(env: Env, new_fee_bps: u32) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::update_platform_fee_as(env, admin, new_fee_bps)
+    }
 
-        // All entries in state.approvals were validated against state.signers
-        // when they were added, so a simple length check is sufficient.
-        if (state.approvals.len() as u32) < state.threshold {
-            // Threshold not yet met -- persist updated state and return.
-            env.storage().persistent().set(&state_key, &state);
-            Self::extend_persistent(&env, &state_key);
-            return Ok(());
-        }
+    /// Update platform fee percentage (`Role::FeeManager`)
+    ///
+    /// # Arguments
+    /// * `caller` - Address holding `Role::FeeManager`
+    /// * `new_fee_bps` - New fee in basis points
+    pub fn update_platform_fee_as(env: Env, caller: Address, new_fee_bps: u32) {
+        Self::require_role(&env, Role::FeeManager, &caller);
+        let config = Self::get_platform_config_internal(&env);
+        Self::check_no_fee_notice(&env).unwrap_or_else(|e| env.panic_with_error(e));
 
-        // Threshold reached -- commit the proposal and clean up approval state.
-        // Remove approval state for this nonce; it is no longer needed.
-        env.storage().persistent().remove(&state_key);
+        if new_fee_bps > MAX_PLATFORM_FEE_BPS {
+            env.panic_with_error(crate::Error::InvalidFee);
+        }
+        let old_fee_bps = Self::current_platform_fee_bps(&env);
+        Self::drop_due_fee_changes(&env, None);
 
-        let config = Self::get_platform_config_internal(&env);
-        let proposed_at = env.ledger().timestamp();
-        let upgrade_at = proposed_at + config.wasm_upgrade_cooldown as u64;
-        let proposal = WasmUpgradeProposal {
-            wasm_hash: new_wasm_hash.clone(),
-            upgrade_at,
-            proposed_by: signer.clone(),
-            proposed_at,
+        let new_config = PlatformConfig {
+            platform_fee_bps: new_fee_bps,
+            platform_wallet: config.platform_wallet,
+            admin: config.admin,
+            arbitrator: config.arbitrator,
+            moderator: config.moderator,
+            is_paused: config.is_paused,
+            min_stake_required: config.min_stake_required,
+            pending_admin: config.pending_admin,
+            wasm_upgrade_cooldown: config.wasm_upgrade_cooldown,
+            max_dispute_duration: config.max_dispute_duration,
+            stake_cooldown: config.stake_cooldown,
+            expired_dispute_fee_policy: config.expired_dispute_fee_policy,
+            min_release_window: config.min_release_window,
         };
 
         env.storage()
-            .persistent()
-            .set(&DataKey::WasmUpgradeProposal, &proposal);
-        Self::extend_persistent(&env, &DataKey::WasmUpgradeProposal);
-
-        Self::emit_upgrade_event(&env, UPGRADE_PROPOSED, new_wasm_hash, signer, upgrade_at);
+            .instance()
+            .set(&DataKey::PlatformConfig, &new_config);
+        Self::emit_config_updated(
+            &env,
+            "platform_fee_bps",
+            ConfigValue::U32(old_fee_bps),
+            ConfigValue::U32(new_fee_bps),
+        );
+    }
 
-        Ok(())
+    /// Update platform wallet address (admin only).
+    ///
+    /// Kept for existing clients; role holders call `update_platform_wallet_as`.
+    pub fn update_platform_wallet(env: Env, new_wallet: Address) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::update_platform_wallet_as(env, admin, new_wallet)
     }
 
-    /// Set the number of distinct signer approvals required to commit a WASM
-    /// upgrade proposal. Must be >= 1. Admin only.
-    pub fn set_upgrade_threshold(env: Env, threshold: u32) -> Result<(), Error> {
-        if threshold == 0 {
-            return Err(Error::InvalidFee);
-        }
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+    /// Update platform wallet address (`Role::Treasurer`)
+    ///
+    /// # Arguments
+    /// * `new_wallet` - New platform wallet address
+    pub fn update_platform_wallet_as(env: Env, caller: Address, new_wallet: Address) {
+        let config = Self::get_platform_config_internal(&env);
+        Self::require_role(&env, Role::Treasurer, &caller);
+
+        let new_config = PlatformConfig {
+            platform_fee_bps: config.platform_fee_bps,
+            platform_wallet: new_wallet,
+            admin: config.admin,
+            arbitrator: config.arbitrator,
+            moderator: config.moderator,
+            is_paused: config.is_paused,
+            min_stake_required: config.min_stake_required,
+            pending_admin: config.pending_admin,
+            wasm_upgrade_cooldown: config.wasm_upgrade_cooldown,
+            max_dispute_duration: config.max_dispute_duration,
+            stake_cooldown: config.stake_cooldown,
+            expired_dispute_fee_policy: config.expired_dispute_fee_policy,
+            min_release_window: config.min_release_window,
+        };
+
         env.storage()
             .instance()
-            .set(&DataKey::UpgradeThreshold, &threshold);
-        Ok(())
+            .set(&DataKey::PlatformConfig, &new_config);
+        Self::emit_config_updated(
+            &env,
+            "platform_wallet",
+            ConfigValue::Address(config.platform_wallet),
+            ConfigValue::Address(new_config.platform_wallet),
+        );
     }
 
-    /// Replace the list of addresses authorized to co-sign WASM upgrade
-    /// proposals. An empty list resets to the admin-only default. Admin only.
-    pub fn set_upgrade_signers(env: Env, signers: Vec<Address>) -> Result<(), Error> {
+    /// Update the expired dispute fee policy (admin only).
+    ///
+    /// Kept for existing clients; role holders call `update_expired_dispute_policy_as`.
+    pub fn update_expired_dispute_policy(
+        env: Env,
+        policy: ExpiredDisputeFeePolicy,
+    ) -> Result<(), Error> {
         let admin = Self::get_admin(&env)?;
-        admin.require_auth();
-        if signers.is_empty() {
-            env.storage().persistent().remove(&DataKey::UpgradeSigners);
-        } else {
-            env.storage()
-                .persistent()
-                .set(&DataKey::UpgradeSigners, &signers);
-            Self::extend_persistent(&env, &DataKey::UpgradeSigners);
-        }
-        Ok(())
+        Self::update_expired_dispute_policy_as(env, admin, policy)
     }
 
-    /// Returns the configured upgrade threshold (defaults to 1).
-    pub fn get_upgrade_threshold(env: Env) -> u32 {
+    /// Update the expired dispute fee policy (`Role::FeeManager`).
+    ///
+    /// Configures how platform fees are handled when a dispute expires without arbitrator resolution.
+    ///
+    /// # Arguments
+    /// * `caller` - Address holding `Role::FeeManager`
+    /// * `policy` - The new fee policy to apply
+    ///
+    /// # Policies
+    /// - RefundFullNoPlatformFee: Buyer gets full refund, platform collects no fee (default)
+    /// - RefundMinusPlatformFee: Buyer gets refund minus fee, platform collects fee from buyer
+    /// - DeductFeeFromSeller: Buyer gets full refund, seller conceptually loses the fee
+    /// - SplitFee: Platform fee split between buyer and seller
+    pub fn update_expired_dispute_policy_as(
+        env: Env,
+        caller: Address,
+        policy: ExpiredDisputeFeePolicy,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+        let mut config = Self::get_platform_config_internal(&env);
+
+        let old_policy = config.expired_dispute_fee_policy;
+        config.expired_dispute_fee_policy = policy;
+
         env.storage()
             .instance()
-            .get(&DataKey::UpgradeThreshold)
-            .unwrap_or(1u32)
+            .set(&DataKey::PlatformConfig, &config);
+
+        Self::emit_config_updated(
+            &env,
+            "expired_dispute_fee_policy",
+            ConfigValue::U32(old_policy as u32),
+            ConfigValue::U32(policy as u32),
+        );
+
+        Ok(())
     }
 
-    /// Returns the current proposal round nonce.
-    ///
-    /// The nonce is incremented on every `cancel_upgrade_wasm` call.  Callers
-    /// can use it to look up the active `UpgradeApprovalState` via
-    /// `get_upgrade_approvals`.
-    pub fn get_upgrade_proposal_nonce(env: Env) -> u32 {
-        env.storage()
-            .persistent()
-            .get::<DataKey, UpgradeApprovalState>(&DataKey::UpgradeApprovalState(0))
-            .map(|s| s.nonce)
-            .unwrap_or(0u32)
+    /// Get the current expired dispute fee policy
+    pub fn get_expired_dispute_policy(env: Env) -> ExpiredDisputeFeePolicy {
+        let config = Self::get_platform_config_internal(&env);
+        config.expired_dispute_fee_policy
     }
 
-    /// Returns the list of pending approvals for the given proposal nonce.
+    /// Set the moderator (admin only).
     ///
-    /// Pass the value returned by `get_upgrade_proposal_nonce` to inspect the
-    /// current round.  Returns an empty vec if no approvals exist for that
-    /// nonce (i.e. the round has not started or was already committed/cancelled).
-    pub fn get_upgrade_approvals(env: Env, nonce: u32) -> Vec<Address> {
-        // Returns approvals only if the stored state matches the requested nonce.
-        env.storage()
-            .persistent()
-            .get::<DataKey, UpgradeApprovalState>(&DataKey::UpgradeApprovalState(0))
-            .filter(|s| s.nonce == nonce)
-            .map(|s| s.approvals)
-            .unwrap_or_else(|| Vec::new(&env))
+    /// Kept for existing clients; role holders call `set_moderator_as`.
+    pub fn set_moderator(env: Env, moderator: Address) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::set_moderator_as(env, admin, moderator)
     }
 
-    /// Return the persisted storage layout version.
-    pub fn get_storage_layout_version(env: Env) -> u32 {
+    /// Set the moderator (`Role::Operator`).
+    pub fn set_moderator_as(env: Env, caller: Address, moderator: Address) {
+        let mut config = Self::get_platform_config(env.clone());
+        Self::require_role(&env, Role::Operator, &caller);
+        let previous = config
+            .moderator
+            .clone()
+            .map(ConfigValue::Address)
+            .unwrap_or_else(|| ConfigValue::String(String::from_str(&env, "unset")));
+        config.moderator = Some(moderator.clone());
         env.storage()
-            .persistent()
-            .get(&DataKey::StorageLayoutVersion)
-            .unwrap_or(0)
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+        Self::emit_config_updated(&env, "moderator", previous, ConfigValue::Address(moderator));
     }
 
-    /// Migrate persisted storage to the current layout version.
+    /// Set the minimum escrow amount for a specific token (admin only).
     ///
-    /// This is an explicit, admin-gated migration path for legacy deployments.
-    /// Existing state is preserved by running the existing lazy migration helpers
-    /// before recording the new layout version.
-    pub fn migrate_storage_layout(env: Env) -> u32 {
-        let admin = Self::get_admin(&env).unwrap_or_else(|_| {
-            env.panic_with_error(crate::Error::PlatformNotInitialized)
-        });
-        admin.require_auth();
+    /// Kept for existing clients; role holders call `set_min_escrow_amount_as`.
+    pub fn set_min_escrow_amount(env: Env, token: Address, min_amount: i128) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::set_min_escrow_amount_as(env, admin, token, min_amount)
+    }
 
-        let current_version: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::StorageLayoutVersion)
-            .unwrap_or(0);
-        if current_version == CURRENT_STORAGE_LAYOUT_VERSION {
-            return 0;
-        }
+    /// Set the minimum escrow amount for a specific token (`Role::TokenManager`)
+    ///
+    /// # Arguments
+    /// * `caller` - Address holding `Role::TokenManager`
+    /// * `token` - Token address
+    /// * `min_amount` - Minimum amount in smallest unit
+    pub fn set_min_escrow_amount_as(
+        env: Env,
+        caller: Address,
+        token: Address,
+        min_amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::TokenManager, &caller);
+
+        let key = DataKey::MinEscrowAmount(token.clone());
+        let old_amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+
+        env.storage().persistent().set(&key, &min_amount);
+        Self::extend_persistent(&env, &key);
+        Self::emit_config_updated(
+            &env,
+            "min_escrow_amount",
+            ConfigValue::I128(old_amount),
+            ConfigValue::I128(min_amount),
+        );
+        Ok(())
+    }
 
-        Self::migrate_legacy_all_escrow_ids(&env);
-        Self::migrate_legacy_whitelisted_tokens(&env);
+    /// Get current platform fee percentage
+    pub fn get_platform_fee(env: Env) -> u32 {
+        Self::current_platform_fee_bps(&env)
+    }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::StorageLayoutVersion, &CURRENT_STORAGE_LAYOUT_VERSION);
-        Self::extend_persistent(&env, &DataKey::StorageLayoutVersion);
+    /// Get platform wallet address
+    pub fn get_platform_wallet(env: Env) -> Address {
+        let config = Self::get_platform_config_internal(&env);
+        config.platform_wallet
+    }
 
-        1
+    /// Get total fees collected by platform
+    pub fn get_total_fees_collected(env: Env) -> i128 {
+        Self::get_all_tracked_total_fees(&env)
     }
 
-    fn ensure_storage_layout_compatible(env: &Env) -> Result<(), Error> {
-        let stored_version: u32 = env
-            .storage()
+    /// Get total fees collected for a specific token.
+    pub fn get_total_fees_for_token(env: Env, token: Address) -> i128 {
+        env.storage()
             .persistent()
-            .get(&DataKey::StorageLayoutVersion)
-            .unwrap_or(0);
-        if stored_version != CURRENT_STORAGE_LAYOUT_VERSION {
-            return Err(Error::StorageLayoutMismatch);
-        }
-        Ok(())
+            .get(&DataKey::TotalFees(token))
+            .unwrap_or(0)
     }
 
-    /// Upgrade the contract's WASM code after the grace period has elapsed.
-    ///
-    /// The caller passes the `expected_wasm_hash` they think is pending; if it
-    /// does not match the stored proposal the call fails with
-    /// `InvalidUpgradeHash`. This is defense-in-depth against a scenario where
-    /// the admin's signing tool is shown a different proposal than what was
-    /// actually stored on-chain, and forces the operator to confirm exactly
-    /// which payload is being applied (#230).
+    /// Calculate the fee for a given amount (for display purposes)
     ///
-    /// On success a new `UpgradeRecord` is appended to `UpgradeHistory`,
-    /// `ContractVersion` is bumped, and the proposal is cleared atomically.
-    pub fn execute_upgrade(env: Env, expected_wasm_hash: BytesN<32>) -> Result<(), Error> {
-        Self::ensure_storage_layout_compatible(&env)?;
+    /// # Arguments
+    /// * `amount` - The escrow amount
+    pub fn calculate_fee_for_amount(env: Env, amount: i128) -> i128 {
+        Self::calculate_fee(&env, amount, Self::current_platform_fee_bps(&env))
+    }
 
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+    /// Calculate net amount seller will receive
+    ///
+    /// # Arguments
+    /// * `amount` - The escrow amount
+    pub fn calculate_seller_net_amount(env: Env, amount: i128) -> i128 {
+        let fee = Self::calculate_fee_for_amount(env, amount);
+        amount - fee
+    }
 
-        let proposal: WasmUpgradeProposal = env
-            .storage()
-            .persistent()
-            .get(&DataKey::WasmUpgradeProposal)
-            .ok_or(Error::NoUpgradeProposed)?;
+    /// Returns the current deterministic fee policy version.
+    ///
+    /// Increment this constant whenever fee allocation formulas change.
+    /// Callers can compare versions off-chain to detect policy updates.
+    pub fn get_fee_policy_version(_env: Env) -> u32 {
+        FEE_POLICY_VERSION
+    }
 
-        if proposal.wasm_hash != expected_wasm_hash {
-            return Err(Error::InvalidUpgradeHash);
+    /// Validate escrow parameters for batch creation
+    fn validate_escrow_params(env: &Env, params: &EscrowCreateParams) -> Result<(), Error> {
+        // Validate amount is positive
+        if params.amount <= 0 {
+            return Err(Error::AmountBelowMinimum);
         }
 
-        if env.ledger().timestamp() < proposal.upgrade_at {
-            return Err(Error::UpgradeCooldownActive);
-        }
+        // Check minimum amount
+        Self::check_min_amount(env, params.token.clone(), params.amount)?;
 
-        env.deployer()
-            .update_current_contract_wasm(proposal.wasm_hash.clone());
+        // Validate buyer and seller are different; a seller may not fund
+        // its own escrow either
+        if params.buyer == params.seller || params.payer.as_ref() == Some(&params.seller) {
+            return Err(Error::SameBuyerSeller);
+        }
 
-        // Update version in storage
-        let current_version: u32 = env
+        // Validate token is whitelisted (#103)
+        let whitelist: Map<Address, bool> = env
             .storage()
             .persistent()
-            .get(&DataKey::ContractVersion)
-            .unwrap_or(0);
-        let new_version = current_version + 1;
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::ContractVersion, &new_version);
-        Self::extend_persistent(&env, &DataKey::ContractVersion);
+            .get(&DataKey::WhitelistedTokens)
+            .unwrap_or(Map::new(env));
+        if !whitelist.is_empty() && !whitelist.get(params.token.clone()).unwrap_or(false) {
+            return Err(Error::TokenNotWhitelisted);
+        }
 
-        Self::append_upgrade_history(
-            &env,
-            UpgradeRecord {
-                from_version: current_version,
-                to_version: new_version,
-                wasm_hash: proposal.wasm_hash.clone(),
-                admin: admin.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        // Validate release window bounds (#67)
+        let window = params.release_window.unwrap_or(604800u32);
+        if window == 0 {
+            return Err(Error::ReleaseWindowTooShort);
+        }
+        let max_window = Self::get_max_release_window(env);
+        if window > max_window {
+            return Err(Error::ReleaseWindowTooLong);
+        }
 
-        // Clear proposal
-        env.storage()
-            .persistent()
-            .remove(&DataKey::WasmUpgradeProposal);
+        // Validate IPFS hash if provided
+        Self::validate_optional_ipfs_hash(env, &params.ipfs_hash);
 
-        Self::emit_upgrade_event(
-            &env,
-            UPGRADE_EXECUTED,
-            proposal.wasm_hash,
-            admin,
-            proposal.upgrade_at,
-        );
+        if let Some(hash) = &params.metadata_hash {
+            if hash.len() != 32 {
+                return Err(Error::InvalidMetadataHash);
+            }
+        }
 
         Ok(())
     }
 
-    /// Cancel a proposed WASM upgrade (admin only) (#95, #230).
-    ///
-    /// Emits an `UPG_CANC` event so cancellations are visible alongside
-    /// proposals in the audit trail. Returning `NoUpgradeProposed` instead of
-    /// silently succeeding makes accidental double-cancels visible.
-    pub fn cancel_upgrade_wasm(env: Env) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+    /// Create a single escrow from parameters (internal helper)
+    /// Note: For batch operations, buyer/seller escrow list updates are consolidated
+    /// by the caller to minimize storage writes (Issue #111)
+    fn create_single_escrow(
+        env: &Env,
+        params: EscrowCreateParams,
+        batch_id: Option<u64>,
+    ) -> Result<u64, Error> {
+        // Validate first
+        Self::validate_escrow_params(env, &params)?;
 
-        let proposal: WasmUpgradeProposal = env
-            .storage()
-            .persistent()
-            .get(&DataKey::WasmUpgradeProposal)
-            .ok_or(Error::NoUpgradeProposed)?;
+        // Default to 7 days if not specified
+        let window = params.release_window.unwrap_or(604800u32);
+        let created_at_u64 = env.ledger().timestamp();
+        assert!(
+            created_at_u64 <= u32::MAX as u64,
+            "Ledger timestamp overflow"
+        );
+        let created_at = created_at_u64 as u32;
 
-        env.storage()
-            .persistent()
-            .remove(&DataKey::WasmUpgradeProposal);
+        // Validate metadata (validate_escrow_params already checked ipfs_hash via validate_optional_ipfs_hash)
+        Self::validate_optional_metadata_hash(env, &params.metadata_hash);
 
-        // Increment the round nonce inside the approval state so that any
-        // residual approvals cannot be replayed in the next round.
-        // We write a "poisoned" state (empty approvals, bumped nonce) rather
-        // than removing the key so the nonce survives across cancel cycles.
-        let state_key = DataKey::UpgradeApprovalState(0);
-        let next_nonce: u32 = env
-            .storage()
-            .persistent()
-            .get::<DataKey, UpgradeApprovalState>(&state_key)
-            .map(|s| s.nonce.saturating_add(1))
-            .unwrap_or(1u32);
-        // Store a sentinel state with the bumped nonce so propose_upgrade_wasm
-        // knows it must open a fresh round on the next call.
-        let reset_state = UpgradeApprovalState {
-            nonce: next_nonce,
-            signers: Vec::new(&env),
-            threshold: 1u32,
-            approvals: Vec::new(&env),
+        let payer = params.payer.clone().filter(|payer| *payer != params.buyer);
+        Self::snapshot_fee_rate(env, params.order_id, &params.seller, &params.token);
+        let amount = params.amount
+            + Self::apply_fee_payer_terms(
+                env,
+                params.order_id,
+                &params.seller,
+                &params.token,
+                params.amount,
+            );
+
+        let escrow = Escrow {
+            version: CURRENT_ESCROW_VERSION,
+            id: params.order_id as u64,
+            batch_id,
+            buyer: params.buyer.clone(),
+            seller: params.seller.clone(),
+            token: params.token.clone(),
+            amount,
+            status: EscrowStatus::Active,
+            release_window: window,
+            created_at,
+            ipfs_hash: params.ipfs_hash.clone(),
+            metadata_hash: params.metadata_hash.clone(),
+            dispute_reason: None,
+            dispute_initiated_at: None,
+            funded: true,
+            funding_deadline: None, // Immediately funded; no deadline required (#656)
+            payer: payer.clone(),
+            refund_address: params.refund_address.clone(),
         };
-        env.storage().persistent().set(&state_key, &reset_state);
-        Self::extend_persistent(&env, &state_key);
 
-        // Issue #618: Record the cancellation timestamp so propose_upgrade_wasm
-        // can enforce CANCEL_REPROPOSE_COOLDOWN against the cancel-and-repropose
-        // bypass pattern.
-        let cancelled_at = env.ledger().timestamp();
         env.storage()
             .persistent()
-            .set(&DataKey::LastUpgradeCancelledAt, &cancelled_at);
-        Self::extend_persistent(&env, &DataKey::LastUpgradeCancelledAt);
+            .set(&(ESCROW, params.order_id), &escrow);
+        Self::extend_persistent(env, &(ESCROW, params.order_id));
 
-        Self::emit_upgrade_event(
-            &env,
-            UPGRADE_CANCELLED,
-            proposal.wasm_hash,
-            admin,
-            proposal.upgrade_at,
-        );
+        // Track active escrows (batch)
+        Self::update_active_obligations(env, &params.buyer, 1);
+        Self::update_active_obligations(env, &params.seller, 1);
 
-        Ok(())
-    }
+        Self::update_total_locked(env, &params.token, amount);
+        let funder = payer.unwrap_or_else(|| params.buyer.clone());
+        Self::transfer_tokens_and_record_audit(env, &params.token, &funder, &env.current_contract_address(), amount, &funder, Symbol::new(env, "escrow_funded"), -amount);
 
-    /// Returns the currently pending WASM upgrade proposal, if any (#230).
-    /// Read-only â€” useful for off-chain monitors and admin dashboards that
-    /// need to confirm what `execute_upgrade` will apply.
-    pub fn get_upgrade_proposal(env: Env) -> Option<WasmUpgradeProposal> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::WasmUpgradeProposal)
-    }
+        Self::emit_escrow_created(
+            env,
+            EscrowEvent {
+                escrow_id: params.order_id as u64,
+                action: EscrowAction::Created,
+                buyer: params.buyer.clone(),
+                seller: params.seller.clone(),
+                amount,
+                token: params.token.clone(),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
 
-    /// Returns the current contract version.
-    ///
-    /// `ContractVersion` semantics:
-    /// - Initialized to `1` by `initialize`.
-    /// - Incremented by exactly `1` for each successful `execute_upgrade`.
-    /// - Independent of the on-disk WASM hash; the hash + version pair is
-    ///   captured per-upgrade in `UpgradeHistory` for auditability.
-    /// - Migration code that needs to gate behavior across upgrades should
-    ///   compare against this value rather than embedding magic numbers.
-    pub fn get_version(env: Env) -> u32 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::ContractVersion)
-            .unwrap_or(0)
+        Ok(params.order_id as u64)
     }
 
-    /// Append a record to the bounded `UpgradeHistory` log (#241). The Vec is
-    /// trimmed FIFO once it exceeds `MAX_UPGRADE_HISTORY`.
-    fn append_upgrade_history(env: &Env, record: UpgradeRecord) {
-        let mut history: Vec<UpgradeRecord> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::UpgradeHistory)
-            .unwrap_or_else(|| Vec::new(env));
+    /// DevEx: Dry-Run Batch Validation
+    /// Validates a batch of escrow creations without modifying state.
+    /// Returns a map of index -> Error for any escrow that fails validation.
+    pub fn validate_batch_creation(
+        env: Env,
+        escrows: soroban_sdk::Vec<EscrowCreateParams>,
+    ) -> Map<u32, Error> {
+        let mut errors: Map<u32, Error> = Map::new(&env);
 
-        history.push_back(record);
-        while history.len() > MAX_UPGRADE_HISTORY {
-            history.pop_front();
+        if escrows.len() > MAX_BATCH_SIZE {
+            env.panic_with_error(crate::Error::BatchLimitExceeded);
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::UpgradeHistory, &history);
-        Self::extend_persistent(env, &DataKey::UpgradeHistory);
+        for i in 0..escrows.len() {
+            if let Some(params) = escrows.get(i) {
+                if let Err(e) = Self::validate_escrow_params(&env, &params) {
+                    errors.set(i, e);
+                }
+            }
+        }
+
+        errors
     }
 
-    /// Returns the bounded log of past contract upgrades (#241).
+    /// Create multiple escrows in a batch operation (Issue #111: Optimized)
     ///
-    /// Newer entries are at the back. The log is capped at
-    /// `MAX_UPGRADE_HISTORY` records â€” older entries are dropped FIFO so
-    /// storage cannot grow unbounded. For long-term audit trails operators
-    /// should mirror the `wasm_upgrade` events to off-chain storage.
-    pub fn get_upgrade_history(env: Env) -> Vec<UpgradeRecord> {
+    /// Validates all escrows first before processing any to ensure atomic behavior.
+    /// Authorization model:
+    /// - Every distinct buyer in the batch must provide an authorization signature
+    /// - This prevents a single transaction from creating escrows on behalf of
+    ///   buyers who did not approve the operation
+    /// Optimizations:
+    /// - Consolidated storage updates for buyer/seller escrow lists
+    /// - Batch size limit to prevent resource exhaustion
+    ///
+    /// # Arguments
+    /// * `escrows` - Vector of escrow creation parameters (max MAX_BATCH_SIZE items)
+    /// * `batch_id` - Unique identifier for this batch operation
+    ///
+    /// # Returns
+    /// Vector of created escrow IDs
+    ///
+    /// # Errors
+    /// - BatchLimitExceeded if batch exceeds MAX_BATCH_SIZE
+    /// - Any validation error from individual escrows
+    pub fn create_escrows_batch(
+        env: Env,
+        params: soroban_sdk::Vec<EscrowCreateParams>,
+    ) -> Result<soroban_sdk::Vec<u64>, Error> {
+        let batch_id = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "next_batch_id"))
+            .unwrap_or(1u64);
         env.storage()
-            .persistent()
-            .get(&DataKey::UpgradeHistory)
-            .unwrap_or_else(|| Vec::new(&env))
+            .instance()
+            .set(&Symbol::new(&env, "next_batch_id"), &(batch_id + 1));
+        Self::create_batch_escrow(env, batch_id, params)
     }
 
-    /// Returns aggregate version + last-upgrade metadata (#241). Pairs the
-    /// scalar `ContractVersion` with the most recent `UpgradeRecord` so a
-    /// dashboard or migration script can read everything in one call.
-    pub fn get_version_info(env: Env) -> VersionInfo {
-        let current_version = Self::get_version(env.clone());
-        let history = Self::get_upgrade_history(env);
-        let upgrade_count = history.len();
-        VersionInfo {
-            current_version,
-            upgrade_count,
+    pub fn create_batch_escrow(
+        env: Env,
+        batch_id: u64,
+        escrows: soroban_sdk::Vec<EscrowCreateParams>,
+    ) -> Result<soroban_sdk::Vec<u64>, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::check_not_paused(&env);
+
+        // Issue #111: Enforce batch size limit
+        if escrows.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchLimitExceeded);
         }
-    }
 
-    /// Refund funds to buyer (admin only)
-    ///
-    /// # Arguments
-    /// * `escrow_id` - Escrow/Order identifier
-    pub fn refund(env: Env, escrow_id: u64) -> Result<(), Error> {
-        let _guard = ReentryGuardScope::new(&env);
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+        let mut results = soroban_sdk::Vec::new(&env);
 
-        let order_id = escrow_id as u32;
-        let mut escrow =
-            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::RefundPending)?;
+        // Early exit for empty batch
+        if escrows.is_empty() {
+            return Ok(results);
+        }
 
-        // Deterministic fee allocation via the central FeePolicy engine.
-        let allocation = Self::compute_fee_allocation(
-            &env,
-            escrow.amount,
-            0,
-            SettlementKind::FullRefundNoFee,
-        );
+        // Issue #606: Require authorization from every distinct buyer in the batch.
+        // This prevents a single transaction from creating escrows on behalf of
+        // buyers who did not sign the operation. Gift escrows are authorized by
+        // their payer, whose funds are moved.
+        let mut authorized_buyers: Map<Address, u32> = Map::new(&env);
+        for i in 0..escrows.len() {
+            if let Some(params) = escrows.get(i) {
+                let buyer_key = params.payer.clone().unwrap_or(params.buyer.clone());
+                if !authorized_buyers.contains_key(buyer_key.clone()) {
+                    buyer_key.require_auth();
+                    authorized_buyers.set(buyer_key, 1u32);
+                }
+            }
+        }
 
-        // Update status
-        escrow.status = EscrowStatus::Refunded;
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
-        Self::extend_persistent(&env, &(ESCROW, order_id));
+        // Issue #111: Validate all first (single pass)
+        for i in 0..escrows.len() {
+            if let Some(params) = escrows.get(i) {
+                Self::validate_escrow_params(&env, &params)?;
+            }
+        }
 
-        // Decrement active counts
-        Self::update_active_obligations(&env, &escrow.buyer, -1);
-        Self::update_active_obligations(&env, &escrow.seller, -1);
+        // Issue #111: Collect buyer/seller updates to consolidate storage writes
+        // Using indexed storage for scalability. Precompute the starting counts for
+        // each buyer/seller once so the batch creation loop can reuse that state
+        // without repeatedly re-reading the same count map while populating indices.
+        let mut buyer_count_state: Map<Address, u32> = Map::new(&env);
+        let mut seller_count_state: Map<Address, u32> = Map::new(&env);
 
-        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+        for i in 0..escrows.len() {
+            if let Some(params) = escrows.get(i) {
+                let buyer_key = params.buyer.clone();
+                let seller_key = params.seller.clone();
 
-        Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+                if !buyer_count_state.contains_key(buyer_key.clone()) {
+                    let count_key = DataKey::BuyerEscrowCount(buyer_key.clone());
+                    let existing_count: u32 =
+                        env.storage().persistent().get(&count_key).unwrap_or(0u32);
+                    buyer_count_state.set(buyer_key.clone(), existing_count);
+                }
 
-        // Refund to buyer and record audit
-        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.buyer, allocation.buyer_amount, &escrow.buyer, Symbol::new(&env, "refund"), allocation.buyer_amount);
+                if !seller_count_state.contains_key(seller_key.clone()) {
+                    let count_key = DataKey::SellerEscrowCount(seller_key.clone());
+                    let existing_count: u32 =
+                        env.storage().persistent().get(&count_key).unwrap_or(0u32);
+                    seller_count_state.set(seller_key.clone(), existing_count);
+                }
+            }
+        }
 
-        Self::emit_escrow_created(
-            &env,
-            EscrowEvent {
-                escrow_id,
-                action: EscrowAction::Refunded,
-                buyer: escrow.buyer.clone(),
-                seller: escrow.seller.clone(),
-                amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        let mut buyer_next_counts: Map<Address, u32> = Map::new(&env);
+        let mut seller_next_counts: Map<Address, u32> = Map::new(&env);
 
-        // Emit reputation update events â€” decoupled from onboarding contract (#211)
-        let ts = env.ledger().timestamp();
-        Self::emit_reputation_update(
-            &env,
-            ReputationUpdateEvent {
-                address: escrow.buyer.clone(),
-                successful_delta: 1,
-                disputed_delta: 0,
-                metrics_sales_delta: 0,
-                metrics_amount: 0,
-                token: escrow.token.clone(),
-                timestamp: ts,
-            },
-        );
-        Self::emit_reputation_update(
-            &env,
-            ReputationUpdateEvent {
-                address: escrow.seller.clone(),
-                successful_delta: 0,
-                disputed_delta: 1,
-                metrics_sales_delta: 0,
-                metrics_amount: 0,
-                token: escrow.token.clone(),
-                timestamp: ts,
-            },
-        );
-        Ok(())
-    }
+        // Create all escrows using the precomputed count state.
+        for i in 0..escrows.len() {
+            if let Some(params) = escrows.get(i) {
+                match Self::create_single_escrow(&env, params.clone(), Some(batch_id)) {
+                    Ok(id) => {
+                        let buyer_key = params.buyer.clone();
+                        let seller_key = params.seller.clone();
 
-    /// Get escrow details
-    ///
-    /// # Arguments
-    /// * `order_id` - Order identifier
-    pub fn get_escrow(env: Env, order_id: u32) -> Escrow {
-        Self::get_stored_escrow(&env, order_id)
-    }
+                        if !buyer_next_counts.contains_key(buyer_key.clone()) {
+                            let existing_count = buyer_count_state
+                                .get(buyer_key.clone())
+                                .unwrap_or(0u32);
+                            buyer_next_counts.set(buyer_key.clone(), existing_count);
+                        }
+                        let buyer_count = buyer_next_counts.get(buyer_key.clone()).unwrap();
 
-    /// Read the immutable fund-movement audit history for an account.
-    pub fn get_fund_audit_history(env: Env, actor: Address) -> Vec<FundMovementAuditEntry> {
-        let count_key = DataKey::FundAuditCount(actor.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        let mut history = Vec::new(&env);
+                        // Store escrow ID at indexed position
+                        let buyer_index_key =
+                            DataKey::BuyerEscrowIndexed(buyer_key.clone(), buyer_count);
+                        env.storage().persistent().set(&buyer_index_key, &id);
+                        Self::extend_persistent(&env, &buyer_index_key);
 
-        for index in 0..count {
-            let entry_key = DataKey::FundAuditIndexed(actor.clone(), index);
-            if let Some(entry) = env.storage().persistent().get::<DataKey, FundMovementAuditEntry>(&entry_key) {
-                history.push_back(entry);
-            }
-        }
+                        buyer_next_counts.set(buyer_key, buyer_count + 1);
 
-        history
-    }
+                        if !seller_next_counts.contains_key(seller_key.clone()) {
+                            let existing_count = seller_count_state
+                                .get(seller_key.clone())
+                                .unwrap_or(0u32);
+                            seller_next_counts.set(seller_key.clone(), existing_count);
+                        }
+                        let seller_count = seller_next_counts.get(seller_key.clone()).unwrap();
 
-    /// Read the total number of fund-movement audit entries for an account.
-    pub fn get_fund_audit_count(env: Env, actor: Address) -> u32 {
-        let count_key = DataKey::FundAuditCount(actor);
-        env.storage().persistent().get(&count_key).unwrap_or(0)
-    }
+                        // Store escrow ID at indexed position
+                        let seller_index_key =
+                            DataKey::SellerEscrowIndexed(seller_key.clone(), seller_count);
+                        env.storage().persistent().set(&seller_index_key, &id);
+                        Self::extend_persistent(&env, &seller_index_key);
 
-    /// Read a paginated slice of the fund-movement audit history for an account.
-    ///
-    /// # Arguments
-    /// * `actor` - Account address to query audit history for
-    /// * `start_index` - Starting zero-based index of audit records to read
-    /// * `limit` - Maximum number of entries to return
-    pub fn get_fund_audit_history_paginated(
-        env: Env,
-        actor: Address,
-        start_index: u32,
-        limit: u32,
-    ) -> Vec<FundMovementAuditEntry> {
-        let count_key = DataKey::FundAuditCount(actor.clone());
-        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-        let mut history = Vec::new(&env);
+                        seller_next_counts.set(seller_key, seller_count + 1);
 
-        if start_index >= count || limit == 0 {
-            return history;
+                        // Emit batch event
+                        let escrow_opt: Option<Escrow> =
+                            env.storage().persistent().get(&(ESCROW, id as u32));
+                        if let Some(escrow) = escrow_opt {
+                            Self::emit_escrow_created(
+                                &env,
+                                EscrowEvent {
+                                    escrow_id: id,
+                                    action: EscrowAction::BatchCreated,
+                                    buyer: escrow.buyer,
+                                    seller: escrow.seller,
+                                    amount: escrow.amount,
+                                    token: escrow.token,
+                                    timestamp: env.ledger().timestamp(),
+                                },
+                            );
+                        }
+                        results.push_back(id);
+                    }
+                    Err(e) => {
+                        return Err(e);
+                    }
+                }
+            }
         }
 
-        let end_index = start_index.saturating_add(limit).min(count);
-        for index in start_index..end_index {
-            let entry_key = DataKey::FundAuditIndexed(actor.clone(), index);
-            if let Some(entry) = env.storage().persistent().get::<DataKey, FundMovementAuditEntry>(&entry_key) {
-                history.push_back(entry);
+        // Issue #111: Consolidate all storage updates at once
+        let mut i = 0;
+        loop {
+            if i >= buyer_next_counts.len() {
+                break;
+            }
+            if let Some(buyer_addr) = buyer_next_counts.keys().get(i) {
+                if let Some(final_count) = buyer_next_counts.get(buyer_addr.clone()) {
+                    let count_key = DataKey::BuyerEscrowCount(buyer_addr.clone());
+                    env.storage().persistent().set(&count_key, &final_count);
+                    Self::extend_persistent(&env, &count_key);
+                }
             }
+            i += 1;
         }
 
-        history
-    }
+        let mut i = 0;
+        loop {
+            if i >= seller_next_counts.len() {
+                break;
+            }
+            if let Some(seller_addr) = seller_next_counts.keys().get(i) {
+                if let Some(final_count) = seller_next_counts.get(seller_addr.clone()) {
+                    let count_key = DataKey::SellerEscrowCount(seller_addr.clone());
+                    env.storage().persistent().set(&count_key, &final_count);
+                    Self::extend_persistent(&env, &count_key);
+                }
+            }
+            i += 1;
+        }
 
-    /// Get escrow metadata fields only.
-    pub fn get_escrow_metadata(env: Env, order_id: u32) -> EscrowMetadata {
-        let escrow = Self::get_escrow(env, order_id);
-        EscrowMetadata {
-            ipfs_hash: escrow.ipfs_hash,
-            metadata_hash: escrow.metadata_hash,
+        // Consolidate global index updates for the entire batch using atomic function
+        // This ensures AllEscrowIds and EscrowCount always remain in sync (Issue #226)
+        if !results.is_empty() {
+            // Convert results to u32 order IDs
+            let mut order_ids = soroban_sdk::Vec::new(&env);
+            for j in 0..results.len() {
+                if let Some(id) = results.get(j) {
+                    order_ids.push_back(id as u32);
+                }
+            }
+            Self::update_escrow_indices_batch_atomic(&env, &order_ids);
         }
+
+        Ok(results)
     }
 
-    /// Verify that provided metadata matches the stored hash (Issue #122)
+    /// Release multiple escrows in a batch operation
     ///
-    /// This function allows parties to reveal off-chain metadata and verify it matches
-    /// the commitment stored on-chain. Uses SHA-256 hashing for verification.
+    /// Validates all escrows first before processing any.
     ///
     /// # Arguments
-    /// * `order_id` - Order identifier
-    /// * `proof` - MetadataRevealProof containing the full content and optional secret
-    ///
-    /// # Returns
-    /// true if the provided content hashes to the stored metadata_hash, false otherwise
-    ///
-    /// # Notes
-    /// - The metadata_hash must be set on the escrow for verification to succeed
-    /// - The secret field is optional and can be used for additional application-level verification
-    /// - This function does NOT modify state; it only verifies the commitment
-    pub fn verify_metadata_reveal(
+    /// * `order_ids` - Vector of order IDs to release
+    /// * `batch_id` - Unique identifier for this batch operation
+    /// * `authorized_address` - Address releasing the funds (buyer)
+    pub fn release_batch_funds(
         env: Env,
-        order_id: u32,
-        proof: MetadataRevealProof,
+        _batch_id: u64,
+        order_ids: soroban_sdk::Vec<u32>,
         authorized_address: Address,
-    ) -> bool {
-        let escrow = Self::get_escrow(env.clone(), order_id);
-        let config = Self::get_platform_config_internal(&env);
+    ) -> Result<soroban_sdk::Vec<u64>, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        authorized_address.require_auth();
 
-        let is_authorized = authorized_address == escrow.buyer
-            || authorized_address == escrow.seller
-            || authorized_address == config.arbitrator;
-        if !is_authorized {
-            env.panic_with_error(crate::Error::Unauthorized);
-        }
+        let mut results = soroban_sdk::Vec::new(&env);
 
-        // If no metadata hash was set, verification fails
-        if escrow.metadata_hash.is_none() {
-            return false;
-        }
+        // Validate all escrows first
+        for i in 0..order_ids.len() {
+            if let Some(order_id) = order_ids.get(i) {
+                let escrow_opt = env.storage().persistent().get(&(ESCROW, order_id));
 
-        let stored_hash = escrow.metadata_hash.unwrap();
+                if escrow_opt.is_none() {
+                    return Err(Error::EscrowNotFound);
+                }
 
-        // Compute SHA-256 hash of the provided content
-        let computed_hash = env.crypto().sha256(&proof.content);
+                let escrow: Escrow = escrow_opt.unwrap();
 
-        // Convert Hash to Bytes by creating a new Bytes from the hash
-        // Hash implements Into<Bytes> in Soroban SDK
-        let computed_bytes: Bytes = computed_hash.into();
+                // Check status
+                if escrow.status != EscrowStatus::Active {
+                    return Err(Error::InvalidEscrowState);
+                }
 
-        // Compare hashes
-        computed_bytes == stored_hash
-    }
+                // Check authorization (buyer must match)
+                if escrow.buyer != authorized_address {
+                    return Err(Error::Unauthorized);
+                }
+            }
+        }
 
-    /// Authorized verification that records successful metadata matching on-chain.
-    ///
-    /// Only the escrow buyer, seller, or admin may call this method. A successful verification
-    /// emits a permanent MetadataVerified event.
-    pub fn verify_metadata_reveal_recorded(
-        env: Env,
-        order_id: u32,
-        proof: MetadataRevealProof,
-        authorized_address: Address,
-    ) -> bool {
-        authorized_address.require_auth();
+        // Then process all releases
+        for i in 0..order_ids.len() {
+            if let Some(order_id) = order_ids.get(i) {
+                let escrow_opt: Option<Escrow> =
+                    env.storage().persistent().get(&(ESCROW, order_id));
+                if escrow_opt.is_some() {
+                    Self::extend_persistent_read(&env, &(ESCROW, order_id));
+                }
 
-        let escrow = Self::get_escrow(env.clone(), order_id);
-        let config = Self::get_platform_config_internal(&env);
-        let is_authorized = authorized_address == escrow.buyer
-            || authorized_address == escrow.seller
-            || authorized_address == config.arbitrator;
-        if !is_authorized {
-            env.panic_with_error(crate::Error::Unauthorized);
-        }
+                if let Some(mut escrow) = escrow_opt {
+                    // Get platform config
+                    let config = Self::get_platform_config_internal(&env);
 
-        let is_valid =
-            Self::verify_metadata_reveal(env.clone(), order_id, proof, authorized_address.clone());
-        if is_valid {
-            Self::emit_metadata_verified(&env, order_id, authorized_address);
-        }
-        is_valid
-    }
+                    // Deterministic fee allocation via the central FeePolicy engine.
+                    let fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+                    let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+                    let allocation = Self::apply_referral_share(
+                        &env,
+                        Self::compute_escrow_fee_allocation(
+                            &env,
+                            order_id,
+                            escrow.amount,
+                            &fee_rate,
+                            SettlementKind::ReleaseFunds,
+                        ),
+                        &referrer,
+                    );
 
-    /// Check if escrow can be auto-released
-    ///
-    /// # Arguments
-    /// * `order_id` - Order identifier
-    pub fn can_auto_release(env: Env, order_id: u32) -> bool {
-        let escrow = Self::try_get_escrow_readonly(&env, order_id);
+                    // Update status
+                    escrow.status = EscrowStatus::Released;
+                    env.storage().persistent().set(&(ESCROW, order_id), &escrow);
 
-        if escrow.status != EscrowStatus::Active {
-            return false;
-        }
+                    // Decrement active counts
+                    Self::update_active_obligations(&env, &escrow.buyer, -1);
+                    Self::update_active_obligations(&env, &escrow.seller, -1);
+
+                    Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
+                    Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+                    Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+
+                    // Transfer platform fee to platform wallet
+                    if allocation.platform_fee > 0 {
+                        Self::transfer_platform_fee(
+                            &env,
+                            &escrow.token,
+                            &config.platform_wallet,
+                            &allocation,
+                            referrer.as_ref().map(|referrer| (order_id, referrer)),
+                            None,
+                        );
+                    }
 
-        let current_time = env.ledger().timestamp();
-        let elapsed = current_time - (escrow.created_at as u64);
+                    // Transfer remaining funds to seller
+                    Self::pay_seller_proceeds(&env, order_id, &escrow, allocation.seller_amount, Symbol::new(&env, "escrow_released"));
 
-        elapsed >= escrow.release_window as u64
-    }
+                    Self::clear_settled_escrow_state(&env, order_id);
 
-    /// Dispute an escrow
-    ///
-    /// # Arguments
-    /// * `order_id` - Order identifier
-    /// * `dispute_reason` - Reason for dispute
-    /// * `authorized_address` - Address authorized to dispute (buyer or seller)
-    pub fn dispute_escrow(
-        env: Env,
-        order_id: u32,
-        dispute_reason: Symbol, // UPDATE ARGUMENT TYPE
-        authorized_address: Address,
-    ) {
-        authorized_address.require_auth();
+                    // Emit release event
+                    Self::emit_escrow_created(
+                        &env,
+                        EscrowEvent {
+                            escrow_id: order_id as u64,
+                            action: EscrowAction::BatchReleased,
+                            buyer: escrow.buyer.clone(),
+                            seller: escrow.seller.clone(),
+                            amount: escrow.amount,
+                            token: escrow.token.clone(),
+                            timestamp: env.ledger().timestamp(),
+                        },
+                    );
+                    results.push_back(order_id as u64);
+                }
+            }
+        }
 
-        let escrow_for_auth = Self::get_stored_escrow(&env, order_id);
+        Ok(results)
+    }
 
-        // Allow buyer or seller to dispute
-        if !(escrow_for_auth.buyer == authorized_address
-            || escrow_for_auth.seller == authorized_address)
+    /// Check that the contract is not paused. Panics with ContractPaused if it is.
+    fn check_not_paused(env: &Env) {
+        if let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, PlatformConfig>(&DataKey::PlatformConfig)
         {
-            env.panic_with_error(crate::Error::Unauthorized);
+            if config.is_paused {
+                env.panic_with_error(crate::Error::ContractPaused);
+            }
         }
-
-        let mut escrow =
-            Self::claim_active_escrow_transition(&env, order_id, EscrowStatus::DisputePending)
-                .unwrap_or_else(|e| env.panic_with_error(e));
-
-        escrow.status = EscrowStatus::Disputed;
-        escrow.dispute_reason = Some(dispute_reason); // Assign Symbol
-        escrow.dispute_initiated_at = Some(env.ledger().timestamp());
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
-
-        Self::emit_escrow_created(
-            &env,
-            EscrowEvent {
-                escrow_id: order_id as u64,
-                action: EscrowAction::Disputed,
-                buyer: escrow.buyer.clone(),
-                seller: escrow.seller.clone(),
-                amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
     }
 
-    /// Resolve disputed escrow (arbitrator only).
-    ///
-    /// This function transitions the escrow from `Disputed` to `Resolved`.
-    /// Depending on the `resolution` choice:
-    /// - `ReleaseToSeller`: Funds are sent to the seller minus the platform fee.
-    /// - `RefundToBuyer`: Full original amount is returned to the buyer.
+    /// Pause or unpause the contract (admin only).
     ///
-    /// # Edge Cases
-    /// - **Refund Failure**: If the transfer to the buyer fails (e.g. account revoked),
-    ///   the entire transaction reverts due to Stellar's atomicity.
-    ///   The escrow remains in `Disputed` state for re-investigation.
-    /// - **State Logic**: Can ONLY be called if `status` is currently `Disputed`.
-    pub fn resolve_dispute(
-        env: Env,
-        order_id: u32,
-        resolution: Resolution,
-        authorized_address: Address,
-    ) {
-        let _guard = ReentryGuardScope::new(&env);
-        let config = Self::get_platform_config_internal(&env);
-        authorized_address.require_auth();
-        let is_authorized = authorized_address == config.admin
-            || Some(authorized_address.clone()) == config.moderator
-            || authorized_address == config.arbitrator;
-        if !is_authorized {
-            env.panic_with_error(crate::Error::Unauthorized);
-        }
+    /// Kept for existing clients; role holders call `set_paused_as`.
+    pub fn set_paused(env: Env, paused: bool) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::set_paused_as(env, admin, paused)
+    }
 
-        let mut escrow = Self::get_stored_escrow(&env, order_id);
+    /// Pause or unpause the contract (`Role::Pauser`).
+    pub fn set_paused_as(env: Env, caller: Address, paused: bool) {
+        Self::require_role(&env, Role::Pauser, &caller);
 
-        if escrow.status != EscrowStatus::Disputed {
-            env.panic_with_error(crate::Error::InvalidEscrowState);
+        let mut config = Self::get_platform_config_internal(&env);
+        config.is_paused = paused;
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+
+        if paused {
+            Self::emit_platform_paused(&env, caller);
+        } else {
+            Self::emit_platform_unpaused(&env, caller);
         }
+    }
 
-        // CRITICAL: Update status BEFORE external calls (CEI pattern)
-        escrow.status = EscrowStatus::Resolved;
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+    /// View: check if contract is paused.
+    pub fn is_paused(env: Env) -> bool {
+        let config = Self::get_platform_config_internal(&env);
+        config.is_paused
+    }
 
-        // Decrement active counts
-        Self::update_active_obligations(&env, &escrow.buyer, -1);
-        Self::update_active_obligations(&env, &escrow.seller, -1);
+    // â”€â”€ Tiered Artisan Fees (#98) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
-        // Clean up any orphaned partial refund proposal
-        let proposal_key = DataKey::PartialRefundProposal(order_id);
-        env.storage().persistent().remove(&proposal_key);
+    /// Assign a custom fee tier (in basis points) for an artisan (admin only).
+    ///
+    /// Kept for existing clients; role holders call `set_artisan_fee_tier_as`.
+    pub fn set_artisan_fee_tier(env: Env, artisan: Address, fee_bps: u32) {
+        let admin = Self::get_platform_config_internal(&env).admin;
+        Self::set_artisan_fee_tier_as(env, admin, artisan, fee_bps)
+    }
 
-        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+    /// Assign a custom fee tier (in basis points) for an artisan
+    /// (`Role::FeeManager`).
+    pub fn set_artisan_fee_tier_as(env: Env, caller: Address, artisan: Address, fee_bps: u32) {
+        Self::require_role(&env, Role::FeeManager, &caller);
+        Self::check_no_fee_notice(&env).unwrap_or_else(|e| env.panic_with_error(e));
 
-        // Now perform token transfers (external calls)
-        match resolution {
-            Resolution::ReleaseToSeller => {
-                let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.seller.clone());
-                let allocation = Self::compute_fee_allocation(
-                    &env,
-                    escrow.amount,
-                    fee_bps,
-                    SettlementKind::ReleaseFunds,
-                );
-                Self::update_total_locked(&env, &escrow.token, -escrow.amount);
-                if allocation.platform_fee > 0 {
-                    Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, allocation.platform_fee);
-                }
-                Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.seller, allocation.seller_amount, &escrow.seller, Symbol::new(&env, "escrow_released"), allocation.seller_amount);
-            }
-            Resolution::RefundToBuyer => {
-                let allocation = Self::compute_fee_allocation(
-                    &env,
-                    escrow.amount,
-                    0,
-                    SettlementKind::FullRefundNoFee,
-                );
-                Self::update_total_locked(&env, &escrow.token, -escrow.amount);
-                Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.buyer, allocation.buyer_amount, &escrow.buyer, Symbol::new(&env, "refund"), allocation.buyer_amount);
-            }
+        if fee_bps > MAX_PLATFORM_FEE_BPS {
+            env.panic_with_error(crate::Error::InvalidFee);
         }
+        Self::drop_due_fee_changes(&env, Some(&artisan));
 
-        Self::emit_escrow_created(
-            &env,
-            EscrowEvent {
-                escrow_id: order_id as u64,
-                action: EscrowAction::Resolved,
-                buyer: escrow.buyer.clone(),
-                seller: escrow.seller.clone(),
-                amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
-        Self::emit_escrow_resolved_event(
-            &env,
-            EscrowResolvedEvent {
-                escrow_id: order_id as u64,
-                buyer: escrow.buyer.clone(),
-                seller: escrow.seller.clone(),
-                arbitrator: authorized_address.clone(),
-                amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        env.storage()
+            .persistent()
+            .set(&DataKey::ArtisanFeeTier(artisan.clone()), &fee_bps);
+        Self::extend_persistent(&env, &DataKey::ArtisanFeeTier(artisan.clone()));
+        Self::emit_artisan_fee_tier_updated(&env, artisan, fee_bps);
+    }
 
-        // Emit reputation update events â€” decoupled from onboarding contract (#211)
-        let ts = env.ledger().timestamp();
-        match resolution {
-            Resolution::ReleaseToSeller => {
-                Self::emit_reputation_update(
-                    &env,
-                    ReputationUpdateEvent {
-                        address: escrow.seller.clone(),
-                        successful_delta: 1,
-                        disputed_delta: 0,
-                        metrics_sales_delta: 1,
-                        metrics_amount: escrow.amount,
-                        token: escrow.token.clone(),
-                        timestamp: ts,
-                    },
-                );
-                Self::emit_reputation_update(
-                    &env,
-                    ReputationUpdateEvent {
-                        address: escrow.buyer.clone(),
-                        successful_delta: 0,
-                        disputed_delta: 1,
-                        metrics_sales_delta: 0,
-                        metrics_amount: 0,
-                        token: escrow.token.clone(),
-                        timestamp: ts,
-                    },
-                );
-            }
-            Resolution::RefundToBuyer => {
-                Self::emit_reputation_update(
-                    &env,
-                    ReputationUpdateEvent {
-                        address: escrow.buyer.clone(),
-                        successful_delta: 1,
-                        disputed_delta: 0,
-                        metrics_sales_delta: 0,
-                        metrics_amount: 0,
-                        token: escrow.token.clone(),
-                        timestamp: ts,
-                    },
-                );
-                Self::emit_reputation_update(
-                    &env,
-                    ReputationUpdateEvent {
-                        address: escrow.seller.clone(),
-                        successful_delta: 0,
-                        disputed_delta: 1,
-                        metrics_sales_delta: 0,
-                        metrics_amount: 0,
-                        token: escrow.token.clone(),
-                        timestamp: ts,
-                    },
-                );
-            }
+    /// Get the effective fee basis points for a seller.
+    /// Returns artisan-specific tier if set, otherwise platform default.
+    pub fn get_effective_fee_bps(env: Env, seller: Address) -> u32 {
+        Self::current_artisan_fee_tier(&env, &seller)
+            .or_else(|| Self::volume_fee_tier(&env, &seller))
+            .unwrap_or_else(|| Self::current_platform_fee_bps(&env))
+    }
+
+    /// Get the effective fee bps for a seller in a specific token.
+    ///
+    /// A manual artisan fee tier wins, then a volume-based tier; otherwise
+    /// the token's `FeeTokenInfo.custom_fee_bps` applies, falling back to the
+    /// platform fee.
+    pub fn get_effective_fee_bps_for_token(env: Env, seller: Address, token: Address) -> u32 {
+        if let Some(fee_bps) = Self::current_artisan_fee_tier(&env, &seller)
+            .or_else(|| Self::volume_fee_tier(&env, &seller))
+        {
+            return fee_bps;
         }
+        Self::read_fee_token_info(&env, &token)
+            .and_then(|info| info.custom_fee_bps)
+            .unwrap_or_else(|| Self::current_platform_fee_bps(&env))
     }
 
-    /// Update platform fee percentage (admin only)
+    /// Fee bps from the volume tier table for `seller`, if any row matches.
     ///
-    /// # Arguments
-    /// * `new_fee_bps` - New fee in basis points
-    pub fn update_platform_fee(env: Env, new_fee_bps: u32) {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// Reads the seller's volume and trust score from the onboarding
+    /// contract. A missing onboarding contract or a failed call falls back
+    /// to `None` so fee lookups never trap.
+    fn volume_fee_tier(env: &Env, seller: &Address) -> Option<u32> {
+        let tiers = Self::get_volume_fee_tiers(env.clone());
+        if tiers.is_empty() {
+            return None;
+        }
+
+        let onboarding_address = Self::get_onboarding_address(env)?;
+        let method = Symbol::new(env, "get_fee_tier_metrics");
+        let args: Vec<Val> = (seller.clone(),).into_val(env);
+        let (volume, trust_score) = match env.try_invoke_contract::<(i128, u32), soroban_sdk::Error>(
+            &onboarding_address,
+            &method,
+            args,
+        ) {
+            Ok(Ok(metrics)) => metrics,
+            _ => {
+                Self::emit_onboarding_call_failed(env, method, onboarding_address);
+                return None;
+            }
+        };
 
-        if new_fee_bps > MAX_PLATFORM_FEE_BPS {
-            env.panic_with_error(crate::Error::InvalidFee);
+        let mut fee_bps: Option<u32> = None;
+        for tier in tiers.iter() {
+            if volume >= tier.min_volume
+                && trust_score >= tier.min_trust_score
+                && fee_bps.is_none_or(|bps| tier.fee_bps < bps)
+            {
+                fee_bps = Some(tier.fee_bps);
+            }
         }
+        fee_bps
+    }
 
-        let new_config = PlatformConfig {
-            platform_fee_bps: new_fee_bps,
-            platform_wallet: config.platform_wallet,
-            admin: config.admin,
-            arbitrator: config.arbitrator,
-            moderator: config.moderator,
-            is_paused: config.is_paused,
-            min_stake_required: config.min_stake_required,
-            pending_admin: config.pending_admin,
-            wasm_upgrade_cooldown: config.wasm_upgrade_cooldown,
-            max_dispute_duration: config.max_dispute_duration,
-            stake_cooldown: config.stake_cooldown,
-            expired_dispute_fee_policy: config.expired_dispute_fee_policy,
-            min_release_window: config.min_release_window,
-        };
+    /// Replace the volume-based artisan fee tier table (`Role::FeeManager`).
+    ///
+    /// Manual tiers set with `set_artisan_fee_tier` still take precedence.
+    /// An empty table disables volume tiers. Rejected while a fee change
+    /// notice period is set.
+    pub fn set_volume_fee_tiers(env: Env, caller: Address, tiers: Vec<VolumeFeeTier>) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+        Self::check_no_fee_notice(&env)?;
+
+        if tiers.len() > MAX_VOLUME_FEE_TIERS {
+            return Err(Error::BatchLimitExceeded);
+        }
+        for tier in tiers.iter() {
+            if tier.fee_bps > MAX_PLATFORM_FEE_BPS || tier.min_volume < 0 {
+                return Err(Error::InvalidFee);
+            }
+        }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &new_config);
+        let old_len = Self::get_volume_fee_tiers(env.clone()).len();
+        let key = FeeDataKey::VolumeTiers;
+        if tiers.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &tiers);
+            Self::extend_persistent(&env, &key);
+        }
         Self::emit_config_updated(
             &env,
-            "platform_fee_bps",
-            ConfigValue::U32(config.platform_fee_bps),
-            ConfigValue::U32(new_fee_bps),
+            "volume_fee_tiers",
+            ConfigValue::U32(old_len),
+            ConfigValue::U32(tiers.len()),
         );
+        Ok(())
     }
 
-    /// Update platform wallet address (admin only)
-    ///
-    /// # Arguments
-    /// * `new_wallet` - New platform wallet address
-    pub fn update_platform_wallet(env: Env, new_wallet: Address) {
-        let config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
+    /// Get the volume-based artisan fee tier table.
+    pub fn get_volume_fee_tiers(env: Env) -> Vec<VolumeFeeTier> {
+        let key = FeeDataKey::VolumeTiers;
+        match env.storage().persistent().get::<_, Vec<VolumeFeeTier>>(&key) {
+            Some(tiers) => {
+                Self::extend_persistent(&env, &key);
+                tiers
+            }
+            None => Vec::new(&env),
+        }
+    }
 
-        let new_config = PlatformConfig {
-            platform_fee_bps: config.platform_fee_bps,
-            platform_wallet: new_wallet,
-            admin: config.admin,
-            arbitrator: config.arbitrator,
-            moderator: config.moderator,
-            is_paused: config.is_paused,
-            min_stake_required: config.min_stake_required,
-            pending_admin: config.pending_admin,
-            wasm_upgrade_cooldown: config.wasm_upgrade_cooldown,
-            max_dispute_duration: config.max_dispute_duration,
-            stake_cooldown: config.stake_cooldown,
-            expired_dispute_fee_policy: config.expired_dispute_fee_policy,
-            min_release_window: config.min_release_window,
-        };
+    /// Platform fee bps in force now, including scheduled changes that are
+    /// due but not yet applied by `apply_fee_changes`.
+    fn current_platform_fee_bps(env: &Env) -> u32 {
+        Self::due_fee_change(env, None)
+            .unwrap_or_else(|| Self::get_platform_config_internal(env).platform_fee_bps)
+    }
+
+    /// Artisan fee tier in force now, including due scheduled changes.
+    fn current_artisan_fee_tier(env: &Env, artisan: &Address) -> Option<u32> {
+        if let Some(fee_bps) = Self::due_fee_change(env, Some(artisan)) {
+            return Some(fee_bps);
+        }
+        let key = DataKey::ArtisanFeeTier(artisan.clone());
+        let fee_bps = env.storage().persistent().get::<DataKey, u32>(&key);
+        if fee_bps.is_some() {
+            Self::extend_persistent(env, &key);
+        }
+        fee_bps
+    }
+
+    /// Latest due scheduled bps for the platform fee (`artisan` is `None`)
+    /// or for an artisan's tier.
+    fn due_fee_change(env: &Env, artisan: Option<&Address>) -> Option<u32> {
+        let now = env.ledger().timestamp();
+        let mut fee_bps = None;
+        for change in Self::load_fee_changes(env).iter() {
+            if change.effective_at > now {
+                break;
+            }
+            if change.artisan.as_ref() == artisan {
+                fee_bps = Some(change.fee_bps);
+            }
+        }
+        fee_bps
+    }
+
+    /// Remove due scheduled changes for the platform fee (`artisan` is
+    /// `None`) or an artisan's tier. Called by the immediate setters, whose
+    /// value would otherwise be shadowed by an older unapplied change.
+    fn drop_due_fee_changes(env: &Env, artisan: Option<&Address>) {
+        let now = env.ledger().timestamp();
+        let changes = Self::load_fee_changes(env);
+        let mut remaining = Vec::new(env);
+        for change in changes.iter() {
+            if change.effective_at > now || change.artisan.as_ref() != artisan {
+                remaining.push_back(change);
+            }
+        }
+        if remaining.len() != changes.len() {
+            Self::store_fee_changes(env, &remaining);
+        }
+    }
 
+    fn load_fee_changes(env: &Env) -> Vec<ScheduledFeeChange> {
         env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &new_config);
-        Self::emit_config_updated(
-            &env,
-            "platform_wallet",
-            ConfigValue::Address(config.platform_wallet),
-            ConfigValue::Address(new_config.platform_wallet),
-        );
+            .persistent()
+            .get(&FeeDataKey::PendingChanges)
+            .unwrap_or_else(|| Vec::new(env))
     }
 
-    /// Update the expired dispute fee policy (admin only).
-    ///
-    /// Configures how platform fees are handled when a dispute expires without arbitrator resolution.
-    ///
-    /// # Arguments
-    /// * `policy` - The new fee policy to apply
+    fn store_fee_changes(env: &Env, changes: &Vec<ScheduledFeeChange>) {
+        let key = FeeDataKey::PendingChanges;
+        env.storage().persistent().set(&key, changes);
+        Self::extend_persistent(env, &key);
+    }
+
+    /// Fail with `AdminActionTimelockActive` when fee changes require
+    /// advance notice; they must then go through
+    /// `schedule_platform_fee_change` or `schedule_artisan_fee_tier_change`.
+    /// Fee schedules, volume tiers and token fee overrides have no schedule
+    /// queue and can only change once the notice period is lowered to zero.
+    fn check_no_fee_notice(env: &Env) -> Result<(), Error> {
+        if Self::get_fee_change_notice_period(env.clone()) > 0 {
+            return Err(Error::AdminActionTimelockActive);
+        }
+        Ok(())
+    }
+
+    /// Set the minimum notice, in seconds, for fee changes
+    /// (`Role::FeeManager`).
     ///
-    /// # Policies
-    /// - RefundFullNoPlatformFee: Buyer gets full refund, platform collects no fee (default)
-    /// - RefundMinusPlatformFee: Buyer gets refund minus fee, platform collects fee from buyer
-    /// - DeductFeeFromSeller: Buyer gets full refund, seller conceptually loses the fee
-    /// - SplitFee: Platform fee split between buyer and seller
-    pub fn update_expired_dispute_policy(
+    /// While non-zero, `update_platform_fee`, `set_artisan_fee_tier`,
+    /// `set_volume_fee_tiers`, `set_fee_schedule`, `clear_fee_schedule`, a
+    /// change of a token's `custom_fee_bps` and the matching admin actions
+    /// are disabled and fee changes must be scheduled at least this far
+    /// ahead. A longer notice applies at once; a shorter
+    /// one only once the current notice has elapsed, so it cannot be used to
+    /// rush a fee change through.
+    pub fn set_fee_change_notice_period(
         env: Env,
-        policy: ExpiredDisputeFeePolicy,
+        caller: Address,
+        seconds: u32,
     ) -> Result<(), Error> {
-        let mut config = Self::get_platform_config_internal(&env);
-        config.admin.require_auth();
-
-        let old_policy = config.expired_dispute_fee_policy;
-        config.expired_dispute_fee_policy = policy;
+        Self::require_role(&env, Role::FeeManager, &caller);
 
+        // Settle a due reduction first; an earlier pending one is replaced.
+        let old_value = Self::get_fee_change_notice_period(env.clone());
         env.storage()
             .instance()
-            .set(&DataKey::PlatformConfig, &config);
+            .set(&FeeDataKey::NoticePeriod, &old_value);
+        env.storage()
+            .instance()
+            .remove(&FeeDataKey::NoticePeriodChange);
+
+        if seconds < old_value {
+            let effective_at = env.ledger().timestamp().saturating_add(old_value as u64);
+            env.storage()
+                .instance()
+                .set(&FeeDataKey::NoticePeriodChange, &(seconds, effective_at));
+            Self::emit_config_updated(
+                &env,
+                "pending_fee_change_notice_period",
+                ConfigValue::U32(old_value),
+                ConfigValue::U32(seconds),
+            );
+            return Ok(());
+        }
 
+        env.storage()
+            .instance()
+            .set(&FeeDataKey::NoticePeriod, &seconds);
         Self::emit_config_updated(
             &env,
-            "expired_dispute_fee_policy",
-            ConfigValue::U32(old_policy as u32),
-            ConfigValue::U32(policy as u32),
+            "fee_change_notice_period",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(seconds),
         );
-
         Ok(())
     }
 
-    /// Get the current expired dispute fee policy
-    pub fn get_expired_dispute_policy(env: Env) -> ExpiredDisputeFeePolicy {
-        let config = Self::get_platform_config_internal(&env);
-        config.expired_dispute_fee_policy
+    /// Get the minimum notice, in seconds, for fee changes.
+    pub fn get_fee_change_notice_period(env: Env) -> u32 {
+        if let Some((seconds, effective_at)) = env
+            .storage()
+            .instance()
+            .get::<_, (u32, u64)>(&FeeDataKey::NoticePeriodChange)
+        {
+            if env.ledger().timestamp() >= effective_at {
+                return seconds;
+            }
+        }
+        env.storage()
+            .instance()
+            .get(&FeeDataKey::NoticePeriod)
+            .unwrap_or(0)
     }
 
-    pub fn set_moderator(env: Env, moderator: Address) {
-        let mut config = Self::get_platform_config(env.clone());
-        config.admin.require_auth();
-        let previous = config
-            .moderator
-            .clone()
-            .map(ConfigValue::Address)
-            .unwrap_or_else(|| ConfigValue::String(String::from_str(&env, "unset")));
-        config.moderator = Some(moderator.clone());
+    /// Get a pending reduction of the fee change notice period as
+    /// `(seconds, effective_at)`, if one has not taken effect yet.
+    pub fn get_pending_notice_period(env: Env) -> Option<(u32, u64)> {
         env.storage()
             .instance()
-            .set(&DataKey::PlatformConfig, &config);
-        Self::emit_config_updated(&env, "moderator", previous, ConfigValue::Address(moderator));
+            .get::<_, (u32, u64)>(&FeeDataKey::NoticePeriodChange)
+            .filter(|(_, effective_at)| env.ledger().timestamp() < *effective_at)
     }
 
-    /// Set the minimum escrow amount for a specific token (admin only)
+    /// Schedule a platform fee change (`Role::FeeManager`). Returns the change id.
     ///
-    /// # Arguments
-    /// * `token` - Token address
-    /// * `min_amount` - Minimum amount in smallest unit
-    pub fn set_min_escrow_amount(env: Env, token: Address, min_amount: i128) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
-
-        let key = DataKey::MinEscrowAmount(token.clone());
-        let old_amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    /// `effective_at` must be at least the notice period away. Escrows
+    /// created before then keep the fee they were created with.
+    pub fn schedule_platform_fee_change(
+        env: Env,
+        caller: Address,
+        new_fee_bps: u32,
+        effective_at: u64,
+    ) -> Result<u32, Error> {
+        let old_value = Self::current_platform_fee_bps(&env);
+        let id = Self::schedule_fee_change(&env, &caller, None, new_fee_bps, effective_at)?;
+        Self::emit_config_updated(
+            &env,
+            "pending_platform_fee_bps",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(new_fee_bps),
+        );
+        Ok(id)
+    }
 
-        env.storage().persistent().set(&key, &min_amount);
-        Self::extend_persistent(&env, &key);
+    /// Schedule an artisan fee tier change (`Role::FeeManager`). Returns the change
+    /// id. Same notice rules as `schedule_platform_fee_change`.
+    pub fn schedule_artisan_fee_tier_change(
+        env: Env,
+        caller: Address,
+        artisan: Address,
+        fee_bps: u32,
+        effective_at: u64,
+    ) -> Result<u32, Error> {
+        let old_value = Self::get_effective_fee_bps(env.clone(), artisan.clone());
+        let id = Self::schedule_fee_change(&env, &caller, Some(artisan), fee_bps, effective_at)?;
         Self::emit_config_updated(
             &env,
-            "min_escrow_amount",
-            ConfigValue::I128(old_amount),
-            ConfigValue::I128(min_amount),
+            "pending_artisan_fee_bps",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(fee_bps),
         );
+        Ok(id)
+    }
+
+    fn schedule_fee_change(
+        env: &Env,
+        caller: &Address,
+        artisan: Option<Address>,
+        fee_bps: u32,
+        effective_at: u64,
+    ) -> Result<u32, Error> {
+        Self::require_role(env, Role::FeeManager, caller);
+
+        if fee_bps > MAX_PLATFORM_FEE_BPS {
+            return Err(Error::InvalidFee);
+        }
+        let notice = Self::get_fee_change_notice_period(env.clone()) as u64;
+        if effective_at < env.ledger().timestamp().saturating_add(notice) {
+            return Err(Error::AdminActionTimelockActive);
+        }
+        let changes = Self::load_fee_changes(env);
+        if changes.len() >= MAX_PENDING_FEE_CHANGES {
+            return Err(Error::BatchLimitExceeded);
+        }
+
+        let id = env
+            .storage()
+            .instance()
+            .get(&FeeDataKey::NextChangeId)
+            .unwrap_or(0u32);
+        env.storage()
+            .instance()
+            .set(&FeeDataKey::NextChangeId, &(id + 1));
+
+        // Keep the list ordered by `effective_at`; equal times apply in
+        // scheduling order.
+        let mut ordered = Vec::new(env);
+        let mut inserted = false;
+        for change in changes.iter() {
+            if !inserted && change.effective_at > effective_at {
+                ordered.push_back(ScheduledFeeChange {
+                    id,
+                    artisan: artisan.clone(),
+                    fee_bps,
+                    effective_at,
+                });
+                inserted = true;
+            }
+            ordered.push_back(change);
+        }
+        if !inserted {
+            ordered.push_back(ScheduledFeeChange {
+                id,
+                artisan,
+                fee_bps,
+                effective_at,
+            });
+        }
+        Self::store_fee_changes(env, &ordered);
+        Ok(id)
+    }
+
+    /// Cancel a scheduled fee change that has not taken effect (`Role::FeeManager`).
+    pub fn cancel_fee_change(env: Env, caller: Address, id: u32) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+
+        let now = env.ledger().timestamp();
+        let changes = Self::load_fee_changes(&env);
+        let mut remaining = Vec::new(&env);
+        let mut found = false;
+        for change in changes.iter() {
+            if change.id == id && change.effective_at > now {
+                found = true;
+            } else {
+                remaining.push_back(change);
+            }
+        }
+        if !found {
+            return Err(Error::ProposalNotFound);
+        }
+        Self::store_fee_changes(&env, &remaining);
         Ok(())
     }
 
-    /// Get current platform fee percentage
-    pub fn get_platform_fee(env: Env) -> u32 {
-        let config = Self::get_platform_config_internal(&env);
-        config.platform_fee_bps
+    /// Get scheduled fee changes that have not taken effect yet, ordered by
+    /// `effective_at`.
+    pub fn get_pending_fee_changes(env: Env) -> Vec<ScheduledFeeChange> {
+        let now = env.ledger().timestamp();
+        let mut pending = Vec::new(&env);
+        for change in Self::load_fee_changes(&env).iter() {
+            if change.effective_at > now {
+                pending.push_back(change);
+            }
+        }
+        pending
     }
 
-    /// Get platform wallet address
-    pub fn get_platform_wallet(env: Env) -> Address {
-        let config = Self::get_platform_config_internal(&env);
-        config.platform_wallet
+    /// Write due scheduled fee changes into the platform config and artisan
+    /// tiers. Anyone may call this; due changes already apply to new escrows
+    /// before it runs. Returns the number of changes applied.
+    pub fn apply_fee_changes(env: Env) -> u32 {
+        let now = env.ledger().timestamp();
+        let changes = Self::load_fee_changes(&env);
+        let mut remaining = Vec::new(&env);
+        let mut applied = 0u32;
+        for change in changes.iter() {
+            if change.effective_at > now {
+                remaining.push_back(change);
+                continue;
+            }
+            match change.artisan {
+                Some(artisan) => {
+                    let key = DataKey::ArtisanFeeTier(artisan.clone());
+                    env.storage().persistent().set(&key, &change.fee_bps);
+                    Self::extend_persistent(&env, &key);
+                    Self::emit_artisan_fee_tier_updated(&env, artisan, change.fee_bps);
+                }
+                None => {
+                    let mut config = Self::get_platform_config_internal(&env);
+                    let old_value = config.platform_fee_bps;
+                    config.platform_fee_bps = change.fee_bps;
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::PlatformConfig, &config);
+                    Self::emit_config_updated(
+                        &env,
+                        "platform_fee_bps",
+                        ConfigValue::U32(old_value),
+                        ConfigValue::U32(change.fee_bps),
+                    );
+                }
+            }
+            applied += 1;
+        }
+        if applied > 0 {
+            Self::store_fee_changes(&env, &remaining);
+        }
+        applied
     }
 
-    /// Get total fees collected by platform
-    pub fn get_total_fees_collected(env: Env) -> i128 {
-        Self::get_all_tracked_total_fees(&env)
+    /// Set the fee schedule for a token (`Role::FeeManager`).
+    ///
+    /// The fee becomes `flat_fee` plus the effective bps fee, raised to
+    /// `min_fee` and capped at `max_fee` (`0` means no cap). The fee never
+    /// exceeds the amount it is charged on. Rejected while a fee change
+    /// notice period is set.
+    pub fn set_fee_schedule(
+        env: Env,
+        caller: Address,
+        token: Address,
+        flat_fee: i128,
+        min_fee: i128,
+        max_fee: i128,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+        Self::check_no_fee_notice(&env)?;
+
+        if flat_fee < 0 || min_fee < 0 || max_fee < 0 || (max_fee > 0 && max_fee < min_fee) {
+            return Err(Error::InvalidFee);
+        }
+        let key = FeeDataKey::Schedule(token);
+        env.storage().persistent().set(
+            &key,
+            &FeeSchedule {
+                flat_fee,
+                min_fee,
+                max_fee,
+            },
+        );
+        Self::extend_persistent(&env, &key);
+        Ok(())
     }
 
-    /// Get total fees collected for a specific token.
-    pub fn get_total_fees_for_token(env: Env, token: Address) -> i128 {
+    /// Remove a token's fee schedule, returning it to plain bps fees
+    /// (`Role::FeeManager`). Dropping a maximum fee can raise fees, so this
+    /// is rejected while a fee change notice period is set.
+    pub fn clear_fee_schedule(env: Env, caller: Address, token: Address) -> Result<(), Error> {
+        Self::require_role(&env, Role::FeeManager, &caller);
+        Self::check_no_fee_notice(&env)?;
         env.storage()
             .persistent()
-            .get(&DataKey::TotalFees(token))
-            .unwrap_or(0)
+            .remove(&FeeDataKey::Schedule(token));
+        Ok(())
     }
 
-    /// Calculate the fee for a given amount (for display purposes)
-    ///
-    /// # Arguments
-    /// * `amount` - The escrow amount
-    pub fn calculate_fee_for_amount(env: Env, amount: i128) -> i128 {
-        let config = Self::get_platform_config_internal(&env);
-        Self::calculate_fee(&env, amount, config.platform_fee_bps)
+    /// Get the fee schedule of a token, if one is set.
+    pub fn get_fee_schedule(env: Env, token: Address) -> Option<FeeSchedule> {
+        env.storage()
+            .persistent()
+            .get(&FeeDataKey::Schedule(token))
     }
 
-    /// Calculate net amount seller will receive
-    ///
-    /// # Arguments
-    /// * `amount` - The escrow amount
-    pub fn calculate_seller_net_amount(env: Env, amount: i128) -> i128 {
-        let fee = Self::calculate_fee_for_amount(env, amount);
-        amount - fee
+    /// Quote how a release of a hypothetical escrow of `amount` from `seller`
+    /// in `token` would be split, using the current tiers, schedule and fee
+    /// payer model. Under a buyer-side model `amount` is the price and the
+    /// buyer's fee share comes on top. Referral and keeper shares depend on
+    /// the settlement and are reported as zero.
+    pub fn quote_fee_allocation(
+        env: Env,
+        seller: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<FeeAllocation, Error> {
+        if amount < 0 {
+            return Err(Error::AmountBelowMinimum);
+        }
+        let fee_rate = Self::effective_fee_rate(&env, &seller, &token);
+        let payer = Self::get_token_fee_payer(env.clone(), token);
+        if payer == FeePayer::Seller {
+            return Ok(Self::compute_fee_allocation(
+                &env,
+                amount,
+                &fee_rate,
+                SettlementKind::ReleaseFunds,
+            ));
+        }
+        let full_fee = Self::scheduled_fee(&env, amount, &fee_rate);
+        let buyer_fee = if payer == FeePayer::Split {
+            full_fee / 2
+        } else {
+            full_fee
+        };
+        Ok(Self::compute_fee_allocation(
+            &env,
+            amount + buyer_fee,
+            &fee_rate,
+            SettlementKind::ReleaseWithBuyerFee(buyer_fee, payer == FeePayer::Split),
+        ))
     }
 
-    /// Returns the current deterministic fee policy version.
+    // â”€â”€ Dispute Resolution Deadline (#93) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+
+    /// Resolve a dispute that has exceeded the maximum dispute duration.
     ///
-    /// Increment this constant whenever fee allocation formulas change.
-    /// Callers can compare versions off-chain to detect policy updates.
-    pub fn get_fee_policy_version(_env: Env) -> u32 {
-        FEE_POLICY_VERSION
+    /// If the dispute has been open for longer than the configured max_dispute_duration,
+    /// the escrow is resolved according to the configured expired_dispute_fee_policy.
+    /// Returns DisputeExpired error if the deadline has not yet passed.
+    pub fn resolve_expired_dispute(env: Env, order_id: u32) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        Self::resolve_expired_dispute_internal(env.clone(), order_id, None).map(|_| ())
     }
 
-    /// Validate escrow parameters for batch creation
-    fn validate_escrow_params(env: &Env, params: &EscrowCreateParams) -> Result<(), Error> {
-        // Validate amount is positive
-        if params.amount <= 0 {
-            return Err(Error::AmountBelowMinimum);
-        }
-
-        // Check minimum amount
-        Self::check_min_amount(env, params.token.clone(), params.amount)?;
-
-        // Validate buyer and seller are different
-        if params.buyer == params.seller {
-            return Err(Error::SameBuyerSeller);
+    fn expired_dispute_settlement_kind(config: &PlatformConfig) -> SettlementKind {
+        match config.expired_dispute_fee_policy {
+            ExpiredDisputeFeePolicy::RefundFullNoPlatformFee => SettlementKind::ExpiredDisputeDeductFromSeller,
+            ExpiredDisputeFeePolicy::RefundMinusPlatformFee => SettlementKind::ExpiredDisputeDeductFromBuyer,
+            ExpiredDisputeFeePolicy::DeductFeeFromSeller => SettlementKind::ExpiredDisputeDeductFromSeller,
+            ExpiredDisputeFeePolicy::SplitFee => SettlementKind::ExpiredDisputeSplitFee,
         }
+    }
 
-        // Validate token is whitelisted (#103)
-        let whitelist: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::WhitelistedTokens)
-            .unwrap_or(Map::new(env));
-        if !whitelist.is_empty() && !whitelist.get(params.token.clone()).unwrap_or(false) {
-            return Err(Error::TokenNotWhitelisted);
+    /// Shared body of `resolve_expired_dispute` and keeper resolutions.
+    ///
+    /// Returns the keeper bounty paid.
+    fn resolve_expired_dispute_internal(
+        env: Env,
+        order_id: u32,
+        keeper: Option<&Address>,
+    ) -> Result<i128, Error> {
+        let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
+        if escrow_opt.is_none() {
+            return Err(Error::EscrowNotFound);
         }
+        Self::extend_persistent(&env, &(ESCROW, order_id));
+        let mut escrow: Escrow = escrow_opt.unwrap();
 
-        // Validate release window bounds (#67)
-        let window = params.release_window.unwrap_or(604800u32);
-        if window == 0 {
-            return Err(Error::ReleaseWindowTooShort);
-        }
-        let max_window = Self::get_max_release_window(env);
-        if window > max_window {
-            return Err(Error::ReleaseWindowTooLong);
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::InvalidEscrowState);
         }
 
-        // Validate IPFS hash if provided
-        Self::validate_optional_ipfs_hash(env, &params.ipfs_hash);
+        let initiated_at = escrow
+            .dispute_initiated_at
+            .ok_or(Error::InvalidEscrowState)?;
+        let current_time = env.ledger().timestamp();
 
-        if let Some(hash) = &params.metadata_hash {
-            if hash.len() != 32 {
-                return Err(Error::InvalidMetadataHash);
-            }
+        let config = Self::get_platform_config_internal(&env);
+        if initiated_at + config.max_dispute_duration as u64 > current_time {
+            return Err(Error::DisputeExpired);
         }
 
-        Ok(())
-    }
-
-    /// Create a single escrow from parameters (internal helper)
-    /// Note: For batch operations, buyer/seller escrow list updates are consolidated
-    /// by the caller to minimize storage writes (Issue #111)
-    fn create_single_escrow(
-        env: &Env,
-        params: EscrowCreateParams,
-        batch_id: Option<u64>,
-    ) -> Result<u64, Error> {
-        // Validate first
-        Self::validate_escrow_params(env, &params)?;
-
-        // Default to 7 days if not specified
-        let window = params.release_window.unwrap_or(604800u32);
-        let created_at_u64 = env.ledger().timestamp();
-        assert!(
-            created_at_u64 <= u32::MAX as u64,
-            "Ledger timestamp overflow"
-        );
-        let created_at = created_at_u64 as u32;
+        // CRITICAL: Update status BEFORE external calls (CEI pattern)
+        escrow.status = EscrowStatus::Resolved;
+        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
 
-        // Validate metadata (validate_escrow_params already checked ipfs_hash via validate_optional_ipfs_hash)
-        Self::validate_optional_metadata_hash(env, &params.metadata_hash);
+        // Decrement active counts
+        Self::update_active_obligations(&env, &escrow.buyer, -1);
+        Self::update_active_obligations(&env, &escrow.seller, -1);
 
-        let escrow = Escrow {
-            version: CURRENT_ESCROW_VERSION,
-            id: params.order_id as u64,
-            batch_id,
-            buyer: params.buyer.clone(),
-            seller: params.seller.clone(),
-            token: params.token.clone(),
-            amount: params.amount,
-            status: EscrowStatus::Active,
-            release_window: window,
-            created_at,
-            ipfs_hash: params.ipfs_hash.clone(),
-            metadata_hash: params.metadata_hash.clone(),
-            dispute_reason: None,
-            dispute_initiated_at: None,
-            funded: true,
-            funding_deadline: None, // Immediately funded; no deadline required (#656)
-        };
+        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
+        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
+        Self::update_total_locked(&env, &escrow.token, -escrow.amount);
 
-        env.storage()
-            .persistent()
-            .set(&(ESCROW, params.order_id), &escrow);
-        Self::extend_persistent(env, &(ESCROW, params.order_id));
+        // Now perform token transfers (external calls)
+        let fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+        let settlement_kind = Self::expired_dispute_settlement_kind(&config);
+        let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+        let allocation = Self::apply_referral_share(
+            &env,
+            Self::compute_escrow_fee_allocation(
+                &env,
+                order_id,
+                escrow.amount,
+                &fee_rate,
+                settlement_kind,
+            ),
+            &referrer,
+        );
+        let allocation = Self::apply_keeper_bounty(&env, allocation, keeper);
 
-        // Track active escrows (batch)
-        Self::update_active_obligations(env, &params.buyer, 1);
-        Self::update_active_obligations(env, &params.seller, 1);
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(
+                &env,
+                &escrow.token,
+                &config.platform_wallet,
+                &allocation,
+                referrer.as_ref().map(|referrer| (order_id, referrer)),
+                keeper,
+            );
+        }
+        Self::pay_refund(&env, order_id, &escrow, escrow.amount, allocation.buyer_amount, Symbol::new(&env, "expired_dispute_refund"));
 
-        Self::update_total_locked(env, &params.token, params.amount);
-        Self::transfer_tokens_and_record_audit(env, &params.token, &params.buyer, &env.current_contract_address(), params.amount, &params.buyer, Symbol::new(env, "escrow_funded"), -params.amount);
+        Self::clear_settled_escrow_state(&env, order_id);
 
         Self::emit_escrow_created(
-            env,
+            &env,
             EscrowEvent {
-                escrow_id: params.order_id as u64,
-                action: EscrowAction::Created,
-                buyer: params.buyer.clone(),
-                seller: params.seller.clone(),
-                amount: params.amount,
-                token: params.token.clone(),
-                timestamp: env.ledger().timestamp(),
+                escrow_id: order_id as u64,
+                action: EscrowAction::Resolved,
+                buyer: escrow.buyer.clone(),
+                seller: escrow.seller.clone(),
+                amount: escrow.amount,
+                token: escrow.token.clone(),
+                timestamp: current_time,
             },
         );
 
-        Ok(params.order_id as u64)
-    }
-
-    /// DevEx: Dry-Run Batch Validation
-    /// Validates a batch of escrow creations without modifying state.
-    /// Returns a map of index -> Error for any escrow that fails validation.
-    pub fn validate_batch_creation(
-        env: Env,
-        escrows: soroban_sdk::Vec<EscrowCreateParams>,
-    ) -> Map<u32, Error> {
-        let mut errors: Map<u32, Error> = Map::new(&env);
-
-        if escrows.len() > MAX_BATCH_SIZE {
-            env.panic_with_error(crate::Error::BatchLimitExceeded);
-        }
-
-        for i in 0..escrows.len() {
-            if let Some(params) = escrows.get(i) {
-                if let Err(e) = Self::validate_escrow_params(&env, &params) {
-                    errors.set(i, e);
-                }
-            }
-        }
-
-        errors
-    }
-
-    /// Create multiple escrows in a batch operation (Issue #111: Optimized)
-    ///
-    /// Validates all escrows first before processing any to ensure atomic behavior.
-    /// Authorization model:
-    /// - Every distinct buyer in the batch must provide an authorization signature
-    /// - This prevents a single transaction from creating escrows on behalf of
-    ///   buyers who did not approve the operation
-    /// Optimizations:
-    /// - Consolidated storage updates for buyer/seller escrow lists
-    /// - Batch size limit to prevent resource exhaustion
-    ///
-    /// # Arguments
-    /// * `escrows` - Vector of escrow creation parameters (max MAX_BATCH_SIZE items)
-    /// * `batch_id` - Unique identifier for this batch operation
-    ///
-    /// # Returns
-    /// Vector of created escrow IDs
-    ///
-    /// # Errors
-    /// - BatchLimitExceeded if batch exceeds MAX_BATCH_SIZE
-    /// - Any validation error from individual escrows
-    pub fn create_escrows_batch(
-        env: Env,
-        params: soroban_sdk::Vec<EscrowCreateParams>,
-    ) -> Result<soroban_sdk::Vec<u64>, Error> {
-        let batch_id = env
-            .storage()
-            .instance()
-            .get(&Symbol::new(&env, "next_batch_id"))
-            .unwrap_or(1u64);
-        env.storage()
-            .instance()
-            .set(&Symbol::new(&env, "next_batch_id"), &(batch_id + 1));
-        Self::create_batch_escrow(env, batch_id, params)
-    }
-
-    pub fn create_batch_escrow(
-        env: Env,
-        batch_id: u64,
-        escrows: soroban_sdk::Vec<EscrowCreateParams>,
-    ) -> Result<soroban_sdk::Vec<u64>, Error> {
-        let _guard = ReentryGuardScope::new(&env);
-        Self::check_not_paused(&env);
+        Ok(allocation.keeper_fee)
+    }
 
-        // Issue #111: Enforce batch size limit
-        if escrows.len() > MAX_BATCH_SIZE {
-            return Err(Error::BatchLimitExceeded);
-        }
+    // â”€â”€ Staking Requirement for Artisans (#99) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
 
-        let mut results = soroban_sdk::Vec::new(&env);
+    /// Stake tokens to satisfy the platform's minimum stake requirement.
+    ///
+    /// The artisan transfers `amount` of `token` to the contract. The stake is stored
+    /// and a cooldown timer is set so the tokens cannot be unstaked immediately.
+    ///
+    /// Enhanced with bounded queue management to prevent storage bloat. Automatically
+    /// prunes matured deposits when queue approaches capacity limits.
+    ///
+    /// Staked balances remain owned by the artisan. The contract does not accrue,
+    /// distribute, or sweep interest/yield from these reserved funds into platform fees.
+    pub fn stake_tokens(env: Env, artisan: Address, token: Address, amount: i128) {
+        let _guard = ReentryGuardScope::new(&env);
+        artisan.require_auth();
 
-        // Early exit for empty batch
-        if escrows.is_empty() {
-            return Ok(results);
+        if amount <= 0 {
+            env.panic_with_error(crate::Error::AmountBelowMinimum);
         }
 
-        // Issue #606: Require authorization from every distinct buyer in the batch.
-        // This prevents a single transaction from creating escrows on behalf of
-        // buyers who did not sign the operation.
-        let mut authorized_buyers: Map<Address, u32> = Map::new(&env);
-        for i in 0..escrows.len() {
-            if let Some(params) = escrows.get(i) {
-                let buyer_key = params.buyer.clone();
-                if !authorized_buyers.contains_key(buyer_key.clone()) {
-                    buyer_key.require_auth();
-                    authorized_buyers.set(buyer_key, 1u32);
-                }
+        // Effects are committed before the token interaction.
+        Self::update_total_staked(&env, &token, amount);
+
+        // Accumulate stake in a single record with token metadata.
+        let stake_key = DataKey::ArtisanStake(artisan.clone());
+        let current_stake: Option<ArtisanStakeData> = env.storage().persistent().get(&stake_key);
+        let new_stake = if let Some(existing_stake) = current_stake {
+            if existing_stake.token != token {
+                env.panic_with_error(crate::Error::StakeTokenMismatch);
+            }
+            ArtisanStakeData {
+                amount: existing_stake.amount + amount,
+                token: token.clone(),
             }
+        } else {
+            ArtisanStakeData { amount, token: token.clone() }
+        };
+
+        let config = Self::get_platform_config_internal(&env);
+        if config.min_stake_required > 0 && new_stake.amount < config.min_stake_required {
+            env.panic_with_error(crate::Error::InsufficientStake);
         }
 
-        // Issue #111: Validate all first (single pass)
-        for i in 0..escrows.len() {
-            if let Some(params) = escrows.get(i) {
-                Self::validate_escrow_params(&env, &params)?;
-            }
+        env.storage().persistent().set(&stake_key, &new_stake);
+        Self::extend_persistent(&env, &stake_key);
+
+        // Record stake operation in history queue for audit trail (#237)
+        if let Err(_) = Self::record_stake_history(&env, &artisan, new_stake.amount, "stake_added")
+        {
+            env.panic_with_error(Error::StakeQueueFull);
         }
 
-        // Issue #111: Collect buyer/seller updates to consolidate storage writes
-        // Using indexed storage for scalability. Precompute the starting counts for
-        // each buyer/seller once so the batch creation loop can reuse that state
-        // without repeatedly re-reading the same count map while populating indices.
-        let mut buyer_count_state: Map<Address, u32> = Map::new(&env);
-        let mut seller_count_state: Map<Address, u32> = Map::new(&env);
+        // Check queue capacity and current cooldown state (#237)
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
 
-        for i in 0..escrows.len() {
-            if let Some(params) = escrows.get(i) {
-                let buyer_key = params.buyer.clone();
-                let seller_key = params.seller.clone();
+        // Initialize cooldown only if artisan doesn't already have one.
+        // This prevents cooldown reset gaming where artisans extend their cooldown by continuously staking.
+        let existing_cooldown = if current_count > 0 {
+            let last_deposit_key =
+                DataKey::ArtisanStakeQueueIndexed(artisan.clone(), current_count - 1);
+            let deposit: StakeDeposit = env.storage().persistent().get(&last_deposit_key).unwrap();
+            deposit.cooldown_end
+        } else {
+            0
+        };
 
-                if !buyer_count_state.contains_key(buyer_key.clone()) {
-                    let count_key = DataKey::BuyerEscrowCount(buyer_key.clone());
-                    let existing_count: u32 =
-                        env.storage().persistent().get(&count_key).unwrap_or(0u32);
-                    buyer_count_state.set(buyer_key.clone(), existing_count);
-                }
+        let cooldown_end = if existing_cooldown == 0 {
+            // No existing cooldown, initialize new one
+            let config = Self::get_platform_config_internal(&env);
+            env.ledger().timestamp() + config.stake_cooldown as u64
+        } else {
+            existing_cooldown
+        };
 
-                if !seller_count_state.contains_key(seller_key.clone()) {
-                    let count_key = DataKey::SellerEscrowCount(seller_key.clone());
-                    let existing_count: u32 =
-                        env.storage().persistent().get(&count_key).unwrap_or(0u32);
-                    seller_count_state.set(seller_key.clone(), existing_count);
-                }
-            }
-        }
+        // Check queue capacity and prune if necessary
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
 
-        let mut buyer_next_counts: Map<Address, u32> = Map::new(&env);
-        let mut seller_next_counts: Map<Address, u32> = Map::new(&env);
+        if current_count >= STAKE_QUEUE_PRUNE_THRESHOLD {
+            Self::prune_matured_stake_deposits(&env, &artisan);
+        }
 
-        // Create all escrows using the precomputed count state.
-        for i in 0..escrows.len() {
-            if let Some(params) = escrows.get(i) {
-                match Self::create_single_escrow(&env, params.clone(), Some(batch_id)) {
-                    Ok(id) => {
-                        let buyer_key = params.buyer.clone();
-                        let seller_key = params.seller.clone();
+        // Add new deposit to bounded indexed queue
+        Self::add_stake_deposit(&env, &artisan, amount, cooldown_end);
 
-                        if !buyer_next_counts.contains_key(buyer_key.clone()) {
-                            let existing_count = buyer_count_state
-                                .get(buyer_key.clone())
-                                .unwrap_or(0u32);
-                            buyer_next_counts.set(buyer_key.clone(), existing_count);
-                        }
-                        let buyer_count = buyer_next_counts.get(buyer_key.clone()).unwrap();
+        Self::transfer_tokens_and_record_audit(&env, &token, &artisan, &env.current_contract_address(), amount, &artisan, Symbol::new(&env, "stake_deposit"), -amount);
+    }
 
-                        // Store escrow ID at indexed position
-                        let buyer_index_key =
-                            DataKey::BuyerEscrowIndexed(buyer_key.clone(), buyer_count);
-                        env.storage().persistent().set(&buyer_index_key, &id);
-                        Self::extend_persistent(&env, &buyer_index_key);
+    /// Add a stake deposit to the bounded indexed queue.
+    ///
+    /// Implements individual key-value storage for scalability. Each deposit is stored
+    /// as DataKey::ArtisanStakeQueueIndexed(artisan, index) -> StakeDeposit.
+    fn add_stake_deposit(env: &Env, artisan: &Address, amount: i128, cooldown_end: u64) {
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
 
-                        buyer_next_counts.set(buyer_key, buyer_count + 1);
+        if current_count >= MAX_STAKE_QUEUE_SIZE {
+            env.panic_with_error(Error::StakeQueueFull);
+        }
 
-                        if !seller_next_counts.contains_key(seller_key.clone()) {
-                            let existing_count = seller_count_state
-                                .get(seller_key.clone())
-                                .unwrap_or(0u32);
-                            seller_next_counts.set(seller_key.clone(), existing_count);
-                        }
-                        let seller_count = seller_next_counts.get(seller_key.clone()).unwrap();
+        // Add new deposit at the end of the queue
+        let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), current_count);
+        let deposit = StakeDeposit {
+            amount,
+            cooldown_end,
+        };
+        env.storage().persistent().set(&deposit_key, &deposit);
+        Self::extend_persistent(env, &deposit_key);
 
-                        // Store escrow ID at indexed position
-                        let seller_index_key =
-                            DataKey::SellerEscrowIndexed(seller_key.clone(), seller_count);
-                        env.storage().persistent().set(&seller_index_key, &id);
-                        Self::extend_persistent(&env, &seller_index_key);
+        // Update count
+        env.storage()
+            .persistent()
+            .set(&count_key, &(current_count + 1));
+        Self::extend_persistent(env, &count_key);
+    }
 
-                        seller_next_counts.set(seller_key, seller_count + 1);
+    /// Prune matured stake deposits from the queue to prevent storage bloat.
+    ///
+    /// Removes deposits where cooldown_end <= current_time and compacts the queue
+    /// by shifting remaining deposits to fill gaps. This maintains queue ordering
+    /// while keeping storage bounded.
+    fn prune_matured_stake_deposits(env: &Env, artisan: &Address) {
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
 
-                        // Emit batch event
-                        let escrow_opt: Option<Escrow> =
-                            env.storage().persistent().get(&(ESCROW, id as u32));
-                        if let Some(escrow) = escrow_opt {
-                            Self::emit_escrow_created(
-                                &env,
-                                EscrowEvent {
-                                    escrow_id: id,
-                                    action: EscrowAction::BatchCreated,
-                                    buyer: escrow.buyer,
-                                    seller: escrow.seller,
-                                    amount: escrow.amount,
-                                    token: escrow.token,
-                                    timestamp: env.ledger().timestamp(),
-                                },
-                            );
-                        }
-                        results.push_back(id);
-                    }
-                    Err(e) => {
-                        return Err(e);
-                    }
-                }
-            }
+        if current_count == 0 {
+            return;
         }
 
-        // Issue #111: Consolidate all storage updates at once
-        let mut i = 0;
-        loop {
-            if i >= buyer_next_counts.len() {
-                break;
-            }
-            if let Some(buyer_addr) = buyer_next_counts.keys().get(i) {
-                if let Some(final_count) = buyer_next_counts.get(buyer_addr.clone()) {
-                    let count_key = DataKey::BuyerEscrowCount(buyer_addr.clone());
-                    env.storage().persistent().set(&count_key, &final_count);
-                    Self::extend_persistent(&env, &count_key);
-                }
-            }
-            i += 1;
-        }
+        let now = env.ledger().timestamp();
+        let mut write_index = 0u32;
 
-        let mut i = 0;
-        loop {
-            if i >= seller_next_counts.len() {
-                break;
-            }
-            if let Some(seller_addr) = seller_next_counts.keys().get(i) {
-                if let Some(final_count) = seller_next_counts.get(seller_addr.clone()) {
-                    let count_key = DataKey::SellerEscrowCount(seller_addr.clone());
-                    env.storage().persistent().set(&count_key, &final_count);
-                    Self::extend_persistent(&env, &count_key);
+        // Compact queue by moving non-matured deposits to fill gaps
+        for read_index in 0..current_count {
+            let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), read_index);
+
+            if let Some(deposit) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, StakeDeposit>(&deposit_key)
+            {
+                if deposit.cooldown_end > now {
+                    // Deposit is not matured, keep it
+                    if write_index != read_index {
+                        // Move deposit to new position
+                        let new_key =
+                            DataKey::ArtisanStakeQueueIndexed(artisan.clone(), write_index);
+                        env.storage().persistent().set(&new_key, &deposit);
+                        Self::extend_persistent(env, &new_key);
+                    }
+                    write_index += 1;
                 }
-            }
-            i += 1;
-        }
 
-        // Consolidate global index updates for the entire batch using atomic function
-        // This ensures AllEscrowIds and EscrowCount always remain in sync (Issue #226)
-        if !results.is_empty() {
-            // Convert results to u32 order IDs
-            let mut order_ids = soroban_sdk::Vec::new(&env);
-            for j in 0..results.len() {
-                if let Some(id) = results.get(j) {
-                    order_ids.push_back(id as u32);
+                // Remove old entry if we moved it or if it was matured
+                if write_index != read_index + 1 {
+                    env.storage().persistent().remove(&deposit_key);
                 }
             }
-            Self::update_escrow_indices_batch_atomic(&env, &order_ids);
         }
 
-        Ok(results)
+        // Update count to reflect pruned queue. If nothing remains, remove the
+        // count entry rather than leaving a stale counter behind.
+        if write_index > 0 {
+            env.storage().persistent().set(&count_key, &write_index);
+            Self::extend_persistent(env, &count_key);
+        } else {
+            env.storage().persistent().remove(&count_key);
+        }
     }
 
-    /// Release multiple escrows in a batch operation
-    ///
-    /// Validates all escrows first before processing any.
+    /// Unstake previously staked tokens after the cooldown period has elapsed.
     ///
-    /// # Arguments
-    /// * `order_ids` - Vector of order IDs to release
-    /// * `batch_id` - Unique identifier for this batch operation
-    /// * `authorized_address` - Address releasing the funds (buyer)
-    pub fn release_batch_funds(
-        env: Env,
-        _batch_id: u64,
-        order_ids: soroban_sdk::Vec<u32>,
-        authorized_address: Address,
-    ) -> Result<soroban_sdk::Vec<u64>, Error> {
+    /// Stakes can only be returned in the exact token originally deposited, which
+    /// prevents reserved artisan collateral from being treated as platform-managed fees.
+    /// Enhanced with bounded indexed queue and automatic pruning for scalability.
+    pub fn unstake_tokens(env: Env, artisan: Address, token: Address) {
         let _guard = ReentryGuardScope::new(&env);
-        authorized_address.require_auth();
-
-        let mut results = soroban_sdk::Vec::new(&env);
+        artisan.require_auth();
 
-        // Validate all escrows first
-        for i in 0..order_ids.len() {
-            if let Some(order_id) = order_ids.get(i) {
-                let escrow_opt = env.storage().persistent().get(&(ESCROW, order_id));
+        // Validate the requested token matches the token recorded at stake time.
+        // Rejects attempts to withdraw in a cheaper/different asset (#421).
+        let stake_key = DataKey::ArtisanStake(artisan.clone());
+        let current_stake: ArtisanStakeData = env
+            .storage()
+            .persistent()
+            .get(&stake_key)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::InsufficientStake));
+        if current_stake.token != token {
+            env.panic_with_error(crate::Error::StakeTokenMismatch);
+        }
 
-                if escrow_opt.is_none() {
-                    return Err(Error::EscrowNotFound);
-                }
+        // Use bounded indexed queue: only matured deposits can be unstaked.
+        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
+        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
 
-                let escrow: Escrow = escrow_opt.unwrap();
+        let now = env.ledger().timestamp();
+        let mut matured_amount: i128 = 0;
+        let mut write_index = 0u32;
 
-                // Check status
-                if escrow.status != EscrowStatus::Active {
-                    return Err(Error::InvalidEscrowState);
-                }
+        // Process all deposits, collecting matured amounts and compacting the queue
+        for read_index in 0..current_count {
+            let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), read_index);
 
-                // Check authorization (buyer must match)
-                if escrow.buyer != authorized_address {
-                    return Err(Error::Unauthorized);
+            if let Some(deposit) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, StakeDeposit>(&deposit_key)
+            {
+                if now >= deposit.cooldown_end {
+                    // Deposit is matured, add to unstake amount
+                    matured_amount += deposit.amount;
+                    // Remove the matured deposit
+                    env.storage().persistent().remove(&deposit_key);
+                } else {
+                    // Deposit is not matured, keep it in the queue
+                    if write_index != read_index {
+                        // Move deposit to new position to compact the queue
+                        let new_key =
+                            DataKey::ArtisanStakeQueueIndexed(artisan.clone(), write_index);
+                        env.storage().persistent().set(&new_key, &deposit);
+                        Self::extend_persistent(&env, &new_key);
+                        // Remove old position
+                        env.storage().persistent().remove(&deposit_key);
+                    }
+                    write_index += 1;
                 }
             }
         }
 
-        // Then process all releases
-        for i in 0..order_ids.len() {
-            if let Some(order_id) = order_ids.get(i) {
-                let escrow_opt: Option<Escrow> =
-                    env.storage().persistent().get(&(ESCROW, order_id));
-                if escrow_opt.is_some() {
-                    Self::extend_persistent_read(&env, &(ESCROW, order_id));
-                }
+        if matured_amount <= 0 {
+            env.panic_with_error(crate::Error::StakeCooldownActive);
+        }
 
-                if let Some(mut escrow) = escrow_opt {
-                    // Get platform config
-                    let config = Self::get_platform_config_internal(&env);
+        // Update queue count after processing
+        if write_index > 0 {
+            env.storage().persistent().set(&count_key, &write_index);
+            Self::extend_persistent(&env, &count_key);
+        } else {
+            // Queue is empty, remove count key
+            env.storage().persistent().remove(&count_key);
+        }
 
-                    // Deterministic fee allocation via the central FeePolicy engine.
-                    let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.seller.clone());
-                    let allocation = Self::compute_fee_allocation(
-                        &env,
-                        escrow.amount,
-                        fee_bps,
-                        SettlementKind::ReleaseFunds,
-                    );
+        // Validate remaining collateral safety and active obligation rules
+        let remaining_amount = current_stake.amount - matured_amount;
+        let config = Self::get_platform_config_internal(&env);
+        let active_obligations = Self::has_active_escrows(env.clone(), artisan.clone());
+        if config.min_stake_required > 0 {
+            if active_obligations && remaining_amount < config.min_stake_required {
+                env.panic_with_error(crate::Error::InsufficientStake);
+            }
+            if !active_obligations && remaining_amount > 0 && remaining_amount < config.min_stake_required {
+                env.panic_with_error(crate::Error::InsufficientStake);
+            }
+        }
 
-                    // Update status
-                    escrow.status = EscrowStatus::Released;
-                    env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        // Update stake metadata
+        if remaining_amount > 0 {
+            let updated_stake = ArtisanStakeData {
+                amount: remaining_amount,
+                token: current_stake.token,
+            };
+            env.storage().persistent().set(&stake_key, &updated_stake);
+            Self::extend_persistent(&env, &stake_key);
+        } else {
+            env.storage().persistent().remove(&stake_key);
+        }
 
-                    // Decrement active counts
-                    Self::update_active_obligations(&env, &escrow.buyer, -1);
-                    Self::update_active_obligations(&env, &escrow.seller, -1);
+        // Record unstake operation in history for audit trail (#237)
+        if let Err(_) = Self::record_stake_history(&env, &artisan, 0, "stake_removed") {
+            // Don't fail on history recording, but log the issue
+            env.events().publish(
+                (Symbol::new(&env, "stake_history_warning"), "queue_full"),
+                String::from_str(&env, "Could not record stake removal in history"),
+            );
+        }
 
-                    Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-                    Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
-                    Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+        // Complete reserve accounting before returning tokens.
+        Self::update_total_staked(&env, &token, -matured_amount);
+        Self::transfer_tokens_and_record_audit(&env, &token, &env.current_contract_address(), &artisan, matured_amount, &artisan, Symbol::new(&env, "stake_unstaked"), matured_amount);
 
-                    // Transfer platform fee to platform wallet
-                    if allocation.platform_fee > 0 {
-                        Self::transfer_platform_fee(
-                            &env,
-                            &escrow.token,
-                            &config.platform_wallet,
-                            allocation.platform_fee,
-                        );
-                    }
+        env.events().publish(
+            (Symbol::new(&env, "tokens_unstaked"), artisan.clone()),
+            TokensUnstakedEvent {
+                artisan,
+                token,
+                amount: matured_amount,
+            },
+        );
+    }
 
-                    // Transfer remaining funds to seller
-                    Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.seller, allocation.seller_amount, &escrow.seller, Symbol::new(&env, "escrow_released"), allocation.seller_amount);
+    /// Return the current staked amount for an artisan.
+    pub fn get_stake(env: Env, artisan: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get::<DataKey, ArtisanStakeData>(&DataKey::ArtisanStake(artisan))
+            .map(|stake: ArtisanStakeData| stake.amount)
+            .unwrap_or(0)
+    }
 
-                    // Emit release event
-                    Self::emit_escrow_created(
-                        &env,
-                        EscrowEvent {
-                            escrow_id: order_id as u64,
-                            action: EscrowAction::BatchReleased,
-                            buyer: escrow.buyer.clone(),
-                            seller: escrow.seller.clone(),
-                            amount: escrow.amount,
-                            token: escrow.token.clone(),
-                            timestamp: env.ledger().timestamp(),
-                        },
-                    );
-                    results.push_back(order_id as u64);
-                }
-            }
+    /// Check if an artisan account is under-collateralized (active obligations exist while holding less than minimum required stake).
+    pub fn is_account_under_collateralized(env: Env, artisan: Address) -> bool {
+        let config = Self::get_platform_config_internal(&env);
+        if config.min_stake_required <= 0 {
+            return false;
         }
+        let stake = Self::get_stake(env.clone(), artisan.clone());
+        let active = Self::has_active_escrows(env, artisan);
+        active && stake < config.min_stake_required
+    }
+
+    /// Admin sets the minimum stake required for artisans to create escrows.
+    ///
+    /// Kept for existing clients; role holders call `set_min_stake_required_as`.
+    pub fn set_min_stake_required(env: Env, min_stake: i128) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::set_min_stake_required_as(env, admin, min_stake)
+    }
+
+    /// Set the minimum stake required for artisans to create escrows
+    /// (`Role::Operator`).
+    pub fn set_min_stake_required_as(
+        env: Env,
+        caller: Address,
+        min_stake: i128,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
+
+        let mut config = Self::get_platform_config_internal(&env);
+        config.min_stake_required = min_stake;
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
+        Ok(())
+    }
+
+    /// Admin sets the WASM upgrade cooldown period (in seconds).
+    pub fn set_wasm_upgrade_cooldown(env: Env, cooldown_seconds: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        admin.require_auth();
+
+        let mut config = Self::get_platform_config_internal(&env);
+        let old_value = config.wasm_upgrade_cooldown;
+        config.wasm_upgrade_cooldown = cooldown_seconds;
+        env.storage()
+            .instance()
+            .set(&DataKey::PlatformConfig, &config);
 
-        Ok(results)
+        Self::emit_config_updated(
+            &env,
+            "wasm_upgrade_cooldown",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(cooldown_seconds),
+        );
+        Ok(())
     }
 
-    // NOTE: referral payout support has been removed from the contract. The configuration key is
-    // retained only for storage compatibility during upgrades.
+    /// Admin sets the maximum dispute duration (in seconds).
+    ///
+    /// Kept for existing clients; role holders call `set_max_dispute_duration_as`.
+    pub fn set_max_dispute_duration(env: Env, duration_seconds: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::set_max_dispute_duration_as(env, admin, duration_seconds)
+    }
 
-    /// Check that the contract is not paused. Panics with ContractPaused if it is.
-    fn check_not_paused(env: &Env) {
-        if let Some(config) = env
-            .storage()
+    /// Set the maximum dispute duration in seconds (`Role::Operator`).
+    pub fn set_max_dispute_duration_as(
+        env: Env,
+        caller: Address,
+        duration_seconds: u32,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
+
+        let mut config = Self::get_platform_config_internal(&env);
+        let old_value = config.max_dispute_duration;
+        config.max_dispute_duration = duration_seconds;
+        env.storage()
             .instance()
-            .get::<DataKey, PlatformConfig>(&DataKey::PlatformConfig)
-        {
-            if config.is_paused {
-                env.panic_with_error(crate::Error::ContractPaused);
-            }
-        }
+            .set(&DataKey::PlatformConfig, &config);
+
+        Self::emit_config_updated(
+            &env,
+            "max_dispute_duration",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(duration_seconds),
+        );
+        Ok(())
     }
 
-    /// Admin pauses or unpauses the contract.
-    pub fn set_paused(env: Env, paused: bool) {
-        let admin = Self::get_admin(&env)
-            .unwrap_or_else(|_| env.panic_with_error(crate::Error::Unauthorized));
-        admin.require_auth();
+    /// Admin sets the stake cooldown period (in seconds).
+    ///
+    /// Kept for existing clients; role holders call `set_stake_cooldown_as`.
+    pub fn set_stake_cooldown(env: Env, cooldown_seconds: u32) -> Result<(), Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::set_stake_cooldown_as(env, admin, cooldown_seconds)
+    }
+
+    /// Set the stake cooldown period in seconds (`Role::Operator`).
+    pub fn set_stake_cooldown_as(
+        env: Env,
+        caller: Address,
+        cooldown_seconds: u32,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
 
         let mut config = Self::get_platform_config_internal(&env);
-        config.is_paused = paused;
+        let old_value = config.stake_cooldown;
+        config.stake_cooldown = cooldown_seconds;
         env.storage()
             .instance()
             .set(&DataKey::PlatformConfig, &config);
 
-        if paused {
-            Self::emit_platform_paused(&env, admin);
-        } else {
-            Self::emit_platform_unpaused(&env, admin);
+        Self::emit_config_updated(
+            &env,
+            "stake_cooldown",
+            ConfigValue::U32(old_value),
+            ConfigValue::U32(cooldown_seconds),
+        );
+        Ok(())
+    }
+
+    // â”€â”€ Partial Refund Negotiation (#101) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+
+    /// Propose a partial refund for a disputed escrow.
+    ///
+    /// Either the buyer or seller may submit a proposal. Only one proposal may be
+    /// active at a time; a second call returns ProposalAlreadyExists.
+    ///
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    /// * `refund_amount` - Gross amount to refund to the buyer before any
+    ///   potential refund-side platform fee is deducted.
+    /// * `proposed_by` - Address of the party proposing the refund (must be buyer or seller)
+    pub fn propose_partial_refund(
+        env: Env,
+        order_id: u32,
+        refund_amount: i128,
+        caller: Address,
+    ) -> Result<(), Error> {
+        let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
+        if escrow_opt.is_none() {
+            return Err(Error::EscrowNotFound);
+        }
+        let escrow: Escrow = escrow_opt.unwrap();
+
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::InvalidEscrowState);
+        }
+
+        // Verify caller is either the buyer or seller
+        if caller != escrow.buyer && caller != escrow.seller {
+            return Err(Error::Unauthorized);
+        }
+
+        // Require auth from the proposing party
+        caller.require_auth();
+
+        // `refund_amount` is interpreted as gross; validation includes any
+        // configured refund-side fee to ensure transfers remain solvent.
+        if !Self::is_valid_partial_refund_gross_amount(&env, &escrow, refund_amount) {
+            return Err(Error::InvalidRefundAmount);
+        }
+
+        let proposal_key = DataKey::PartialRefundProposal(order_id);
+        if env.storage().persistent().has(&proposal_key) {
+            return Err(Error::ProposalAlreadyExists);
         }
+
+        let proposal = PartialRefundProposal {
+            order_id,
+            refund_amount,
+            proposed_by: caller,
+            proposed_at: env.ledger().timestamp(),
+        };
+
+        env.storage().persistent().set(&proposal_key, &proposal);
+        Self::extend_persistent(&env, &proposal_key);
+
+        Ok(())
     }
 
-    /// View: check if contract is paused.
-    pub fn is_paused(env: Env) -> bool {
-        let config = Self::get_platform_config_internal(&env);
-        config.is_paused
+    // â”€â”€ Storage Explorer â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+
+    /// Returns the total number of escrows ever created on this platform.
+    ///
+    /// This is an O(1) read â€” safe to call at any scale. Pair with
+    /// `get_all_escrow_ids_iterative` to paginate the full ID set without
+    /// hitting Soroban CPU/memory resource limits.
+    pub fn get_escrow_count(env: Env) -> u32 {
+        Self::migrate_legacy_all_escrow_ids(&env);
+        Self::get_persistent_u32(&env, &DataKey::EscrowCount)
     }
 
-    // â”€â”€ Tiered Artisan Fees (#98) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+    /// Returns a page of all escrow order IDs created on the platform, in creation order.
+    ///
+    /// This is the recommended pattern for frontends to enumerate every escrow without
+    /// hitting Soroban resource limits. The function reads a bounded slice of the
+    /// indexed `GlobalEscrowIdIndexed` registry; no on-chain loops proportional to
+    /// the total escrow count are performed at call time.
+    ///
+    /// # Usage pattern (frontend / off-chain)
+    /// ```text
+    /// total  = get_escrow_count()
+    /// pages  = ceil(total / PAGE_SIZE)
+    /// for p in 0..pages:
+    ///     ids = get_all_escrow_ids_iterative(p, PAGE_SIZE)
+    ///     for id in ids:
+    ///         escrow = get_escrow(id)
+    /// ```
+    ///
+    /// # Soroban RPC key browsing
+    /// To enumerate storage keys directly via the RPC without calling this function,
+    /// use the `getLedgerEntries` method or the experimental `getContractData` cursor
+    /// endpoint.  Relevant key patterns:
+    /// - `DataKey::GlobalEscrowIdIndexed(index)` â€“ indexed global escrow ID (#515)
+    /// - `DataKey::EscrowCount`            â€“ u32 total count
+    /// - `DataKey::AllEscrowIds`           â€“ DEPRECATED legacy Vec index
+    /// - `(ESCROW, order_id: u32)`         â€“ individual escrow struct
+    /// - `DataKey::BuyerEscrows(address)`  â€“ DEPRECATED: Legacy Vec<u64> of IDs for a buyer
+    /// - `DataKey::SellerEscrows(address)` â€“ DEPRECATED: Legacy Vec<u64> of IDs for a seller
+    /// - `DataKey::BuyerEscrowIndexed(address, index)` â€“ Indexed storage: u64 escrow ID at position
+    /// - `DataKey::BuyerEscrowCount(address)` â€“ u32 total count of buyer's escrows
+    /// - `DataKey::SellerEscrowIndexed(address, index)` â€“ Indexed storage: u64 escrow ID at position
+    /// - `DataKey::SellerEscrowCount(address)` â€“ u32 total count of seller's escrows
+    ///
+    /// # Arguments
+    /// * `page`  â€“ Zero-indexed page number
+    /// * `limit` â€“ Page size; values above `MAX_BATCH_SIZE` are silently capped
+    ///
+    /// # Returns
+    /// A `Vec<u32>` of escrow IDs for the requested page; empty when `page` is out of range.
+    pub fn get_all_escrow_ids_iterative(env: Env, page: u32, limit: u32) -> soroban_sdk::Vec<u32> {
+        let limit = limit.min(MAX_BATCH_SIZE);
+        if limit == 0 {
+            return soroban_sdk::Vec::new(&env);
+        }
 
-    /// Admin assigns a custom fee tier (in basis points) for an artisan.
-    pub fn set_artisan_fee_tier(env: Env, artisan: Address, fee_bps: u32) {
-        let admin = Self::get_admin(&env)
-            .unwrap_or_else(|_| env.panic_with_error(crate::Error::Unauthorized));
-        admin.require_auth();
+        Self::migrate_legacy_all_escrow_ids(&env);
 
-        if fee_bps > MAX_PLATFORM_FEE_BPS {
-            env.panic_with_error(crate::Error::InvalidFee);
+        let total = Self::get_persistent_u32(&env, &DataKey::EscrowCount);
+        let start = page * limit;
+
+        if start >= total {
+            return soroban_sdk::Vec::new(&env);
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::ArtisanFeeTier(artisan.clone()), &fee_bps);
-        Self::extend_persistent(&env, &DataKey::ArtisanFeeTier(artisan.clone()));
-        Self::emit_artisan_fee_tier_updated(&env, artisan, fee_bps);
-    }
+        let end = (start + limit).min(total);
+        let mut result = soroban_sdk::Vec::new(&env);
 
-    /// Get the effective fee basis points for a seller.
-    /// Returns artisan-specific tier if set, otherwise platform default.
-    pub fn get_effective_fee_bps(env: Env, seller: Address) -> u32 {
-        let key = DataKey::ArtisanFeeTier(seller);
-        if let Some(fee) = env.storage().persistent().get::<DataKey, u32>(&key) {
-            Self::extend_persistent(&env, &key);
-            fee
-        } else {
-            let config = Self::get_platform_config_internal(&env);
-            config.platform_fee_bps
+        for index in start..end {
+            let index_key = DataKey::GlobalEscrowIdIndexed(index);
+            if let Some(id) = env.storage().persistent().get(&index_key) {
+                result.push_back(id);
+                Self::extend_persistent(&env, &index_key);
+            }
         }
-    }
 
-    // â”€â”€ Dispute Resolution Deadline (#93) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+        result
+    }
 
-    /// Resolve a dispute that has exceeded the maximum dispute duration.
+    /// Accept the outstanding partial refund proposal for a disputed escrow.
     ///
-    /// If the dispute has been open for longer than the configured max_dispute_duration,
-    /// the escrow is resolved according to the configured expired_dispute_fee_policy.
-    /// Returns DisputeExpired error if the deadline has not yet passed.
-    pub fn resolve_expired_dispute(env: Env, order_id: u32) -> Result<(), Error> {
+    /// The counterparty (the party that did NOT submit the proposal) calls this function.
+    /// Funds are distributed from a gross refund model: buyer receives
+    /// `refund_amount - refund_fee`, seller receives the remainder minus seller-side
+    /// platform fee. The escrow status is set to Resolved.
+    pub fn accept_partial_refund(env: Env, order_id: u32) -> Result<(), Error> {
         let _guard = ReentryGuardScope::new(&env);
         let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
         if escrow_opt.is_none() {
             return Err(Error::EscrowNotFound);
         }
-        Self::extend_persistent(&env, &(ESCROW, order_id));
         let mut escrow: Escrow = escrow_opt.unwrap();
 
         if escrow.status != EscrowStatus::Disputed {
             return Err(Error::InvalidEscrowState);
         }
 
-        let initiated_at = escrow
-            .dispute_initiated_at
-            .ok_or(Error::InvalidEscrowState)?;
-        let current_time = env.ledger().timestamp();
+        let proposal_key = DataKey::PartialRefundProposal(order_id);
+        let proposal_opt: Option<PartialRefundProposal> =
+            env.storage().persistent().get(&proposal_key);
+        if proposal_opt.is_none() {
+            return Err(Error::ProposalNotFound);
+        }
+        let proposal: PartialRefundProposal = proposal_opt.unwrap();
 
-        let config = Self::get_platform_config_internal(&env);
-        if initiated_at + config.max_dispute_duration as u64 > current_time {
-            return Err(Error::DisputeExpired);
+        // The counterparty is whoever did NOT propose
+        if proposal.proposed_by == escrow.buyer {
+            escrow.seller.require_auth();
+        } else {
+            escrow.buyer.require_auth();
         }
 
-        // CRITICAL: Update status BEFORE external calls (CEI pattern)
+        let refund_amount_gross = proposal.refund_amount;
+        let seller_gross = escrow.amount - refund_amount_gross;
+
+        // Deterministic fee allocation via the central FeePolicy engine.
+        let config = Self::get_platform_config_internal(&env);
+        let fee_rate = Self::escrow_fee_rate(&env, order_id, &escrow);
+        let referrer = Self::resolve_escrow_referrer(&env, order_id, &escrow.seller);
+        let allocation = Self::apply_referral_share(
+            &env,
+            Self::compute_escrow_fee_allocation(
+                &env,
+                order_id,
+                escrow.amount,
+                &fee_rate,
+                SettlementKind::PartialRefund(
+                    refund_amount_gross,
+                    seller_gross,
+                ),
+            ),
+            &referrer,
+        );
+
+        // CEI Pattern: EFFECTS - Update state BEFORE external calls
         escrow.status = EscrowStatus::Resolved;
         env.storage().persistent().set(&(ESCROW, order_id), &escrow);
 
+        // Clean up proposal
+        env.storage().persistent().remove(&proposal_key);
+
         // Decrement active counts
         Self::update_active_obligations(&env, &escrow.buyer, -1);
         Self::update_active_obligations(&env, &escrow.seller, -1);
@@ -6528,25 +11622,31 @@ impl CraftNexusContract {
         Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
         Self::update_total_locked(&env, &escrow.token, -escrow.amount);
 
-        // Now perform token transfers (external calls)
-        let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.seller.clone());
-        let settlement_kind = match config.expired_dispute_fee_policy {
-            ExpiredDisputeFeePolicy::RefundFullNoPlatformFee => SettlementKind::ExpiredDisputeDeductFromSeller,
-            ExpiredDisputeFeePolicy::RefundMinusPlatformFee => SettlementKind::ExpiredDisputeDeductFromBuyer,
-            ExpiredDisputeFeePolicy::DeductFeeFromSeller => SettlementKind::ExpiredDisputeDeductFromSeller,
-            ExpiredDisputeFeePolicy::SplitFee => SettlementKind::ExpiredDisputeSplitFee,
-        };
-        let allocation = Self::compute_fee_allocation(
-            &env,
-            escrow.amount,
-            fee_bps,
-            settlement_kind,
-        );
+        // CEI Pattern: INTERACTIONS - External calls AFTER state updates
+
+        // Refund buyer and record audit
+        if allocation.buyer_amount > 0 {
+            Self::pay_refund(&env, order_id, &escrow, escrow.amount, allocation.buyer_amount, Symbol::new(&env, "partial_refund_buyer"));
+        }
 
+        // Pay platform fee
         if allocation.platform_fee > 0 {
-            Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, allocation.platform_fee);
+            Self::transfer_platform_fee(
+                &env,
+                &escrow.token,
+                &config.platform_wallet,
+                &allocation,
+                referrer.as_ref().map(|referrer| (order_id, referrer)),
+                None,
+            );
+        }
+
+        // Pay seller
+        if allocation.seller_amount > 0 {
+            Self::pay_seller_proceeds(&env, order_id, &escrow, allocation.seller_amount, Symbol::new(&env, "partial_refund_seller"));
         }
-        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.buyer, allocation.buyer_amount, &escrow.buyer, Symbol::new(&env, "expired_dispute_refund"), allocation.buyer_amount);
+
+        Self::clear_settled_escrow_state(&env, order_id);
 
         Self::emit_escrow_created(
             &env,
@@ -6557,969 +11657,1364 @@ impl CraftNexusContract {
                 seller: escrow.seller.clone(),
                 amount: escrow.amount,
                 token: escrow.token.clone(),
-                timestamp: current_time,
+                timestamp: env.ledger().timestamp(),
             },
         );
 
         Ok(())
     }
 
-    // â”€â”€ Staking Requirement for Artisans (#99) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
-
-    /// Stake tokens to satisfy the platform's minimum stake requirement.
-    ///
-    /// The artisan transfers `amount` of `token` to the contract. The stake is stored
-    /// and a cooldown timer is set so the tokens cannot be unstaked immediately.
+    /// Cancel a partial refund proposal.
     ///
-    /// Enhanced with bounded queue management to prevent storage bloat. Automatically
-    /// prunes matured deposits when queue approaches capacity limits.
+    /// Only the proposer can cancel their own proposal. This removes the proposal
+    /// from storage, allowing a new proposal to be submitted if needed.
     ///
-    /// Staked balances remain owned by the artisan. The contract does not accrue,
-    /// distribute, or sweep interest/yield from these reserved funds into platform fees.
-    pub fn stake_tokens(env: Env, artisan: Address, token: Address, amount: i128) {
-        let _guard = ReentryGuardScope::new(&env);
-        artisan.require_auth();
+    /// # Arguments
+    /// * `order_id` - Order identifier
+    pub fn cancel_partial_refund(env: Env, order_id: u32) -> Result<(), Error> {
+        let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
+        if escrow_opt.is_none() {
+            return Err(Error::EscrowNotFound);
+        }
+        let escrow: Escrow = escrow_opt.unwrap();
 
-        if amount <= 0 {
-            env.panic_with_error(crate::Error::AmountBelowMinimum);
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::InvalidEscrowState);
         }
 
-        // Effects are committed before the token interaction.
-        Self::update_total_staked(&env, &token, amount);
+        let proposal_key = DataKey::PartialRefundProposal(order_id);
+        let proposal_opt: Option<PartialRefundProposal> =
+            env.storage().persistent().get(&proposal_key);
+        if proposal_opt.is_none() {
+            return Err(Error::ProposalNotFound);
+        }
+        let proposal: PartialRefundProposal = proposal_opt.unwrap();
 
-        // Accumulate stake in a single record with token metadata.
-        let stake_key = DataKey::ArtisanStake(artisan.clone());
-        let current_stake: Option<ArtisanStakeData> = env.storage().persistent().get(&stake_key);
-        let new_stake = if let Some(existing_stake) = current_stake {
-            if existing_stake.token != token {
-                env.panic_with_error(crate::Error::StakeTokenMismatch);
-            }
-            ArtisanStakeData {
-                amount: existing_stake.amount + amount,
-                token: token.clone(),
-            }
-        } else {
-            ArtisanStakeData { amount, token: token.clone() }
-        };
+        // Only the proposer can cancel
+        proposal.proposed_by.require_auth();
 
-        let config = Self::get_platform_config_internal(&env);
-        if config.min_stake_required > 0 && new_stake.amount < config.min_stake_required {
-            env.panic_with_error(crate::Error::InsufficientStake);
-        }
+        // Remove the proposal from storage
+        env.storage().persistent().remove(&proposal_key);
 
-        env.storage().persistent().set(&stake_key, &new_stake);
-        Self::extend_persistent(&env, &stake_key);
+        Ok(())
+    }
 
-        // Record stake operation in history queue for audit trail (#237)
-        if let Err(_) = Self::record_stake_history(&env, &artisan, new_stake.amount, "stake_added")
-        {
-            env.panic_with_error(Error::StakeQueueFull);
-        }
+    /// Returns the currently configured refund-side fee basis points.
+    ///
+    /// Today this is intentionally fixed at 0 bps. A future governance feature
+    /// can replace this implementation with configurable storage without changing
+    /// partial-refund validation semantics.
+    fn get_refund_fee_bps(_env: &Env) -> u32 {
+        0
+    }
 
-        // Check queue capacity and current cooldown state (#237)
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    /// Calculate refund-side fee charged against a proposed gross partial refund.
+    fn calculate_partial_refund_fee(env: &Env, gross_refund_amount: i128) -> i128 {
+        let refund_fee_bps = Self::get_refund_fee_bps(env);
+        Self::calculate_fee(env, gross_refund_amount, refund_fee_bps)
+    }
 
-        // Initialize cooldown only if artisan doesn't already have one.
-        // This prevents cooldown reset gaming where artisans extend their cooldown by continuously staking.
-        let existing_cooldown = if current_count > 0 {
-            let last_deposit_key =
-                DataKey::ArtisanStakeQueueIndexed(artisan.clone(), current_count - 1);
-            let deposit: StakeDeposit = env.storage().persistent().get(&last_deposit_key).unwrap();
-            deposit.cooldown_end
-        } else {
-            0
-        };
+    /// Validate gross partial refund amount against escrow solvency including any
+    /// potential refund-side fee that may apply.
+    #[inline(always)]
+    fn is_valid_partial_refund_gross_amount(
+        env: &Env,
+        escrow: &Escrow,
+        gross_refund: i128,
+    ) -> bool {
+        if gross_refund <= 0 || gross_refund > escrow.amount {
+            return false;
+        }
+        let potential_refund_fee = Self::calculate_partial_refund_fee(env, gross_refund);
+        gross_refund.saturating_add(potential_refund_fee) <= escrow.amount
+    }
 
-        let cooldown_end = if existing_cooldown == 0 {
-            // No existing cooldown, initialize new one
-            let config = Self::get_platform_config_internal(&env);
-            env.ledger().timestamp() + config.stake_cooldown as u64
-        } else {
-            existing_cooldown
-        };
+    /// Create a new recurring escrow for recurring payments/subscriptions.
+    pub fn create_recurring_escrow(
+        env: Env,
+        buyer: Address,
+        artisan: Address,
+        token: Address,
+        total_amount: i128,
+        frequency: u64,
+        duration: u32,
+    ) -> Result<RecurringEscrow, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        buyer.require_auth();
 
-        // Check queue capacity and prune if necessary
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if duration == 0 || frequency == 0 || total_amount <= 0 {
+            return Err(Error::AmountBelowMinimum);
+        }
+        Self::create_recurring_internal(
+            &env,
+            buyer,
+            artisan,
+            token,
+            total_amount,
+            frequency,
+            duration,
+            None,
+        )
+    }
 
-        if current_count >= STAKE_QUEUE_PRUNE_THRESHOLD {
-            Self::prune_matured_stake_deposits(&env, &artisan);
+    /// Create a recurring escrow that releases an explicit list of cycle
+    /// amounts at their due timestamps, e.g. a deposit followed by smaller
+    /// weekly payments. `total_amount` is locked up front and the cycle
+    /// amounts must sum to it exactly.
+    ///
+    /// Due times must be strictly increasing and not in the past; the
+    /// schedule holds at most `MAX_RECURRING_SCHEDULE_LEN` cycles. Pausing
+    /// the escrow pushes every remaining due time back by the paused time.
+    pub fn create_recurring_schedule(
+        env: Env,
+        buyer: Address,
+        artisan: Address,
+        token: Address,
+        total_amount: i128,
+        schedule: Vec<RecurringCycle>,
+    ) -> Result<RecurringEscrow, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
         }
+        buyer.require_auth();
 
-        // Add new deposit to bounded indexed queue
-        Self::add_stake_deposit(&env, &artisan, amount, cooldown_end);
+        if total_amount <= 0 || schedule.is_empty() {
+            return Err(Error::AmountBelowMinimum);
+        }
+        if schedule.len() > MAX_RECURRING_SCHEDULE_LEN {
+            return Err(Error::BatchLimitExceeded);
+        }
+        let mut scheduled_total: i128 = 0;
+        let mut previous_due = env.ledger().timestamp();
+        for (i, cycle) in schedule.iter().enumerate() {
+            if cycle.amount <= 0 {
+                return Err(Error::AmountBelowMinimum);
+            }
+            if cycle.due_at < previous_due || (i > 0 && cycle.due_at == previous_due) {
+                return Err(Error::InvalidEscrowState);
+            }
+            previous_due = cycle.due_at;
+            scheduled_total = scheduled_total
+                .checked_add(cycle.amount)
+                .ok_or(Error::InvalidEscrowState)?;
+        }
+        if scheduled_total != total_amount {
+            return Err(Error::InvalidEscrowState);
+        }
 
-        Self::transfer_tokens_and_record_audit(&env, &token, &artisan, &env.current_contract_address(), amount, &artisan, Symbol::new(&env, "stake_deposit"), -amount);
+        let duration = schedule.len();
+        Self::create_recurring_internal(
+            &env,
+            buyer,
+            artisan,
+            token,
+            total_amount,
+            0,
+            duration,
+            Some(schedule),
+        )
     }
 
-    /// Add a stake deposit to the bounded indexed queue.
-    ///
-    /// Implements individual key-value storage for scalability. Each deposit is stored
-    /// as DataKey::ArtisanStakeQueueIndexed(artisan, index) -> StakeDeposit.
-    fn add_stake_deposit(env: &Env, artisan: &Address, amount: i128, cooldown_end: u64) {
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    /// Shared creation path. A `schedule` marks a variable-amount escrow,
+    /// stored with a `frequency` of 0.
+    #[allow(clippy::too_many_arguments)]
+    fn create_recurring_internal(
+        env: &Env,
+        buyer: Address,
+        artisan: Address,
+        token: Address,
+        total_amount: i128,
+        frequency: u64,
+        duration: u32,
+        schedule: Option<Vec<RecurringCycle>>,
+    ) -> Result<RecurringEscrow, Error> {
+        if buyer == artisan {
+            return Err(Error::SameBuyerSeller);
+        }
 
-        if current_count >= MAX_STAKE_QUEUE_SIZE {
-            env.panic_with_error(Error::StakeQueueFull);
+        // Validate token whitelist
+        if !Self::is_token_whitelisted(env.clone(), token.clone()) {
+            return Err(Error::TokenNotWhitelisted);
         }
 
-        // Add new deposit at the end of the queue
-        let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), current_count);
-        let deposit = StakeDeposit {
-            amount,
-            cooldown_end,
+        // Issue #233: bounded, overflow-safe allocation. Reject once the
+        // counter reaches the cap instead of wrapping into an existing ID.
+        Self::recurring_index_start(env);
+        let id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextRecurringEscrowId)
+            .unwrap_or(1);
+        if id > MAX_RECURRING_ESCROW_ID {
+            return Err(crate::Error::RecurringEscrowIdExhausted);
+        }
+        let next_id = id
+            .checked_add(1)
+            .ok_or(crate::Error::RecurringEscrowIdExhausted)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::NextRecurringEscrowId, &next_id);
+        Self::extend_persistent(env, &DataKey::NextRecurringEscrowId);
+
+        let now = env.ledger().timestamp();
+
+        let escrow = RecurringEscrow {
+            id,
+            buyer: buyer.clone(),
+            artisan: artisan.clone(),
+            token: token.clone(),
+            total_amount,
+            released_amount: 0,
+            frequency,
+            duration,
+            current_cycle: 0,
+            last_release_time: now,
+            is_active: true,
         };
-        env.storage().persistent().set(&deposit_key, &deposit);
-        Self::extend_persistent(env, &deposit_key);
 
-        // Update count
         env.storage()
             .persistent()
-            .set(&count_key, &(current_count + 1));
-        Self::extend_persistent(env, &count_key);
-    }
-
-    /// Prune matured stake deposits from the queue to prevent storage bloat.
-    ///
-    /// Removes deposits where cooldown_end <= current_time and compacts the queue
-    /// by shifting remaining deposits to fill gaps. This maintains queue ordering
-    /// while keeping storage bounded.
-    fn prune_matured_stake_deposits(env: &Env, artisan: &Address) {
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
-
-        if current_count == 0 {
-            return;
-        }
+            .set(&DataKey::RecurringEscrow(id), &escrow);
+        Self::extend_persistent(env, &DataKey::RecurringEscrow(id));
+        if let Some(schedule) = schedule {
+            let schedule_key = RecurringDataKey::Schedule(id);
+            env.storage().persistent().set(&schedule_key, &schedule);
+            Self::extend_persistent(env, &schedule_key);
+        }
+        let fee_rate_key = RecurringDataKey::FeeRate(id);
+        let fee_rate = Self::effective_fee_rate(env, &artisan, &token).snapshot();
+        env.storage().persistent().set(&fee_rate_key, &fee_rate);
+        Self::extend_persistent(env, &fee_rate_key);
+        Self::index_recurring_escrow(env, &escrow, false);
 
-        let now = env.ledger().timestamp();
-        let mut write_index = 0u32;
+        // Track active recurring escrows
+        Self::update_active_obligations(env, &buyer, 1);
+        Self::update_active_obligations(env, &artisan, 1);
 
-        // Compact queue by moving non-matured deposits to fill gaps
-        for read_index in 0..current_count {
-            let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), read_index);
+        Self::safe_update_active_contracts(env, buyer.clone(), 1);
+        Self::safe_update_active_contracts(env, artisan.clone(), 1);
 
-            if let Some(deposit) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, StakeDeposit>(&deposit_key)
-            {
-                if deposit.cooldown_end > now {
-                    // Deposit is not matured, keep it
-                    if write_index != read_index {
-                        // Move deposit to new position
-                        let new_key =
-                            DataKey::ArtisanStakeQueueIndexed(artisan.clone(), write_index);
-                        env.storage().persistent().set(&new_key, &deposit);
-                        Self::extend_persistent(env, &new_key);
-                    }
-                    write_index += 1;
-                }
+        Self::update_total_locked(env, &token, total_amount);
+        Self::transfer_tokens_and_record_audit(env, &token, &buyer, &env.current_contract_address(), total_amount, &buyer, Symbol::new(env, "recurring_escrow_locked"), -total_amount);
 
-                // Remove old entry if we moved it or if it was matured
-                if write_index != read_index + 1 {
-                    env.storage().persistent().remove(&deposit_key);
-                }
-            }
-        }
+        env.events().publish(
+            (Symbol::new(env, "recurring_escrow"), id),
+            RecurringEscrowEvent {
+                id,
+                action: RecurringEscrowAction::Created,
+                buyer,
+                artisan,
+                amount: total_amount,
+                timestamp: now,
+            },
+        );
 
-        // Update count to reflect pruned queue. If nothing remains, remove the
-        // count entry rather than leaving a stale counter behind.
-        if write_index > 0 {
-            env.storage().persistent().set(&count_key, &write_index);
-            Self::extend_persistent(env, &count_key);
-        } else {
-            env.storage().persistent().remove(&count_key);
-        }
+        Ok(escrow)
     }
 
-    /// Unstake previously staked tokens after the cooldown period has elapsed.
-    ///
-    /// Stakes can only be returned in the exact token originally deposited, which
-    /// prevents reserved artisan collateral from being treated as platform-managed fees.
-    /// Enhanced with bounded indexed queue and automatic pruning for scalability.
-    pub fn unstake_tokens(env: Env, artisan: Address, token: Address) {
+    /// Release funds for the next cycle in a recurring escrow.
+    pub fn release_next_cycle(env: Env, id: u64) {
         let _guard = ReentryGuardScope::new(&env);
-        artisan.require_auth();
-
-        // Validate the requested token matches the token recorded at stake time.
-        // Rejects attempts to withdraw in a cheaper/different asset (#421).
-        let stake_key = DataKey::ArtisanStake(artisan.clone());
-        let current_stake: ArtisanStakeData = env
+        let key = DataKey::RecurringEscrow(id);
+        let mut escrow: RecurringEscrow = env
             .storage()
             .persistent()
-            .get(&stake_key)
-            .unwrap_or_else(|| env.panic_with_error(crate::Error::InsufficientStake));
-        if current_stake.token != token {
-            env.panic_with_error(crate::Error::StakeTokenMismatch);
-        }
+            .get(&key)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::RecurringEscrowNotFound));
 
-        // Use bounded indexed queue: only matured deposits can be unstaked.
-        let count_key = DataKey::ArtisanStakeQueueCount(artisan.clone());
-        let current_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if !escrow.is_active {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
+        if escrow.current_cycle >= escrow.duration as u64 {
+            env.panic_with_error(crate::Error::CycleNotReady);
+        }
+        if Self::get_recurring_paused_at(env.clone(), id).is_some() {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
 
         let now = env.ledger().timestamp();
-        let mut matured_amount: i128 = 0;
-        let mut write_index = 0u32;
+        let schedule = Self::get_recurring_schedule(env.clone(), id);
+        let (_, due_at) = Self::recurring_cycle_window(&env, &escrow, &schedule);
+        if now < due_at {
+            env.panic_with_error(crate::Error::CycleNotReady);
+        }
 
-        // Process all deposits, collecting matured amounts and compacting the queue
-        for read_index in 0..current_count {
-            let deposit_key = DataKey::ArtisanStakeQueueIndexed(artisan.clone(), read_index);
+        let cycle_amount = Self::recurring_cycle_amount(&escrow, &schedule);
 
-            if let Some(deposit) = env
+        // Calculate distribution amounts using the deterministic fee engine.
+        let config = Self::get_platform_config_internal(&env);
+        let fee_rate = Self::recurring_fee_rate(&env, &escrow);
+        let allocation = Self::compute_fee_allocation(
+            &env,
+            cycle_amount,
+            &fee_rate,
+            SettlementKind::ReleaseFunds,
+        );
+
+        // Effects: commit all cycle and reserve accounting first.
+        Self::update_total_locked(&env, &escrow.token, -cycle_amount);
+        escrow.released_amount += cycle_amount;
+        escrow.current_cycle += 1;
+        escrow.last_release_time = now;
+
+        // With a cycle still disputed, `resolve_recurring_dispute` closes the
+        // escrow instead.
+        let became_inactive = escrow.current_cycle == escrow.duration as u64
+            && !env
                 .storage()
                 .persistent()
-                .get::<DataKey, StakeDeposit>(&deposit_key)
-            {
-                if now >= deposit.cooldown_end {
-                    // Deposit is matured, add to unstake amount
-                    matured_amount += deposit.amount;
-                    // Remove the matured deposit
-                    env.storage().persistent().remove(&deposit_key);
-                } else {
-                    // Deposit is not matured, keep it in the queue
-                    if write_index != read_index {
-                        // Move deposit to new position to compact the queue
-                        let new_key =
-                            DataKey::ArtisanStakeQueueIndexed(artisan.clone(), write_index);
-                        env.storage().persistent().set(&new_key, &deposit);
-                        Self::extend_persistent(&env, &new_key);
-                        // Remove old position
-                        env.storage().persistent().remove(&deposit_key);
-                    }
-                    write_index += 1;
-                }
-            }
+                .has(&RecurringDataKey::CycleDispute(id));
+        if became_inactive {
+            escrow.is_active = false;
+            // Decrement active recurring counts
+            Self::update_active_obligations(&env, &escrow.buyer, -1);
+            Self::update_active_obligations(&env, &escrow.artisan, -1);
         }
 
-        if matured_amount <= 0 {
-            env.panic_with_error(crate::Error::StakeCooldownActive);
-        }
+        env.storage().persistent().set(&key, &escrow);
+        Self::extend_persistent(&env, &key);
 
-        // Update queue count after processing
-        if write_index > 0 {
-            env.storage().persistent().set(&count_key, &write_index);
-            Self::extend_persistent(&env, &count_key);
-        } else {
-            // Queue is empty, remove count key
-            env.storage().persistent().remove(&count_key);
+        if became_inactive {
+            Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
+            Self::safe_update_active_contracts(&env, escrow.artisan.clone(), -1);
         }
 
-        // Validate remaining collateral safety and active obligation rules
-        let remaining_amount = current_stake.amount - matured_amount;
-        let config = Self::get_platform_config_internal(&env);
-        let active_obligations = Self::has_active_escrows(env.clone(), artisan.clone());
-        if config.min_stake_required > 0 {
-            if active_obligations && remaining_amount < config.min_stake_required {
-                env.panic_with_error(crate::Error::InsufficientStake);
-            }
-            if !active_obligations && remaining_amount > 0 && remaining_amount < config.min_stake_required {
-                env.panic_with_error(crate::Error::InsufficientStake);
-            }
+        // Interactions: token callbacks can only observe the completed cycle.
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, &allocation, None, None);
         }
+        Self::record_epoch_stats(&env, &escrow.token, |stats| {
+            stats.released_volume += allocation.seller_amount
+        });
+        Self::payout_tokens_and_record_audit(&env, &escrow.token, &escrow.artisan, allocation.seller_amount, Symbol::new(&env, "recurring_release"));
 
-        // Update stake metadata
-        if remaining_amount > 0 {
-            let updated_stake = ArtisanStakeData {
-                amount: remaining_amount,
-                token: current_stake.token,
-            };
-            env.storage().persistent().set(&stake_key, &updated_stake);
-            Self::extend_persistent(&env, &stake_key);
-        } else {
-            env.storage().persistent().remove(&stake_key);
-        }
+        env.events().publish(
+            (Symbol::new(&env, "recurring_escrow"), id),
+            RecurringEscrowEvent {
+                id,
+                action: RecurringEscrowAction::CycleReleased,
+                buyer: escrow.buyer.clone(),
+                artisan: escrow.artisan.clone(),
+                amount: cycle_amount,
+                timestamp: now,
+            },
+        );
 
-        // Record unstake operation in history for audit trail (#237)
-        if let Err(_) = Self::record_stake_history(&env, &artisan, 0, "stake_removed") {
-            // Don't fail on history recording, but log the issue
-            env.events().publish(
-                (Symbol::new(&env, "stake_history_warning"), "queue_full"),
-                String::from_str(&env, "Could not record stake removal in history"),
+        // Emit reputation update events â€” decoupled from onboarding contract (#211)
+        let ts = env.ledger().timestamp();
+        Self::emit_reputation_update(
+            &env,
+            ReputationUpdateEvent {
+                address: escrow.artisan.clone(),
+                successful_delta: if !escrow.is_active { 1 } else { 0 },
+                disputed_delta: 0,
+                metrics_sales_delta: 1,
+                metrics_amount: cycle_amount,
+                token: escrow.token.clone(),
+                timestamp: ts,
+            },
+        );
+        if !escrow.is_active {
+            Self::emit_reputation_update(
+                &env,
+                ReputationUpdateEvent {
+                    address: escrow.buyer.clone(),
+                    successful_delta: 1,
+                    disputed_delta: 0,
+                    metrics_sales_delta: 0,
+                    metrics_amount: 0,
+                    token: escrow.token.clone(),
+                    timestamp: ts,
+                },
             );
         }
+    }
 
-        // Complete reserve accounting before returning tokens.
-        Self::update_total_staked(&env, &token, -matured_amount);
-        Self::transfer_tokens_and_record_audit(&env, &token, &env.current_contract_address(), &artisan, matured_amount, &artisan, Symbol::new(&env, "stake_unstaked"), matured_amount);
-
-        env.events().publish(
-            (Symbol::new(&env, "tokens_unstaked"), artisan.clone()),
-            TokensUnstakedEvent {
-                artisan,
-                token,
-                amount: matured_amount,
-            },
-        );
+    /// Cancel a recurring escrow and refund remaining funds to the buyer.
+    pub fn cancel_recurring_escrow(env: Env, id: u64) {
+        let _guard = ReentryGuardScope::new(&env);
+        let escrow = Self::get_active_recurring_escrow(&env, id);
+        escrow.buyer.require_auth();
+        Self::cancel_recurring_internal(&env, id, escrow, 0);
     }
 
-    /// Return the current staked amount for an artisan.
-    pub fn get_stake(env: Env, artisan: Address) -> i128 {
+    /// Get the cycle list of a variable-amount recurring escrow; empty for
+    /// escrows with equal cycles.
+    pub fn get_recurring_schedule(env: Env, id: u64) -> Vec<RecurringCycle> {
         env.storage()
             .persistent()
-            .get::<DataKey, ArtisanStakeData>(&DataKey::ArtisanStake(artisan))
-            .map(|stake: ArtisanStakeData| stake.amount)
-            .unwrap_or(0)
+            .get(&RecurringDataKey::Schedule(id))
+            .unwrap_or_else(|| Vec::new(&env))
     }
 
-    /// Check if an artisan account is under-collateralized (active obligations exist while holding less than minimum required stake).
-    pub fn is_account_under_collateralized(env: Env, artisan: Address) -> bool {
-        let config = Self::get_platform_config_internal(&env);
-        if config.min_stake_required <= 0 {
-            return false;
+    /// Amount released by the next cycle. Scheduled escrows use the listed
+    /// amount; otherwise the last cycle takes the remainder.
+    fn recurring_cycle_amount(escrow: &RecurringEscrow, schedule: &Vec<RecurringCycle>) -> i128 {
+        if let Some(cycle) = schedule.get(escrow.current_cycle as u32) {
+            cycle.amount
+        } else if escrow.current_cycle == (escrow.duration as u64) - 1 {
+            escrow.total_amount - escrow.released_amount
+        } else {
+            escrow.total_amount / (escrow.duration as i128)
         }
-        let stake = Self::get_stake(env.clone(), artisan.clone());
-        let active = Self::has_active_escrows(env, artisan);
-        active && stake < config.min_stake_required
     }
 
-    /// Admin sets the minimum stake required for artisans to create escrows.
-    pub fn set_min_stake_required(env: Env, min_stake: i128) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+    /// Start and due time of the next cycle. For scheduled escrows both are
+    /// taken from the schedule, shifted by the time spent paused.
+    fn recurring_cycle_window(
+        env: &Env,
+        escrow: &RecurringEscrow,
+        schedule: &Vec<RecurringCycle>,
+    ) -> (u64, u64) {
+        let Some(cycle) = schedule.get(escrow.current_cycle as u32) else {
+            return (
+                escrow.last_release_time,
+                escrow.last_release_time.saturating_add(escrow.frequency),
+            );
+        };
+        let shift: u64 = env
+            .storage()
+            .persistent()
+            .get(&RecurringDataKey::ScheduleShift(escrow.id))
+            .unwrap_or(0);
+        let start = match escrow.current_cycle.checked_sub(1) {
+            Some(previous) => schedule
+                .get(previous as u32)
+                .map(|c| c.due_at.saturating_add(shift))
+                .unwrap_or(escrow.last_release_time),
+            None => escrow.last_release_time,
+        };
+        (start, cycle.due_at.saturating_add(shift))
+    }
 
-        let mut config = Self::get_platform_config_internal(&env);
-        config.min_stake_required = min_stake;
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
-        Ok(())
+    fn get_active_recurring_escrow(env: &Env, id: u64) -> RecurringEscrow {
+        let escrow: RecurringEscrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecurringEscrow(id))
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::RecurringEscrowNotFound));
+        if !escrow.is_active {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
+        escrow
     }
 
-    /// Admin sets the WASM upgrade cooldown period (in seconds).
-    pub fn set_wasm_upgrade_cooldown(env: Env, cooldown_seconds: u32) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+    /// Close a recurring escrow, paying `artisan_amount` (before fees) to the
+    /// artisan and refunding the rest of the unreleased balance to the buyer.
+    fn cancel_recurring_internal(
+        env: &Env,
+        id: u64,
+        mut escrow: RecurringEscrow,
+        artisan_amount: i128,
+    ) {
+        if env
+            .storage()
+            .persistent()
+            .has(&RecurringDataKey::CycleDispute(id))
+        {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
+        let key = DataKey::RecurringEscrow(id);
+        let remaining = escrow.total_amount - escrow.released_amount - artisan_amount;
 
-        let mut config = Self::get_platform_config_internal(&env);
-        let old_value = config.wasm_upgrade_cooldown;
-        config.wasm_upgrade_cooldown = cooldown_seconds;
+        // CEI Pattern: EFFECTS - Update state BEFORE external calls
+        escrow.released_amount += artisan_amount;
+        escrow.is_active = false;
+        env.storage().persistent().set(&key, &escrow);
+        Self::extend_persistent(env, &key);
         env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
+            .persistent()
+            .remove(&RecurringDataKey::PausedAt(id));
 
-        Self::emit_config_updated(
-            &env,
-            "wasm_upgrade_cooldown",
-            ConfigValue::U32(old_value),
-            ConfigValue::U32(cooldown_seconds),
-        );
-        Ok(())
-    }
+        // Decrement active recurring counts
+        Self::update_active_obligations(env, &escrow.buyer, -1);
+        Self::update_active_obligations(env, &escrow.artisan, -1);
 
-    /// Admin sets the maximum dispute duration (in seconds).
-    pub fn set_max_dispute_duration(env: Env, duration_seconds: u32) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+        Self::safe_update_active_contracts(env, escrow.buyer.clone(), -1);
+        Self::safe_update_active_contracts(env, escrow.artisan.clone(), -1);
 
-        let mut config = Self::get_platform_config_internal(&env);
-        let old_value = config.max_dispute_duration;
-        config.max_dispute_duration = duration_seconds;
-        env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
+        // CEI Pattern: INTERACTIONS - External calls AFTER state updates
+        if artisan_amount > 0 {
+            let config = Self::get_platform_config_internal(env);
+            let fee_rate = Self::recurring_fee_rate(env, &escrow);
+            let allocation = Self::compute_fee_allocation(
+                env,
+                artisan_amount,
+                &fee_rate,
+                SettlementKind::ReleaseFunds,
+            );
+            Self::update_total_locked(env, &escrow.token, -artisan_amount);
+            if allocation.platform_fee > 0 {
+                Self::transfer_platform_fee(env, &escrow.token, &config.platform_wallet, &allocation, None, None);
+            }
+            Self::record_epoch_stats(env, &escrow.token, |stats| {
+                stats.released_volume += allocation.seller_amount
+            });
+            Self::payout_tokens_and_record_audit(env, &escrow.token, &escrow.artisan, allocation.seller_amount, Symbol::new(env, "recurring_prorata_release"));
+        }
+        if remaining > 0 {
+            Self::update_total_locked(env, &escrow.token, -remaining);
+            Self::record_epoch_stats(env, &escrow.token, |stats| stats.refunded_volume += remaining);
+            Self::payout_tokens_and_record_audit(env, &escrow.token, &escrow.buyer, remaining, Symbol::new(env, "recurring_cancel_refund"));
+        }
 
-        Self::emit_config_updated(
-            &env,
-            "max_dispute_duration",
-            ConfigValue::U32(old_value),
-            ConfigValue::U32(duration_seconds),
+        Self::emit_recurring_event(env, &escrow, RecurringEscrowAction::Cancelled, remaining);
+    }
+
+    fn emit_recurring_event(
+        env: &Env,
+        escrow: &RecurringEscrow,
+        action: RecurringEscrowAction,
+        amount: i128,
+    ) {
+        env.events().publish(
+            (Symbol::new(env, "recurring_escrow"), escrow.id),
+            RecurringEscrowEvent {
+                id: escrow.id,
+                action,
+                buyer: escrow.buyer.clone(),
+                artisan: escrow.artisan.clone(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
         );
-        Ok(())
     }
 
-    /// Admin sets the stake cooldown period (in seconds).
-    pub fn set_stake_cooldown(env: Env, cooldown_seconds: u32) -> Result<(), Error> {
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+    /// Pause a recurring escrow (buyer only). No cycles can be released
+    /// while paused and the paused time is not counted towards the next
+    /// cycle. Fails once the next cycle is due, so a pause cannot withhold
+    /// a cycle the artisan has already earned; release it first. Emits
+    /// `RecurringEscrowAction::Paused` for the artisan.
+    pub fn pause_recurring_escrow(env: Env, id: u64) {
+        let _guard = ReentryGuardScope::new(&env);
+        let escrow = Self::get_active_recurring_escrow(&env, id);
+        escrow.buyer.require_auth();
 
-        let mut config = Self::get_platform_config_internal(&env);
-        let old_value = config.stake_cooldown;
-        config.stake_cooldown = cooldown_seconds;
+        let key = RecurringDataKey::PausedAt(id);
+        if env.storage().persistent().has(&key) {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
+        let schedule = Self::get_recurring_schedule(env.clone(), id);
+        let (_, due_at) = Self::recurring_cycle_window(&env, &escrow, &schedule);
+        if env.ledger().timestamp() >= due_at {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
         env.storage()
-            .instance()
-            .set(&DataKey::PlatformConfig, &config);
+            .persistent()
+            .set(&key, &env.ledger().timestamp());
+        Self::extend_persistent(&env, &key);
 
-        Self::emit_config_updated(
-            &env,
-            "stake_cooldown",
-            ConfigValue::U32(old_value),
-            ConfigValue::U32(cooldown_seconds),
-        );
-        Ok(())
+        Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::Paused, 0);
     }
 
-    // â”€â”€ Partial Refund Negotiation (#101) â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+    /// Resume a paused recurring escrow (buyer only). The cycle clock is
+    /// shifted by the paused time so no missed cycles accumulate. Fails once
+    /// the maximum pause duration has passed; use
+    /// `expire_paused_recurring_escrow` then. A pause imposed by a cycle
+    /// dispute is held until `resolve_recurring_dispute`.
+    pub fn resume_recurring_escrow(env: Env, id: u64) {
+        let _guard = ReentryGuardScope::new(&env);
+        let mut escrow = Self::get_active_recurring_escrow(&env, id);
+        escrow.buyer.require_auth();
 
-    /// Propose a partial refund for a disputed escrow.
-    ///
-    /// Either the buyer or seller may submit a proposal. Only one proposal may be
-    /// active at a time; a second call returns ProposalAlreadyExists.
-    ///
-    /// # Arguments
-    /// * `order_id` - Order identifier
-    /// * `refund_amount` - Gross amount to refund to the buyer before any
-    ///   potential refund-side platform fee is deducted.
-    /// * `proposed_by` - Address of the party proposing the refund (must be buyer or seller)
-    pub fn propose_partial_refund(
-        env: Env,
-        order_id: u32,
-        refund_amount: i128,
-        caller: Address,
-    ) -> Result<(), Error> {
-        let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
-        if escrow_opt.is_none() {
-            return Err(Error::EscrowNotFound);
+        let paused_at = Self::get_recurring_paused_at(env.clone(), id)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::InvalidEscrowState));
+        if Self::is_recurring_pause_expired(&env, paused_at) {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
         }
-        let escrow: Escrow = escrow_opt.unwrap();
-
-        if escrow.status != EscrowStatus::Disputed {
-            return Err(Error::InvalidEscrowState);
+        if Self::get_recurring_cycle_dispute(env.clone(), id)
+            .is_some_and(|dispute| dispute.paused_schedule)
+        {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
         }
 
-        // Verify caller is either the buyer or seller
-        if caller != escrow.buyer && caller != escrow.seller {
-            return Err(Error::Unauthorized);
-        }
+        Self::resume_recurring_schedule(&env, &mut escrow, paused_at);
+        let key = DataKey::RecurringEscrow(id);
+        env.storage().persistent().set(&key, &escrow);
+        Self::extend_persistent(&env, &key);
 
-        // Require auth from the proposing party
-        caller.require_auth();
+        Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::Resumed, 0);
+    }
 
-        // `refund_amount` is interpreted as gross; validation includes any
-        // configured refund-side fee to ensure transfers remain solvent.
-        if !Self::is_valid_partial_refund_gross_amount(&env, &escrow, refund_amount) {
-            return Err(Error::InvalidRefundAmount);
-        }
+    /// Cancel a recurring escrow whose pause outlasted the maximum pause
+    /// duration. Anyone may call this. The artisan is paid for the part of
+    /// the current cycle that ran before the pause; the rest is refunded to
+    /// the buyer.
+    pub fn expire_paused_recurring_escrow(env: Env, id: u64) {
+        let _guard = ReentryGuardScope::new(&env);
+        let escrow = Self::get_active_recurring_escrow(&env, id);
 
-        let proposal_key = DataKey::PartialRefundProposal(order_id);
-        if env.storage().persistent().has(&proposal_key) {
-            return Err(Error::ProposalAlreadyExists);
+        let paused_at = Self::get_recurring_paused_at(env.clone(), id)
+            .unwrap_or_else(|| env.panic_with_error(crate::Error::InvalidEscrowState));
+        if !Self::is_recurring_pause_expired(&env, paused_at) {
+            env.panic_with_error(crate::Error::CycleNotReady);
         }
 
-        let proposal = PartialRefundProposal {
-            order_id,
-            refund_amount,
-            proposed_by: caller,
-            proposed_at: env.ledger().timestamp(),
+        let schedule = Self::get_recurring_schedule(env.clone(), id);
+        let (start, due_at) = Self::recurring_cycle_window(&env, &escrow, &schedule);
+        let cycle_amount = Self::recurring_cycle_amount(&escrow, &schedule);
+        let period = due_at.saturating_sub(start);
+        let artisan_amount = if period == 0 || paused_at >= due_at {
+            cycle_amount
+        } else {
+            cycle_amount * paused_at.saturating_sub(start) as i128 / period as i128
         };
-
-        env.storage().persistent().set(&proposal_key, &proposal);
-        Self::extend_persistent(&env, &proposal_key);
-
-        Ok(())
+        Self::cancel_recurring_internal(&env, id, escrow, artisan_amount);
     }
 
-    // â”€â”€ Storage Explorer â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€â”€
+    fn is_recurring_pause_expired(env: &Env, paused_at: u64) -> bool {
+        let max_pause = Self::get_max_recurring_pause_duration(env.clone());
+        max_pause > 0 && env.ledger().timestamp() >= paused_at.saturating_add(max_pause)
+    }
 
-    /// Returns the total number of escrows ever created on this platform.
-    ///
-    /// This is an O(1) read â€” safe to call at any scale. Pair with
-    /// `get_all_escrow_ids_iterative` to paginate the full ID set without
-    /// hitting Soroban CPU/memory resource limits.
-    pub fn get_escrow_count(env: Env) -> u32 {
-        Self::migrate_legacy_all_escrow_ids(&env);
-        Self::get_persistent_u32(&env, &DataKey::EscrowCount)
+    /// Get the timestamp a recurring escrow was paused at, if it is paused.
+    pub fn get_recurring_paused_at(env: Env, id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&RecurringDataKey::PausedAt(id))
     }
 
-    /// Returns a page of all escrow order IDs created on the platform, in creation order.
-    ///
-    /// This is the recommended pattern for frontends to enumerate every escrow without
-    /// hitting Soroban resource limits. The function reads a bounded slice of the
-    /// indexed `GlobalEscrowIdIndexed` registry; no on-chain loops proportional to
-    /// the total escrow count are performed at call time.
-    ///
-    /// # Usage pattern (frontend / off-chain)
-    /// ```text
-    /// total  = get_escrow_count()
-    /// pages  = ceil(total / PAGE_SIZE)
-    /// for p in 0..pages:
-    ///     ids = get_all_escrow_ids_iterative(p, PAGE_SIZE)
-    ///     for id in ids:
-    ///         escrow = get_escrow(id)
-    /// ```
-    ///
-    /// # Soroban RPC key browsing
-    /// To enumerate storage keys directly via the RPC without calling this function,
-    /// use the `getLedgerEntries` method or the experimental `getContractData` cursor
-    /// endpoint.  Relevant key patterns:
-    /// - `DataKey::GlobalEscrowIdIndexed(index)` â€“ indexed global escrow ID (#515)
-    /// - `DataKey::EscrowCount`            â€“ u32 total count
-    /// - `DataKey::AllEscrowIds`           â€“ DEPRECATED legacy Vec index
-    /// - `(ESCROW, order_id: u32)`         â€“ individual escrow struct
-    /// - `DataKey::BuyerEscrows(address)`  â€“ DEPRECATED: Legacy Vec<u64> of IDs for a buyer
-    /// - `DataKey::SellerEscrows(address)` â€“ DEPRECATED: Legacy Vec<u64> of IDs for a seller
-    /// - `DataKey::BuyerEscrowIndexed(address, index)` â€“ Indexed storage: u64 escrow ID at position
-    /// - `DataKey::BuyerEscrowCount(address)` â€“ u32 total count of buyer's escrows
-    /// - `DataKey::SellerEscrowIndexed(address, index)` â€“ Indexed storage: u64 escrow ID at position
-    /// - `DataKey::SellerEscrowCount(address)` â€“ u32 total count of seller's escrows
-    ///
-    /// # Arguments
-    /// * `page`  â€“ Zero-indexed page number
-    /// * `limit` â€“ Page size; values above `MAX_BATCH_SIZE` are silently capped
-    ///
-    /// # Returns
-    /// A `Vec<u32>` of escrow IDs for the requested page; empty when `page` is out of range.
-    pub fn get_all_escrow_ids_iterative(env: Env, page: u32, limit: u32) -> soroban_sdk::Vec<u32> {
-        let limit = limit.min(MAX_BATCH_SIZE);
-        if limit == 0 {
-            return soroban_sdk::Vec::new(&env);
+    /// Set the maximum pause duration for recurring escrows in seconds
+    /// (`Role::Operator`). 0 disables the limit.
+    pub fn set_max_recurring_pause_duration(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
+
+        if seconds > u32::MAX as u64 {
+            return Err(Error::ReleaseWindowTooLong);
         }
+        let old_value = Self::get_max_recurring_pause_duration(env.clone());
+        env.storage()
+            .instance()
+            .set(&RecurringDataKey::MaxPauseDuration, &seconds);
+        Self::emit_config_updated(
+            &env,
+            "max_recurring_pause_duration",
+            ConfigValue::U32(old_value as u32),
+            ConfigValue::U32(seconds as u32),
+        );
+        Ok(())
+    }
 
-        Self::migrate_legacy_all_escrow_ids(&env);
+    /// Get the maximum pause duration for recurring escrows; 0 is unlimited.
+    pub fn get_max_recurring_pause_duration(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&RecurringDataKey::MaxPauseDuration)
+            .unwrap_or(0)
+    }
 
-        let total = Self::get_persistent_u32(&env, &DataKey::EscrowCount);
-        let start = page * limit;
+    /// Shift the cycle clock of a paused recurring escrow by the paused time
+    /// and clear the pause.
+    fn resume_recurring_schedule(env: &Env, escrow: &mut RecurringEscrow, paused_at: u64) {
+        let now = env.ledger().timestamp();
+        let paused_for = now.saturating_sub(paused_at);
+        escrow.last_release_time = escrow.last_release_time.saturating_add(paused_for);
+        if escrow.frequency == 0 {
+            let shift_key = RecurringDataKey::ScheduleShift(escrow.id);
+            let shift: u64 = env.storage().persistent().get(&shift_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&shift_key, &shift.saturating_add(paused_for));
+            Self::extend_persistent(env, &shift_key);
+        }
+        env.storage()
+            .persistent()
+            .remove(&RecurringDataKey::PausedAt(escrow.id));
+    }
 
-        if start >= total {
-            return soroban_sdk::Vec::new(&env);
+    /// Dispute the current cycle of a recurring escrow (buyer or artisan).
+    ///
+    /// The cycle amount is frozen in the contract and the cycle is skipped
+    /// by `release_next_cycle`. Later cycles continue or the escrow pauses
+    /// according to `get_recurring_dispute_policy`. Only one cycle can be
+    /// disputed at a time, and the escrow cannot be cancelled meanwhile.
+    ///
+    /// A cycle can be disputed once its window has started, so later cycles
+    /// cannot be frozen in advance. An unresolved dispute can be closed with
+    /// `resolve_expired_cycle_dispute` after `max_dispute_duration`.
+    pub fn dispute_recurring_cycle(
+        env: Env,
+        id: u64,
+        reason: Symbol,
+        authorized_address: Address,
+    ) {
+        let _guard = ReentryGuardScope::new(&env);
+        let mut escrow = Self::get_active_recurring_escrow(&env, id);
+        authorized_address.require_auth();
+        if authorized_address != escrow.buyer && authorized_address != escrow.artisan {
+            env.panic_with_error(crate::Error::Unauthorized);
         }
 
-        let end = (start + limit).min(total);
-        let mut result = soroban_sdk::Vec::new(&env);
+        let dispute_key = RecurringDataKey::CycleDispute(id);
+        if escrow.current_cycle >= escrow.duration as u64
+            || env.storage().persistent().has(&dispute_key)
+        {
+            env.panic_with_error(crate::Error::InvalidEscrowState);
+        }
 
-        for index in start..end {
-            let index_key = DataKey::GlobalEscrowIdIndexed(index);
-            if let Some(id) = env.storage().persistent().get(&index_key) {
-                result.push_back(id);
-                Self::extend_persistent(&env, &index_key);
-            }
+        let now = env.ledger().timestamp();
+        let schedule = Self::get_recurring_schedule(env.clone(), id);
+        let (window_start, _) = Self::recurring_cycle_window(&env, &escrow, &schedule);
+        if now < window_start {
+            env.panic_with_error(crate::Error::CycleNotReady);
+        }
+        let amount = Self::recurring_cycle_amount(&escrow, &schedule);
+        let paused_key = RecurringDataKey::PausedAt(id);
+        let paused_schedule = Self::get_recurring_dispute_policy(env.clone())
+            == RecurringDisputePolicy::Pause
+            && !env.storage().persistent().has(&paused_key);
+        let dispute = RecurringCycleDispute {
+            cycle: escrow.current_cycle,
+            amount,
+            reason,
+            opened_by: authorized_address,
+            opened_at: now,
+            paused_schedule,
+        };
+
+        // The frozen amount leaves the schedule: it counts as released and
+        // the cycle's slot is consumed, so later cycles keep their due times.
+        escrow.released_amount += amount;
+        escrow.current_cycle += 1;
+        escrow.last_release_time = escrow.last_release_time.saturating_add(escrow.frequency);
+        let key = DataKey::RecurringEscrow(id);
+        env.storage().persistent().set(&key, &escrow);
+        Self::extend_persistent(&env, &key);
+        env.storage().persistent().set(&dispute_key, &dispute);
+        Self::extend_persistent(&env, &dispute_key);
+        if paused_schedule {
+            env.storage().persistent().set(&paused_key, &now);
+            Self::extend_persistent(&env, &paused_key);
         }
+        Self::record_epoch_stats(&env, &escrow.token, |stats| stats.disputes += 1);
 
-        result
+        Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::CycleDisputed, amount);
+        if paused_schedule {
+            Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::Paused, 0);
+        }
     }
 
-    /// Accept the outstanding partial refund proposal for a disputed escrow.
+    /// Resolve a disputed recurring cycle (admin, moderator or arbitrator).
     ///
-    /// The counterparty (the party that did NOT submit the proposal) calls this function.
-    /// Funds are distributed from a gross refund model: buyer receives
-    /// `refund_amount - refund_fee`, seller receives the remainder minus seller-side
-    /// platform fee. The escrow status is set to Resolved.
-    pub fn accept_partial_refund(env: Env, order_id: u32) -> Result<(), Error> {
+    /// `ReleaseToSeller` pays the frozen amount to the artisan under the
+    /// normal fee policy; `RefundToBuyer` returns it to the buyer without a
+    /// fee, matching `resolve_dispute`. A schedule paused by the dispute
+    /// resumes without accumulating missed cycles.
+    pub fn resolve_recurring_dispute(
+        env: Env,
+        id: u64,
+        resolution: Resolution,
+        authorized_address: Address,
+    ) {
         let _guard = ReentryGuardScope::new(&env);
-        let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
-        if escrow_opt.is_none() {
-            return Err(Error::EscrowNotFound);
-        }
-        let mut escrow: Escrow = escrow_opt.unwrap();
+        let config = Self::get_platform_config_internal(&env);
+        Self::require_dispute_resolver(&env, &config, &authorized_address);
 
-        if escrow.status != EscrowStatus::Disputed {
-            return Err(Error::InvalidEscrowState);
-        }
+        let (mut escrow, dispute) = Self::get_recurring_dispute_internal(&env, id)
+            .unwrap_or_else(|e| env.panic_with_error(e));
+        let resumed = Self::close_recurring_dispute(&env, &mut escrow, &dispute);
 
-        let proposal_key = DataKey::PartialRefundProposal(order_id);
-        let proposal_opt: Option<PartialRefundProposal> =
-            env.storage().persistent().get(&proposal_key);
-        if proposal_opt.is_none() {
-            return Err(Error::ProposalNotFound);
+        match resolution {
+            Resolution::ReleaseToSeller => {
+                let fee_rate = Self::recurring_fee_rate(&env, &escrow);
+                let allocation = Self::compute_fee_allocation(
+                    &env,
+                    dispute.amount,
+                    &fee_rate,
+                    SettlementKind::ReleaseFunds,
+                );
+                if allocation.platform_fee > 0 {
+                    Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, &allocation, None, None);
+                }
+                Self::record_epoch_stats(&env, &escrow.token, |stats| {
+                    stats.released_volume += allocation.seller_amount
+                });
+                Self::payout_tokens_and_record_audit(&env, &escrow.token, &escrow.artisan, allocation.seller_amount, Symbol::new(&env, "recurring_release"));
+            }
+            Resolution::RefundToBuyer => {
+                Self::record_epoch_stats(&env, &escrow.token, |stats| {
+                    stats.refunded_volume += dispute.amount
+                });
+                Self::payout_tokens_and_record_audit(&env, &escrow.token, &escrow.buyer, dispute.amount, Symbol::new(&env, "recurring_dispute_refund"));
+            }
         }
-        let proposal: PartialRefundProposal = proposal_opt.unwrap();
 
-        // The counterparty is whoever did NOT propose
-        if proposal.proposed_by == escrow.buyer {
-            escrow.seller.require_auth();
-        } else {
-            escrow.buyer.require_auth();
+        Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::CycleResolved, dispute.amount);
+        if resumed {
+            Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::Resumed, 0);
         }
+    }
 
-        let refund_amount_gross = proposal.refund_amount;
-        let seller_gross = escrow.amount - refund_amount_gross;
+    /// Resolve a recurring cycle dispute that has exceeded the maximum
+    /// dispute duration (callable by anyone).
+    ///
+    /// The frozen cycle amount is refunded to the buyer under the configured
+    /// `expired_dispute_fee_policy`, as `resolve_expired_dispute` does for
+    /// escrows. Returns DisputeExpired if the deadline has not yet passed.
+    pub fn resolve_expired_cycle_dispute(env: Env, id: u64) -> Result<(), Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        let (mut escrow, dispute) = Self::get_recurring_dispute_internal(&env, id)?;
 
-        // Deterministic fee allocation via the central FeePolicy engine.
         let config = Self::get_platform_config_internal(&env);
-        let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.seller.clone());
+        let now = env.ledger().timestamp();
+        if dispute.opened_at + config.max_dispute_duration as u64 > now {
+            return Err(Error::DisputeExpired);
+        }
+
+        let resumed = Self::close_recurring_dispute(&env, &mut escrow, &dispute);
+
+        let fee_rate = Self::recurring_fee_rate(&env, &escrow);
         let allocation = Self::compute_fee_allocation(
             &env,
-            escrow.amount,
-            fee_bps,
-            SettlementKind::PartialRefund(
-                refund_amount_gross,
-                seller_gross,
-            ),
+            dispute.amount,
+            &fee_rate,
+            Self::expired_dispute_settlement_kind(&config),
         );
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, &allocation, None, None);
+        }
+        Self::record_epoch_stats(&env, &escrow.token, |stats| {
+            stats.refunded_volume += allocation.buyer_amount
+        });
+        Self::payout_tokens_and_record_audit(&env, &escrow.token, &escrow.buyer, allocation.buyer_amount, Symbol::new(&env, "recurring_expired_dispute_refund"));
 
-        // CEI Pattern: EFFECTS - Update state BEFORE external calls
-        escrow.status = EscrowStatus::Resolved;
-        env.storage().persistent().set(&(ESCROW, order_id), &escrow);
+        Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::CycleResolved, dispute.amount);
+        if resumed {
+            Self::emit_recurring_event(&env, &escrow, RecurringEscrowAction::Resumed, 0);
+        }
+        Ok(())
+    }
 
-        // Clean up proposal
-        env.storage().persistent().remove(&proposal_key);
+    fn get_recurring_dispute_internal(
+        env: &Env,
+        id: u64,
+    ) -> Result<(RecurringEscrow, RecurringCycleDispute), Error> {
+        let escrow: RecurringEscrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecurringEscrow(id))
+            .ok_or(Error::RecurringEscrowNotFound)?;
+        let dispute: RecurringCycleDispute = env
+            .storage()
+            .persistent()
+            .get(&RecurringDataKey::CycleDispute(id))
+            .ok_or(Error::NotInDispute)?;
+        Ok((escrow, dispute))
+    }
 
-        // Decrement active counts
-        Self::update_active_obligations(&env, &escrow.buyer, -1);
-        Self::update_active_obligations(&env, &escrow.seller, -1);
+    /// Effects of closing a cycle dispute: clear it, resume a schedule it
+    /// paused, finish the escrow if no cycles remain and unlock the frozen
+    /// amount. Returns whether the schedule was resumed.
+    fn close_recurring_dispute(
+        env: &Env,
+        escrow: &mut RecurringEscrow,
+        dispute: &RecurringCycleDispute,
+    ) -> bool {
+        let id = escrow.id;
+        env.storage()
+            .persistent()
+            .remove(&RecurringDataKey::CycleDispute(id));
+        let resumed = dispute.paused_schedule
+            && Self::get_recurring_paused_at(env.clone(), id) == Some(dispute.opened_at);
+        if resumed {
+            Self::resume_recurring_schedule(env, escrow, dispute.opened_at);
+        }
+        let completed = escrow.current_cycle >= escrow.duration as u64;
+        if completed {
+            escrow.is_active = false;
+            env.storage()
+                .persistent()
+                .remove(&RecurringDataKey::PausedAt(id));
+            Self::update_active_obligations(env, &escrow.buyer, -1);
+            Self::update_active_obligations(env, &escrow.artisan, -1);
+        }
+        let key = DataKey::RecurringEscrow(id);
+        env.storage().persistent().set(&key, &*escrow);
+        Self::extend_persistent(env, &key);
+        Self::update_total_locked(env, &escrow.token, -dispute.amount);
+        if completed {
+            Self::safe_update_active_contracts(env, escrow.buyer.clone(), -1);
+            Self::safe_update_active_contracts(env, escrow.artisan.clone(), -1);
+        }
+        resumed
+    }
 
-        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-        Self::safe_update_active_contracts(&env, escrow.seller.clone(), -1);
-        Self::update_total_locked(&env, &escrow.token, -escrow.amount);
+    /// Get the open cycle dispute of a recurring escrow, if any.
+    pub fn get_recurring_cycle_dispute(env: Env, id: u64) -> Option<RecurringCycleDispute> {
+        env.storage()
+            .persistent()
+            .get(&RecurringDataKey::CycleDispute(id))
+    }
 
-        // CEI Pattern: INTERACTIONS - External calls AFTER state updates
+    /// Set what happens to later cycles while a cycle is disputed
+    /// (`Role::Operator`).
+    pub fn set_recurring_dispute_policy(
+        env: Env,
+        caller: Address,
+        policy: RecurringDisputePolicy,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, Role::Operator, &caller);
 
-        // Refund buyer and record audit
-        if allocation.buyer_amount > 0 {
-            Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.buyer, allocation.buyer_amount, &escrow.buyer, Symbol::new(&env, "partial_refund_buyer"), allocation.buyer_amount);
+        let old_value = Self::get_recurring_dispute_policy(env.clone());
+        env.storage()
+            .instance()
+            .set(&RecurringDataKey::DisputePolicy, &policy);
+        Self::emit_config_updated(
+            &env,
+            "recurring_dispute_policy",
+            ConfigValue::U32(old_value as u32),
+            ConfigValue::U32(policy as u32),
+        );
+        Ok(())
+    }
+
+    /// Get the recurring dispute policy; defaults to `Continue`.
+    pub fn get_recurring_dispute_policy(env: Env) -> RecurringDisputePolicy {
+        env.storage()
+            .instance()
+            .get(&RecurringDataKey::DisputePolicy)
+            .unwrap_or(RecurringDisputePolicy::Continue)
+    }
+
+    /// First recurring escrow id covered by the per-user indexes. Pinned to
+    /// the next id the first time it is needed.
+    fn recurring_index_start(env: &Env) -> u64 {
+        let key = RecurringDataKey::IndexStart;
+        if let Some(start) = env.storage().persistent().get::<_, u64>(&key) {
+            return start;
         }
+        let start: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::NextRecurringEscrowId)
+            .unwrap_or(1);
+        env.storage().persistent().set(&key, &start);
+        Self::extend_persistent(env, &key);
+        start
+    }
 
-        // Pay platform fee
-        if allocation.platform_fee > 0 {
-            Self::transfer_platform_fee(
-                &env,
-                &escrow.token,
-                &config.platform_wallet,
-                allocation.platform_fee,
-            );
+    /// Count key of a user's recurring escrow list.
+    fn recurring_count_key(user: &Address, is_buyer: bool, legacy: bool) -> RecurringDataKey {
+        match (is_buyer, legacy) {
+            (true, false) => RecurringDataKey::BuyerCount(user.clone()),
+            (true, true) => RecurringDataKey::BuyerLegacyCount(user.clone()),
+            (false, false) => RecurringDataKey::ArtisanCount(user.clone()),
+            (false, true) => RecurringDataKey::ArtisanLegacyCount(user.clone()),
         }
+    }
 
-        // Pay seller
-        if allocation.seller_amount > 0 {
-            Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.seller, allocation.seller_amount, &escrow.seller, Symbol::new(&env, "partial_refund_seller"), allocation.seller_amount);
+    /// Key of the recurring escrow id at `position` in a user's list.
+    fn recurring_index_key(
+        user: &Address,
+        is_buyer: bool,
+        legacy: bool,
+        position: u32,
+    ) -> RecurringDataKey {
+        match (is_buyer, legacy) {
+            (true, false) => RecurringDataKey::BuyerIndexed(user.clone(), position),
+            (true, true) => RecurringDataKey::BuyerLegacyIndexed(user.clone(), position),
+            (false, false) => RecurringDataKey::ArtisanIndexed(user.clone(), position),
+            (false, true) => RecurringDataKey::ArtisanLegacyIndexed(user.clone(), position),
         }
+    }
 
-        Self::emit_escrow_created(
-            &env,
-            EscrowEvent {
-                escrow_id: order_id as u64,
-                action: EscrowAction::Resolved,
-                buyer: escrow.buyer.clone(),
-                seller: escrow.seller.clone(),
-                amount: escrow.amount,
-                token: escrow.token.clone(),
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+    fn recurring_list_count(env: &Env, user: &Address, is_buyer: bool, legacy: bool) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&Self::recurring_count_key(user, is_buyer, legacy))
+            .unwrap_or(0)
+    }
 
-        Ok(())
+    /// Append a recurring escrow to its buyer's and artisan's lists.
+    /// Backfilled escrows go to the legacy lists: every one of them predates
+    /// the escrows indexed on creation, so reading the legacy list first
+    /// keeps a user's list oldest first whenever the backfill runs.
+    fn index_recurring_escrow(env: &Env, escrow: &RecurringEscrow, legacy: bool) {
+        for (user, is_buyer) in [(&escrow.buyer, true), (&escrow.artisan, false)] {
+            let count = Self::recurring_list_count(env, user, is_buyer, legacy);
+            let index_key = Self::recurring_index_key(user, is_buyer, legacy, count);
+            env.storage().persistent().set(&index_key, &escrow.id);
+            Self::extend_persistent(env, &index_key);
+            let count_key = Self::recurring_count_key(user, is_buyer, legacy);
+            env.storage().persistent().set(&count_key, &(count + 1));
+            Self::extend_persistent(env, &count_key);
+        }
     }
 
-    /// Cancel a partial refund proposal.
-    ///
-    /// Only the proposer can cancel their own proposal. This removes the proposal
-    /// from storage, allowing a new proposal to be submitted if needed.
-    ///
-    /// # Arguments
-    /// * `order_id` - Order identifier
-    pub fn cancel_partial_refund(env: Env, order_id: u32) -> Result<(), Error> {
-        let escrow_opt: Option<Escrow> = env.storage().persistent().get(&(ESCROW, order_id));
-        if escrow_opt.is_none() {
-            return Err(Error::EscrowNotFound);
+    fn get_recurring_escrows_page(
+        env: &Env,
+        user: &Address,
+        is_buyer: bool,
+        page: u32,
+        page_size: u32,
+        reverse: bool,
+    ) -> Vec<u64> {
+        let mut result = Vec::new(env);
+        let page_size = page_size.min(MAX_PAGE_SIZE);
+        if page_size == 0 {
+            return result;
         }
-        let escrow: Escrow = escrow_opt.unwrap();
 
-        if escrow.status != EscrowStatus::Disputed {
-            return Err(Error::InvalidEscrowState);
+        let legacy_count = Self::recurring_list_count(env, user, is_buyer, true);
+        let total_count = legacy_count + Self::recurring_list_count(env, user, is_buyer, false);
+        let start = page.saturating_mul(page_size);
+        if start >= total_count {
+            return result;
         }
+        let end = start.saturating_add(page_size).min(total_count);
 
-        let proposal_key = DataKey::PartialRefundProposal(order_id);
-        let proposal_opt: Option<PartialRefundProposal> =
-            env.storage().persistent().get(&proposal_key);
-        if proposal_opt.is_none() {
-            return Err(Error::ProposalNotFound);
+        for position in start..end {
+            let storage_index = if reverse {
+                total_count - 1 - position
+            } else {
+                position
+            };
+            let index_key = if storage_index < legacy_count {
+                Self::recurring_index_key(user, is_buyer, true, storage_index)
+            } else {
+                Self::recurring_index_key(user, is_buyer, false, storage_index - legacy_count)
+            };
+            if let Some(id) = env.storage().persistent().get::<_, u64>(&index_key) {
+                result.push_back(id);
+                Self::extend_persistent_read(env, &index_key);
+            }
         }
-        let proposal: PartialRefundProposal = proposal_opt.unwrap();
+        for legacy in [true, false] {
+            let count_key = Self::recurring_count_key(user, is_buyer, legacy);
+            if env.storage().persistent().has(&count_key) {
+                Self::extend_persistent_read(env, &count_key);
+            }
+        }
+        result
+    }
 
-        // Only the proposer can cancel
-        proposal.proposed_by.require_auth();
+    /// Get recurring escrow ids of a buyer with pagination, oldest first
+    /// unless `reverse` is set.
+    pub fn get_recurring_escrows_by_buyer(
+        env: Env,
+        buyer: Address,
+        page: u32,
+        page_size: u32,
+        reverse: bool,
+    ) -> Result<Vec<u64>, Error> {
+        buyer.require_auth();
+        Ok(Self::get_recurring_escrows_page(
+            &env, &buyer, true, page, page_size, reverse,
+        ))
+    }
+
+    /// Get recurring escrow ids of an artisan with pagination, oldest first
+    /// unless `reverse` is set.
+    pub fn get_recurring_escrows_by_artisan(
+        env: Env,
+        artisan: Address,
+        page: u32,
+        page_size: u32,
+        reverse: bool,
+    ) -> Result<Vec<u64>, Error> {
+        artisan.require_auth();
+        Ok(Self::get_recurring_escrows_page(
+            &env, &artisan, false, page, page_size, reverse,
+        ))
+    }
 
-        // Remove the proposal from storage
-        env.storage().persistent().remove(&proposal_key);
+    /// Get the number of recurring escrows in a buyer's list.
+    pub fn get_buyer_recurring_count(env: Env, buyer: Address) -> u32 {
+        Self::recurring_list_count(&env, &buyer, true, true)
+            + Self::recurring_list_count(&env, &buyer, true, false)
+    }
 
-        Ok(())
+    /// Get the number of recurring escrows in an artisan's list.
+    pub fn get_artisan_recurring_count(env: Env, artisan: Address) -> u32 {
+        Self::recurring_list_count(&env, &artisan, false, true)
+            + Self::recurring_list_count(&env, &artisan, false, false)
     }
 
-    /// Returns the currently configured refund-side fee basis points.
+    /// Backfill the buyer and artisan indexes for recurring escrows created
+    /// before they existed (`Role::Migrator`). Backfilled escrows are listed
+    /// before the ones indexed on creation, so lists stay oldest first even
+    /// when the backfill runs after new escrows were created.
     ///
-    /// Today this is intentionally fixed at 0 bps. A future governance feature
-    /// can replace this implementation with configurable storage without changing
-    /// partial-refund validation semantics.
-    fn get_refund_fee_bps(_env: &Env) -> u32 {
-        0
+    /// Resumable: each call indexes up to `limit` (capped at `MAX_PAGE_SIZE`)
+    /// ids after the previous call. Returns the number of escrows indexed;
+    /// 0 once the backfill is complete.
+    pub fn migrate_recurring_escrow_indexes(
+        env: Env,
+        caller: Address,
+        limit: u32,
+    ) -> Result<u32, Error> {
+        Self::require_role(&env, Role::Migrator, &caller);
+
+        let end = Self::recurring_index_start(&env);
+        let cursor_key = RecurringDataKey::BackfillCursor;
+        let mut cursor: u64 = env.storage().persistent().get(&cursor_key).unwrap_or(1);
+        let mut migrated = 0u32;
+        while cursor < end && migrated < limit.min(MAX_PAGE_SIZE) {
+            if let Some(escrow) = env
+                .storage()
+                .persistent()
+                .get::<_, RecurringEscrow>(&DataKey::RecurringEscrow(cursor))
+            {
+                Self::index_recurring_escrow(&env, &escrow, true);
+                migrated += 1;
+            }
+            cursor += 1;
+        }
+        env.storage().persistent().set(&cursor_key, &cursor);
+        Self::extend_persistent(&env, &cursor_key);
+        Ok(migrated)
     }
 
-    /// Calculate refund-side fee charged against a proposed gross partial refund.
-    fn calculate_partial_refund_fee(env: &Env, gross_refund_amount: i128) -> i128 {
-        let refund_fee_bps = Self::get_refund_fee_bps(env);
-        Self::calculate_fee(env, gross_refund_amount, refund_fee_bps)
+    /// Get details of a recurring escrow.
+    pub fn get_recurring_escrow(env: Env, id: u64) -> RecurringEscrow {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecurringEscrow(id))
+            .expect("")
     }
 
-    /// Validate gross partial refund amount against escrow solvency including any
-    /// potential refund-side fee that may apply.
-    #[inline(always)]
-    fn is_valid_partial_refund_gross_amount(
-        env: &Env,
-        escrow: &Escrow,
-        gross_refund: i128,
-    ) -> bool {
-        if gross_refund <= 0 || gross_refund > escrow.amount {
-            return false;
-        }
-        let potential_refund_fee = Self::calculate_partial_refund_fee(env, gross_refund);
-        gross_refund.saturating_add(potential_refund_fee) <= escrow.amount
-    }
+    // ── Payment Streams ─────────────────────────────────────────────────
 
-    /// Create a new recurring escrow for recurring payments/subscriptions.
-    pub fn create_recurring_escrow(
+    /// Create a stream that vests `total_amount` linearly between
+    /// `start_time` and `end_time`. The buyer funds it in full up front.
+    pub fn create_stream(
         env: Env,
         buyer: Address,
         artisan: Address,
         token: Address,
         total_amount: i128,
-        frequency: u64,
-        duration: u32,
-    ) -> Result<RecurringEscrow, Error> {
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<PaymentStream, Error> {
         let _guard = ReentryGuardScope::new(&env);
         Self::check_not_paused(&env);
         buyer.require_auth();
 
-        if duration == 0 || frequency == 0 || total_amount <= 0 {
-            env.panic_with_error(crate::Error::AmountBelowMinimum);
+        if total_amount <= 0 {
+            return Err(Error::AmountBelowMinimum);
         }
         if buyer == artisan {
-            env.panic_with_error(crate::Error::SameBuyerSeller);
+            return Err(Error::SameBuyerSeller);
+        }
+        if end_time <= start_time || start_time < env.ledger().timestamp() {
+            return Err(Error::InvalidEscrowState);
         }
-
-        // Validate token whitelist
         Self::check_token_whitelisted(&env, &token);
 
-        // Issue #233: bounded, overflow-safe allocation. Reject once the
-        // counter reaches the cap instead of wrapping into an existing ID.
         let id: u64 = env
             .storage()
             .persistent()
-            .get(&DataKey::NextRecurringEscrowId)
+            .get(&StreamDataKey::NextId)
             .unwrap_or(1);
-        if id > MAX_RECURRING_ESCROW_ID {
-            return Err(crate::Error::RecurringEscrowIdExhausted);
-        }
-        let next_id = id
-            .checked_add(1)
-            .ok_or(crate::Error::RecurringEscrowIdExhausted)?;
         env.storage()
             .persistent()
-            .set(&DataKey::NextRecurringEscrowId, &next_id);
-        Self::extend_persistent(&env, &DataKey::NextRecurringEscrowId);
+            .set(&StreamDataKey::NextId, &(id + 1));
+        Self::extend_persistent(&env, &StreamDataKey::NextId);
 
-        let now = env.ledger().timestamp();
-
-        let escrow = RecurringEscrow {
+        let stream = PaymentStream {
             id,
             buyer: buyer.clone(),
             artisan: artisan.clone(),
             token: token.clone(),
             total_amount,
-            released_amount: 0,
-            frequency,
-            duration,
-            current_cycle: 0,
-            last_release_time: now,
+            withdrawn_amount: 0,
+            start_time,
+            end_time,
             is_active: true,
+            fee_rate: Self::effective_fee_rate(&env, &artisan, &token).snapshot(),
         };
+        Self::store_stream(&env, &stream);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::RecurringEscrow(id), &escrow);
-        Self::extend_persistent(&env, &DataKey::RecurringEscrow(id));
-
-        // Track active recurring escrows
         Self::update_active_obligations(&env, &buyer, 1);
         Self::update_active_obligations(&env, &artisan, 1);
-
-        Self::safe_update_active_contracts(&env, buyer.clone(), 1);
-        Self::safe_update_active_contracts(&env, artisan.clone(), 1);
-
         Self::update_total_locked(&env, &token, total_amount);
-        Self::transfer_tokens_and_record_audit(&env, &token, &buyer, &env.current_contract_address(), total_amount, &buyer, Symbol::new(&env, "recurring_escrow_locked"), -total_amount);
-
-        env.events().publish(
-            (Symbol::new(&env, "recurring_escrow"), id),
-            RecurringEscrowEvent {
-                id,
-                action: RecurringEscrowAction::Created,
-                buyer,
-                artisan,
-                amount: total_amount,
-                timestamp: now,
-            },
-        );
+        Self::transfer_tokens_and_record_audit(&env, &token, &buyer, &env.current_contract_address(), total_amount, &buyer, Symbol::new(&env, "stream_locked"), -total_amount);
 
-        Ok(escrow)
+        Self::emit_stream_event(&env, &stream, StreamAction::Created, total_amount);
+        Ok(stream)
     }
 
-    /// Release funds for the next cycle in a recurring escrow.
-    pub fn release_next_cycle(env: Env, id: u64) {
+    /// Withdraw everything vested so far to the artisan (artisan only). The
+    /// platform fee is charged on each withdrawal, pro rata under a fee
+    /// schedule. Returns the gross amount.
+    pub fn withdraw_from_stream(env: Env, id: u64) -> Result<i128, Error> {
         let _guard = ReentryGuardScope::new(&env);
-        let key = DataKey::RecurringEscrow(id);
-        let mut escrow: RecurringEscrow = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .unwrap_or_else(|| env.panic_with_error(crate::Error::RecurringEscrowNotFound));
+        let mut stream = Self::get_active_stream(&env, id)?;
+        stream.artisan.require_auth();
 
-        if !escrow.is_active {
-            env.panic_with_error(crate::Error::InvalidEscrowState);
-        }
-        if escrow.current_cycle >= escrow.duration as u64 {
-            env.panic_with_error(crate::Error::CycleNotReady);
+        let amount = Self::stream_vested_amount(&env, &stream) - stream.withdrawn_amount;
+        if amount <= 0 {
+            return Ok(0);
         }
 
-        let now = env.ledger().timestamp();
-        if now < escrow.last_release_time + escrow.frequency {
-            env.panic_with_error(crate::Error::CycleNotReady);
+        stream.withdrawn_amount += amount;
+        let completed = stream.withdrawn_amount == stream.total_amount;
+        if completed {
+            stream.is_active = false;
+            Self::update_active_obligations(&env, &stream.buyer, -1);
+            Self::update_active_obligations(&env, &stream.artisan, -1);
         }
+        Self::store_stream(&env, &stream);
+        Self::pay_stream_artisan(&env, &stream, amount);
 
-        let cycle_amount = if escrow.current_cycle == (escrow.duration as u64) - 1 {
-            // Last cycle: handle remainder
-            escrow.total_amount - escrow.released_amount
+        let action = if completed {
+            StreamAction::Completed
         } else {
-            escrow.total_amount / (escrow.duration as i128)
+            StreamAction::Withdrawn
         };
+        Self::emit_stream_event(&env, &stream, action, amount);
+        Ok(amount)
+    }
 
-        // Calculate distribution amounts using the deterministic fee engine.
-        let config = Self::get_platform_config_internal(&env);
-        let fee_bps = Self::get_effective_fee_bps(env.clone(), escrow.artisan.clone());
-        let allocation = Self::compute_fee_allocation(
-            &env,
-            cycle_amount,
-            fee_bps,
-            SettlementKind::ReleaseFunds,
-        );
-
-        // Effects: commit all cycle and reserve accounting first.
-        Self::update_total_locked(&env, &escrow.token, -cycle_amount);
-        escrow.released_amount += cycle_amount;
-        escrow.current_cycle += 1;
-        escrow.last_release_time = now;
-
-        let became_inactive = escrow.current_cycle == escrow.duration as u64;
-        if became_inactive {
-            escrow.is_active = false;
-            // Decrement active recurring counts
-            Self::update_active_obligations(&env, &escrow.buyer, -1);
-            Self::update_active_obligations(&env, &escrow.artisan, -1);
-        }
+    /// Cancel a stream (buyer only). Vested funds not yet withdrawn are paid
+    /// to the artisan and the unvested rest is refunded to the buyer.
+    /// Returns the refund.
+    pub fn cancel_stream(env: Env, id: u64) -> Result<i128, Error> {
+        let _guard = ReentryGuardScope::new(&env);
+        let mut stream = Self::get_active_stream(&env, id)?;
+        stream.buyer.require_auth();
 
-        env.storage().persistent().set(&key, &escrow);
-        Self::extend_persistent(&env, &key);
+        let vested = Self::stream_vested_amount(&env, &stream);
+        let artisan_amount = vested - stream.withdrawn_amount;
+        let refund = stream.total_amount - vested;
 
-        if became_inactive {
-            Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-            Self::safe_update_active_contracts(&env, escrow.artisan.clone(), -1);
-        }
+        stream.withdrawn_amount = vested;
+        stream.is_active = false;
+        Self::store_stream(&env, &stream);
+        Self::update_active_obligations(&env, &stream.buyer, -1);
+        Self::update_active_obligations(&env, &stream.artisan, -1);
 
-        // Interactions: token callbacks can only observe the completed cycle.
-        if allocation.platform_fee > 0 {
-            Self::transfer_platform_fee(&env, &escrow.token, &config.platform_wallet, allocation.platform_fee);
+        Self::pay_stream_artisan(&env, &stream, artisan_amount);
+        if refund > 0 {
+            Self::update_total_locked(&env, &stream.token, -refund);
+            Self::record_epoch_stats(&env, &stream.token, |stats| stats.refunded_volume += refund);
+            Self::payout_tokens_and_record_audit(&env, &stream.token, &stream.buyer, refund, Symbol::new(&env, "stream_cancel_refund"));
         }
-        Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.artisan, allocation.seller_amount, &escrow.artisan, Symbol::new(&env, "recurring_release"), allocation.seller_amount);
-
-        env.events().publish(
-            (Symbol::new(&env, "recurring_escrow"), id),
-            RecurringEscrowEvent {
-                id,
-                action: RecurringEscrowAction::CycleReleased,
-                buyer: escrow.buyer.clone(),
-                artisan: escrow.artisan.clone(),
-                amount: cycle_amount,
-                timestamp: now,
-            },
-        );
 
-        // Emit reputation update events â€” decoupled from onboarding contract (#211)
-        let ts = env.ledger().timestamp();
-        Self::emit_reputation_update(
-            &env,
-            ReputationUpdateEvent {
-                address: escrow.artisan.clone(),
-                successful_delta: if !escrow.is_active { 1 } else { 0 },
-                disputed_delta: 0,
-                metrics_sales_delta: 1,
-                metrics_amount: cycle_amount,
-                token: escrow.token.clone(),
-                timestamp: ts,
-            },
-        );
-        if !escrow.is_active {
-            Self::emit_reputation_update(
-                &env,
-                ReputationUpdateEvent {
-                    address: escrow.buyer.clone(),
-                    successful_delta: 1,
-                    disputed_delta: 0,
-                    metrics_sales_delta: 0,
-                    metrics_amount: 0,
-                    token: escrow.token.clone(),
-                    timestamp: ts,
-                },
-            );
-        }
+        Self::emit_stream_event(&env, &stream, StreamAction::Cancelled, refund);
+        Ok(refund)
     }
 
-    /// Cancel a recurring escrow and refund remaining funds to the buyer.
-    pub fn cancel_recurring_escrow(env: Env, id: u64) {
-        let _guard = ReentryGuardScope::new(&env);
-        let key = DataKey::RecurringEscrow(id);
-        let mut escrow: RecurringEscrow = env
-            .storage()
+    /// Get a payment stream.
+    pub fn get_stream(env: Env, id: u64) -> Result<PaymentStream, Error> {
+        env.storage()
             .persistent()
-            .get(&key)
-            .unwrap_or_else(|| env.panic_with_error(crate::Error::RecurringEscrowNotFound));
+            .get(&StreamDataKey::Stream(id))
+            .ok_or(Error::EscrowNotFound)
+    }
 
-        escrow.buyer.require_auth();
-        if !escrow.is_active {
-            env.panic_with_error(crate::Error::InvalidEscrowState);
+    /// Get the amount the artisan could withdraw from a stream right now.
+    pub fn get_stream_withdrawable(env: Env, id: u64) -> Result<i128, Error> {
+        let stream = Self::get_stream(env.clone(), id)?;
+        if !stream.is_active {
+            return Ok(0);
         }
+        Ok(Self::stream_vested_amount(&env, &stream) - stream.withdrawn_amount)
+    }
 
-        let remaining = escrow.total_amount - escrow.released_amount;
-
-        // CEI Pattern: EFFECTS - Update state BEFORE external calls
-        escrow.is_active = false;
-        env.storage().persistent().set(&key, &escrow);
-        Self::extend_persistent(&env, &key);
+    fn get_active_stream(env: &Env, id: u64) -> Result<PaymentStream, Error> {
+        let stream = Self::get_stream(env.clone(), id)?;
+        if !stream.is_active {
+            return Err(Error::InvalidEscrowState);
+        }
+        Ok(stream)
+    }
 
-        // Decrement active recurring counts
-        Self::update_active_obligations(&env, &escrow.buyer, -1);
-        Self::update_active_obligations(&env, &escrow.artisan, -1);
+    fn store_stream(env: &Env, stream: &PaymentStream) {
+        let key = StreamDataKey::Stream(stream.id);
+        env.storage().persistent().set(&key, stream);
+        Self::extend_persistent(env, &key);
+    }
 
-        Self::safe_update_active_contracts(&env, escrow.buyer.clone(), -1);
-        Self::safe_update_active_contracts(&env, escrow.artisan.clone(), -1);
+    /// Gross amount vested by now, rounded down.
+    fn stream_vested_amount(env: &Env, stream: &PaymentStream) -> i128 {
+        let now = env.ledger().timestamp();
+        if now <= stream.start_time {
+            0
+        } else if now >= stream.end_time {
+            stream.total_amount
+        } else {
+            stream.total_amount * (now - stream.start_time) as i128
+                / (stream.end_time - stream.start_time) as i128
+        }
+    }
 
-        // CEI Pattern: INTERACTIONS - External calls AFTER state updates
-        if remaining > 0 {
-            Self::update_total_locked(&env, &escrow.token, -remaining);
-            Self::transfer_tokens_and_record_audit(&env, &escrow.token, &env.current_contract_address(), &escrow.buyer, remaining, &escrow.buyer, Symbol::new(&env, "recurring_cancel_refund"), remaining);
+    /// Release `amount` of a stream to the artisan, less the platform fee.
+    /// `stream.withdrawn_amount` already includes `amount`.
+    fn pay_stream_artisan(env: &Env, stream: &PaymentStream, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let config = Self::get_platform_config_internal(env);
+        let mut fee_rate = FeeRate::from_snapshot(&stream.fee_rate);
+        if fee_rate.schedule.is_some() {
+            // Charge the slice's share of the whole stream's scheduled fee,
+            // so the flat and minimum fee are paid once and the maximum holds
+            // across withdrawals. Differencing the cumulative share keeps the
+            // rounding from drifting.
+            let total_fee = Self::scheduled_fee(env, stream.total_amount, &fee_rate);
+            let withdrawn = stream.withdrawn_amount;
+            let slice_fee = total_fee * withdrawn / stream.total_amount
+                - total_fee * (withdrawn - amount) / stream.total_amount;
+            fee_rate = Self::fixed_fee_rate(slice_fee);
+        }
+        let allocation =
+            Self::compute_fee_allocation(env, amount, &fee_rate, SettlementKind::ReleaseFunds);
+        Self::update_total_locked(env, &stream.token, -amount);
+        if allocation.platform_fee > 0 {
+            Self::transfer_platform_fee(env, &stream.token, &config.platform_wallet, &allocation, None, None);
         }
+        Self::record_epoch_stats(env, &stream.token, |stats| {
+            stats.released_volume += allocation.seller_amount
+        });
+        Self::payout_tokens_and_record_audit(env, &stream.token, &stream.artisan, allocation.seller_amount, Symbol::new(env, "stream_withdrawal"));
+    }
 
+    fn emit_stream_event(env: &Env, stream: &PaymentStream, action: StreamAction, amount: i128) {
         env.events().publish(
-            (Symbol::new(&env, "recurring_escrow"), id),
-            RecurringEscrowEvent {
-                id,
-                action: RecurringEscrowAction::Cancelled,
-                buyer: escrow.buyer.clone(),
-                artisan: escrow.artisan.clone(),
-                amount: remaining,
+            (Symbol::new(env, "payment_stream"), stream.id),
+            StreamEvent {
+                id: stream.id,
+                action,
+                buyer: stream.buyer.clone(),
+                artisan: stream.artisan.clone(),
+                amount,
                 timestamp: env.ledger().timestamp(),
             },
         );
     }
 
-    /// Get details of a recurring escrow.
-    pub fn get_recurring_escrow(env: Env, id: u64) -> RecurringEscrow {
-        env.storage()
-            .persistent()
-            .get(&DataKey::RecurringEscrow(id))
-            .expect("")
+    /// Sweep unallocated tokens from the contract (admin only).
+    ///
+    /// Kept for existing clients; role holders call `sweep_unallocated_funds_as`.
+    pub fn sweep_unallocated_funds(
+        env: Env,
+        token: Address,
+        destination: Address,
+    ) -> Result<i128, Error> {
+        let admin = Self::get_admin(&env)?;
+        Self::sweep_unallocated_funds_as(env, admin, token, destination)
     }
 
-    /// Recovery function to sweep unallocated tokens from the contract (admin only).
-    /// Unallocated funds = current_balance - (total_locked_in_escrows + total_staked_by_artisans).
-    pub fn sweep_unallocated_funds(
+    /// Recovery function to sweep unallocated tokens from the contract (`Role::Treasurer`).
+    /// Unallocated funds = current_balance - (total_locked_in_escrows + total_staked_by_artisans
+    /// + total_claimable + treasury_balance + promo_balance).
+    pub fn sweep_unallocated_funds_as(
         env: Env,
+        caller: Address,
         token: Address,
         destination: Address,
     ) -> Result<i128, Error> {
         let _guard = ReentryGuardScope::new(&env);
-        let admin = Self::get_admin(&env)?;
-        admin.require_auth();
+        Self::require_role(&env, Role::Treasurer, &caller);
 
         let token_client = token::Client::new(&env, &token);
         let balance = token_client.balance(&env.current_contract_address());
@@ -7535,7 +13030,11 @@ impl CraftNexusContract {
             .get(&DataKey::TotalStaked(token.clone()))
             .unwrap_or(0);
 
-        let unallocated = balance - (locked + staked);
+        let claimable = Self::get_total_claimable(env.clone(), token.clone());
+        let treasury = Self::get_treasury_balance(env.clone(), token.clone());
+        let promo = Self::get_promo_balance(env.clone(), token.clone());
+
+        let unallocated = balance - (locked + staked + claimable + treasury + promo);
 
         if unallocated > 0 {
             Self::transfer_tokens_and_record_audit(&env, &token, &env.current_contract_address(), &destination, unallocated, &destination, Symbol::new(&env, "sweep_unallocated"), unallocated);
@@ -317,7 +317,7 @@ fn test_multiple_escrows_with_different_windows() {
 
 #[test]
 fn test_min_window_persists_across_config_updates() {
-    let (_, client, _, _, _, _admin, _) = setup_test();
+    let (_, client, _, _, _, _, _) = setup_test();
 
     // Set min window to 2 days
     let two_days = 2 * ONE_DAY;
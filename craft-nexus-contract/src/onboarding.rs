@@ -28,11 +28,13 @@
 //! | [`OnboardingContract::get_user_metrics`] | [`UserMetrics`] | Escrow count / volume used for auto-verification. |
 //! | [`OnboardingContract::get_user_reputation`] | `(u32, u32)` | Lifetime `(successful_trades, disputed_trades)` counters. |
 //! | [`OnboardingContract::get_trust_score`] | `u32` | Decaying trust score (#939); lazy-applies decay on read. |
+//! | [`OnboardingContract::get_fee_tier_metrics`] | `(i128, u32)` | `(total_volume, trust_score)` for escrow-side fee tiers; escrow contract only. |
 //! | [`OnboardingContract::get_reputation_history`] | `Vec<ReputationHistoryEntry>` | Recent score-change log for abuse detection (#939). |
 //! | [`OnboardingContract::get_reputation_policy`] | [`ReputationPolicy`] | Decay / cooldown / anti-farming policy (#939). |
 //! | [`OnboardingContract::get_verification_history`] | `Vec<VerificationEntry>` | Compact entries decoded to human-readable actions. |
 //! | [`OnboardingContract::get_verification_queue`] | `Vec<Address>` | Pending manual-verification requests in FIFO order. |
 //! | [`OnboardingContract::get_config`] | [`OnboardingConfig`] | Global contract configuration. |
+//! | [`OnboardingContract::get_referrer`] | `Option<Address>` | Referrer recorded via `set_referrer`, if any. |
 //!
 //! ## Event stream
 //!
@@ -229,6 +231,8 @@ pub enum DataKey {
     ReputationHistoryCount(Address),
     /// Indexed compact reputation history entry (#939)
     ReputationHistoryIndexed(Address, u32),
+    /// Address that referred the user, kept outside the flat profile record.
+    Referrer(Address),
 }
 
 /// User roles in the CraftNexus platform.
@@ -792,6 +796,8 @@ pub enum Error {
     ContractPaused = 16,
     /// Reputation policy parameters are invalid (#939)
     InvalidReputationPolicy = 17,
+    /// A referrer is already recorded for this user, or the referrer is the user
+    ReferrerAlreadySet = 18,
 }
 
 /// Cross-contract interface the onboarding contract uses to query the escrow
@@ -3993,6 +3999,38 @@ impl OnboardingContract {
         state.trust_score
     }
 
+    /// Return `(total_volume, trust_score)` for `address`, used by the escrow
+    /// contract to pick volume-based fee tiers.
+    ///
+    /// Lazily applies reputation decay like [`get_trust_score`]. Unknown
+    /// addresses report a zero trust score.
+    ///
+    /// # Auth
+    /// Caller must be the registered escrow contract (or `platform_admin`
+    /// when none is registered), mirroring `update_user_metrics`.
+    pub fn get_fee_tier_metrics(env: Env, address: Address) -> (i128, u32) {
+        let config: OnboardingConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Config)
+            .unwrap_or_else(|| env.panic_with_error(Error::NotInitialized));
+        match config.escrow_contract {
+            Some(ref escrow_addr) => escrow_addr.require_auth(),
+            None => config.platform_admin.require_auth(),
+        }
+
+        let volume = Self::read_user_metrics(&env, &address).total_volume;
+        if Self::try_get_user_profile(&env, address.clone()).is_none() {
+            return (volume, 0);
+        }
+        let policy = Self::get_reputation_policy_internal(&env);
+        let mut state = Self::get_or_init_reputation_state(&env, &address);
+        if Self::apply_reputation_decay(&env, &mut state, &policy) {
+            Self::persist_reputation_state(&env, &address, &state);
+        }
+        (volume, state.trust_score)
+    }
+
     /// Get the full per-user reputation state (#939).
     ///
     /// Useful for dashboards that need window counters alongside the trust
@@ -4571,4 +4609,44 @@ impl OnboardingContract {
 
         profile
     }
+
+    /// Record the address that referred `user` to the platform.
+    ///
+    /// The escrow contract pays this referrer its configured share of the
+    /// platform fee when one of the user's escrows settles, unless the escrow
+    /// carries its own referrer. Both `user` and `referrer` must sign, so a
+    /// user cannot credit an address that did not agree to the referral. The
+    /// referrer can be set only once and is stored under
+    /// [`DataKey::Referrer`] so the main profile stays flat.
+    ///
+    /// # Reverts if
+    /// - User not onboarded (`Error::UserNotFound`) or deactivated
+    ///   (`Error::ProfileDeactivated`)
+    /// - A referrer is already recorded, or `referrer == user`
+    ///   (`Error::ReferrerAlreadySet`)
+    pub fn set_referrer(env: Env, user: Address, referrer: Address) {
+        user.require_auth();
+        Self::assert_user_onboarded_and_active(&env, user.clone());
+
+        let key = DataKey::Referrer(user.clone());
+        if referrer == user || env.storage().persistent().has(&key) {
+            env.panic_with_error(Error::ReferrerAlreadySet);
+        }
+        referrer.require_auth();
+        env.storage().persistent().set(&key, &referrer);
+        Self::extend_persistent(&env, &key);
+
+        env.events()
+            .publish((Symbol::new(&env, "ReferrerSet"), user), referrer);
+    }
+
+    /// Return the referrer recorded for `user`, or `None` if there is none.
+    pub fn get_referrer(env: Env, user: Address) -> Option<Address> {
+        let key = DataKey::Referrer(user);
+        let referrer = env.storage().persistent().get(&key);
+        if referrer.is_some() {
+            Self::extend_persistent(&env, &key);
+        }
+        referrer
+    }
 }
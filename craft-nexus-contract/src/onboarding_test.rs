@@ -2921,3 +2921,73 @@ fn test_profile_read_budget_smoke() {
     let _ = client.get_user_reputation(&user);
     let _ = client.get_user_metrics(&user);
 }
+
+// ===== Referrer Tests =====
+
+#[test]
+fn test_set_referrer_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    client.onboard_user(
+        &user,
+        &String::from_str(&env, "artisan_ref"),
+        &UserRole::Artisan,
+    );
+
+    assert_eq!(client.get_referrer(&user), None);
+    client.set_referrer(&user, &referrer);
+    assert_eq!(client.get_referrer(&user), Some(referrer));
+
+    let other = Address::generate(&env);
+    let result = client.try_set_referrer(&user, &other);
+    assert_eq!(
+        result,
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::ReferrerAlreadySet as u32
+        )))
+    );
+}
+
+#[test]
+fn test_set_referrer_records_referrer_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    client.onboard_user(
+        &user,
+        &String::from_str(&env, "artisan_auth"),
+        &UserRole::Artisan,
+    );
+
+    client.set_referrer(&user, &referrer);
+
+    let auths = env.auths();
+    assert!(
+        auths.iter().any(|(addr, _)| addr == &referrer),
+        "referrer auth must be recorded for set_referrer"
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_set_referrer_rejects_self_referral() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _) = setup_test(&env);
+    let user = Address::generate(&env);
+    client.onboard_user(
+        &user,
+        &String::from_str(&env, "artisan_self"),
+        &UserRole::Artisan,
+    );
+
+    client.set_referrer(&user, &user);
+}
@@ -0,0 +1,152 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, DataKey, Error, EscrowStatus};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env,
+};
+
+struct PartialReleaseTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+    platform_wallet: Address,
+}
+
+fn setup_test() -> PartialReleaseTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+    client.create_escrow(&buyer, &seller, &token, &10_000, &1, &Some(3600));
+
+    PartialReleaseTest {
+        env,
+        client,
+        admin,
+        buyer,
+        seller,
+        token,
+        platform_wallet,
+    }
+}
+
+fn total_locked(t: &PartialReleaseTest) -> i128 {
+    t.env.as_contract(&t.client.address, || {
+        t.env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalLocked(t.token.clone()))
+            .unwrap_or(0)
+    })
+}
+
+#[test]
+fn test_partial_release_pays_seller_and_keeps_escrow_active() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.release_partial_funds(&1, &4_000);
+
+    // 5% fee on the released 4_000 only.
+    assert_eq!(token_client.balance(&t.seller), 3_800);
+    assert_eq!(token_client.balance(&t.platform_wallet), 200);
+
+    let escrow = t.client.get_escrow(&1);
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.amount, 6_000);
+    assert_eq!(total_locked(&t), 6_000);
+    assert_eq!(token_client.balance(&t.client.address), 6_000);
+
+    // The remainder settles normally.
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.seller), 3_800 + 5_700);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(total_locked(&t), 0);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+}
+
+#[test]
+fn test_partial_releases_share_one_scheduled_fee() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client
+        .set_fee_schedule(&t.admin, &t.token, &100, &0, &600);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &Some(3600));
+
+    // 5% of 10_000 plus 100, capped at 600, split by share of the escrow.
+    t.client.release_partial_funds(&2, &2_500);
+    assert_eq!(token_client.balance(&t.platform_wallet), 150);
+    t.client.release_partial_funds(&2, &2_500);
+    assert_eq!(token_client.balance(&t.platform_wallet), 300);
+    t.client.release_funds(&2);
+    assert_eq!(token_client.balance(&t.platform_wallet), 600);
+    assert_eq!(token_client.balance(&t.seller), 10_000 - 600);
+}
+
+#[test]
+fn test_partial_release_then_refund_returns_remainder() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.release_partial_funds(&1, &2_500);
+    t.client.refund(&1);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Refunded);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000 - 2_500);
+    assert_eq!(total_locked(&t), 0);
+}
+
+#[test]
+fn test_partial_release_rejects_invalid_amounts() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client.try_release_partial_funds(&1, &0),
+        Err(Ok(Error::AmountBelowMinimum.into()))
+    );
+    assert_eq!(
+        t.client.try_release_partial_funds(&1, &10_000),
+        Err(Ok(Error::InvalidEscrowState.into()))
+    );
+
+    t.client.release_funds(&1);
+    assert_eq!(
+        t.client.try_release_partial_funds(&1, &1_000),
+        Err(Ok(Error::InvalidEscrowState.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_partial_release_blocked_while_paused() {
+    let t = setup_test();
+
+    t.client.set_paused(&true);
+    t.client.release_partial_funds(&1, &2_500);
+}
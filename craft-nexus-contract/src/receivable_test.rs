@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, Error, MAX_RECEIVABLE_ASSIGNMENTS};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, Symbol,
+};
+
+struct ReceivableTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    financier: Address,
+    token: Address,
+}
+
+fn setup_test() -> ReceivableTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let financier = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+
+    ReceivableTest {
+        env,
+        client,
+        buyer,
+        seller,
+        financier,
+        token,
+    }
+}
+
+#[test]
+fn test_assigned_receivable_is_paid_on_release() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    assert_eq!(t.client.get_receivable_holder(&1), t.seller);
+
+    t.client.assign_receivable(&1, &t.financier);
+    assert_eq!(t.client.get_receivable_holder(&1), t.financier);
+
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.financier), 9_500);
+    assert_eq!(token_client.balance(&t.seller), 0);
+
+    let audit = t.client.get_fund_audit_history(&t.financier);
+    assert_eq!(audit.len(), 1);
+    assert_eq!(
+        audit.get(0).unwrap().reason,
+        Symbol::new(&t.env, "receivable_paid")
+    );
+}
+
+#[test]
+fn test_assigned_receivable_is_paid_on_auto_release() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client.assign_receivable(&1, &t.financier);
+
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 3601;
+    });
+    t.client.auto_release(&1);
+    assert_eq!(token_client.balance(&t.financier), 9_500);
+    assert_eq!(token_client.balance(&t.seller), 0);
+}
+
+#[test]
+fn test_reassignment_and_bounded_history() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.assign_receivable(&1, &t.financier);
+
+    // Only the current holder may move the receivable on.
+    let next = Address::generate(&t.env);
+    t.client.assign_receivable(&1, &next);
+    let auths = t.env.auths();
+    assert!(auths.iter().any(|(address, _)| *address == t.financier));
+    assert!(!auths.iter().any(|(address, _)| *address == t.seller));
+
+    let mut holder = next;
+    for _ in 0..MAX_RECEIVABLE_ASSIGNMENTS {
+        let assignee = Address::generate(&t.env);
+        t.client.assign_receivable(&1, &assignee);
+        holder = assignee;
+    }
+    let history = t.client.get_receivable_history(&1);
+    assert_eq!(history.len(), MAX_RECEIVABLE_ASSIGNMENTS);
+    assert_eq!(history.last().unwrap().to, holder);
+
+    // Assigning back to the seller clears the assignment.
+    t.client.assign_receivable(&1, &t.seller);
+    assert_eq!(t.client.get_receivable_holder(&1), t.seller);
+}
+
+#[test]
+fn test_assign_receivable_validation() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    assert_eq!(
+        t.client.try_assign_receivable(&1, &t.buyer),
+        Err(Ok(Error::SameBuyerSeller))
+    );
+    assert_eq!(
+        t.client.try_assign_receivable(&2, &t.financier),
+        Err(Ok(Error::EscrowNotFound))
+    );
+
+    t.client.release_funds(&1);
+    assert_eq!(
+        t.client.try_assign_receivable(&1, &t.financier),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+}
+
+#[test]
+fn test_price_reduction_needs_assignee_consent() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.assign_receivable(&1, &t.financier);
+
+    t.client.amend_escrow_amount(&1, &6_000);
+    let auths = t.env.auths();
+    assert!(auths.iter().any(|(address, _)| *address == t.financier));
+    assert!(auths.iter().any(|(address, _)| *address == t.seller));
+
+    // A top-up does not touch the assignee's payout.
+    t.client.amend_escrow_amount(&1, &8_000);
+    let auths = t.env.auths();
+    assert!(!auths.iter().any(|(address, _)| *address == t.financier));
+}
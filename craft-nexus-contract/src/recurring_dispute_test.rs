@@ -0,0 +1,260 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, ExpiredDisputeFeePolicy,
+    RecurringDisputePolicy, Resolution,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, Symbol,
+};
+
+const START: u64 = 1711368000;
+const HOUR: u64 = 3_600;
+
+struct RecurringDisputeTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    artisan: Address,
+    platform_wallet: Address,
+    admin: Address,
+    token: Address,
+}
+
+fn setup_test() -> RecurringDisputeTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &3_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = START;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    RecurringDisputeTest {
+        env,
+        client,
+        buyer,
+        artisan,
+        platform_wallet,
+        admin,
+        token,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp = timestamp;
+    });
+}
+
+fn reason(env: &Env) -> Symbol {
+    Symbol::new(env, "not_delivered")
+}
+
+#[test]
+fn test_disputed_cycle_is_frozen_and_later_cycles_continue() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &3_000, &HOUR, &3)
+        .id;
+
+    set_time(&t.env, START + HOUR);
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer);
+    let dispute = t.client.get_recurring_cycle_dispute(&id).unwrap();
+    assert_eq!(dispute.cycle, 0);
+    assert_eq!(dispute.amount, 1_000);
+    assert!(!dispute.paused_schedule);
+    assert_eq!(
+        t.client.try_cancel_recurring_escrow(&id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::InvalidEscrowState as u32
+        )))
+    );
+
+    // The next cycle keeps its original due time.
+    assert!(t.client.try_release_next_cycle(&id).is_err());
+    set_time(&t.env, START + 2 * HOUR);
+    t.client.release_next_cycle(&id);
+    assert_eq!(token_client.balance(&t.artisan), 950);
+
+    t.client
+        .resolve_recurring_dispute(&id, &Resolution::RefundToBuyer, &t.admin);
+    assert_eq!(token_client.balance(&t.buyer), 1_000);
+    assert_eq!(t.client.get_recurring_cycle_dispute(&id), None);
+
+    set_time(&t.env, START + 3 * HOUR);
+    t.client.release_next_cycle(&id);
+    assert_eq!(token_client.balance(&t.artisan), 1_900);
+    assert_eq!(token_client.balance(&t.platform_wallet), 100);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+    assert!(!t.client.get_recurring_escrow(&id).is_active);
+    assert!(!t.client.has_active_escrows(&t.buyer));
+}
+
+#[test]
+fn test_pause_policy_holds_schedule_until_release_resolution() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client
+        .set_recurring_dispute_policy(&t.admin, &RecurringDisputePolicy::Pause);
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &2_000, &HOUR, &2)
+        .id;
+
+    set_time(&t.env, START + HOUR);
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.artisan);
+    assert_eq!(t.client.get_recurring_paused_at(&id), Some(START + HOUR));
+
+    set_time(&t.env, START + 5 * HOUR);
+    assert!(t.client.try_release_next_cycle(&id).is_err());
+    // Only the resolution lifts a pause imposed by the dispute.
+    assert_eq!(
+        t.client.try_resume_recurring_escrow(&id),
+        Err(Ok(Error::InvalidEscrowState.into()))
+    );
+
+    t.client
+        .resolve_recurring_dispute(&id, &Resolution::ReleaseToSeller, &t.admin);
+    assert_eq!(token_client.balance(&t.artisan), 950);
+    assert_eq!(token_client.balance(&t.platform_wallet), 50);
+    assert_eq!(t.client.get_recurring_paused_at(&id), None);
+
+    // The paused four hours do not count towards the next cycle.
+    assert!(t.client.try_release_next_cycle(&id).is_err());
+    set_time(&t.env, START + 6 * HOUR);
+    t.client.release_next_cycle(&id);
+    assert_eq!(token_client.balance(&t.artisan), 1_900);
+    assert!(!t.client.get_recurring_escrow(&id).is_active);
+}
+
+#[test]
+fn test_dispute_of_last_cycle_closes_escrow_on_resolution() {
+    let t = setup_test();
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &1_000, &HOUR, &1)
+        .id;
+
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer);
+    assert!(t.client.get_recurring_escrow(&id).is_active);
+    assert!(t.client.has_active_escrows(&t.artisan));
+
+    t.client
+        .resolve_recurring_dispute(&id, &Resolution::RefundToBuyer, &t.admin);
+    assert!(!t.client.get_recurring_escrow(&id).is_active);
+    assert!(!t.client.has_active_escrows(&t.buyer));
+    assert!(!t.client.has_active_escrows(&t.artisan));
+}
+
+#[test]
+fn test_recurring_dispute_authorization() {
+    let t = setup_test();
+    let stranger = Address::generate(&t.env);
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &2_000, &HOUR, &2)
+        .id;
+
+    assert!(t
+        .client
+        .try_dispute_recurring_cycle(&id, &reason(&t.env), &stranger)
+        .is_err());
+    assert!(t
+        .client
+        .try_resolve_recurring_dispute(&id, &Resolution::RefundToBuyer, &t.admin)
+        .is_err());
+
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer);
+    assert!(t
+        .client
+        .try_dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer)
+        .is_err());
+    assert!(t
+        .client
+        .try_resolve_recurring_dispute(&id, &Resolution::RefundToBuyer, &t.buyer)
+        .is_err());
+}
+
+#[test]
+fn test_later_cycle_cannot_be_disputed_before_its_window() {
+    let t = setup_test();
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &3_000, &HOUR, &3)
+        .id;
+
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer);
+    t.client
+        .resolve_recurring_dispute(&id, &Resolution::RefundToBuyer, &t.admin);
+
+    // Cycle 1 covers the second hour.
+    assert_eq!(
+        t.client
+            .try_dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer),
+        Err(Ok(Error::CycleNotReady.into()))
+    );
+    set_time(&t.env, START + HOUR);
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.buyer);
+    assert_eq!(t.client.get_recurring_cycle_dispute(&id).unwrap().cycle, 1);
+}
+
+#[test]
+fn test_expired_cycle_dispute_refunds_under_expired_policy() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client.set_max_dispute_duration(&86_400);
+    t.client
+        .update_expired_dispute_policy(&ExpiredDisputeFeePolicy::RefundMinusPlatformFee);
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &1_000, &HOUR, &1)
+        .id;
+
+    t.client
+        .dispute_recurring_cycle(&id, &reason(&t.env), &t.artisan);
+    assert_eq!(
+        t.client.try_resolve_expired_cycle_dispute(&id),
+        Err(Ok(Error::DisputeExpired))
+    );
+
+    set_time(&t.env, START + 86_400);
+    t.client.resolve_expired_cycle_dispute(&id);
+
+    assert_eq!(token_client.balance(&t.buyer), 2_950);
+    assert_eq!(token_client.balance(&t.platform_wallet), 50);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+    assert_eq!(t.client.get_recurring_cycle_dispute(&id), None);
+    assert!(!t.client.get_recurring_escrow(&id).is_active);
+    assert!(!t.client.has_active_escrows(&t.buyer));
+    assert_eq!(
+        t.client.try_resolve_expired_cycle_dispute(&id),
+        Err(Ok(Error::NotInDispute))
+    );
+}
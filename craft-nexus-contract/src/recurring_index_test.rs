@@ -0,0 +1,157 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, DataKey, RecurringEscrow};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env,
+};
+
+struct RecurringIndexTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    artisan: Address,
+    token: Address,
+}
+
+fn setup_test() -> RecurringIndexTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &100_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    RecurringIndexTest {
+        env,
+        client,
+        admin,
+        buyer,
+        artisan,
+        token,
+    }
+}
+
+fn create(t: &RecurringIndexTest, artisan: &Address) -> u64 {
+    t.client
+        .create_recurring_escrow(&t.buyer, artisan, &t.token, &1_000, &3_600, &2)
+        .id
+}
+
+#[test]
+fn test_recurring_escrows_are_listed_per_user() {
+    let t = setup_test();
+    let other_artisan = Address::generate(&t.env);
+
+    let first = create(&t, &t.artisan);
+    let second = create(&t, &other_artisan);
+    let third = create(&t, &t.artisan);
+
+    assert_eq!(t.client.get_buyer_recurring_count(&t.buyer), 3);
+    assert_eq!(t.client.get_artisan_recurring_count(&t.artisan), 2);
+    assert_eq!(t.client.get_artisan_recurring_count(&other_artisan), 1);
+
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_buyer(&t.buyer, &0, &10, &false),
+        vec![&t.env, first, second, third]
+    );
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_buyer(&t.buyer, &1, &2, &false),
+        vec![&t.env, third]
+    );
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_buyer(&t.buyer, &0, &2, &true),
+        vec![&t.env, third, second]
+    );
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_artisan(&t.artisan, &0, &10, &true),
+        vec![&t.env, third, first]
+    );
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_artisan(&t.artisan, &1, &10, &false)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_backfill_indexes_legacy_recurring_escrows() {
+    let t = setup_test();
+
+    // Recurring escrows stored before the indexes existed.
+    t.env.as_contract(&t.client.address, || {
+        for id in 1..=2u64 {
+            let escrow = RecurringEscrow {
+                id,
+                buyer: t.buyer.clone(),
+                artisan: t.artisan.clone(),
+                token: t.token.clone(),
+                total_amount: 1_000,
+                released_amount: 0,
+                frequency: 3_600,
+                duration: 2,
+                current_cycle: 0,
+                last_release_time: 1711368000,
+                is_active: true,
+            };
+            t.env
+                .storage()
+                .persistent()
+                .set(&DataKey::RecurringEscrow(id), &escrow);
+        }
+        t.env
+            .storage()
+            .persistent()
+            .set(&DataKey::NextRecurringEscrowId, &3u64);
+    });
+
+    let indexed = create(&t, &t.artisan);
+    assert_eq!(indexed, 3);
+    assert_eq!(t.client.get_buyer_recurring_count(&t.buyer), 1);
+
+    assert_eq!(t.client.migrate_recurring_escrow_indexes(&t.admin, &1), 1);
+    assert_eq!(t.client.migrate_recurring_escrow_indexes(&t.admin, &10), 1);
+    assert_eq!(t.client.migrate_recurring_escrow_indexes(&t.admin, &10), 0);
+
+    assert_eq!(t.client.get_buyer_recurring_count(&t.buyer), 3);
+    // Backfilled escrows are listed before newer ones in both directions.
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_artisan(&t.artisan, &0, &10, &false),
+        vec![&t.env, 1, 2, 3]
+    );
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_buyer(&t.buyer, &0, &2, &true),
+        vec![&t.env, 3, 2]
+    );
+    assert_eq!(
+        t.client
+            .get_recurring_escrows_by_buyer(&t.buyer, &1, &2, &true),
+        vec![&t.env, 1]
+    );
+}
@@ -0,0 +1,198 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, RecurringEscrowAction,
+    RecurringEscrowEvent,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    token, Address, Env, TryIntoVal,
+};
+
+const START: u64 = 1711368000;
+const HOUR: u64 = 3_600;
+
+struct RecurringPauseTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    artisan: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> RecurringPauseTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = START;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    RecurringPauseTest {
+        env,
+        client,
+        admin,
+        buyer,
+        artisan,
+        platform_wallet,
+        token,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp = timestamp;
+    });
+}
+
+fn last_recurring_event(env: &Env) -> RecurringEscrowEvent {
+    env.events()
+        .all()
+        .last()
+        .unwrap()
+        .2
+        .try_into_val(env)
+        .unwrap()
+}
+
+#[test]
+fn test_pause_does_not_accumulate_missed_cycles() {
+    let t = setup_test();
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &1_000, &HOUR, &2)
+        .id;
+
+    set_time(&t.env, START + HOUR / 2);
+    t.client.pause_recurring_escrow(&id);
+    assert_eq!(
+        t.client.get_recurring_paused_at(&id),
+        Some(START + HOUR / 2)
+    );
+    let event = last_recurring_event(&t.env);
+    assert_eq!(event.action, RecurringEscrowAction::Paused);
+    assert_eq!(event.artisan, t.artisan);
+
+    set_time(&t.env, START + 10 * HOUR);
+    assert_eq!(
+        t.client.try_release_next_cycle(&id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::InvalidEscrowState as u32
+        )))
+    );
+
+    t.client.resume_recurring_escrow(&id);
+    assert_eq!(t.client.get_recurring_paused_at(&id), None);
+    assert_eq!(
+        last_recurring_event(&t.env).action,
+        RecurringEscrowAction::Resumed
+    );
+    // Half a cycle ran before the pause, so the next cycle is due half a
+    // cycle after resuming.
+    assert_eq!(
+        t.client.try_release_next_cycle(&id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::CycleNotReady as u32
+        )))
+    );
+
+    set_time(&t.env, START + 10 * HOUR + HOUR / 2);
+    t.client.release_next_cycle(&id);
+    assert_eq!(
+        t.client.try_release_next_cycle(&id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::CycleNotReady as u32
+        )))
+    );
+    assert_eq!(t.client.get_recurring_escrow(&id).current_cycle, 1);
+}
+
+#[test]
+fn test_pause_past_max_duration_cancels_pro_rata() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client
+        .set_max_recurring_pause_duration(&t.admin, &(24 * HOUR));
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &1_000, &HOUR, &2)
+        .id;
+
+    set_time(&t.env, START + HOUR / 4);
+    t.client.pause_recurring_escrow(&id);
+
+    set_time(&t.env, START + HOUR / 4 + 24 * HOUR - 1);
+    assert_eq!(
+        t.client.try_expire_paused_recurring_escrow(&id),
+        Err(Ok(soroban_sdk::Error::from_contract_error(
+            Error::CycleNotReady as u32
+        )))
+    );
+
+    set_time(&t.env, START + HOUR / 4 + 24 * HOUR);
+    assert!(t.client.try_resume_recurring_escrow(&id).is_err());
+    t.client.expire_paused_recurring_escrow(&id);
+
+    // A quarter of the 500 cycle is owed to the artisan, less the 5% fee.
+    assert_eq!(token_client.balance(&t.artisan), 119);
+    assert_eq!(token_client.balance(&t.platform_wallet), 6);
+    assert_eq!(token_client.balance(&t.buyer), 875);
+    assert_eq!(token_client.balance(&t.client.address), 0);
+
+    let escrow = t.client.get_recurring_escrow(&id);
+    assert!(!escrow.is_active);
+    assert_eq!(escrow.released_amount, 125);
+    assert_eq!(t.client.get_recurring_paused_at(&id), None);
+    assert!(!t.client.has_active_escrows(&t.buyer));
+}
+
+#[test]
+fn test_pause_and_resume_validation() {
+    let t = setup_test();
+    let id = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &1_000, &HOUR, &2)
+        .id;
+
+    assert!(t.client.try_resume_recurring_escrow(&id).is_err());
+    // A due cycle must be released before the escrow can be paused.
+    set_time(&t.env, START + HOUR);
+    assert_eq!(
+        t.client.try_pause_recurring_escrow(&id),
+        Err(Ok(Error::InvalidEscrowState.into()))
+    );
+    t.client.release_next_cycle(&id);
+    t.client.pause_recurring_escrow(&id);
+    let auths = t.env.auths();
+    assert!(auths.iter().any(|(address, _)| *address == t.buyer));
+    assert!(t.client.try_pause_recurring_escrow(&id).is_err());
+
+    // Without a maximum pause duration a pause never expires.
+    set_time(&t.env, START + 1_000 * HOUR);
+    assert!(t.client.try_expire_paused_recurring_escrow(&id).is_err());
+
+    // A paused subscription can still be cancelled by the buyer.
+    t.client.cancel_recurring_escrow(&id);
+    assert_eq!(t.client.get_recurring_paused_at(&id), None);
+    assert!(t.client.try_pause_recurring_escrow(&id).is_err());
+}
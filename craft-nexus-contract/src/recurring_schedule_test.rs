@@ -0,0 +1,223 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, RecurringCycle, MAX_RECURRING_SCHEDULE_LEN,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env, Vec,
+};
+
+const START: u64 = 1711368000;
+const WEEK: u64 = 7 * 24 * 3_600;
+
+struct RecurringScheduleTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    artisan: Address,
+    token: Address,
+}
+
+fn setup_test() -> RecurringScheduleTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = START;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    RecurringScheduleTest {
+        env,
+        client,
+        buyer,
+        artisan,
+        token,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp = timestamp;
+    });
+}
+
+/// 30% deposit due immediately, then 10% per week for seven weeks.
+fn deposit_schedule(env: &Env) -> Vec<RecurringCycle> {
+    let mut schedule = vec![
+        env,
+        RecurringCycle {
+            amount: 300,
+            due_at: START,
+        },
+    ];
+    for week in 1..=7 {
+        schedule.push_back(RecurringCycle {
+            amount: 100,
+            due_at: START + week * WEEK,
+        });
+    }
+    schedule
+}
+
+#[test]
+fn test_schedule_releases_listed_amounts_at_due_times() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let escrow = t.client.create_recurring_schedule(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &1_000,
+        &deposit_schedule(&t.env),
+    );
+    assert_eq!(escrow.total_amount, 1_000);
+    assert_eq!(escrow.duration, 8);
+    assert_eq!(t.client.get_recurring_schedule(&escrow.id).len(), 8);
+    assert_eq!(token_client.balance(&t.buyer), 0);
+
+    // The deposit is due at creation.
+    t.client.release_next_cycle(&escrow.id);
+    assert_eq!(token_client.balance(&t.artisan), 285);
+    assert_eq!(
+        t.client.try_release_next_cycle(&escrow.id),
+        Err(Ok(Error::CycleNotReady.into()))
+    );
+
+    set_time(&t.env, START + WEEK);
+    t.client.release_next_cycle(&escrow.id);
+    assert_eq!(token_client.balance(&t.artisan), 380);
+
+    // Late releases catch up one cycle per call.
+    set_time(&t.env, START + 7 * WEEK);
+    for _ in 0..6 {
+        t.client.release_next_cycle(&escrow.id);
+    }
+    let escrow = t.client.get_recurring_escrow(&escrow.id);
+    assert_eq!(escrow.released_amount, 1_000);
+    assert!(!escrow.is_active);
+    assert_eq!(token_client.balance(&t.artisan), 950);
+}
+
+#[test]
+fn test_schedule_validation() {
+    let t = setup_test();
+    let create = |total: i128, schedule: &Vec<RecurringCycle>| {
+        t.client
+            .try_create_recurring_schedule(&t.buyer, &t.artisan, &t.token, &total, schedule)
+    };
+
+    assert_eq!(
+        create(0, &Vec::new(&t.env)),
+        Err(Ok(Error::AmountBelowMinimum))
+    );
+    let zero_cycle = vec![
+        &t.env,
+        RecurringCycle {
+            amount: 0,
+            due_at: START,
+        },
+    ];
+    assert_eq!(create(100, &zero_cycle), Err(Ok(Error::AmountBelowMinimum)));
+
+    // The cycle amounts must add up to the total exactly.
+    assert_eq!(
+        create(900, &deposit_schedule(&t.env)),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+    assert_eq!(
+        t.client.try_create_recurring_schedule(
+            &t.buyer,
+            &t.buyer,
+            &t.token,
+            &1_000,
+            &deposit_schedule(&t.env)
+        ),
+        Err(Ok(Error::SameBuyerSeller))
+    );
+
+    // Due times in the past or out of order are rejected.
+    let past = vec![
+        &t.env,
+        RecurringCycle {
+            amount: 100,
+            due_at: START - 1,
+        },
+    ];
+    assert_eq!(create(100, &past), Err(Ok(Error::InvalidEscrowState)));
+    let unordered = vec![
+        &t.env,
+        RecurringCycle {
+            amount: 100,
+            due_at: START + WEEK,
+        },
+        RecurringCycle {
+            amount: 100,
+            due_at: START + WEEK,
+        },
+    ];
+    assert_eq!(create(200, &unordered), Err(Ok(Error::InvalidEscrowState)));
+
+    let mut too_long = Vec::new(&t.env);
+    for i in 0..=MAX_RECURRING_SCHEDULE_LEN as u64 {
+        too_long.push_back(RecurringCycle {
+            amount: 1,
+            due_at: START + i * WEEK,
+        });
+    }
+    assert_eq!(
+        create(too_long.len() as i128, &too_long),
+        Err(Ok(Error::BatchLimitExceeded))
+    );
+
+    // Equal-cycle escrows report no schedule.
+    let escrow = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &100, &WEEK, &2);
+    assert!(t.client.get_recurring_schedule(&escrow.id).is_empty());
+}
+
+#[test]
+fn test_pause_shifts_remaining_due_times() {
+    let t = setup_test();
+    let escrow = t.client.create_recurring_schedule(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &1_000,
+        &deposit_schedule(&t.env),
+    );
+    t.client.release_next_cycle(&escrow.id);
+
+    set_time(&t.env, START + WEEK / 2);
+    t.client.pause_recurring_escrow(&escrow.id);
+    set_time(&t.env, START + WEEK / 2 + 3 * 24 * 3_600);
+    t.client.resume_recurring_escrow(&escrow.id);
+
+    set_time(&t.env, START + WEEK);
+    assert!(t.client.try_release_next_cycle(&escrow.id).is_err());
+    set_time(&t.env, START + WEEK + 3 * 24 * 3_600);
+    t.client.release_next_cycle(&escrow.id);
+    assert_eq!(
+        t.client.get_recurring_escrow(&escrow.id).released_amount,
+        400
+    );
+}
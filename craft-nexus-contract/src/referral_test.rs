@@ -0,0 +1,213 @@
+#![cfg(test)]
+
+use crate::onboarding::{OnboardingContract, OnboardingContractClient, UserRole};
+use crate::{CraftNexusContract, CraftNexusContractClient, Error};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, String,
+};
+
+const PLATFORM_FEE_BPS: u32 = 500;
+
+struct ReferralTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    onboarding: OnboardingContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+    platform_wallet: Address,
+}
+
+fn setup_test() -> ReferralTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let onboarding_id = env.register_contract(None, OnboardingContract);
+    let onboarding = OnboardingContractClient::new(&env, &onboarding_id);
+    onboarding.initialize(&Address::generate(&env));
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token_admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(
+        &platform_wallet,
+        &admin,
+        &arbitrator,
+        &PLATFORM_FEE_BPS,
+        &Some(onboarding_id),
+    );
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+
+    ReferralTest {
+        env,
+        client,
+        admin,
+        onboarding,
+        buyer,
+        seller,
+        token,
+        platform_wallet,
+    }
+}
+
+#[test]
+fn test_release_pays_escrow_referrer_share_of_fee() {
+    let t = setup_test();
+    let referrer = Address::generate(&t.env);
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.set_referral_share_bps(&t.admin, &2_000);
+    t.client.create_escrow_with_referrer(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &Some(3600),
+        &referrer,
+    );
+    t.client.release_funds(&1);
+
+    // 5% fee = 500; 20% of the fee goes to the referrer.
+    assert_eq!(token_client.balance(&referrer), 100);
+    assert_eq!(token_client.balance(&t.platform_wallet), 400);
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+
+    assert_eq!(t.client.get_referral_earnings(&referrer, &t.token), 100);
+    assert_eq!(t.client.get_total_referral_fees(&t.token), 100);
+    assert_eq!(t.client.get_total_fees_for_token(&t.token), 400);
+}
+
+#[test]
+fn test_release_falls_back_to_onboarding_profile_referrer() {
+    let t = setup_test();
+    let referrer = Address::generate(&t.env);
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.onboarding.onboard_user(
+        &t.seller,
+        &String::from_str(&t.env, "referred_artisan"),
+        &UserRole::Artisan,
+    );
+    t.onboarding.set_referrer(&t.seller, &referrer);
+
+    t.client.set_referral_share_bps(&t.admin, &2_000);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &Some(3600));
+    t.client.release_funds(&1);
+
+    assert_eq!(t.client.get_escrow_referrer(&1), None);
+    assert_eq!(token_client.balance(&referrer), 100);
+    assert_eq!(token_client.balance(&t.platform_wallet), 400);
+    assert_eq!(t.client.get_referral_earnings(&referrer, &t.token), 100);
+}
+
+#[test]
+fn test_zero_share_pays_no_referral() {
+    let t = setup_test();
+    let referrer = Address::generate(&t.env);
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client.create_escrow_with_referrer(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &Some(3600),
+        &referrer,
+    );
+    t.client.release_funds(&1);
+
+    assert_eq!(token_client.balance(&referrer), 0);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(t.client.get_total_referral_fees(&t.token), 0);
+    assert_eq!(t.client.get_total_fees_for_token(&t.token), 500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_escrow_referrer_may_not_be_the_seller() {
+    let t = setup_test();
+
+    t.client.create_escrow_with_referrer(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &Some(3600),
+        &t.seller,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_escrow_referrer_may_not_be_the_buyer() {
+    let t = setup_test();
+
+    t.client.create_escrow_with_referrer(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &Some(3600),
+        &t.buyer,
+    );
+}
+
+#[test]
+fn test_escrow_referrer_is_fixed_at_creation() {
+    let t = setup_test();
+    let referrer = Address::generate(&t.env);
+
+    t.client.create_escrow_with_referrer(
+        &t.buyer,
+        &t.seller,
+        &t.token,
+        &10_000,
+        &1,
+        &Some(3600),
+        &referrer,
+    );
+    // The buyer, as funder, authorized the referrer along with the escrow.
+    assert!(t.env.auths().iter().any(|(addr, _)| addr == &t.buyer));
+    assert_eq!(t.client.get_escrow_referrer(&1), Some(referrer));
+}
+
+#[test]
+fn test_referral_share_is_capped() {
+    let t = setup_test();
+
+    t.client.set_referral_share_bps(&t.admin, &2_000);
+    assert_eq!(t.client.get_referral_share_bps(), 2_000);
+    assert_eq!(
+        t.client.try_set_referral_share_bps(&t.admin, &2_001),
+        Err(Ok(Error::InvalidFee))
+    );
+    assert_eq!(
+        t.client.try_set_referral_share_bps(&t.admin, &10_000),
+        Err(Ok(Error::InvalidFee))
+    );
+}
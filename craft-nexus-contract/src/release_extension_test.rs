@@ -0,0 +1,181 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, Error, EscrowStatus, ExtensionReason};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    token, Address, Env, IntoVal, Symbol,
+};
+
+const WINDOW: u32 = 7 * 24 * 60 * 60;
+const MAX_TOTAL_RELEASE_WINDOW: u32 = 30 * 24 * 60 * 60;
+
+struct ExtensionTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+}
+
+fn setup_test() -> ExtensionTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.create_escrow(&buyer, &seller, &token, &10_000, &1, &Some(WINDOW));
+
+    ExtensionTest { env, client }
+}
+
+fn has_extension_event(env: &Env, order_id: u32) -> bool {
+    let topic = (Symbol::new(env, "release_extension"), order_id).into_val(env);
+    env.events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == topic)
+}
+
+#[test]
+fn test_buyer_accepts_extension_request() {
+    let t = setup_test();
+
+    t.client
+        .request_release_extension(&1, &(3 * 24 * 60 * 60), &ExtensionReason::EquipmentFailure);
+    assert!(has_extension_event(&t.env, 1));
+
+    let request = t.client.get_release_extension_request(&1).unwrap();
+    assert_eq!(request.reason, ExtensionReason::EquipmentFailure);
+    assert_eq!(t.client.get_escrow(&1).release_window, WINDOW);
+
+    t.client.respond_to_release_extension(&1, &true);
+    assert!(has_extension_event(&t.env, 1));
+    assert_eq!(
+        t.client.get_escrow(&1).release_window,
+        WINDOW + 3 * 24 * 60 * 60
+    );
+    assert_eq!(t.client.get_release_extension_request(&1), None);
+}
+
+#[test]
+fn test_buyer_rejects_extension_request() {
+    let t = setup_test();
+
+    t.client
+        .request_release_extension(&1, &86_400, &ExtensionReason::SupplyShortage);
+    t.client.respond_to_release_extension(&1, &false);
+    assert!(has_extension_event(&t.env, 1));
+
+    assert_eq!(t.client.get_escrow(&1).release_window, WINDOW);
+    assert_eq!(t.client.get_release_extension_request(&1), None);
+    assert_eq!(
+        t.client.try_respond_to_release_extension(&1, &true),
+        Err(Ok(Error::ProposalNotFound))
+    );
+
+    // A new request may be made once the previous one is settled.
+    t.client
+        .request_release_extension(&1, &86_400, &ExtensionReason::ShippingDelay);
+}
+
+#[test]
+fn test_extension_request_is_bounded_and_unique() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client.try_request_release_extension(
+            &1,
+            &(MAX_TOTAL_RELEASE_WINDOW - WINDOW + 1),
+            &ExtensionReason::ProductionDelay
+        ),
+        Err(Ok(Error::ReleaseWindowTooLong))
+    );
+    assert_eq!(
+        t.client
+            .try_request_release_extension(&1, &0, &ExtensionReason::Other),
+        Err(Ok(Error::ReleaseWindowTooShort))
+    );
+
+    t.client.request_release_extension(
+        &1,
+        &(MAX_TOTAL_RELEASE_WINDOW - WINDOW),
+        &ExtensionReason::ProductionDelay,
+    );
+    assert_eq!(
+        t.client
+            .try_request_release_extension(&1, &60, &ExtensionReason::Other),
+        Err(Ok(Error::ProposalAlreadyExists))
+    );
+
+    // The buyer extending in the meantime makes the request exceed the cap.
+    t.client.extend_release_window(&1, &60);
+    assert_eq!(
+        t.client.try_respond_to_release_extension(&1, &true),
+        Err(Ok(Error::ReleaseWindowTooLong))
+    );
+}
+
+#[test]
+fn test_seller_cancels_extension_request() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client.try_cancel_release_extension(&1),
+        Err(Ok(Error::ProposalNotFound))
+    );
+
+    t.client
+        .request_release_extension(&1, &86_400, &ExtensionReason::ShippingDelay);
+    t.client.cancel_release_extension(&1);
+
+    assert_eq!(t.client.get_release_extension_request(&1), None);
+    assert_eq!(t.client.get_escrow(&1).release_window, WINDOW);
+    assert_eq!(
+        t.client.try_respond_to_release_extension(&1, &true),
+        Err(Ok(Error::ProposalNotFound))
+    );
+
+    // The seller may ask again with different terms.
+    t.client
+        .request_release_extension(&1, &3_600, &ExtensionReason::Other);
+}
+
+#[test]
+fn test_extension_request_cleared_on_release() {
+    let t = setup_test();
+
+    t.client
+        .request_release_extension(&1, &86_400, &ExtensionReason::ShippingDelay);
+    t.client.release_funds(&1);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(t.client.get_release_extension_request(&1), None);
+}
+
+#[test]
+fn test_extension_request_cleared_on_refund() {
+    let t = setup_test();
+
+    t.client
+        .request_release_extension(&1, &86_400, &ExtensionReason::ShippingDelay);
+    t.client.refund(&1);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Refunded);
+    assert_eq!(t.client.get_release_extension_request(&1), None);
+}
@@ -0,0 +1,324 @@
+#![cfg(test)]
+
+use crate::{
+    AdminActionKind, CraftNexusContract, CraftNexusContractClient, Error, Resolution, Role,
+};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token, Address, BytesN, Env,
+};
+
+struct RoleTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    artisan: Address,
+    token: Address,
+}
+
+fn setup_test() -> RoleTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_admin_action_timelock_delay(&0);
+
+    RoleTest {
+        env,
+        client,
+        admin,
+        buyer,
+        artisan,
+        token,
+    }
+}
+
+fn run_admin_action(t: &RoleTest, action: AdminActionKind) {
+    let proposal = t.client.propose_admin_action(&t.admin, &action);
+    t.client.execute_admin_action(&proposal.id);
+}
+
+#[test]
+fn test_granted_role_unlocks_only_its_entrypoints() {
+    let t = setup_test();
+    let manager = Address::generate(&t.env);
+    assert_eq!(
+        t.client.try_update_platform_fee_as(&manager, &300),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::FeeManager, manager.clone()),
+    );
+    assert!(t.client.has_role(&Role::FeeManager, &manager));
+    assert!(t.client.has_role(&Role::FeeManager, &t.admin));
+    assert_eq!(t.client.get_role_members(&Role::FeeManager).len(), 1);
+
+    t.client.update_platform_fee_as(&manager, &300);
+    assert_eq!(t.client.get_platform_config().platform_fee_bps, 300);
+
+    // Other permissions stay with the admin.
+    assert_eq!(
+        t.client.try_set_paused_as(&manager, &true),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+    assert!(t.client.try_whitelist_token_as(&manager, &t.token).is_err());
+}
+
+#[test]
+fn test_role_can_be_granted_to_several_addresses_and_revoked() {
+    let t = setup_test();
+    let first = Address::generate(&t.env);
+    let second = Address::generate(&t.env);
+    run_admin_action(&t, AdminActionKind::GrantRole(Role::Pauser, first.clone()));
+    run_admin_action(&t, AdminActionKind::GrantRole(Role::Pauser, second.clone()));
+    assert_eq!(t.client.get_role_members(&Role::Pauser).len(), 2);
+
+    t.client.set_paused_as(&first, &true);
+    t.client.set_paused_as(&second, &false);
+
+    run_admin_action(&t, AdminActionKind::RevokeRole(Role::Pauser, first.clone()));
+    assert!(!t.client.has_role(&Role::Pauser, &first));
+    assert_eq!(
+        t.client.try_set_paused_as(&first, &true),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+    t.client.set_paused_as(&second, &true);
+    assert!(t.client.is_paused());
+}
+
+#[test]
+fn test_dispute_resolver_role_resolves_recurring_disputes() {
+    let t = setup_test();
+    let resolver = Address::generate(&t.env);
+    let escrow = t
+        .client
+        .create_recurring_escrow(&t.buyer, &t.artisan, &t.token, &300, &3_600, &3);
+    t.client
+        .dispute_recurring_cycle(&escrow.id, &symbol_short!("late"), &t.buyer);
+
+    assert!(t
+        .client
+        .try_resolve_recurring_dispute(&escrow.id, &Resolution::RefundToBuyer, &resolver)
+        .is_err());
+
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::DisputeResolver, resolver.clone()),
+    );
+    t.client
+        .resolve_recurring_dispute(&escrow.id, &Resolution::RefundToBuyer, &resolver);
+    assert!(t.client.get_recurring_cycle_dispute(&escrow.id).is_none());
+}
+
+#[test]
+fn test_upgrade_round_must_be_opened_by_upgrade_proposer() {
+    let t = setup_test();
+    let signer = Address::generate(&t.env);
+    let mut signers = soroban_sdk::Vec::new(&t.env);
+    signers.push_back(t.admin.clone());
+    signers.push_back(signer.clone());
+    t.client.set_upgrade_signers(&signers);
+    t.client.set_upgrade_threshold(&2);
+
+    let hash = BytesN::from_array(&t.env, &[7u8; 32]);
+    assert_eq!(
+        t.client.try_propose_upgrade_wasm(&signer, &hash),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::UpgradeProposer, signer.clone()),
+    );
+    t.client.propose_upgrade_wasm(&signer, &hash);
+    t.client.propose_upgrade_wasm(&t.admin, &hash);
+    assert_eq!(t.client.get_upgrade_proposal().unwrap().wasm_hash, hash);
+}
+
+#[test]
+fn test_fee_and_ops_settings_follow_their_roles() {
+    let t = setup_test();
+    let manager = Address::generate(&t.env);
+    let operator = Address::generate(&t.env);
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::FeeManager, manager.clone()),
+    );
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::Operator, operator.clone()),
+    );
+
+    t.client.set_fee_change_notice_period(&manager, &3_600);
+    assert_eq!(t.client.get_fee_change_notice_period(), 3_600);
+    t.client.set_max_recurring_pause_duration(&operator, &7_200);
+    assert_eq!(t.client.get_max_recurring_pause_duration(), 7_200);
+
+    // Each setting stays out of reach of the other role.
+    assert_eq!(
+        t.client.try_set_fee_change_notice_period(&operator, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        t.client.try_set_max_recurring_pause_duration(&manager, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_pauser_role_only_pauses() {
+    let t = setup_test();
+    let pauser = Address::generate(&t.env);
+    run_admin_action(&t, AdminActionKind::GrantRole(Role::Pauser, pauser.clone()));
+
+    t.client.set_paused_as(&pauser, &true);
+    assert!(t.client.is_paused());
+
+    assert_eq!(
+        t.client.try_set_epoch_length(&pauser, &3_600),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        t.client.try_set_max_recurring_pause_duration(&pauser, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_operator_role_sets_windows_and_attesters() {
+    let t = setup_test();
+    let operator = Address::generate(&t.env);
+    assert_eq!(
+        t.client.try_set_max_dispute_duration_as(&operator, &86_400),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::Operator, operator.clone()),
+    );
+    t.client.set_max_dispute_duration_as(&operator, &86_400);
+    assert_eq!(t.client.get_platform_config().max_dispute_duration, 86_400);
+    t.client.set_min_release_window_as(&operator, &60);
+    assert_eq!(t.client.get_min_release_window(), 60);
+    t.client.set_attested_release_window(&operator, &120);
+    assert_eq!(t.client.get_attested_release_window(), 120);
+    let attester = BytesN::from_array(&t.env, &[9u8; 32]);
+    t.client.set_delivery_attester(&operator, &attester, &true);
+    assert!(t.client.is_delivery_attester(&attester));
+
+    assert_eq!(
+        t.client.try_set_paused_as(&operator, &true),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+}
+
+#[test]
+fn test_migrator_role_runs_migrations() {
+    let t = setup_test();
+    let migrator = Address::generate(&t.env);
+    let operator = Address::generate(&t.env);
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::Migrator, migrator.clone()),
+    );
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::Operator, operator.clone()),
+    );
+
+    assert_eq!(t.client.migrate_recurring_escrow_indexes(&migrator, &10), 0);
+    assert_eq!(t.client.migrate_fee_token_configs_as(&migrator), 0);
+    assert_eq!(
+        t.client
+            .try_migrate_recurring_escrow_indexes(&operator, &10),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        t.client.try_migrate_storage_layout_as(&operator),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+}
+
+#[test]
+fn test_treasurer_role_moves_fee_income() {
+    let t = setup_test();
+    let treasurer = Address::generate(&t.env);
+    let manager = Address::generate(&t.env);
+    let wallet = Address::generate(&t.env);
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::Treasurer, treasurer.clone()),
+    );
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::FeeManager, manager.clone()),
+    );
+
+    assert_eq!(
+        t.client.try_update_platform_wallet_as(&manager, &wallet),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+    t.client.update_platform_wallet_as(&treasurer, &wallet);
+    assert_eq!(t.client.get_platform_config().platform_wallet, wallet);
+
+    token::StellarAssetClient::new(&t.env, &t.token).mint(&t.client.address, &250);
+    assert_eq!(
+        t.client
+            .try_sweep_unallocated_funds_as(&manager, &t.token, &wallet),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        t.client
+            .sweep_unallocated_funds_as(&treasurer, &t.token, &wallet),
+        250
+    );
+    assert_eq!(token::Client::new(&t.env, &t.token).balance(&wallet), 250);
+}
+
+#[test]
+fn test_dispute_resolver_role_refunds_escrows() {
+    let t = setup_test();
+    let resolver = Address::generate(&t.env);
+    t.client
+        .create_escrow(&t.buyer, &t.artisan, &t.token, &400, &1, &None);
+
+    assert_eq!(
+        t.client.try_refund_as(&resolver, &1),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    run_admin_action(
+        &t,
+        AdminActionKind::GrantRole(Role::DisputeResolver, resolver.clone()),
+    );
+    t.client.refund_as(&resolver, &1);
+    assert_eq!(
+        token::Client::new(&t.env, &t.token).balance(&t.buyer),
+        1_000
+    );
+}
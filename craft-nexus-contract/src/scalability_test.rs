@@ -142,6 +142,8 @@ fn test_batch_escrow_indexing_scales_linearly_for_twenty_entries() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         });
     }
 
@@ -454,7 +456,7 @@ fn test_whitelisted_tokens_individual_storage() {
 
 #[test]
 fn test_whitelisted_tokens_scalability() {
-    let (env, client, _, _, _, _admin, _, _) = setup_test();
+    let (env, client, _, _, _, _, _, _) = setup_test();
 
     // Create many tokens to test scalability.
     // Each token must be a real contract so whitelist_token can call decimals() on it.
@@ -0,0 +1,151 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, Error, EscrowStatus};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env,
+};
+
+struct SellerAutoReleaseTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    token: Address,
+}
+
+fn setup_test() -> SellerAutoReleaseTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+    client.set_min_release_window(&1);
+
+    SellerAutoReleaseTest {
+        env,
+        client,
+        buyer,
+        seller,
+        token,
+    }
+}
+
+#[test]
+fn test_seller_batch_releases_only_matured_escrows() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &1_000, &1, &Some(3600));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &1_000, &2, &Some(7200));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &1_000, &3, &Some(3600));
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+
+    let batch = t.client.auto_release_seller_escrows(&t.seller, &0, &10);
+    assert_eq!(batch.released, vec![&t.env, 1u64, 3u64]);
+    assert_eq!(batch.next_cursor, 3);
+    assert!(batch.complete);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(t.client.get_escrow(&2).status, EscrowStatus::Active);
+    assert_eq!(t.client.get_escrow(&3).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.seller), 1_900);
+
+    // Re-running the batch does not release anything twice.
+    let batch = t.client.auto_release_seller_escrows(&t.seller, &0, &10);
+    assert_eq!(batch.released.len(), 0);
+    assert_eq!(token_client.balance(&t.seller), 1_900);
+}
+
+#[test]
+fn test_seller_batch_resumes_from_cursor() {
+    let t = setup_test();
+
+    for order_id in 1..=5u32 {
+        t.client.create_escrow(
+            &t.buyer,
+            &t.seller,
+            &t.token,
+            &1_000,
+            &order_id,
+            &Some(3600),
+        );
+    }
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+
+    let first = t.client.auto_release_seller_escrows(&t.seller, &0, &2);
+    assert_eq!(first.released, vec![&t.env, 1u64, 2u64]);
+    assert_eq!(first.next_cursor, 2);
+    assert!(!first.complete);
+
+    let second = t
+        .client
+        .auto_release_seller_escrows(&t.seller, &first.next_cursor, &2);
+    assert_eq!(second.released, vec![&t.env, 3u64, 4u64]);
+
+    let last = t
+        .client
+        .auto_release_seller_escrows(&t.seller, &second.next_cursor, &2);
+    assert_eq!(last.released, vec![&t.env, 5u64]);
+    assert_eq!(last.next_cursor, 5);
+    assert!(last.complete);
+}
+
+#[test]
+fn test_seller_batch_skips_unfunded_escrows() {
+    let t = setup_test();
+
+    t.client.create_unfunded_escrow(
+        &1, &t.buyer, &t.seller, &t.token, &1_000, &3600, &None, &None,
+    );
+    t.env.ledger().with_mut(|li| {
+        li.timestamp += 3600;
+    });
+
+    let batch = t.client.auto_release_seller_escrows(&t.seller, &0, &10);
+    assert_eq!(batch.released.len(), 0);
+    assert!(!t.client.get_escrow(&1).funded);
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Active);
+}
+
+#[test]
+fn test_seller_batch_limit_is_bounded() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client.try_auto_release_seller_escrows(&t.seller, &0, &21),
+        Err(Ok(Error::BatchLimitExceeded))
+    );
+
+    // A seller without escrows gets an empty, complete page.
+    let batch = t.client.auto_release_seller_escrows(&t.seller, &7, &20);
+    assert_eq!(batch.released.len(), 0);
+    assert_eq!(batch.next_cursor, 0);
+    assert!(batch.complete);
+}
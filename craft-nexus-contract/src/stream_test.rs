@@ -0,0 +1,330 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, DataKey, Error, StreamAction, StreamEvent,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    token, Address, Env, TryIntoVal,
+};
+
+const START: u64 = 1711368000;
+
+struct StreamTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    buyer: Address,
+    artisan: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> StreamTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &100_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = START;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    StreamTest {
+        env,
+        client,
+        admin,
+        buyer,
+        artisan,
+        platform_wallet,
+        token,
+    }
+}
+
+fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| {
+        li.timestamp = timestamp;
+    });
+}
+
+fn total_locked(t: &StreamTest) -> i128 {
+    t.env.as_contract(&t.client.address, || {
+        t.env
+            .storage()
+            .persistent()
+            .get(&DataKey::TotalLocked(t.token.clone()))
+            .unwrap_or(0)
+    })
+}
+
+/// `TotalLocked` must equal the unwithdrawn balance of every active stream
+/// and the contract's token balance.
+fn assert_locked_invariant(t: &StreamTest, ids: &[u64]) {
+    let mut outstanding = 0;
+    for id in ids {
+        let stream = t.client.get_stream(id);
+        if stream.is_active {
+            outstanding += stream.total_amount - stream.withdrawn_amount;
+        }
+    }
+    let balance = token::Client::new(&t.env, &t.token).balance(&t.client.address);
+    assert_eq!(total_locked(t), outstanding);
+    assert_eq!(balance, outstanding);
+}
+
+#[test]
+fn test_artisan_withdraws_linearly_vested_funds() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let stream = t.client.create_stream(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &10_000,
+        &(START + 100),
+        &(START + 1_100),
+    );
+    assert_locked_invariant(&t, &[stream.id]);
+
+    set_time(&t.env, START + 50);
+    assert_eq!(t.client.get_stream_withdrawable(&stream.id), 0);
+    assert_eq!(t.client.withdraw_from_stream(&stream.id), 0);
+
+    set_time(&t.env, START + 350);
+    assert_eq!(t.client.withdraw_from_stream(&stream.id), 2_500);
+    assert_eq!(token_client.balance(&t.artisan), 2_375);
+    assert_eq!(token_client.balance(&t.platform_wallet), 125);
+    assert_locked_invariant(&t, &[stream.id]);
+
+    set_time(&t.env, START + 2_000);
+    assert_eq!(t.client.withdraw_from_stream(&stream.id), 7_500);
+    assert_eq!(token_client.balance(&t.artisan), 9_500);
+    let event: StreamEvent = t
+        .env
+        .events()
+        .all()
+        .last()
+        .unwrap()
+        .2
+        .try_into_val(&t.env)
+        .unwrap();
+    assert_eq!(event.action, StreamAction::Completed);
+    assert!(!t.client.get_stream(&stream.id).is_active);
+    assert!(!t.client.has_active_escrows(&t.artisan));
+    assert_locked_invariant(&t, &[stream.id]);
+}
+
+#[test]
+fn test_stream_keeps_fee_from_creation() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let stream = t.client.create_stream(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &10_000,
+        &START,
+        &(START + 1_000),
+    );
+    assert_eq!(stream.fee_rate.bps, 500);
+    t.client.update_platform_fee(&1000);
+    t.client.set_fee_schedule(&t.admin, &t.token, &100, &0, &0);
+
+    set_time(&t.env, START + 1_000);
+    t.client.withdraw_from_stream(&stream.id);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(token_client.balance(&t.artisan), 9_500);
+}
+
+#[test]
+fn test_buyer_cancels_and_reclaims_unvested_funds() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let stream = t.client.create_stream(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &10_000,
+        &START,
+        &(START + 1_000),
+    );
+
+    set_time(&t.env, START + 200);
+    t.client.withdraw_from_stream(&stream.id);
+    set_time(&t.env, START + 600);
+    assert_eq!(t.client.cancel_stream(&stream.id), 4_000);
+
+    // 6_000 vested in total: 2_000 withdrawn earlier and 4_000 on cancel.
+    assert_eq!(token_client.balance(&t.artisan), 5_700);
+    assert_eq!(token_client.balance(&t.platform_wallet), 300);
+    assert_eq!(token_client.balance(&t.buyer), 94_000);
+    assert_eq!(t.client.get_stream(&stream.id).withdrawn_amount, 6_000);
+    assert_locked_invariant(&t, &[stream.id]);
+
+    assert_eq!(
+        t.client.try_withdraw_from_stream(&stream.id),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+    assert_eq!(
+        t.client.try_cancel_stream(&stream.id),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+}
+
+#[test]
+fn test_locked_invariant_across_concurrent_streams() {
+    let t = setup_test();
+    let first =
+        t.client
+            .create_stream(&t.buyer, &t.artisan, &t.token, &3_333, &START, &(START + 7));
+    let second = t.client.create_stream(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &5_000,
+        &START,
+        &(START + 13),
+    );
+    let ids = [first.id, second.id];
+
+    for step in 1..=15u64 {
+        set_time(&t.env, START + step);
+        if step % 2 == 1 && t.client.get_stream(&first.id).is_active {
+            t.client.withdraw_from_stream(&first.id);
+        }
+        if step == 9 {
+            t.client.cancel_stream(&second.id);
+        } else if step % 3 == 0 && t.client.get_stream(&second.id).is_active {
+            t.client.withdraw_from_stream(&second.id);
+        }
+        assert_locked_invariant(&t, &ids);
+    }
+    assert!(!t.client.get_stream(&first.id).is_active);
+    assert_eq!(total_locked(&t), 0);
+}
+
+#[test]
+fn test_scheduled_fee_is_charged_once_across_withdrawals() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    // 5% + 100 flat, capped at 400.
+    t.client
+        .set_fee_schedule(&t.admin, &t.token, &100, &0, &400);
+    let stream = t.client.create_stream(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &10_000,
+        &START,
+        &(START + 1_000),
+    );
+
+    for step in 1..=4u64 {
+        set_time(&t.env, START + step * 250);
+        t.client.withdraw_from_stream(&stream.id);
+        assert_eq!(
+            token_client.balance(&t.platform_wallet),
+            400 * step as i128 / 4
+        );
+        assert_locked_invariant(&t, &[stream.id]);
+    }
+    assert_eq!(token_client.balance(&t.artisan), 9_600);
+    assert_eq!(total_locked(&t), 0);
+}
+
+#[test]
+fn test_locked_invariant_with_fee_schedule_and_cancel() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    t.client.set_fee_schedule(&t.admin, &t.token, &7, &50, &0);
+    let first =
+        t.client
+            .create_stream(&t.buyer, &t.artisan, &t.token, &3_333, &START, &(START + 7));
+    let second = t.client.create_stream(
+        &t.buyer,
+        &t.artisan,
+        &t.token,
+        &5_000,
+        &START,
+        &(START + 13),
+    );
+    let ids = [first.id, second.id];
+
+    for step in 1..=15u64 {
+        set_time(&t.env, START + step);
+        if t.client.get_stream(&first.id).is_active {
+            t.client.withdraw_from_stream(&first.id);
+        }
+        if step == 9 {
+            t.client.cancel_stream(&second.id);
+        } else if step % 2 == 0 && t.client.get_stream(&second.id).is_active {
+            t.client.withdraw_from_stream(&second.id);
+        }
+        assert_locked_invariant(&t, &ids);
+    }
+    assert_eq!(total_locked(&t), 0);
+
+    // First: 166 + 7 once. Second: 250 + 7 on the full amount, of which the
+    // 9/13 vested at cancellation was charged (177).
+    let fees = 173 + 257 * 9 / 13;
+    assert_eq!(token_client.balance(&t.platform_wallet), fees);
+    assert_eq!(
+        token_client.balance(&t.artisan),
+        3_333 + 5_000 * 9 / 13 - fees
+    );
+}
+
+#[test]
+fn test_create_stream_validation() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client
+            .try_create_stream(&t.buyer, &t.artisan, &t.token, &0, &START, &(START + 10)),
+        Err(Ok(Error::AmountBelowMinimum))
+    );
+    assert_eq!(
+        t.client
+            .try_create_stream(&t.buyer, &t.buyer, &t.token, &100, &START, &(START + 10)),
+        Err(Ok(Error::SameBuyerSeller))
+    );
+    assert_eq!(
+        t.client.try_create_stream(
+            &t.buyer,
+            &t.artisan,
+            &t.token,
+            &100,
+            &(START + 10),
+            &(START + 10)
+        ),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+    assert_eq!(
+        t.client.try_create_stream(
+            &t.buyer,
+            &t.artisan,
+            &t.token,
+            &100,
+            &(START - 1),
+            &(START + 10)
+        ),
+        Err(Ok(Error::InvalidEscrowState))
+    );
+    assert_eq!(t.client.try_get_stream(&1), Err(Ok(Error::EscrowNotFound)));
+}
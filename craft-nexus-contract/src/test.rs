@@ -2291,6 +2291,8 @@ fn test_create_batch_escrow_success() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
         EscrowCreateParams {
             buyer: buyer.clone(),
@@ -2301,6 +2303,8 @@ fn test_create_batch_escrow_success() {
             release_window: Some(7200),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
         EscrowCreateParams {
             buyer: buyer.clone(),
@@ -2311,6 +2315,8 @@ fn test_create_batch_escrow_success() {
             release_window: None, // Uses default
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
     ];
 
@@ -2377,6 +2383,8 @@ fn test_create_batch_escrow_fails_on_invalid_amount() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
     ];
 
@@ -2404,6 +2412,8 @@ fn test_create_batch_escrow_fails_same_buyer_seller() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
     ];
 
@@ -2432,6 +2442,8 @@ fn test_create_batch_escrow_requires_authorization_for_each_distinct_buyer() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
         EscrowCreateParams {
             buyer: second_buyer.clone(),
@@ -2442,6 +2454,8 @@ fn test_create_batch_escrow_requires_authorization_for_each_distinct_buyer() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
     ];
 
@@ -2651,6 +2665,8 @@ fn test_reentrancy_guard_cleared_after_batch_create_error() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
     ];
 
@@ -3138,6 +3154,8 @@ fn test_batch_escrow_non_whitelisted_token_rejected() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         },
     ];
     let result = client.try_create_batch_escrow(&1u64, &params);
@@ -3241,9 +3259,9 @@ fn test_whitelist_token_accepts_zero_decimals() {
     let (client, _, _, _, _, _, _) = setup_test(&env, true);
 
     use crate::onboarding::decimal_test_token::{DecimalTestToken, DecimalTestTokenClient};
-    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
     let contract_id = env.register_contract(None, DecimalTestToken);
-    DecimalTestTokenClient::new(&env, &contract_id).initialize(&admin, &0u32);
+    DecimalTestTokenClient::new(&env, &contract_id).initialize(&token_admin, &0u32);
 
     client.whitelist_token(&contract_id);
     assert!(client.is_token_whitelisted(&contract_id));
@@ -3257,9 +3275,9 @@ fn test_whitelist_token_accepts_seven_decimals() {
     let (client, _, _, _, _, _, _) = setup_test(&env, true);
 
     use crate::onboarding::decimal_test_token::{DecimalTestToken, DecimalTestTokenClient};
-    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
     let contract_id = env.register_contract(None, DecimalTestToken);
-    DecimalTestTokenClient::new(&env, &contract_id).initialize(&admin, &7u32);
+    DecimalTestTokenClient::new(&env, &contract_id).initialize(&token_admin, &7u32);
 
     client.whitelist_token(&contract_id);
     assert!(client.is_token_whitelisted(&contract_id));
@@ -3273,9 +3291,9 @@ fn test_whitelist_token_accepts_eighteen_decimals() {
     let (client, _, _, _, _, _, _) = setup_test(&env, true);
 
     use crate::onboarding::decimal_test_token::{DecimalTestToken, DecimalTestTokenClient};
-    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
     let contract_id = env.register_contract(None, DecimalTestToken);
-    DecimalTestTokenClient::new(&env, &contract_id).initialize(&admin, &18u32);
+    DecimalTestTokenClient::new(&env, &contract_id).initialize(&token_admin, &18u32);
 
     client.whitelist_token(&contract_id);
     assert!(client.is_token_whitelisted(&contract_id));
@@ -3290,9 +3308,9 @@ fn test_whitelist_token_rejects_nineteen_decimals() {
     let (client, _, _, _, _, _, _) = setup_test(&env, true);
 
     use crate::onboarding::decimal_test_token::{DecimalTestToken, DecimalTestTokenClient};
-    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
     let contract_id = env.register_contract(None, DecimalTestToken);
-    DecimalTestTokenClient::new(&env, &contract_id).initialize(&admin, &19u32);
+    DecimalTestTokenClient::new(&env, &contract_id).initialize(&token_admin, &19u32);
 
     let result = client.try_whitelist_token(&contract_id);
     assert_eq!(
@@ -3317,9 +3335,9 @@ fn test_whitelist_token_rejects_255_decimals() {
     let (client, _, _, _, _, _, _) = setup_test(&env, true);
 
     use crate::onboarding::decimal_test_token::{DecimalTestToken, DecimalTestTokenClient};
-    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
     let contract_id = env.register_contract(None, DecimalTestToken);
-    DecimalTestTokenClient::new(&env, &contract_id).initialize(&admin, &255u32);
+    DecimalTestTokenClient::new(&env, &contract_id).initialize(&token_admin, &255u32);
 
     let result = client.try_whitelist_token(&contract_id);
     assert_eq!(
@@ -3375,6 +3393,8 @@ fn test_migrate_fee_token_configs_migrates_twenty_tokens_and_emits_summary() {
                 active: true,
                 custom_fee_bps: None,
                 accumulated: (i as i128 + 1) * 1_000,
+                fee_payer: FeePayer::Seller,
+                custom_fee_payer: false,
             }
         );
     }
@@ -3430,6 +3450,8 @@ fn test_migrate_fee_token_configs_is_idempotent_and_preserves_existing_configs()
                 active: false,
                 custom_fee_bps: Some(250),
                 accumulated: 777_777,
+                fee_payer: FeePayer::Seller,
+                custom_fee_payer: false,
             },
         );
         env.storage().persistent().set(
@@ -3438,6 +3460,8 @@ fn test_migrate_fee_token_configs_is_idempotent_and_preserves_existing_configs()
                 active: true,
                 custom_fee_bps: Some(900),
                 accumulated: 888_888,
+                fee_payer: FeePayer::Seller,
+                custom_fee_payer: false,
             },
         );
     });
@@ -3453,6 +3477,8 @@ fn test_migrate_fee_token_configs_is_idempotent_and_preserves_existing_configs()
             active: false,
             custom_fee_bps: Some(250),
             accumulated: 777_777,
+            fee_payer: FeePayer::Seller,
+            custom_fee_payer: false,
         }
     );
     assert_eq!(
@@ -3461,6 +3487,8 @@ fn test_migrate_fee_token_configs_is_idempotent_and_preserves_existing_configs()
             active: true,
             custom_fee_bps: Some(900),
             accumulated: 888_888,
+            fee_payer: FeePayer::Seller,
+            custom_fee_payer: false,
         }
     );
 
@@ -3474,6 +3502,8 @@ fn test_migrate_fee_token_configs_is_idempotent_and_preserves_existing_configs()
                     active: true,
                     custom_fee_bps: None,
                     accumulated: (i as i128 + 1) * 500,
+                    fee_payer: FeePayer::Seller,
+                    custom_fee_payer: false,
                 }
             );
         }
@@ -3524,6 +3554,8 @@ fn test_create_batch_escrow_consolidates_storage() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: None,
+            payer: None,
+            refund_address: None,
         });
     }
 
@@ -3838,6 +3870,8 @@ fn test_create_batch_escrow_with_metadata() {
             release_window: Some(3600),
             ipfs_hash: None,
             metadata_hash: Some(metadata_hash_bytes.clone()),
+            payer: None,
+            refund_address: None,
         });
     }
 
@@ -3870,6 +3904,8 @@ fn test_validate_batch_creation() {
         release_window: Some(3600),
         ipfs_hash: None,
         metadata_hash: None,
+        payer: None,
+        refund_address: None,
     };
 
     let invalid_parties = EscrowCreateParams {
@@ -3881,6 +3917,8 @@ fn test_validate_batch_creation() {
         release_window: Some(3600),
         ipfs_hash: None,
         metadata_hash: None,
+        payer: None,
+        refund_address: None,
     };
 
     let valid_param = EscrowCreateParams {
@@ -3892,6 +3930,8 @@ fn test_validate_batch_creation() {
         release_window: Some(3600),
         ipfs_hash: None,
         metadata_hash: None,
+        payer: None,
+        refund_address: None,
     };
 
     let mut batch_params = soroban_sdk::Vec::new(&env);
@@ -3923,6 +3963,8 @@ fn test_validate_batch_creation_rejects_invalid_metadata_hash_length() {
         release_window: Some(3600),
         ipfs_hash: None,
         metadata_hash: Some(Bytes::from_array(&env, &[9; 31])),
+        payer: None,
+        refund_address: None,
     });
 
     let errors = client.validate_batch_creation(&batch_params);
@@ -3947,6 +3989,8 @@ fn test_validate_batch_creation_exceeds_limit() {
         release_window: Some(3600),
         ipfs_hash: None,
         metadata_hash: None,
+        payer: None,
+        refund_address: None,
     };
 
     let mut batch_params = soroban_sdk::Vec::new(&env);
@@ -4121,6 +4165,8 @@ fn test_get_escrow_count_batch_creation() {
         release_window: Some(3600),
         ipfs_hash: None,
         metadata_hash: None,
+        payer: None,
+        refund_address: None,
     };
 
     let mut batch = soroban_sdk::Vec::new(&env);
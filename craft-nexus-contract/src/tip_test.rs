@@ -0,0 +1,138 @@
+#![cfg(test)]
+
+use crate::{CraftNexusContract, CraftNexusContractClient, EscrowStatus, ReputationUpdateEvent};
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger as _},
+    token, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+
+struct TipTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> TipTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    TipTest {
+        env,
+        client,
+        buyer,
+        seller,
+        platform_wallet,
+        token,
+    }
+}
+
+#[test]
+fn test_tip_is_paid_on_top_without_platform_fee() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds_with_tip(&1, &1_000);
+
+    assert_eq!(t.client.get_escrow(&1).status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&t.seller), 9_500 + 1_000);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(token_client.balance(&t.buyer), 1_000_000 - 10_000 - 1_000);
+    assert_eq!(t.client.get_total_fees_for_token(&t.token), 500);
+}
+
+#[test]
+fn test_tip_is_recorded_in_fund_audit_log() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds_with_tip(&1, &250);
+
+    let tip_reason = Symbol::new(&t.env, "release_tip");
+    let seller_audit = t.client.get_fund_audit_history(&t.seller);
+    let seller_tip = seller_audit.last().unwrap();
+    assert_eq!(seller_tip.reason, tip_reason);
+    assert_eq!(seller_tip.amount, 250);
+    assert_eq!(seller_tip.balance_impact, 250);
+
+    let buyer_audit = t.client.get_fund_audit_history(&t.buyer);
+    let buyer_tip = buyer_audit.last().unwrap();
+    assert_eq!(buyer_tip.reason, tip_reason);
+    assert_eq!(buyer_tip.balance_impact, -250);
+}
+
+#[test]
+fn test_zero_tip_matches_plain_release() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds_with_tip(&1, &0);
+
+    assert_eq!(token_client.balance(&t.seller), 9_500);
+    assert_eq!(t.client.get_fund_audit_count(&t.seller), 1);
+}
+
+#[test]
+#[should_panic(expected = "HostError: Error(Contract, #6)")]
+fn test_negative_tip_is_rejected() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds_with_tip(&1, &-1);
+}
+
+#[test]
+fn test_tip_is_counted_once_in_seller_metrics_event() {
+    let t = setup_test();
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds_with_tip(&1, &1_000);
+
+    let topics: Vec<Val> = (
+        Symbol::new(&t.env, "stake_reputation_update"),
+        t.seller.clone(),
+    )
+        .into_val(&t.env);
+    let mut seller_updates = t
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, event_topics, _)| *event_topics == topics);
+    let (_, _, data) = seller_updates.next().expect("seller reputation update");
+    let event: ReputationUpdateEvent = data.into_val(&t.env);
+    assert_eq!(event.metrics_amount, 10_000 + 1_000);
+    assert_eq!(event.metrics_sales_delta, 1);
+    assert!(seller_updates.next().is_none());
+}
@@ -0,0 +1,192 @@
+#![cfg(test)]
+
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, TreasuryRecipient, MAX_TREASURY_RECIPIENTS,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env, Symbol, Vec,
+};
+
+struct TreasuryTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    buyer: Address,
+    seller: Address,
+    platform_wallet: Address,
+    admin: Address,
+    token: Address,
+}
+
+fn setup_test() -> TreasuryTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    client.initialize(&platform_wallet, &admin, &arbitrator, &500, &None);
+    client.set_min_escrow_amount(&token, &0);
+
+    TreasuryTest {
+        env,
+        client,
+        buyer,
+        seller,
+        platform_wallet,
+        admin,
+        token,
+    }
+}
+
+fn share(recipient: &Address, share_bps: u32) -> TreasuryRecipient {
+    TreasuryRecipient {
+        recipient: recipient.clone(),
+        share_bps,
+    }
+}
+
+#[test]
+fn test_fees_accrue_and_distribute_by_share() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let ops = Address::generate(&t.env);
+    let community = Address::generate(&t.env);
+    let insurance = Address::generate(&t.env);
+
+    t.client.set_treasury_recipients(
+        &t.admin,
+        &vec![
+            &t.env,
+            share(&ops, 6_000),
+            share(&community, 3_000),
+            share(&insurance, 1_000),
+        ],
+    );
+
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.platform_wallet), 0);
+    assert_eq!(t.client.get_treasury_balance(&t.token), 500);
+    assert_eq!(t.client.get_total_fees_for_token(&t.token), 500);
+
+    assert_eq!(t.client.distribute_treasury(&t.token), 500);
+    assert_eq!(token_client.balance(&ops), 300);
+    assert_eq!(token_client.balance(&community), 150);
+    assert_eq!(token_client.balance(&insurance), 50);
+    assert_eq!(t.client.get_treasury_balance(&t.token), 0);
+    assert_eq!(t.client.distribute_treasury(&t.token), 0);
+
+    let audit = t.client.get_fund_audit_history(&ops);
+    assert_eq!(audit.len(), 1);
+    assert_eq!(
+        audit.get(0).unwrap().reason,
+        Symbol::new(&t.env, "treasury_distribution")
+    );
+    let ledger = t.client.get_fund_audit_history(&t.client.address);
+    assert_eq!(ledger.len(), 2);
+    assert_eq!(ledger.get(0).unwrap().balance_impact, 500);
+    assert_eq!(ledger.get(1).unwrap().balance_impact, -500);
+}
+
+#[test]
+fn test_rounding_dust_goes_to_last_recipient() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let first = Address::generate(&t.env);
+    let second = Address::generate(&t.env);
+    let third = Address::generate(&t.env);
+
+    t.client.set_treasury_recipients(
+        &t.admin,
+        &vec![
+            &t.env,
+            share(&first, 3_333),
+            share(&second, 3_333),
+            share(&third, 3_334),
+        ],
+    );
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+
+    t.client.distribute_treasury(&t.token);
+    assert_eq!(token_client.balance(&first), 166);
+    assert_eq!(token_client.balance(&second), 166);
+    assert_eq!(token_client.balance(&third), 168);
+}
+
+#[test]
+fn test_treasury_off_pays_platform_wallet_and_is_not_swept() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+    let ops = Address::generate(&t.env);
+
+    t.client
+        .set_treasury_recipients(&t.admin, &vec![&t.env, share(&ops, 10_000)]);
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+    assert_eq!(t.client.sweep_unallocated_funds(&t.token, &t.admin), 0);
+
+    t.client
+        .set_treasury_recipients(&t.admin, &Vec::new(&t.env));
+    t.client
+        .create_escrow(&t.buyer, &t.seller, &t.token, &10_000, &2, &None);
+    t.client.release_funds(&2);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+
+    // Fees accrued before the treasury was turned off go to the wallet.
+    t.client.distribute_treasury(&t.token);
+    assert_eq!(token_client.balance(&t.platform_wallet), 1_000);
+    assert_eq!(token_client.balance(&ops), 0);
+}
+
+#[test]
+fn test_set_treasury_recipients_validation() {
+    let t = setup_test();
+    let ops = Address::generate(&t.env);
+    let community = Address::generate(&t.env);
+
+    assert_eq!(
+        t.client.try_set_treasury_recipients(
+            &t.admin,
+            &vec![&t.env, share(&ops, 6_000), share(&community, 3_000),]
+        ),
+        Err(Ok(Error::InvalidFee))
+    );
+    assert_eq!(
+        t.client.try_set_treasury_recipients(
+            &t.admin,
+            &vec![&t.env, share(&ops, 10_000), share(&community, 0),]
+        ),
+        Err(Ok(Error::InvalidFee))
+    );
+
+    let mut recipients = Vec::new(&t.env);
+    for _ in 0..=MAX_TREASURY_RECIPIENTS {
+        recipients.push_back(share(&Address::generate(&t.env), 1));
+    }
+    assert_eq!(
+        t.client.try_set_treasury_recipients(&t.admin, &recipients),
+        Err(Ok(Error::BatchLimitExceeded))
+    );
+    assert_eq!(t.client.get_treasury_recipients().len(), 0);
+}
@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+use crate::onboarding::{OnboardingContract, OnboardingContractClient, UserRole};
+use crate::{
+    CraftNexusContract, CraftNexusContractClient, Error, VolumeFeeTier, MAX_VOLUME_FEE_TIERS,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, vec, Address, Env, String, Vec,
+};
+
+struct VolumeFeeTierTest {
+    env: Env,
+    client: CraftNexusContractClient<'static>,
+    admin: Address,
+    onboarding: OnboardingContractClient<'static>,
+    buyer: Address,
+    artisan: Address,
+    platform_wallet: Address,
+    token: Address,
+}
+
+fn setup_test() -> VolumeFeeTierTest {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    let onboarding_id = env.register_contract(None, OnboardingContract);
+    let onboarding = OnboardingContractClient::new(&env, &onboarding_id);
+    let contract_id = env.register_contract(None, CraftNexusContract);
+    let client = CraftNexusContractClient::new(&env, &contract_id);
+
+    let buyer = Address::generate(&env);
+    let artisan = Address::generate(&env);
+    let platform_wallet = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token).mint(&buyer, &1_000_000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1711368000;
+    });
+
+    onboarding.initialize(&admin);
+    onboarding.set_escrow_contract(&contract_id);
+    client.initialize(
+        &platform_wallet,
+        &admin,
+        &arbitrator,
+        &500,
+        &Some(onboarding_id),
+    );
+    onboarding.onboard_user(&buyer, &String::from_str(&env, "buyer"), &UserRole::Buyer);
+    onboarding.onboard_user(
+        &artisan,
+        &String::from_str(&env, "artisan"),
+        &UserRole::Artisan,
+    );
+
+    client.set_min_escrow_amount(&token, &0);
+
+    VolumeFeeTierTest {
+        env,
+        client,
+        admin,
+        onboarding,
+        buyer,
+        artisan,
+        platform_wallet,
+        token,
+    }
+}
+
+fn tier(min_volume: i128, min_trust_score: u32, fee_bps: u32) -> VolumeFeeTier {
+    VolumeFeeTier {
+        min_volume,
+        min_trust_score,
+        fee_bps,
+    }
+}
+
+#[test]
+fn test_volume_tier_applies_once_threshold_is_reached() {
+    let t = setup_test();
+    let token_client = token::Client::new(&t.env, &t.token);
+
+    t.client
+        .set_volume_fee_tiers(&t.admin, &vec![&t.env, tier(10_000, 0, 200)]);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 500);
+
+    t.client
+        .create_escrow(&t.buyer, &t.artisan, &t.token, &10_000, &1, &None);
+    t.client.release_funds(&1);
+    assert_eq!(token_client.balance(&t.platform_wallet), 500);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 500);
+
+    t.onboarding
+        .update_user_metrics(&t.artisan, &1, &10_000, &t.token);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 200);
+
+    t.client
+        .create_escrow(&t.buyer, &t.artisan, &t.token, &10_000, &2, &None);
+    t.client.release_funds(&2);
+    assert_eq!(token_client.balance(&t.platform_wallet), 700);
+}
+
+#[test]
+fn test_lowest_qualifying_tier_and_manual_override() {
+    let t = setup_test();
+
+    t.client
+        .set_volume_fee_tiers(&t.admin, &vec![&t.env, tier(0, 0, 400), tier(0, 1, 100)]);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 400);
+
+    t.onboarding.update_reputation(&t.artisan, &1, &0);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 100);
+    assert_eq!(
+        t.client
+            .get_effective_fee_bps_for_token(&t.artisan, &t.token),
+        100
+    );
+
+    t.client.set_artisan_fee_tier(&t.artisan, &300);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 300);
+}
+
+#[test]
+fn test_volume_tiers_fall_back_without_onboarding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client =
+        CraftNexusContractClient::new(&env, &env.register_contract(None, CraftNexusContract));
+    let artisan = Address::generate(&env);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &Address::generate(&env),
+        &admin,
+        &Address::generate(&env),
+        &500,
+        &None,
+    );
+
+    client.set_volume_fee_tiers(&admin, &vec![&env, tier(0, 0, 100)]);
+    assert_eq!(client.get_effective_fee_bps(&artisan), 500);
+}
+
+#[test]
+fn test_set_volume_fee_tiers_validation() {
+    let t = setup_test();
+
+    assert_eq!(
+        t.client
+            .try_set_volume_fee_tiers(&t.admin, &vec![&t.env, tier(0, 0, 5_000)]),
+        Err(Ok(Error::InvalidFee))
+    );
+
+    let mut tiers = Vec::new(&t.env);
+    for i in 0..=MAX_VOLUME_FEE_TIERS {
+        tiers.push_back(tier(i as i128, 0, 100));
+    }
+    assert_eq!(
+        t.client.try_set_volume_fee_tiers(&t.admin, &tiers),
+        Err(Ok(Error::BatchLimitExceeded))
+    );
+
+    t.client
+        .set_volume_fee_tiers(&t.admin, &vec![&t.env, tier(0, 0, 100)]);
+    assert_eq!(t.client.get_volume_fee_tiers().len(), 1);
+    t.client.set_volume_fee_tiers(&t.admin, &Vec::new(&t.env));
+    assert_eq!(t.client.get_volume_fee_tiers().len(), 0);
+    assert_eq!(t.client.get_effective_fee_bps(&t.artisan), 500);
+}